@@ -11,6 +11,21 @@ from datetime import datetime
 from tabulate import tabulate
 
 
+STATUS_CODES = {'solved': 0, 'timeout': 1, 'no_solution': 2, 'depth_limit_reached': 4, 'memory_exceeded': 2}
+
+
+def status_code(result):
+    """Code numérique du statut (ancien format bool/entier ou nouveau format chaîne): 0=succès, 1=timeout, 2=pas de solution, 3=erreur, 4=limite atteinte"""
+    if 'status' not in result:
+        return 0 if result.get('success', False) else 2
+    status = result['status']
+    if isinstance(status, int):
+        return status
+    if isinstance(status, dict):
+        return 3
+    return STATUS_CODES.get(status, 2)
+
+
 class ReportGenerator:
     def __init__(self, results_file='results/benchmark_results.json'):
         self.results_file = results_file
@@ -29,24 +44,23 @@ class ReportGenerator:
         """Charge les résultats depuis le fichier JSON"""
         with open(self.results_file, 'r') as f:
             results = json.load(f)
+        # Les tâches écartées par --budget n'ont pas été exécutées
+        results = [r for r in results if r.get('status') != 'skipped']
         
         # Conserver les données brutes pour le rapport détaillé
         self.results_raw = results
         
         data = []
         for result in results:
-            # Support ancien format (success: bool) et nouveau format (status: int)
-            if 'status' in result:
-                status = result['status']
-            else:
-                status = 0 if result.get('success', False) else 2
+            status = status_code(result)
+            result['status'] = status
             
             row = {
                 'algorithm': result['algorithm'],
                 'problem': result['problem'],
                 'problem_size': result['problem_size'],
                 'instance_id': result['instance_id'],
-                'status': status,  # 0=succès, 1=timeout, 2=pas de solution
+                'status': status,  # 0=succès, 1=timeout, 2=pas de solution, 3=erreur, 4=limite atteinte
                 'time_ms': result['metrics']['time_ms'],
                 'memory_kb': result['metrics']['memory_kb'],
                 'nodes_visited': result['metrics']['nodes_visited'],
@@ -60,7 +74,7 @@ class ReportGenerator:
     
     def generate_summary_statistics(self):
         """Génère les statistiques résumées (succès, timeout, pas de solution), triées par problème puis algorithme"""
-        # status: 0=succès, 1=timeout, 2=pas de solution
+        # status: 0=succès, 1=timeout, 2=pas de solution, 3=erreur, 4=limite atteinte
         df_success = self.df[self.df['status'] == 0]
         df_timeout = self.df[(self.df['status'] == 1) & (self.df['nodes_visited'] > 0)]
         df_not_found = self.df[(self.df['status'] == 2) & (self.df['nodes_visited'] > 0)]
@@ -253,8 +267,8 @@ class ReportGenerator:
                 error = result.get('error', None)
                 initial_state = result.get('initial_state', None)
                 
-                # 0=succès, 1=timeout, 2=pas de solution
-                status_emoji = "OK" if status == 0 else ("TO" if status == 1 else "ER")
+                # 0=succès, 1=timeout, 2=pas de solution, 3=solution invalide, 4=limite atteinte
+                status_emoji = {0: "OK", 1: "TO", 4: "LIM"}.get(status, "ER")
                 f.write(f"#### {status_emoji} Instance #{instance_id}\n\n")
                 
                 if status == 0:
@@ -436,7 +450,7 @@ class ReportGenerator:
                 metrics = result['metrics']
                 error = result.get('error', None)
                 
-                status_text = "[OK]" if status == 0 else ("[TIMEOUT]" if status == 1 else "[ECHEC]")
+                status_text = {0: "[OK]", 1: "[TIMEOUT]", 4: "[LIMITE]"}.get(status, "[ECHEC]")
                 content.append(f"\n#### {status_text} Instance #{instance_id}\n")
                 
                 if status == 0:
@@ -536,12 +550,24 @@ class ReportGenerator:
         return "".join(content)
 
 
+def resolve_run(target, index_file='results/index.json'):
+    """Remplace un identifiant d'exécution (ou latest, latest~N) par son fichier de résultats"""
+    if Path(target).exists() or not Path(index_file).is_file():
+        return target
+    with open(index_file, 'r') as f:
+        runs = json.load(f)
+    if target == 'latest' or target.startswith('latest~'):
+        back = int(target.partition('~')[2] or 0)
+        return runs[-1 - back]['results'] if back < len(runs) else target
+    return next((run['results'] for run in runs if run['id'] == target), target)
+
+
 def main():
     import sys
     
     target = 'results/benchmark_results.json'
     if len(sys.argv) > 1:
-        target = sys.argv[1]
+        target = resolve_run(sys.argv[1])
     
     print("Génération du Rapport de Benchmarking...")
     
@@ -551,7 +577,8 @@ def main():
     
     if target_path.is_dir():
         # Traiter tous les fichiers JSON du dossier
-        json_files = list(target_path.glob('*.json'))
+        json_files = [path for path in target_path.glob('*.json')
+                      if path.name != 'index.json' and not path.name.endswith('.meta.json')]
         if not json_files:
             print(f"Warning: Aucun fichier JSON trouvé dans {target}")
             sys.exit(1)
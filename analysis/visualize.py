@@ -15,6 +15,21 @@ sns.set_theme(style="whitegrid")
 plt.rcParams['figure.figsize'] = (12, 8)
 plt.rcParams['font.size'] = 10
 
+STATUS_CODES = {'solved': 0, 'timeout': 1, 'no_solution': 2, 'depth_limit_reached': 4, 'memory_exceeded': 2}
+
+
+def status_code(result):
+    """Code numérique du statut (ancien format bool/entier ou nouveau format chaîne): 0=succès, 1=timeout, 2=pas de solution, 3=erreur, 4=limite atteinte"""
+    if 'status' not in result:
+        return 0 if result.get('success', False) else 2
+    status = result['status']
+    if isinstance(status, int):
+        return status
+    if isinstance(status, dict):
+        return 3
+    return STATUS_CODES.get(status, 2)
+
+
 class BenchmarkVisualizer:
     def __init__(self, results_file='results/benchmark_results.json'):
         self.results_file = results_file
@@ -33,18 +48,15 @@ class BenchmarkVisualizer:
         # Convertir en DataFrame pandas
         data = []
         for result in results:
-            # Support ancien format (success: bool) et nouveau format (status: int)
-            if 'status' in result:
-                status = result['status']
-            else:
-                status = 0 if result.get('success', False) else 2
+            status = status_code(result)
+            result['status'] = status
             
             row = {
                 'algorithm': result['algorithm'],
                 'problem': result['problem'],
                 'problem_size': result['problem_size'],
                 'instance_id': result['instance_id'],
-                'status': status,  # 0=succès, 1=timeout, 2=pas de solution
+                'status': status,  # 0=succès, 1=timeout, 2=pas de solution, 3=erreur, 4=limite atteinte
                 'time_ms': result['metrics']['time_ms'],
                 'memory_kb': result['metrics']['memory_kb'],
                 'nodes_visited': result['metrics']['nodes_visited'],
@@ -62,7 +74,7 @@ class BenchmarkVisualizer:
             output_dir = self.output_dir
         Path(output_dir).mkdir(parents=True, exist_ok=True)
         
-        # status: 0=succès, 1=timeout, 2=pas de solution
+        # status: 0=succès, 1=timeout, 2=pas de solution, 3=erreur, 4=limite atteinte
         df_success = self.df[self.df['status'] == 0]
         df_not_found = self.df[(self.df['status'] == 2) & (self.df['nodes_visited'] > 0)]
         
@@ -127,7 +139,7 @@ class BenchmarkVisualizer:
             output_dir = self.output_dir
         Path(output_dir).mkdir(parents=True, exist_ok=True)
         
-        # status: 0=succès, 1=timeout, 2=pas de solution
+        # status: 0=succès, 1=timeout, 2=pas de solution, 3=erreur, 4=limite atteinte
         df_success = self.df[self.df['status'] == 0]
         df_not_found = self.df[(self.df['status'] == 2) & (self.df['memory_kb'] > 0)]
         
@@ -197,7 +209,7 @@ class BenchmarkVisualizer:
             output_dir = self.output_dir
         Path(output_dir).mkdir(parents=True, exist_ok=True)
         
-        # status: 0=succès, 1=timeout, 2=pas de solution
+        # status: 0=succès, 1=timeout, 2=pas de solution, 3=erreur, 4=limite atteinte
         df_success = self.df[self.df['status'] == 0]
         df_not_found = self.df[(self.df['status'] == 2) & (self.df['nodes_visited'] > 0)]
         
@@ -251,7 +263,7 @@ class BenchmarkVisualizer:
             output_dir = self.output_dir
         Path(output_dir).mkdir(parents=True, exist_ok=True)
         
-        # status: 0=succès, 1=timeout, 2=pas de solution
+        # status: 0=succès, 1=timeout, 2=pas de solution, 3=erreur, 4=limite atteinte
         df_success = self.df[self.df['status'] == 0]
         df_not_found = self.df[(self.df['status'] == 2) & (self.df['nodes_generated'] > 0)]
         
@@ -0,0 +1,65 @@
+use benchmarking_rust::algorithms::Problem;
+use benchmarking_rust::problems::taquin::HeuristicType;
+use benchmarking_rust::problems::{PackedTaquin, Taquin};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rustc_hash::FxHashSet;
+use std::collections::HashSet;
+use std::hash::Hash;
+
+fn reachable_states<P: Problem>(problem: &P, count: usize) -> Vec<P::State> {
+    let mut seen = HashSet::new();
+    let mut states = vec![problem.initial_state()];
+    seen.insert(problem.initial_state());
+    let mut index = 0;
+    while states.len() < count && index < states.len() {
+        let current = states[index].clone();
+        for (next, _) in problem.successors(&current) {
+            if seen.insert(next.clone()) {
+                states.push(next);
+            }
+        }
+        index += 1;
+    }
+    states
+}
+
+fn bench_states<S: Clone + Eq + Hash>(c: &mut Criterion, name: &str, states: &[S]) {
+    let mut group = c.benchmark_group(name);
+
+    group.bench_function("siphash", |b| {
+        b.iter(|| {
+            let mut set = HashSet::new();
+            for state in states {
+                set.insert(state.clone());
+            }
+            black_box(states.iter().filter(|s| set.contains(*s)).count())
+        })
+    });
+
+    group.bench_function("fxhash", |b| {
+        b.iter(|| {
+            let mut set = FxHashSet::default();
+            for state in states {
+                set.insert(state.clone());
+            }
+            black_box(states.iter().filter(|s| set.contains(*s)).count())
+        })
+    });
+
+    group.finish();
+}
+
+fn hashers(c: &mut Criterion) {
+    let taquin = Taquin::new(4, HeuristicType::Manhattan);
+    let packed = PackedTaquin::from_taquin(&taquin, HeuristicType::Manhattan);
+
+    bench_states(c, "hashers/taquin-4x4", &reachable_states(&taquin, 10_000));
+    bench_states(
+        c,
+        "hashers/taquin-packed-4x4",
+        &reachable_states(&packed, 10_000),
+    );
+}
+
+criterion_group!(benches, hashers);
+criterion_main!(benches);
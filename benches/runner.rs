@@ -0,0 +1,72 @@
+use benchmarking_rust::benchmarking::{
+    AlgorithmChoice, BenchmarkConfig, BenchmarkRunner, Connectivity,
+};
+use benchmarking_rust::problems::registry;
+use clap::ValueEnum;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+const SEED: u64 = 42;
+const INSTANCE: usize = 0;
+const PROBLEMS: &[(&str, usize)] = &[
+    ("taquin", 3),
+    ("taquin-packed", 3),
+    ("shortest-path", 12),
+    ("shortest-path-random", 60),
+];
+
+fn runner(problem: &str, size: usize, algorithm: AlgorithmChoice) -> BenchmarkRunner {
+    let config = BenchmarkConfig {
+        algorithm,
+        problem: problem.to_string(),
+        size,
+        iterations: 1,
+        timeout_secs: 0,
+        seed: SEED,
+        connectivity: if problem == "shortest-path-random" {
+            Connectivity::Path
+        } else {
+            Connectivity::None
+        },
+        ..BenchmarkConfig::default()
+    };
+    config
+        .validate()
+        .expect("configuration de benchmark valide");
+    let mut runner = BenchmarkRunner::new(config);
+    runner.progress_mut().set_console(false);
+    runner
+}
+
+fn runner_tasks(c: &mut Criterion) {
+    for &(name, size) in PROBLEMS {
+        let entry = registry::find(name).expect("problème enregistré");
+        let mut group = c.benchmark_group(format!("runner/{}-{}", name, size));
+        group.sample_size(20);
+
+        for &algorithm in AlgorithmChoice::value_variants() {
+            if algorithm == AlgorithmChoice::All {
+                continue;
+            }
+            let runner = runner(name, size, algorithm);
+            let results = (entry.solve)(&runner, INSTANCE);
+            for result in &results {
+                assert!(
+                    result.status.is_solved(),
+                    "{} sur {}: {:?}",
+                    result.algorithm,
+                    result.problem,
+                    result.error
+                );
+            }
+            let label: Vec<&str> = results.iter().map(|r| r.algorithm.as_str()).collect();
+            group.bench_function(label.join("+"), |b| {
+                b.iter(|| black_box((entry.solve)(&runner, INSTANCE)))
+            });
+        }
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, runner_tasks);
+criterion_main!(benches);
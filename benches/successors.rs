@@ -0,0 +1,92 @@
+use benchmarking_rust::algorithms::Problem;
+use benchmarking_rust::problems::taquin::HeuristicType;
+use benchmarking_rust::problems::{PackedTaquin, ShortestPath, Taquin};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn sample_states<P: Problem>(problem: &P, count: usize) -> Vec<P::State> {
+    let mut states = vec![problem.initial_state()];
+    let mut index = 0;
+    while states.len() < count {
+        let current = states[index].clone();
+        for (next, _) in problem.successors(&current) {
+            states.push(next);
+        }
+        index += 1;
+    }
+    states.truncate(count);
+    states
+}
+
+fn bench_problem<P: Problem>(c: &mut Criterion, name: &str, problem: &P) {
+    let states = sample_states(problem, 256);
+    let mut group = c.benchmark_group(name);
+
+    group.bench_function("fresh_vec", |b| {
+        b.iter(|| {
+            for state in &states {
+                let mut buffer = Vec::new();
+                problem.successors_into(state, &mut buffer);
+                black_box(&buffer);
+            }
+        })
+    });
+
+    group.bench_function("reused_buffer", |b| {
+        let mut buffer = Vec::new();
+        b.iter(|| {
+            for state in &states {
+                problem.successors_into(state, &mut buffer);
+                black_box(&buffer);
+            }
+        })
+    });
+
+    group.bench_function("with_full_heuristic", |b| {
+        let mut buffer = Vec::new();
+        b.iter(|| {
+            for state in &states {
+                problem.successors_into(state, &mut buffer);
+                for (_, next, _) in &buffer {
+                    black_box(problem.heuristic(next));
+                }
+            }
+        })
+    });
+
+    group.bench_function("with_incremental_heuristic", |b| {
+        let heuristics: Vec<usize> = states.iter().map(|s| problem.heuristic(s)).collect();
+        let mut buffer = Vec::new();
+        b.iter(|| {
+            for (state, &h) in states.iter().zip(&heuristics) {
+                problem.successors_with_heuristic_into(state, h, &mut buffer);
+                black_box(&buffer);
+            }
+        })
+    });
+
+    group.bench_function("smallvec", |b| {
+        b.iter(|| {
+            for state in &states {
+                black_box(problem.successors(state));
+            }
+        })
+    });
+
+    group.finish();
+}
+
+fn successors(c: &mut Criterion) {
+    let mut taquin = Taquin::new(4, HeuristicType::Manhattan);
+    taquin.generate_random(40, &mut StdRng::seed_from_u64(0));
+    let packed = PackedTaquin::from_taquin(&taquin, HeuristicType::Manhattan);
+    let grid = ShortestPath::generate_grid(50, 50);
+
+    bench_problem(c, "successors/taquin-4x4", &taquin);
+    bench_problem(c, "successors/taquin-packed-4x4", &packed);
+    bench_problem(c, "successors/grid-50x50", &grid);
+}
+
+criterion_group!(benches, successors);
+criterion_main!(benches);
@@ -0,0 +1,33 @@
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    #[cfg(feature = "grpc")]
+    {
+        use tonic_build::manual::{Builder, Method, Service};
+
+        let method = |name: &str, route: &str, input: &str, output: &str| {
+            Method::builder()
+                .name(name)
+                .route_name(route)
+                .input_type(format!("super::{}", input))
+                .output_type(format!("super::{}", output))
+                .codec_path("tonic_prost::ProstCodec")
+        };
+        let service = Service::builder()
+            .name("Benchmarking")
+            .package("benchmark")
+            .method(method("solve", "Solve", "SolveRequest", "SolveResponse").build())
+            .method(
+                method(
+                    "benchmark",
+                    "Benchmark",
+                    "BenchmarkRequest",
+                    "BenchmarkUpdate",
+                )
+                .server_streaming()
+                .build(),
+            )
+            .build();
+        Builder::new().compile(&[service]);
+    }
+}
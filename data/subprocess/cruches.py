@@ -0,0 +1,58 @@
+#!/usr/bin/env python3
+"""Problème des cruches pour `--problem subprocess` (protocole JSON ligne par ligne).
+
+Chaque requête est un objet {"op", "size", "seed", "state"} sur une ligne de stdin ;
+la réponse tient sur une ligne de stdout :
+  init        -> {"state": ..., "heuristic": true, "admissible": true, "max_depth": 30}
+  is_goal     -> {"goal": bool}
+  successors  -> {"successors": [{"action": "...", "state": ..., "cost": 1}, ...]}
+  heuristic   -> {"h": entier}
+En cas d'erreur : {"error": "message"}.
+"""
+import json
+import sys
+
+
+def capacities(size):
+    size = max(size, 3)
+    return size, size - 1
+
+
+def successors(state, size):
+    max_a, max_b = capacities(size)
+    a, b = state
+    ab = min(a, max_b - b)
+    ba = min(b, max_a - a)
+    moves = [
+        ("remplir A", [max_a, b]),
+        ("remplir B", [a, max_b]),
+        ("vider A", [0, b]),
+        ("vider B", [a, 0]),
+        ("verser A dans B", [a - ab, b + ab]),
+        ("verser B dans A", [a + ba, b - ba]),
+    ]
+    return [{"action": action, "state": next_state, "cost": 1} for action, next_state in moves]
+
+
+def handle(request):
+    op = request["op"]
+    size = request.get("size", 4)
+    state = request.get("state")
+    if op == "init":
+        return {"state": [0, 0], "heuristic": True, "admissible": True, "max_depth": 30}
+    if op == "is_goal":
+        return {"goal": 2 in state}
+    if op == "successors":
+        return {"successors": successors(state, size)}
+    if op == "heuristic":
+        return {"h": 0 if 2 in state else 1}
+    return {"error": "opération inconnue: %s" % op}
+
+
+for line in sys.stdin:
+    try:
+        response = handle(json.loads(line))
+    except Exception as error:  # noqa: BLE001 - renvoyé au benchmark
+        response = {"error": str(error)}
+    sys.stdout.write(json.dumps(response) + "\n")
+    sys.stdout.flush()
@@ -0,0 +1,178 @@
+use super::interner::StateInterner;
+use super::memory;
+use super::Problem;
+use crate::benchmarking::MetricsRecorder;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+pub const UNREACHABLE: usize = usize::MAX;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistanceMatrix {
+    pub size: usize,
+    pub distances: Vec<usize>,
+}
+
+impl DistanceMatrix {
+    fn new(size: usize) -> Self {
+        DistanceMatrix {
+            size,
+            distances: vec![UNREACHABLE; size * size],
+        }
+    }
+
+    pub fn get(&self, from: usize, to: usize) -> usize {
+        self.distances[from * self.size + to]
+    }
+
+    pub fn reachable_pairs(&self) -> usize {
+        self.distances
+            .iter()
+            .filter(|&&distance| distance != UNREACHABLE)
+            .count()
+    }
+
+    pub fn checksum(&self) -> usize {
+        self.distances
+            .iter()
+            .filter(|&&distance| distance != UNREACHABLE)
+            .sum()
+    }
+
+    fn bytes(&self) -> usize {
+        memory::vec_bytes::<usize>(self.distances.capacity())
+    }
+}
+
+fn index<P: Problem>(states: &[P::State]) -> StateInterner<P::State> {
+    let mut interner = StateInterner::new();
+    for state in states {
+        interner.intern(state.clone());
+    }
+    interner
+}
+
+pub fn floyd_warshall<P: Problem>(
+    problem: &P,
+    states: &[P::State],
+    recorder: &mut MetricsRecorder,
+) -> DistanceMatrix {
+    let interner = index::<P>(states);
+    let size = interner.len();
+    let mut matrix = DistanceMatrix::new(size);
+    let mut successors = Vec::new();
+    for (from, state) in states.iter().enumerate() {
+        matrix.distances[from * size + from] = 0;
+        problem.successors_into(state, &mut successors);
+        for (_, next, cost) in successors.drain(..) {
+            let Some(to) = interner.lookup(&next) else {
+                continue;
+            };
+            let cell = &mut matrix.distances[from * size + to.index()];
+            *cell = (*cell).min(cost);
+            recorder.generate();
+        }
+    }
+
+    for pivot in 0..size {
+        for from in 0..size {
+            let to_pivot = matrix.distances[from * size + pivot];
+            if to_pivot == UNREACHABLE {
+                continue;
+            }
+            recorder.visit();
+            for to in 0..size {
+                let from_pivot = matrix.distances[pivot * size + to];
+                if from_pivot == UNREACHABLE {
+                    continue;
+                }
+                let through = to_pivot + from_pivot;
+                if through < matrix.distances[from * size + to] {
+                    matrix.distances[from * size + to] = through;
+                    recorder.generate();
+                }
+            }
+        }
+    }
+
+    recorder.set_memory_kb(memory::kb(matrix.bytes() + interner.memory_bytes(0)));
+    matrix
+}
+
+pub fn repeated_dijkstra<P: Problem>(
+    problem: &P,
+    states: &[P::State],
+    recorder: &mut MetricsRecorder,
+) -> DistanceMatrix {
+    let interner = index::<P>(states);
+    let size = interner.len();
+    let mut matrix = DistanceMatrix::new(size);
+    let mut frontier = BinaryHeap::new();
+    let mut settled = vec![false; size];
+    let mut successors = Vec::new();
+
+    for source in 0..size {
+        settled.fill(false);
+        let row = &mut matrix.distances[source * size..(source + 1) * size];
+        row[source] = 0;
+        frontier.push(Reverse((0, source)));
+        recorder.generate();
+
+        while let Some(Reverse((distance, node))) = frontier.pop() {
+            if settled[node] {
+                continue;
+            }
+            settled[node] = true;
+            recorder.visit();
+
+            problem.successors_into(&states[node], &mut successors);
+            for (_, next, cost) in successors.drain(..) {
+                let Some(next) = interner.lookup(&next) else {
+                    continue;
+                };
+                let tentative = distance + cost;
+                if tentative < row[next.index()] {
+                    row[next.index()] = tentative;
+                    frontier.push(Reverse((tentative, next.index())));
+                    recorder.generate();
+                }
+            }
+            recorder.observe_frontier(frontier.len());
+        }
+    }
+
+    recorder.set_memory_kb(memory::kb(
+        matrix.bytes()
+            + interner.memory_bytes(0)
+            + memory::vec_bytes::<bool>(settled.capacity())
+            + memory::vec_bytes::<Reverse<(usize, usize)>>(frontier.capacity()),
+    ));
+    matrix
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::uniform_cost::UniformCost;
+    use crate::algorithms::SearchAlgorithm;
+    use crate::problems::ShortestPath;
+
+    #[test]
+    fn test_floyd_warshall_agrees_with_dijkstra_from_every_source() {
+        let graph = ShortestPath::generate_random_with_seed(25, 75, 0, 24, Some(11));
+        let nodes: Vec<usize> = (0..25).collect();
+
+        let floyd = floyd_warshall(&graph, &nodes, &mut MetricsRecorder::new());
+        let dijkstra = repeated_dijkstra(&graph, &nodes, &mut MetricsRecorder::new());
+        assert_eq!(floyd, dijkstra);
+        assert!(floyd.reachable_pairs() >= 25);
+
+        let single = UniformCost::new().search(&graph);
+        let expected = if single.status.is_solved() {
+            single.metrics.solution_cost
+        } else {
+            UNREACHABLE
+        };
+        assert_eq!(floyd.get(0, 24), expected);
+    }
+}
@@ -0,0 +1,171 @@
+use super::interner::{StateId, StateInterner};
+use super::memory;
+use super::{NodeStore, Problem, SearchAlgorithm, SearchTree};
+use crate::benchmarking::MetricsRecorder;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::mem::size_of;
+
+const UNKNOWN: usize = usize::MAX;
+
+pub struct AnytimeWeightedAStar {
+    pub weight: f64,
+    pub cost_bound: Option<usize>,
+}
+
+impl AnytimeWeightedAStar {
+    pub fn new(weight: f64) -> Self {
+        AnytimeWeightedAStar {
+            weight: weight.max(1.0),
+            cost_bound: None,
+        }
+    }
+
+    pub fn with_cost_bound(mut self, cost_bound: Option<usize>) -> Self {
+        self.cost_bound = cost_bound;
+        self
+    }
+
+    fn weighted(&self, g: usize, h: usize) -> usize {
+        g + (h as f64 * self.weight).ceil() as usize
+    }
+}
+
+impl SearchAlgorithm for AnytimeWeightedAStar {
+    fn search_recorded<P: Problem>(
+        &self,
+        problem: &P,
+        recorder: &mut MetricsRecorder,
+    ) -> Option<Vec<P::Action>> {
+        let mut interner = StateInterner::new();
+        let mut tree = SearchTree::new();
+        let mut frontier = BinaryHeap::new();
+        let mut g_scores = Vec::new();
+        let mut h_scores = Vec::new();
+        let mut expanded = Vec::new();
+        for initial_state in problem.initial_states() {
+            let h = problem.heuristic(&initial_state);
+            let (initial_id, is_new) = interner.intern(initial_state);
+            if is_new {
+                g_scores.push(0);
+                h_scores.push(h);
+                expanded.push(false);
+                frontier.push(Reverse((self.weighted(0, h), 0, tree.add_root(initial_id))));
+                recorder.generate();
+            }
+        }
+
+        let heap_bytes = problem.state_heap_bytes(&problem.initial_state());
+        let memory_kb =
+            |interner: &StateInterner<P::State>,
+             tree: &SearchTree<StateId, P::Action>,
+             frontier: &BinaryHeap<Reverse<(usize, usize, usize)>>| {
+                memory::kb(
+                    interner.memory_bytes(heap_bytes)
+                        + interner.len() * (2 * size_of::<usize>() + size_of::<bool>())
+                        + tree.memory_bytes(0)
+                        + memory::vec_bytes::<Reverse<(usize, usize, usize)>>(frontier.capacity()),
+                )
+            };
+
+        let mut incumbent: Option<(usize, Vec<P::Action>)> = None;
+        let pruned = |incumbent: &Option<(usize, Vec<P::Action>)>, f: usize| {
+            incumbent.as_ref().is_some_and(|(cost, _)| f >= *cost)
+        };
+        let mut successors = Vec::new();
+
+        while let Some(Reverse((weighted_f, path_cost, id))) = frontier.pop() {
+            let state_id = tree.get(id).state;
+            if path_cost > g_scores[state_id.index()]
+                || pruned(&incumbent, path_cost + h_scores[state_id.index()])
+            {
+                continue;
+            }
+
+            let state = interner.get(state_id).clone();
+            recorder.visit();
+            recorder.sample_frontier(weighted_f, frontier.len());
+
+            if problem.is_goal(&state) {
+                let actions = tree.extract_solution(id);
+                recorder.improve_incumbent(&actions, path_cost);
+                recorder.set_solution_length(tree.get(id).depth);
+                recorder.set_solution_cost(path_cost);
+                incumbent = Some((path_cost, actions));
+                continue;
+            }
+
+            if expanded[state_id.index()] {
+                recorder.reexpand();
+            }
+            expanded[state_id.index()] = true;
+
+            problem.successors_into(&state, &mut successors);
+            for (action, successor_state, cost) in successors.drain(..) {
+                let tentative_g = path_cost + cost;
+                if self.cost_bound.is_some_and(|bound| tentative_g > bound) {
+                    continue;
+                }
+
+                let h = problem.heuristic(&successor_state);
+                if pruned(&incumbent, tentative_g + h) {
+                    continue;
+                }
+                let (successor_id, is_new) = interner.intern(successor_state);
+                if is_new {
+                    g_scores.push(UNKNOWN);
+                    h_scores.push(h);
+                    expanded.push(false);
+                }
+                if tentative_g >= g_scores[successor_id.index()] {
+                    continue;
+                }
+                g_scores[successor_id.index()] = tentative_g;
+
+                let child = tree.add_child(id, successor_id, action, cost);
+                frontier.push(Reverse((self.weighted(tentative_g, h), tentative_g, child)));
+                recorder.generate();
+            }
+
+            recorder.observe_frontier(frontier.len());
+        }
+
+        recorder.set_memory_kb(memory_kb(&interner, &tree, &frontier));
+        incumbent.map(|(_, actions)| actions)
+    }
+
+    fn name(&self) -> &str {
+        "AWA*"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::astar::AStar;
+    use crate::problems::taquin::hardest_eight_puzzle;
+
+    #[test]
+    fn test_improves_towards_the_optimum_and_records_each_step() {
+        let taquin = hardest_eight_puzzle();
+        let optimal = AStar::new().search(&taquin).metrics.solution_cost;
+        let result = AnytimeWeightedAStar::new(5.0).search(&taquin);
+        assert!(result.status.is_solved());
+        assert_eq!(result.metrics.solution_cost, optimal);
+        assert_eq!(result.improvements.last().unwrap().cost, optimal);
+        assert!(result.improvements.len() > 1);
+        assert!(result
+            .improvements
+            .windows(2)
+            .all(|pair| pair[0].cost > pair[1].cost && pair[0].time_ms <= pair[1].time_ms));
+    }
+
+    #[test]
+    fn test_unit_weight_finds_the_optimum_in_one_step() {
+        let taquin = hardest_eight_puzzle();
+        let optimal = AStar::new().search(&taquin).metrics.solution_cost;
+        let result = AnytimeWeightedAStar::new(1.0).search(&taquin);
+        assert_eq!(result.metrics.solution_cost, optimal);
+        assert_eq!(result.improvements.len(), 1);
+    }
+}
@@ -1,196 +1,105 @@
-use super::{Node, Problem, SearchAlgorithm, SearchResult};
-use crate::benchmarking::{Metrics, SharedMetrics};
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashMap};
-use std::time::Instant;
+use super::best_first::{BestFirst, FScore};
 
-#[derive(Clone)]
-struct AStarNode<S> {
-    node: Node<S>,
-    f_score: usize,
-}
+pub type AStar = BestFirst<FScore>;
 
-impl<S> PartialEq for AStarNode<S> {
-    fn eq(&self, other: &Self) -> bool {
-        self.f_score == other.f_score
+impl AStar {
+    pub fn new() -> Self {
+        BestFirst::with_evaluation("A*", FScore)
     }
-}
-
-impl<S> Eq for AStarNode<S> {}
 
-impl<S> PartialOrd for AStarNode<S> {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+    pub fn with_memory_limit(memory_limit_kb: usize) -> Self {
+        Self::new().with_memory_limit_kb(Some(memory_limit_kb))
     }
 }
 
-impl<S> Ord for AStarNode<S> {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.f_score.cmp(&self.f_score)
+impl Default for AStar {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
-pub struct AStar;
-
-impl SearchAlgorithm for AStar {
-    fn search<P: Problem>(&self, problem: &P) -> SearchResult {
-        let start = Instant::now();
-        let mut metrics = Metrics::default();
-
-        let initial_state = problem.initial_state();
-        let initial_h = problem.heuristic(&initial_state);
-        let initial_node = Node::new(initial_state.clone());
-
-        let mut frontier = BinaryHeap::new();
-        frontier.push(AStarNode {
-            node: initial_node,
-            f_score: initial_h,
-        });
-
-        let mut explored = HashMap::new();
-        let mut g_scores = HashMap::new();
-        g_scores.insert(initial_state, 0);
-
-        metrics.nodes_generated = 1;
-
-        while let Some(astar_node) = frontier.pop() {
-            let node = astar_node.node;
-            metrics.nodes_visited += 1;
-
-            if problem.is_goal(&node.state) {
-                let solution = node.extract_solution();
-                metrics.solution_length = solution.len();
-                metrics.time_ms = start.elapsed().as_millis() as f64;
-                metrics.memory_kb =
-                    (explored.len() + frontier.len()) * std::mem::size_of::<P::State>() / 1024;
-
-                return SearchResult {
-                    solution: Some(solution),
-                    metrics,
-                    status: 0,
-                };
-            }
-
-            if explored.contains_key(&node.state) {
-                continue;
-            }
-
-            explored.insert(node.state.clone(), node.path_cost);
-
-            for (successor_state, cost) in problem.successors(&node.state) {
-                let tentative_g = node.path_cost + cost;
-
-                if let Some(&existing_g) = g_scores.get(&successor_state) {
-                    if tentative_g >= existing_g {
-                        continue;
-                    }
-                }
-
-                g_scores.insert(successor_state.clone(), tentative_g);
-                let h = problem.heuristic(&successor_state);
-                let f = tentative_g + h;
-
-                let child = node.child(successor_state, metrics.nodes_generated, cost);
-                frontier.push(AStarNode {
-                    node: child,
-                    f_score: f,
-                });
-                metrics.nodes_generated += 1;
-            }
-
-            metrics.max_frontier_size = metrics.max_frontier_size.max(frontier.len());
-        }
-
-        metrics.time_ms = start.elapsed().as_millis() as f64;
-        metrics.memory_kb = explored.len() * std::mem::size_of::<P::State>() / 1024;
-
-        SearchResult {
-            solution: None,
-            metrics,
-            status: 2,
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::SearchAlgorithm;
+    use crate::benchmarking::TieBreaking;
+    use crate::problems::taquin::hardest_eight_puzzle;
+    use crate::problems::ShortestPath;
+
+    #[test]
+    fn test_memory_limit_evicts_but_stays_optimal() {
+        let taquin = hardest_eight_puzzle();
+        let unbounded = AStar::new().search(&taquin);
+        let bounded = AStar::with_memory_limit(1).search(&taquin);
+
+        assert!(bounded.status.is_solved());
+        assert_eq!(
+            bounded.metrics.solution_length,
+            unbounded.metrics.solution_length
+        );
+        assert!(bounded.metrics.evictions > 0);
+        assert_eq!(unbounded.metrics.evictions, 0);
     }
 
-    fn search_with_shared_metrics<P: Problem>(
-        &self,
-        problem: &P,
-        shared: SharedMetrics,
-    ) -> SearchResult {
-        let initial_state = problem.initial_state();
-        let initial_h = problem.heuristic(&initial_state);
-        let initial_node = Node::new(initial_state.clone());
-
-        let mut frontier = BinaryHeap::new();
-        frontier.push(AStarNode {
-            node: initial_node,
-            f_score: initial_h,
-        });
-
-        let mut explored = HashMap::new();
-        let mut g_scores = HashMap::new();
-        g_scores.insert(initial_state, 0);
-
-        shared.update(|m| m.nodes_generated = 1);
-
-        while let Some(astar_node) = frontier.pop() {
-            let node = astar_node.node;
-            shared.increment_visited();
-
-            if problem.is_goal(&node.state) {
-                let solution = node.extract_solution();
-                shared.set_solution_length(solution.len());
-                shared.set_memory_kb(
-                    (explored.len() + frontier.len()) * std::mem::size_of::<P::State>() / 1024,
-                );
-
-                return SearchResult {
-                    solution: Some(solution),
-                    metrics: shared.get(),
-                    status: 0,
-                };
-            }
-
-            if explored.contains_key(&node.state) {
-                continue;
-            }
-
-            explored.insert(node.state.clone(), node.path_cost);
-
-            for (successor_state, cost) in problem.successors(&node.state) {
-                let tentative_g = node.path_cost + cost;
-
-                if let Some(&existing_g) = g_scores.get(&successor_state) {
-                    if tentative_g >= existing_g {
-                        continue;
-                    }
-                }
-
-                g_scores.insert(successor_state.clone(), tentative_g);
-                let h = problem.heuristic(&successor_state);
-                let f = tentative_g + h;
-
-                let generated = shared.get().nodes_generated;
-                let child = node.child(successor_state, generated, cost);
-                frontier.push(AStarNode {
-                    node: child,
-                    f_score: f,
-                });
-                shared.increment_generated();
-            }
-
-            shared.update_max_frontier(frontier.len());
+    #[test]
+    fn test_reexpansion_recovers_optimum_with_inconsistent_heuristic() {
+        let mut graph = ShortestPath::new(0, 3);
+        graph.add_edge(0, 1, 4);
+        graph.add_edge(0, 2, 1);
+        graph.add_edge(2, 1, 1);
+        graph.add_edge(1, 3, 10);
+        graph.set_heuristic(2, 11);
+
+        for astar in [AStar::new(), AStar::with_memory_limit(1024)] {
+            let pruned = astar.search(&graph);
+            assert_eq!(pruned.metrics.solution_cost, 14);
+            assert_eq!(pruned.metrics.reexpansions, 0);
+
+            let reexpanded = astar.with_reexpansion(true).search(&graph);
+            assert_eq!(reexpanded.metrics.solution_cost, 12);
+            assert_eq!(reexpanded.metrics.reexpansions, 1);
         }
+    }
 
-        shared.set_memory_kb(explored.len() * std::mem::size_of::<P::State>() / 1024);
-
-        SearchResult {
-            solution: None,
-            metrics: shared.get(),
-            status: 2,
-        }
+    #[test]
+    fn test_lazy_duplicate_detection_counts_duplicate_pops() {
+        let taquin = hardest_eight_puzzle();
+        let eager = AStar::new().search(&taquin);
+        let lazy = AStar::new()
+            .with_lazy_duplicate_detection(true)
+            .search(&taquin);
+        assert_eq!(lazy.metrics.solution_cost, eager.metrics.solution_cost);
+        assert!(lazy.metrics.nodes_generated > eager.metrics.nodes_generated);
+        assert!(lazy.metrics.duplicates > 0);
+        assert_eq!(lazy.metrics.reexpansions, 0);
+        assert_eq!(eager.metrics.reexpansions, 0);
+
+        let mut graph = ShortestPath::new(0, 3);
+        graph.add_edge(0, 1, 4);
+        graph.add_edge(0, 2, 1);
+        graph.add_edge(2, 1, 1);
+        graph.add_edge(1, 3, 10);
+        graph.set_heuristic(2, 11);
+        let lazy = AStar::new().with_lazy_duplicate_detection(true);
+        assert_eq!(lazy.search(&graph).metrics.solution_cost, 14);
+        let reopened = lazy.with_reexpansion(true).search(&graph);
+        assert_eq!(reopened.metrics.solution_cost, 12);
+        assert_eq!(reopened.metrics.reexpansions, 1);
     }
 
-    fn name(&self) -> &str {
-        "A*"
+    #[test]
+    fn test_tie_breaking_changes_expansions_not_cost() {
+        let grid = ShortestPath::generate_grid_between(20, 20, 0, 399);
+        let expansions = |policy| {
+            let result = AStar::new().with_tie_breaking(policy).search(&grid);
+            assert_eq!(result.metrics.solution_cost, 38);
+            result.metrics.nodes_visited
+        };
+
+        let deepest = expansions(TieBreaking::HighG);
+        assert_eq!(deepest, 39);
+        assert_eq!(expansions(TieBreaking::LowH), deepest);
+        assert!(expansions(TieBreaking::Fifo) > 10 * deepest);
+        assert!(expansions(TieBreaking::Lifo) < expansions(TieBreaking::Fifo));
     }
 }
@@ -0,0 +1,228 @@
+use super::{
+    ActionList, BackwardProblem, ExternalCalls, HeuristicSuccessors, Problem, SearchResult,
+    SearchStatus, SuccessorList, Successors,
+};
+use crate::utils::hashing::StateSet;
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Regression<S> {
+    pub from: S,
+    pub to: S,
+    pub cost: usize,
+}
+
+impl<S: fmt::Debug> fmt::Display for Regression<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} <- {:?}", self.from, self.to)
+    }
+}
+
+#[derive(Clone)]
+pub struct Backward<'a, P: BackwardProblem> {
+    problem: &'a P,
+    goals: Vec<P::State>,
+    origins: Vec<P::State>,
+    starts: StateSet<P::State>,
+}
+
+impl<'a, P: BackwardProblem> Backward<'a, P> {
+    pub fn new(problem: &'a P) -> Self {
+        let origins = problem.initial_states();
+        Backward {
+            problem,
+            goals: problem.goal_states(),
+            starts: origins.iter().cloned().collect(),
+            origins,
+        }
+    }
+
+    pub fn forward_plan(&self, steps: &[Regression<P::State>]) -> Result<Vec<P::Action>, String> {
+        steps
+            .iter()
+            .rev()
+            .map(|step| {
+                self.problem
+                    .actions(&step.to)
+                    .into_iter()
+                    .find(|action| {
+                        self.problem.apply(&step.to, action) == (step.from.clone(), step.cost)
+                    })
+                    .ok_or_else(|| format!("aucune action de {:?} vers {:?}", step.to, step.from))
+            })
+            .collect()
+    }
+
+    pub fn into_forward(
+        &self,
+        result: SearchResult<Regression<P::State>>,
+    ) -> SearchResult<P::Action> {
+        let (solution, status) = match result.solution.map(|steps| self.forward_plan(&steps)) {
+            Some(Ok(actions)) => (Some(actions), result.status),
+            Some(Err(e)) => (
+                None,
+                SearchStatus::Error(format!("Régression invalide: {}", e)),
+            ),
+            None => (None, result.status),
+        };
+        SearchResult {
+            solution,
+            metrics: result.metrics,
+            status,
+            profile: result.profile,
+            improvements: result.improvements,
+        }
+    }
+}
+
+impl<P: BackwardProblem> Problem for Backward<'_, P> {
+    type State = P::State;
+    type Action = Regression<P::State>;
+
+    fn initial_state(&self) -> Self::State {
+        self.goals
+            .first()
+            .cloned()
+            .expect("recherche arrière sans état but")
+    }
+
+    fn initial_states(&self) -> Vec<Self::State> {
+        self.goals.clone()
+    }
+
+    fn is_goal(&self, state: &Self::State) -> bool {
+        self.starts.contains(state)
+    }
+
+    fn actions(&self, state: &Self::State) -> ActionList<Self::Action> {
+        self.problem
+            .predecessors(state)
+            .into_iter()
+            .map(|(to, cost)| Regression {
+                from: state.clone(),
+                to,
+                cost,
+            })
+            .collect()
+    }
+
+    fn apply(&self, _state: &Self::State, action: &Self::Action) -> (Self::State, usize) {
+        (action.to.clone(), action.cost)
+    }
+
+    fn successors_into(&self, state: &Self::State, buffer: &mut Successors<Self>) {
+        buffer.clear();
+        for (to, cost) in self.problem.predecessors(state) {
+            let regression = Regression {
+                from: state.clone(),
+                to: to.clone(),
+                cost,
+            };
+            buffer.push((regression, to, cost));
+        }
+    }
+
+    fn successors_with_heuristic_into(
+        &self,
+        state: &Self::State,
+        _heuristic: usize,
+        buffer: &mut HeuristicSuccessors<Self>,
+    ) {
+        buffer.clear();
+        for (to, cost) in self.problem.predecessors(state) {
+            let h = self.heuristic(&to);
+            let regression = Regression {
+                from: state.clone(),
+                to: to.clone(),
+                cost,
+            };
+            buffer.push((regression, to, cost, h));
+        }
+    }
+
+    fn heuristic(&self, state: &Self::State) -> usize {
+        self.origins
+            .iter()
+            .map(|origin| self.problem.heuristic_between(origin, state))
+            .min()
+            .unwrap_or(0)
+    }
+
+    fn description(&self) -> String {
+        format!("{} (recherche arrière)", self.problem.description())
+    }
+
+    fn state_heap_bytes(&self, state: &Self::State) -> usize {
+        self.problem.state_heap_bytes(state)
+    }
+
+    fn encode_state(&self, state: &Self::State) -> String {
+        self.problem.encode_state(state)
+    }
+
+    fn take_external_calls(&self) -> Option<ExternalCalls> {
+        self.problem.take_external_calls()
+    }
+}
+
+impl<P: BackwardProblem> BackwardProblem for Backward<'_, P> {
+    fn goal_states(&self) -> Vec<Self::State> {
+        self.problem.initial_states()
+    }
+
+    fn predecessors(&self, state: &Self::State) -> SuccessorList<Self::State> {
+        self.problem.successors(state)
+    }
+
+    fn heuristic_between(&self, from: &Self::State, to: &Self::State) -> usize {
+        self.problem.heuristic_between(to, from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::uniform_cost::UniformCost;
+    use crate::algorithms::validation::validate_solution;
+    use crate::algorithms::{astar::AStar, bfs::BFS, SearchAlgorithm};
+    use crate::problems::taquin::HeuristicType;
+    use crate::problems::{ShortestPath, Taquin};
+
+    #[test]
+    fn test_backward_plan_replays_forward() {
+        let mut graph = ShortestPath::new(0, 3);
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(1, 3, 5);
+        graph.add_edge(0, 2, 2);
+        graph.add_edge(2, 3, 1);
+        graph.add_edge(4, 3, 1);
+
+        let backward = Backward::new(&graph);
+        assert_eq!(backward.initial_states(), vec![3]);
+        let result = backward.into_forward(AStar::new().search(&backward));
+        let plan = result.solution.unwrap();
+        assert_eq!(result.metrics.solution_cost, 3);
+        assert!(validate_solution(&graph, &plan, &result.metrics).is_ok());
+
+        let taquin =
+            Taquin::from_state(3, vec![1, 2, 5, 3, 4, 0, 6, 7, 8], HeuristicType::Manhattan);
+        let backward = Backward::new(&taquin);
+        let result = backward.into_forward(BFS::new().search(&backward));
+        let plan = result.solution.unwrap();
+        assert_eq!(plan.len(), 3);
+        assert!(validate_solution(&taquin, &plan, &result.metrics).is_ok());
+    }
+
+    #[test]
+    fn test_backward_heuristic_estimates_distance_to_start() {
+        let grid = ShortestPath::generate_grid_between(8, 8, 0, 63);
+        let backward = Backward::new(&grid);
+        assert_eq!(backward.heuristic(&63), 14);
+        assert_eq!(backward.heuristic(&0), 0);
+
+        let astar = AStar::new().search(&backward);
+        let dijkstra = UniformCost::new().search(&backward);
+        assert_eq!(astar.metrics.solution_cost, dijkstra.metrics.solution_cost);
+        assert!(astar.metrics.nodes_visited < dijkstra.metrics.nodes_visited);
+    }
+}
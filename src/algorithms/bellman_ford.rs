@@ -0,0 +1,186 @@
+use super::interner::{StateId, StateInterner};
+use super::memory;
+use super::{Problem, SearchAlgorithm};
+use crate::benchmarking::MetricsRecorder;
+use std::collections::VecDeque;
+use std::mem::size_of;
+
+struct Transition<A> {
+    from: StateId,
+    to: StateId,
+    action: A,
+    weight: i64,
+}
+
+pub struct BellmanFord {
+    pub track_solutions: bool,
+}
+
+impl BellmanFord {
+    pub fn new() -> Self {
+        BellmanFord {
+            track_solutions: true,
+        }
+    }
+
+    pub fn with_solution_tracking(mut self, track_solutions: bool) -> Self {
+        self.track_solutions = track_solutions;
+        self
+    }
+}
+
+impl Default for BellmanFord {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SearchAlgorithm for BellmanFord {
+    fn search_recorded<P: Problem>(
+        &self,
+        problem: &P,
+        recorder: &mut MetricsRecorder,
+    ) -> Option<Vec<P::Action>> {
+        let mut interner = StateInterner::new();
+        let mut queue = VecDeque::new();
+        let mut starts = Vec::new();
+        for state in problem.initial_states() {
+            let (id, is_new) = interner.intern(state);
+            if is_new {
+                starts.push(id);
+                queue.push_back(id);
+                recorder.generate();
+            }
+        }
+
+        let mut arcs = Vec::new();
+        let mut goals = Vec::new();
+        let mut successors = Vec::new();
+        while let Some(id) = queue.pop_front() {
+            let state = interner.get(id).clone();
+            recorder.visit();
+            if problem.is_goal(&state) {
+                goals.push(id);
+            }
+            problem.successors_into(&state, &mut successors);
+            for (action, next, _) in successors.drain(..) {
+                let weight = problem.signed_cost(&state, &action);
+                let (next_id, is_new) = interner.intern(next);
+                if is_new {
+                    queue.push_back(next_id);
+                    recorder.generate();
+                }
+                arcs.push(Transition {
+                    from: id,
+                    to: next_id,
+                    action,
+                    weight,
+                });
+            }
+            recorder.observe_frontier(queue.len());
+        }
+
+        let heap_bytes = problem.state_heap_bytes(&problem.initial_state());
+        recorder.set_memory_kb(memory::kb(
+            interner.memory_bytes(heap_bytes)
+                + memory::vec_bytes::<Transition<P::Action>>(arcs.capacity())
+                + interner.len() * (size_of::<Option<i64>>() + size_of::<Option<usize>>()),
+        ));
+
+        let mut distance: Vec<Option<i64>> = vec![None; interner.len()];
+        let mut via: Vec<Option<usize>> = vec![None; interner.len()];
+        for &id in &starts {
+            distance[id.index()] = Some(0);
+        }
+        let relax = |distance: &mut Vec<Option<i64>>, via: &mut Vec<Option<usize>>| {
+            let mut changed = false;
+            for (index, arc) in arcs.iter().enumerate() {
+                let Some(from) = distance[arc.from.index()] else {
+                    continue;
+                };
+                let candidate = from + arc.weight;
+                if distance[arc.to.index()].is_none_or(|known| candidate < known) {
+                    distance[arc.to.index()] = Some(candidate);
+                    via[arc.to.index()] = Some(index);
+                    changed = true;
+                }
+            }
+            changed
+        };
+
+        let mut converged = false;
+        for pass in 1..interner.len().max(1) {
+            if !relax(&mut distance, &mut via) {
+                converged = true;
+                break;
+            }
+            recorder.sample_frontier(pass, arcs.len());
+        }
+        if !converged && relax(&mut distance, &mut via) {
+            recorder.mark_negative_cycle();
+            return None;
+        }
+
+        let goal = goals
+            .into_iter()
+            .min_by_key(|goal| distance[goal.index()])?;
+
+        let mut actions = Vec::new();
+        let mut cost = 0;
+        let mut current = goal.index();
+        while let Some(index) = via[current] {
+            let arc = &arcs[index];
+            let (_, step_cost) = problem.apply(interner.get(arc.from), &arc.action);
+            cost += step_cost;
+            actions.push(arc.action.clone());
+            current = arc.from.index();
+        }
+        actions.reverse();
+        recorder.set_solution_length(actions.len());
+        recorder.set_solution_cost(cost);
+        recorder.set_signed_cost(distance[goal.index()].expect("but atteint"));
+        Some(if self.track_solutions {
+            actions
+        } else {
+            Vec::new()
+        })
+    }
+
+    fn name(&self) -> &str {
+        "Bellman-Ford"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::uniform_cost::UniformCost;
+    use crate::algorithms::validation;
+    use crate::algorithms::SearchStatus;
+    use crate::problems::ShortestPath;
+
+    #[test]
+    fn test_follows_negative_edges_and_reports_negative_cycles() {
+        let random = ShortestPath::generate_connected_with_seed(40, 120, 0, 39, 3);
+        let expected = UniformCost::new().search(&random).metrics.solution_cost;
+        let result = BellmanFord::new().search(&random);
+        assert!(result.status.is_solved());
+        assert_eq!(result.metrics.signed_cost, Some(expected as i64));
+
+        let mut signed = ShortestPath::new(0, 3);
+        signed.add_signed_edge(0, 3, 4);
+        signed.add_signed_edge(0, 1, 2);
+        signed.add_signed_edge(1, 2, 5);
+        signed.add_signed_edge(2, 3, -6);
+        let result = BellmanFord::new().search(&signed);
+        assert!(result.status.is_solved());
+        assert_eq!(result.metrics.signed_cost, Some(1));
+        assert_eq!(result.metrics.solution_length, 3);
+        let actions = result.solution.unwrap();
+        validation::validate_solution(&signed, &actions, &result.metrics).unwrap();
+
+        signed.add_signed_edge(2, 1, -6);
+        let result = BellmanFord::new().search(&signed);
+        assert_eq!(result.status, SearchStatus::NegativeCycle);
+    }
+}
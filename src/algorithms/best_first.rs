@@ -0,0 +1,521 @@
+use super::closed_list::BoundedClosedList;
+use super::interner::{StateId, StateInterner};
+use super::memory;
+use super::{NodePool, NodeStore, Problem, SearchAlgorithm, SearchTree};
+use crate::benchmarking::{MetricsRecorder, TieBreaking};
+use crate::utils::hashing::StateMap;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::mem::size_of;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Node {
+    pub g: usize,
+    pub h: usize,
+    pub depth: usize,
+}
+
+pub trait Evaluation {
+    fn evaluate<P: Problem>(&self, node: &Node, state: &P::State, problem: &P) -> u64;
+}
+
+impl<F: Fn(&Node) -> u64> Evaluation for F {
+    fn evaluate<P: Problem>(&self, node: &Node, _: &P::State, _: &P) -> u64 {
+        self(node)
+    }
+}
+
+pub struct FScore;
+
+impl Evaluation for FScore {
+    fn evaluate<P: Problem>(&self, node: &Node, _: &P::State, _: &P) -> u64 {
+        (node.g + node.h) as u64
+    }
+}
+
+pub struct HScore;
+
+impl Evaluation for HScore {
+    fn evaluate<P: Problem>(&self, node: &Node, _: &P::State, _: &P) -> u64 {
+        node.h as u64
+    }
+}
+
+#[derive(Clone, Copy)]
+struct FrontierNode {
+    id: usize,
+    key: u64,
+    tie: usize,
+    h: usize,
+}
+
+impl PartialEq for FrontierNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key && self.tie == other.tie
+    }
+}
+
+impl Eq for FrontierNode {}
+
+impl PartialOrd for FrontierNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FrontierNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.key.cmp(&self.key).then(self.tie.cmp(&other.tie))
+    }
+}
+
+struct TieKeys {
+    policy: TieBreaking,
+    sequence: usize,
+}
+
+impl TieKeys {
+    fn new(policy: TieBreaking) -> Self {
+        TieKeys {
+            policy,
+            sequence: 0,
+        }
+    }
+
+    fn next(&mut self, g: usize, h: usize) -> usize {
+        self.sequence += 1;
+        match self.policy {
+            TieBreaking::HighG => g,
+            TieBreaking::LowH => usize::MAX - h,
+            TieBreaking::Fifo => usize::MAX - self.sequence,
+            TieBreaking::Lifo => self.sequence,
+        }
+    }
+}
+
+struct Keys<'a, E, P: Problem> {
+    evaluation: &'a E,
+    problem: &'a P,
+    ties: TieKeys,
+}
+
+impl<E: Evaluation, P: Problem> Keys<'_, E, P> {
+    fn entry(&mut self, state: &P::State, node: Node) -> impl FnOnce(usize) -> FrontierNode {
+        let key = self.evaluation.evaluate(&node, state, self.problem);
+        let tie = self.ties.next(node.g, node.h);
+        move |id| FrontierNode {
+            id,
+            key,
+            tie,
+            h: node.h,
+        }
+    }
+}
+
+pub struct BestFirst<E> {
+    pub name: String,
+    pub evaluation: E,
+    pub memory_limit_kb: Option<usize>,
+    pub track_solutions: bool,
+    pub reexpand: bool,
+    pub cost_bound: Option<usize>,
+    pub tie_breaking: TieBreaking,
+    pub lazy_duplicates: bool,
+}
+
+pub type Greedy = BestFirst<HScore>;
+
+impl Greedy {
+    pub fn new() -> Self {
+        BestFirst::with_evaluation("Greedy", HScore)
+    }
+}
+
+impl Default for Greedy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: Evaluation> BestFirst<E> {
+    pub fn with_evaluation(name: &str, evaluation: E) -> Self {
+        BestFirst {
+            name: name.to_string(),
+            evaluation,
+            memory_limit_kb: None,
+            track_solutions: true,
+            reexpand: false,
+            cost_bound: None,
+            tie_breaking: TieBreaking::HighG,
+            lazy_duplicates: false,
+        }
+    }
+
+    pub fn with_memory_limit_kb(mut self, memory_limit_kb: Option<usize>) -> Self {
+        self.memory_limit_kb = memory_limit_kb;
+        self
+    }
+
+    pub fn with_solution_tracking(mut self, track_solutions: bool) -> Self {
+        self.track_solutions = track_solutions;
+        self
+    }
+
+    pub fn with_reexpansion(mut self, reexpand: bool) -> Self {
+        self.reexpand = reexpand;
+        self
+    }
+
+    pub fn with_cost_bound(mut self, cost_bound: Option<usize>) -> Self {
+        self.cost_bound = cost_bound;
+        self
+    }
+
+    pub fn with_tie_breaking(mut self, tie_breaking: TieBreaking) -> Self {
+        self.tie_breaking = tie_breaking;
+        self
+    }
+
+    pub fn with_lazy_duplicate_detection(mut self, lazy_duplicates: bool) -> Self {
+        self.lazy_duplicates = lazy_duplicates;
+        self
+    }
+
+    fn search_lazy<P: Problem, T: NodeStore<P::State, P::Action>>(
+        &self,
+        problem: &P,
+        recorder: &mut MetricsRecorder,
+        mut tree: T,
+    ) -> Option<Vec<P::Action>> {
+        let mut keys = Keys {
+            evaluation: &self.evaluation,
+            problem,
+            ties: TieKeys::new(self.tie_breaking),
+        };
+        let mut frontier = BinaryHeap::new();
+        let mut closed: StateMap<P::State, usize> = StateMap::default();
+        let heap_bytes = problem.state_heap_bytes(&problem.initial_state());
+        for initial_state in problem.initial_states() {
+            let h = problem.heuristic(&initial_state);
+            let entry = keys.entry(&initial_state, Node { g: 0, h, depth: 0 });
+            frontier.push(entry(tree.add_root(initial_state)));
+            recorder.generate();
+        }
+        let memory_kb =
+            |closed: &StateMap<P::State, usize>, tree: &T, frontier: &BinaryHeap<FrontierNode>| {
+                memory::kb(
+                    memory::hash_table_bytes::<(P::State, usize)>(closed.len())
+                        + closed.len() * heap_bytes
+                        + tree.memory_bytes(heap_bytes)
+                        + memory::vec_bytes::<FrontierNode>(frontier.capacity()),
+                )
+            };
+
+        let mut successors = Vec::new();
+
+        while let Some(frontier_node) = frontier.pop() {
+            let id = frontier_node.id;
+            let state = tree.get(id).state.clone();
+            let path_cost = tree.get(id).path_cost;
+            let depth = tree.get(id).depth;
+            recorder.visit();
+            recorder.sample_frontier(frontier_node.key as usize, frontier.len());
+
+            if problem.is_goal(&state) {
+                recorder.set_memory_kb(memory_kb(&closed, &tree, &frontier));
+                recorder.set_solution_length(depth);
+                recorder.set_solution_cost(path_cost);
+                return Some(tree.extract_solution(id));
+            }
+
+            if let Some(&closed_g) = closed.get(&state) {
+                if !self.reexpand || path_cost >= closed_g {
+                    recorder.skip_duplicate();
+                    tree.release(id);
+                    continue;
+                }
+                recorder.reexpand();
+            }
+            closed.insert(state.clone(), path_cost);
+
+            problem.successors_with_heuristic_into(&state, frontier_node.h, &mut successors);
+            for (action, successor_state, cost, h) in successors.drain(..) {
+                let tentative_g = path_cost + cost;
+                if self.cost_bound.is_some_and(|bound| tentative_g > bound) {
+                    continue;
+                }
+                let node = Node {
+                    g: tentative_g,
+                    h,
+                    depth: depth + 1,
+                };
+                let entry = keys.entry(&successor_state, node);
+                frontier.push(entry(tree.add_child(id, successor_state, action, cost)));
+                recorder.generate();
+            }
+            tree.release(id);
+
+            recorder.observe_frontier(frontier.len());
+        }
+
+        recorder.set_memory_kb(memory_kb(&closed, &tree, &frontier));
+        None
+    }
+
+    fn search_bounded<P: Problem, T: NodeStore<P::State, P::Action>>(
+        &self,
+        problem: &P,
+        recorder: &mut MetricsRecorder,
+        memory_limit_kb: usize,
+        mut tree: T,
+    ) -> Option<Vec<P::Action>> {
+        let mut keys = Keys {
+            evaluation: &self.evaluation,
+            problem,
+            ties: TieKeys::new(self.tie_breaking),
+        };
+        let mut frontier = BinaryHeap::new();
+        let mut best_g = BoundedClosedList::with_memory_limit(memory_limit_kb);
+        let heap_bytes = problem.state_heap_bytes(&problem.initial_state());
+        for initial_state in problem.initial_states() {
+            if best_g.get(&initial_state).is_some() {
+                continue;
+            }
+            let h = problem.heuristic(&initial_state);
+            let entry = keys.entry(&initial_state, Node { g: 0, h, depth: 0 });
+            frontier.push(entry(tree.add_root(initial_state.clone())));
+            best_g.insert(initial_state, (0, false));
+            recorder.generate();
+        }
+        let memory_kb = |best_g: &BoundedClosedList<P::State, (usize, bool)>,
+                         tree: &T,
+                         frontier: &BinaryHeap<FrontierNode>| {
+            memory::kb(
+                best_g.memory_bytes(heap_bytes)
+                    + tree.memory_bytes(heap_bytes)
+                    + memory::vec_bytes::<FrontierNode>(frontier.capacity()),
+            )
+        };
+
+        let mut successors = Vec::new();
+
+        while let Some(frontier_node) = frontier.pop() {
+            let id = frontier_node.id;
+            let state = tree.get(id).state.clone();
+            let path_cost = tree.get(id).path_cost;
+            let depth = tree.get(id).depth;
+            recorder.visit();
+            recorder.sample_frontier(frontier_node.key as usize, frontier.len());
+
+            if problem.is_goal(&state) {
+                recorder.set_memory_kb(memory_kb(&best_g, &tree, &frontier));
+                recorder.set_solution_length(depth);
+                recorder.set_solution_cost(path_cost);
+                return Some(tree.extract_solution(id));
+            }
+
+            if best_g.get(&state).is_some_and(|&(g, _)| g < path_cost) {
+                tree.release(id);
+                continue;
+            }
+
+            if let Some((_, expanded)) = best_g.get_mut(&state) {
+                if *expanded {
+                    if !self.reexpand {
+                        tree.release(id);
+                        continue;
+                    }
+                    recorder.reexpand();
+                }
+                *expanded = true;
+            }
+
+            problem.successors_with_heuristic_into(&state, frontier_node.h, &mut successors);
+            for (action, successor_state, cost, h) in successors.drain(..) {
+                let tentative_g = path_cost + cost;
+                if self.cost_bound.is_some_and(|bound| tentative_g > bound) {
+                    continue;
+                }
+
+                let expanded = match best_g.get(&successor_state) {
+                    Some(&(existing_g, _)) if tentative_g >= existing_g => continue,
+                    Some(&(_, expanded)) => expanded,
+                    None => false,
+                };
+
+                recorder
+                    .add_evictions(best_g.insert(successor_state.clone(), (tentative_g, expanded)));
+                let node = Node {
+                    g: tentative_g,
+                    h,
+                    depth: depth + 1,
+                };
+                let entry = keys.entry(&successor_state, node);
+                frontier.push(entry(tree.add_child(id, successor_state, action, cost)));
+                recorder.generate();
+            }
+            tree.release(id);
+
+            recorder.observe_frontier(frontier.len());
+        }
+
+        recorder.set_memory_kb(memory_kb(&best_g, &tree, &frontier));
+        None
+    }
+
+    fn search_interned<P: Problem, T: NodeStore<StateId, P::Action>>(
+        &self,
+        problem: &P,
+        recorder: &mut MetricsRecorder,
+        mut tree: T,
+    ) -> Option<Vec<P::Action>> {
+        let mut keys = Keys {
+            evaluation: &self.evaluation,
+            problem,
+            ties: TieKeys::new(self.tie_breaking),
+        };
+        let mut interner = StateInterner::new();
+        let mut frontier = BinaryHeap::new();
+        for initial_state in problem.initial_states() {
+            let h = problem.heuristic(&initial_state);
+            let (initial_id, is_new) = interner.intern(initial_state);
+            if is_new {
+                let entry = keys.entry(interner.get(initial_id), Node { g: 0, h, depth: 0 });
+                frontier.push(entry(tree.add_root(initial_id)));
+                recorder.generate();
+            }
+        }
+
+        let mut explored = vec![false; interner.len()];
+        let mut g_scores = vec![0; interner.len()];
+        let heap_bytes = problem.state_heap_bytes(&problem.initial_state());
+        let memory_kb =
+            |interner: &StateInterner<P::State>, tree: &T, frontier: &BinaryHeap<FrontierNode>| {
+                memory::kb(
+                    interner.memory_bytes(heap_bytes)
+                        + interner.len() * (size_of::<bool>() + size_of::<usize>())
+                        + tree.memory_bytes(0)
+                        + memory::vec_bytes::<FrontierNode>(frontier.capacity()),
+                )
+            };
+
+        let mut successors = Vec::new();
+
+        while let Some(frontier_node) = frontier.pop() {
+            let id = frontier_node.id;
+            let state_id = tree.get(id).state;
+            let state = interner.get(state_id).clone();
+            let path_cost = tree.get(id).path_cost;
+            let depth = tree.get(id).depth;
+            recorder.visit();
+            recorder.sample_frontier(frontier_node.key as usize, frontier.len());
+
+            if problem.is_goal(&state) {
+                recorder.set_memory_kb(memory_kb(&interner, &tree, &frontier));
+                recorder.set_solution_length(depth);
+                recorder.set_solution_cost(path_cost);
+                return Some(tree.extract_solution(id));
+            }
+
+            if explored[state_id.index()] {
+                if !self.reexpand || path_cost > g_scores[state_id.index()] {
+                    tree.release(id);
+                    continue;
+                }
+                recorder.reexpand();
+            }
+
+            explored[state_id.index()] = true;
+
+            problem.successors_with_heuristic_into(&state, frontier_node.h, &mut successors);
+            for (action, successor_state, cost, h) in successors.drain(..) {
+                let tentative_g = path_cost + cost;
+                if self.cost_bound.is_some_and(|bound| tentative_g > bound) {
+                    continue;
+                }
+
+                let (successor_id, is_new) = interner.intern(successor_state);
+                if is_new {
+                    explored.push(false);
+                    g_scores.push(tentative_g);
+                } else if tentative_g >= g_scores[successor_id.index()] {
+                    continue;
+                } else {
+                    g_scores[successor_id.index()] = tentative_g;
+                }
+
+                let node = Node {
+                    g: tentative_g,
+                    h,
+                    depth: depth + 1,
+                };
+                let entry = keys.entry(interner.get(successor_id), node);
+                frontier.push(entry(tree.add_child(id, successor_id, action, cost)));
+                recorder.generate();
+            }
+            tree.release(id);
+
+            recorder.observe_frontier(frontier.len());
+        }
+
+        recorder.set_memory_kb(memory_kb(&interner, &tree, &frontier));
+        None
+    }
+}
+
+impl<E: Evaluation> SearchAlgorithm for BestFirst<E> {
+    fn search_recorded<P: Problem>(
+        &self,
+        problem: &P,
+        recorder: &mut MetricsRecorder,
+    ) -> Option<Vec<P::Action>> {
+        if self.lazy_duplicates {
+            return if self.track_solutions {
+                self.search_lazy(problem, recorder, SearchTree::new())
+            } else {
+                self.search_lazy(problem, recorder, NodePool::new())
+            };
+        }
+        match (self.memory_limit_kb, self.track_solutions) {
+            (Some(limit), true) => self.search_bounded(problem, recorder, limit, SearchTree::new()),
+            (Some(limit), false) => self.search_bounded(problem, recorder, limit, NodePool::new()),
+            (None, true) => self.search_interned(problem, recorder, SearchTree::new()),
+            (None, false) => self.search_interned(problem, recorder, NodePool::new()),
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::astar::AStar;
+    use crate::algorithms::validation;
+    use crate::problems::taquin::hardest_eight_puzzle;
+
+    #[test]
+    fn test_custom_evaluations_share_the_engine() {
+        let taquin = hardest_eight_puzzle();
+        let astar = AStar::new().search(&taquin);
+        let f_score =
+            BestFirst::with_evaluation("f", |node: &Node| (node.g + node.h) as u64).search(&taquin);
+        assert_eq!(f_score.metrics.nodes_visited, astar.metrics.nodes_visited);
+        assert_eq!(f_score.metrics.solution_cost, astar.metrics.solution_cost);
+
+        let greedy = Greedy::new().search(&taquin);
+        assert!(greedy.status.is_solved());
+        assert!(greedy.metrics.nodes_visited < astar.metrics.nodes_visited);
+        assert!(greedy.metrics.solution_cost >= astar.metrics.solution_cost);
+        let actions = greedy.solution.unwrap();
+        validation::validate_solution(&taquin, &actions, &greedy.metrics).unwrap();
+
+        let weighted =
+            BestFirst::with_evaluation("wA*", |node: &Node| (node.g + 3 * node.h) as u64)
+                .with_memory_limit_kb(Some(1024))
+                .search(&taquin);
+        assert!(weighted.metrics.solution_cost <= 3 * astar.metrics.solution_cost);
+    }
+}
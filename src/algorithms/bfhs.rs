@@ -0,0 +1,86 @@
+use super::frontier::FrontierSearch;
+use super::memory;
+use super::{Problem, SearchAlgorithm};
+use crate::benchmarking::MetricsRecorder;
+
+pub struct BreadthFirstHeuristicSearch {
+    pub max_depth: usize,
+    pub track_solutions: bool,
+}
+
+impl BreadthFirstHeuristicSearch {
+    pub fn new(max_depth: usize) -> Self {
+        BreadthFirstHeuristicSearch {
+            max_depth,
+            track_solutions: true,
+        }
+    }
+
+    pub fn with_solution_tracking(mut self, track_solutions: bool) -> Self {
+        self.track_solutions = track_solutions;
+        self
+    }
+}
+
+impl SearchAlgorithm for BreadthFirstHeuristicSearch {
+    fn search_recorded<P: Problem>(
+        &self,
+        problem: &P,
+        recorder: &mut MetricsRecorder,
+    ) -> Option<Vec<P::Action>> {
+        let mut bound = problem
+            .initial_states()
+            .iter()
+            .map(|state| problem.heuristic(state))
+            .min()
+            .unwrap_or(0);
+        let mut peak_bytes = 0;
+        loop {
+            let layered = FrontierSearch::new(self.max_depth)
+                .with_solution_tracking(self.track_solutions)
+                .with_upper_bound(Some(bound));
+            match layered.search_within(problem, recorder, &mut peak_bytes) {
+                Ok(actions) => {
+                    recorder.set_memory_kb(memory::kb(peak_bytes));
+                    return Some(actions);
+                }
+                Err(Some(next_bound)) => bound = next_bound,
+                Err(None) => {
+                    recorder.set_memory_kb(memory::kb(peak_bytes));
+                    return None;
+                }
+            }
+        }
+    }
+
+    fn name(&self) -> &str {
+        "BFHS"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::astar::AStar;
+    use crate::algorithms::validation;
+    use crate::algorithms::SearchStatus;
+    use crate::problems::taquin::hardest_eight_puzzle;
+
+    #[test]
+    fn test_matches_astar_cost_with_less_memory() {
+        let taquin = hardest_eight_puzzle();
+        let astar = AStar::new().search(&taquin);
+        let result = BreadthFirstHeuristicSearch::new(100).search(&taquin);
+        assert!(result.status.is_solved());
+        assert_eq!(result.metrics.solution_cost, astar.metrics.solution_cost);
+        assert!(result.metrics.memory_kb < astar.metrics.memory_kb);
+        let actions = result.solution.unwrap();
+        validation::validate_solution(&taquin, &actions, &result.metrics).unwrap();
+    }
+
+    #[test]
+    fn test_reports_depth_limit_when_the_optimum_is_deeper() {
+        let result = BreadthFirstHeuristicSearch::new(10).search(&hardest_eight_puzzle());
+        assert_eq!(result.status, SearchStatus::DepthLimitReached);
+    }
+}
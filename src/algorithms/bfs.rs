@@ -1,115 +1,256 @@
-use super::{Node, Problem, SearchAlgorithm, SearchResult};
-use crate::benchmarking::{Metrics, SharedMetrics};
-use std::collections::{HashSet, VecDeque};
-use std::time::Instant;
+use super::closed_list::BoundedClosedList;
+use super::interner::{StateId, StateInterner};
+use super::memory;
+use super::{NodePool, NodeStore, Problem, SearchAlgorithm, SearchTree};
+use crate::benchmarking::MetricsRecorder;
+use std::collections::VecDeque;
+use std::mem::size_of;
 
-pub struct BFS;
+pub struct BFS {
+    pub memory_limit_kb: Option<usize>,
+    pub track_solutions: bool,
+    pub reexpand: bool,
+    pub early_goal_test: bool,
+    pub cost_bound: Option<usize>,
+}
 
-impl SearchAlgorithm for BFS {
-    fn search<P: Problem>(&self, problem: &P) -> SearchResult {
-        let start = Instant::now();
-        let mut metrics = Metrics::default();
+impl BFS {
+    pub fn new() -> Self {
+        BFS {
+            memory_limit_kb: None,
+            track_solutions: true,
+            reexpand: false,
+            early_goal_test: false,
+            cost_bound: None,
+        }
+    }
+
+    pub fn with_memory_limit(memory_limit_kb: usize) -> Self {
+        BFS {
+            memory_limit_kb: Some(memory_limit_kb),
+            ..Self::new()
+        }
+    }
+
+    pub fn with_solution_tracking(mut self, track_solutions: bool) -> Self {
+        self.track_solutions = track_solutions;
+        self
+    }
+
+    pub fn with_reexpansion(mut self, reexpand: bool) -> Self {
+        self.reexpand = reexpand;
+        self
+    }
+
+    pub fn with_early_goal_test(mut self, early_goal_test: bool) -> Self {
+        self.early_goal_test = early_goal_test;
+        self
+    }
+
+    pub fn with_cost_bound(mut self, cost_bound: Option<usize>) -> Self {
+        self.cost_bound = cost_bound;
+        self
+    }
 
-        let initial_node = Node::new(problem.initial_state());
+    fn exceeds_bound(&self, g: usize) -> bool {
+        self.cost_bound.is_some_and(|bound| g > bound)
+    }
+
+    fn search_bounded<P: Problem, T: NodeStore<P::State, P::Action>>(
+        &self,
+        problem: &P,
+        recorder: &mut MetricsRecorder,
+        memory_limit_kb: usize,
+        mut tree: T,
+    ) -> Option<Vec<P::Action>> {
+        let mut seen = BoundedClosedList::with_memory_limit(memory_limit_kb);
         let mut frontier = VecDeque::new();
-        frontier.push_back(initial_node);
+        for state in problem.initial_states() {
+            if seen.get(&state).is_none() {
+                seen.insert(state.clone(), (0, false));
+                frontier.push_back(tree.add_root(state));
+                recorder.generate();
+            }
+        }
 
-        let mut explored = HashSet::new();
-        metrics.nodes_generated = 1;
+        let heap_bytes = problem.state_heap_bytes(&problem.initial_state());
+        let memory_kb = |seen: &BoundedClosedList<P::State, (usize, bool)>,
+                         tree: &T,
+                         frontier: &VecDeque<usize>| {
+            memory::kb(
+                seen.memory_bytes(heap_bytes)
+                    + tree.memory_bytes(heap_bytes)
+                    + memory::vec_bytes::<usize>(frontier.capacity()),
+            )
+        };
 
-        while let Some(node) = frontier.pop_front() {
-            metrics.nodes_visited += 1;
+        let mut successors = Vec::new();
 
-            if problem.is_goal(&node.state) {
-                let solution = node.extract_solution();
-                metrics.solution_length = solution.len();
-                metrics.time_ms = start.elapsed().as_millis() as f64;
-                metrics.memory_kb =
-                    (explored.len() + frontier.len()) * std::mem::size_of::<P::State>() / 1024;
+        while let Some(id) = frontier.pop_front() {
+            recorder.visit();
+            let state = tree.get(id).state.clone();
+            let path_cost = tree.get(id).path_cost;
 
-                return SearchResult {
-                    solution: Some(solution),
-                    metrics,
-                    status: 0,
-                };
+            if self.reexpand && seen.get(&state).is_some_and(|&(g, _)| g < path_cost) {
+                tree.release(id);
+                continue;
             }
 
-            explored.insert(node.state.clone());
+            if problem.is_goal(&state) {
+                recorder.set_memory_kb(memory_kb(&seen, &tree, &frontier));
+                recorder.set_solution_length(tree.get(id).depth);
+                recorder.set_solution_cost(tree.get(id).path_cost);
+                return Some(tree.extract_solution(id));
+            }
 
-            for (successor_state, cost) in problem.successors(&node.state) {
-                if !explored.contains(&successor_state)
-                    && !frontier.iter().any(|n| n.state == successor_state)
-                {
-                    let child = node.child(successor_state, metrics.nodes_generated, cost);
-                    frontier.push_back(child);
-                    metrics.nodes_generated += 1;
+            if let Some((_, expanded)) = seen.get_mut(&state) {
+                if *expanded {
+                    recorder.reexpand();
                 }
+                *expanded = true;
             }
 
-            metrics.max_frontier_size = metrics.max_frontier_size.max(frontier.len());
-        }
-
-        metrics.time_ms = start.elapsed().as_millis() as f64;
-        metrics.memory_kb = explored.len() * std::mem::size_of::<P::State>() / 1024;
+            problem.successors_into(&state, &mut successors);
+            for (action, successor_state, cost) in successors.drain(..) {
+                let tentative_g = path_cost + cost;
+                if self.exceeds_bound(tentative_g) {
+                    continue;
+                }
+                let expanded = match seen.get(&successor_state) {
+                    None => false,
+                    Some(&(g, expanded)) if self.reexpand && tentative_g < g => expanded,
+                    Some(_) => continue,
+                };
+                recorder
+                    .add_evictions(seen.insert(successor_state.clone(), (tentative_g, expanded)));
+                let reached_goal = self.early_goal_test && problem.is_goal(&successor_state);
+                let child = tree.add_child(id, successor_state, action, cost);
+                recorder.generate();
+                if reached_goal {
+                    recorder.set_memory_kb(memory_kb(&seen, &tree, &frontier));
+                    recorder.set_solution_length(tree.get(child).depth);
+                    recorder.set_solution_cost(tree.get(child).path_cost);
+                    return Some(tree.extract_solution(child));
+                }
+                frontier.push_back(child);
+            }
+            tree.release(id);
 
-        SearchResult {
-            solution: None,
-            metrics,
-            status: 2,
+            recorder.observe_frontier(frontier.len());
         }
+
+        recorder.set_memory_kb(memory_kb(&seen, &tree, &frontier));
+        None
     }
 
-    fn search_with_shared_metrics<P: Problem>(
+    fn search_interned<P: Problem, T: NodeStore<StateId, P::Action>>(
         &self,
         problem: &P,
-        shared: SharedMetrics,
-    ) -> SearchResult {
-        let initial_node = Node::new(problem.initial_state());
+        recorder: &mut MetricsRecorder,
+        mut tree: T,
+    ) -> Option<Vec<P::Action>> {
+        let mut interner = StateInterner::new();
         let mut frontier = VecDeque::new();
-        frontier.push_back(initial_node);
+        for state in problem.initial_states() {
+            let (initial_id, is_new) = interner.intern(state);
+            if is_new {
+                frontier.push_back(tree.add_root(initial_id));
+                recorder.generate();
+            }
+        }
 
-        let mut explored = HashSet::new();
-        shared.update(|m| m.nodes_generated = 1);
+        let mut expanded = vec![false; interner.len()];
+        let mut g_scores = vec![0; interner.len()];
+        let heap_bytes = problem.state_heap_bytes(&problem.initial_state());
+        let memory_kb =
+            |interner: &StateInterner<P::State>, tree: &T, frontier: &VecDeque<usize>| {
+                memory::kb(
+                    interner.memory_bytes(heap_bytes)
+                        + interner.len() * (size_of::<bool>() + size_of::<usize>())
+                        + tree.memory_bytes(0)
+                        + memory::vec_bytes::<usize>(frontier.capacity()),
+                )
+            };
 
-        while let Some(node) = frontier.pop_front() {
-            shared.increment_visited();
+        let mut successors = Vec::new();
 
-            if problem.is_goal(&node.state) {
-                let solution = node.extract_solution();
-                shared.set_solution_length(solution.len());
-                shared.set_memory_kb(
-                    (explored.len() + frontier.len()) * std::mem::size_of::<P::State>() / 1024,
-                );
+        while let Some(id) = frontier.pop_front() {
+            recorder.visit();
+            let state_id = tree.get(id).state;
+            let state = interner.get(state_id).clone();
+            let path_cost = tree.get(id).path_cost;
 
-                return SearchResult {
-                    solution: Some(solution),
-                    metrics: shared.get(),
-                    status: 0,
-                };
+            if path_cost > g_scores[state_id.index()] {
+                tree.release(id);
+                continue;
+            }
+
+            if problem.is_goal(&state) {
+                recorder.set_memory_kb(memory_kb(&interner, &tree, &frontier));
+                recorder.set_solution_length(tree.get(id).depth);
+                recorder.set_solution_cost(tree.get(id).path_cost);
+                return Some(tree.extract_solution(id));
             }
 
-            explored.insert(node.state.clone());
+            if expanded[state_id.index()] {
+                recorder.reexpand();
+            }
+            expanded[state_id.index()] = true;
 
-            for (successor_state, cost) in problem.successors(&node.state) {
-                if !explored.contains(&successor_state)
-                    && !frontier.iter().any(|n| n.state == successor_state)
-                {
-                    let generated = shared.get().nodes_generated;
-                    let child = node.child(successor_state, generated, cost);
-                    frontier.push_back(child);
-                    shared.increment_generated();
+            problem.successors_into(&state, &mut successors);
+            for (action, successor_state, cost) in successors.drain(..) {
+                let tentative_g = path_cost + cost;
+                if self.exceeds_bound(tentative_g) {
+                    continue;
                 }
+                let reached_goal = self.early_goal_test && problem.is_goal(&successor_state);
+                let (successor_id, is_new) = interner.intern(successor_state);
+                if is_new {
+                    expanded.push(false);
+                    g_scores.push(tentative_g);
+                } else if !self.reexpand || tentative_g >= g_scores[successor_id.index()] {
+                    continue;
+                } else {
+                    g_scores[successor_id.index()] = tentative_g;
+                }
+
+                let child = tree.add_child(id, successor_id, action, cost);
+                recorder.generate();
+                if reached_goal {
+                    recorder.set_memory_kb(memory_kb(&interner, &tree, &frontier));
+                    recorder.set_solution_length(tree.get(child).depth);
+                    recorder.set_solution_cost(tree.get(child).path_cost);
+                    return Some(tree.extract_solution(child));
+                }
+                frontier.push_back(child);
             }
+            tree.release(id);
 
-            shared.update_max_frontier(frontier.len());
+            recorder.observe_frontier(frontier.len());
         }
 
-        shared.set_memory_kb(explored.len() * std::mem::size_of::<P::State>() / 1024);
+        recorder.set_memory_kb(memory_kb(&interner, &tree, &frontier));
+        None
+    }
+}
 
-        SearchResult {
-            solution: None,
-            metrics: shared.get(),
-            status: 2,
+impl Default for BFS {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SearchAlgorithm for BFS {
+    fn search_recorded<P: Problem>(
+        &self,
+        problem: &P,
+        recorder: &mut MetricsRecorder,
+    ) -> Option<Vec<P::Action>> {
+        match (self.memory_limit_kb, self.track_solutions) {
+            (Some(limit), true) => self.search_bounded(problem, recorder, limit, SearchTree::new()),
+            (Some(limit), false) => self.search_bounded(problem, recorder, limit, NodePool::new()),
+            (None, true) => self.search_interned(problem, recorder, SearchTree::new()),
+            (None, false) => self.search_interned(problem, recorder, NodePool::new()),
         }
     }
 
@@ -117,3 +258,57 @@ impl SearchAlgorithm for BFS {
         "BFS"
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::SearchStatus;
+    use crate::problems::taquin::HeuristicType;
+    use crate::problems::{ShortestPath, Taquin};
+
+    #[test]
+    fn test_untracked_search_reports_same_length() {
+        let taquin =
+            Taquin::from_state(3, vec![1, 4, 2, 3, 0, 5, 6, 7, 8], HeuristicType::Manhattan);
+        let tracked = BFS::new().search(&taquin);
+        let untracked = BFS::new().with_solution_tracking(false).search(&taquin);
+
+        assert_eq!(tracked.solution.as_ref().map(Vec::len), Some(2));
+        assert!(untracked.status.is_solved());
+        assert_eq!(untracked.solution.map(|s| s.len()), Some(0));
+        assert_eq!(untracked.metrics.solution_length, 2);
+        assert_eq!(
+            untracked.metrics.nodes_visited,
+            tracked.metrics.nodes_visited
+        );
+    }
+
+    #[test]
+    fn test_early_goal_test_visits_fewer_nodes() {
+        let taquin =
+            Taquin::from_state(3, vec![1, 2, 5, 3, 4, 0, 6, 7, 8], HeuristicType::Manhattan);
+        let late = BFS::new().search(&taquin);
+        let early = BFS::new().with_early_goal_test(true).search(&taquin);
+
+        assert!(early.status.is_solved());
+        assert_eq!(early.metrics.solution_length, late.metrics.solution_length);
+        assert!(early.metrics.nodes_visited < late.metrics.nodes_visited);
+    }
+
+    #[test]
+    fn test_cost_bound_skips_expensive_shortcut() {
+        let mut graph = ShortestPath::new(0, 3);
+        graph.add_edge(0, 3, 10);
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(1, 2, 1);
+        graph.add_edge(2, 3, 1);
+
+        assert_eq!(BFS::new().search(&graph).metrics.solution_cost, 10);
+        for bfs in [BFS::new(), BFS::with_memory_limit(1024)] {
+            let bounded = bfs.with_cost_bound(Some(5)).search(&graph);
+            assert_eq!(bounded.metrics.solution_cost, 3);
+        }
+        let unreachable = BFS::new().with_cost_bound(Some(2)).search(&graph);
+        assert_eq!(unreachable.status, SearchStatus::NoSolution);
+    }
+}
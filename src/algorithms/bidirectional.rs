@@ -0,0 +1,346 @@
+use super::interner::{StateId, StateInterner};
+use super::memory;
+use super::{BackwardProblem, SearchResult, Successors};
+use crate::benchmarking::MetricsRecorder;
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, BinaryHeap};
+use std::mem::size_of;
+
+const FRONT_TO_FRONT_SCAN: usize = 64;
+
+type Parent<A> = Option<(StateId, usize, Option<A>)>;
+
+struct Frontier<A> {
+    g: Vec<Option<usize>>,
+    parent: Vec<Parent<A>>,
+    closed: Vec<bool>,
+    open: BTreeSet<(usize, StateId)>,
+    heap: BinaryHeap<Reverse<(usize, usize, StateId)>>,
+}
+
+impl<A: Clone> Frontier<A> {
+    fn new() -> Self {
+        Frontier {
+            g: Vec::new(),
+            parent: Vec::new(),
+            closed: Vec::new(),
+            open: BTreeSet::new(),
+            heap: BinaryHeap::new(),
+        }
+    }
+
+    fn grow(&mut self, len: usize) {
+        self.g.resize(len, None);
+        self.parent.resize(len, None);
+        self.closed.resize(len, false);
+    }
+
+    fn g(&self, id: StateId) -> Option<usize> {
+        self.g.get(id.index()).copied().flatten()
+    }
+
+    fn push(&mut self, id: StateId, g: usize, f: usize, parent: Parent<A>) -> bool {
+        let reopened = self.closed[id.index()];
+        if let Some(previous) = self.g[id.index()].filter(|_| !reopened) {
+            self.open.remove(&(previous, id));
+        }
+        self.g[id.index()] = Some(g);
+        self.parent[id.index()] = parent;
+        self.closed[id.index()] = false;
+        self.open.insert((g, id));
+        self.heap.push(Reverse((f, g, id)));
+        reopened
+    }
+
+    fn discard_stale(&mut self) {
+        while let Some(&Reverse((_, g, id))) = self.heap.peek() {
+            if !self.closed[id.index()] && self.g[id.index()] == Some(g) {
+                break;
+            }
+            self.heap.pop();
+        }
+    }
+
+    fn min_f(&mut self) -> Option<usize> {
+        self.discard_stale();
+        self.heap.peek().map(|&Reverse((f, _, _))| f)
+    }
+
+    fn pop(&mut self) -> Option<(StateId, usize, usize)> {
+        self.discard_stale();
+        let Reverse((f, g, id)) = self.heap.pop()?;
+        self.closed[id.index()] = true;
+        self.open.remove(&(g, id));
+        Some((id, g, f))
+    }
+
+    fn memory_bytes(&self) -> usize {
+        self.g.len() * (size_of::<Option<usize>>() + size_of::<Parent<A>>() + size_of::<bool>())
+            + self.open.len() * size_of::<(usize, StateId)>() * 2
+            + memory::vec_bytes::<Reverse<(usize, usize, StateId)>>(self.heap.capacity())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Forward,
+    Backward,
+}
+
+pub struct BidirectionalAStar {
+    pub cost_bound: Option<usize>,
+}
+
+impl BidirectionalAStar {
+    pub fn new() -> Self {
+        BidirectionalAStar { cost_bound: None }
+    }
+
+    pub fn with_cost_bound(mut self, cost_bound: Option<usize>) -> Self {
+        self.cost_bound = cost_bound;
+        self
+    }
+
+    pub fn search_with_recorder<P: BackwardProblem>(
+        &self,
+        problem: &P,
+        mut recorder: MetricsRecorder,
+    ) -> SearchResult<P::Action> {
+        let solution = self.search_recorded(problem, &mut recorder);
+        super::finish_search(solution, recorder)
+    }
+
+    fn front_to_front<P: BackwardProblem>(
+        problem: &P,
+        interner: &StateInterner<P::State>,
+        side: Side,
+        state: &P::State,
+        opposite: &Frontier<P::Action>,
+    ) -> usize {
+        let mut best = None;
+        for (scanned, &(g, id)) in opposite.open.iter().enumerate() {
+            if best.is_some_and(|best| g >= best) {
+                break;
+            }
+            if scanned == FRONT_TO_FRONT_SCAN {
+                return g;
+            }
+            let other = interner.get(id);
+            let h = match side {
+                Side::Forward => problem.heuristic_between(state, other),
+                Side::Backward => problem.heuristic_between(other, state),
+            };
+            best = Some(best.map_or(g + h, |best: usize| best.min(g + h)));
+        }
+        best.unwrap_or(0)
+    }
+
+    fn plan<P: BackwardProblem>(
+        problem: &P,
+        interner: &StateInterner<P::State>,
+        forward: &Frontier<P::Action>,
+        backward: &Frontier<P::Action>,
+        meeting: StateId,
+    ) -> Option<Vec<P::Action>> {
+        let mut actions = Vec::new();
+        let mut current = meeting;
+        while let Some((parent, _, action)) = &forward.parent[current.index()] {
+            actions.push(action.clone()?);
+            current = *parent;
+        }
+        actions.reverse();
+
+        let mut current = meeting;
+        while let Some((next, cost, _)) = &backward.parent[current.index()] {
+            let (state, next_state) = (interner.get(current), interner.get(*next));
+            let action = problem
+                .actions(state)
+                .into_iter()
+                .find(|action| problem.apply(state, action) == (next_state.clone(), *cost))?;
+            actions.push(action);
+            current = *next;
+        }
+        Some(actions)
+    }
+
+    pub fn search_recorded<P: BackwardProblem>(
+        &self,
+        problem: &P,
+        recorder: &mut MetricsRecorder,
+    ) -> Option<Vec<P::Action>> {
+        let mut interner = StateInterner::new();
+        let mut forward = Frontier::new();
+        let mut backward = Frontier::new();
+        let mut best: Option<(usize, StateId)> = None;
+
+        let roots = [
+            (Side::Forward, problem.initial_states()),
+            (Side::Backward, problem.goal_states()),
+        ];
+        for (side, states) in roots {
+            for state in states {
+                let (id, _) = interner.intern(state);
+                forward.grow(interner.len());
+                backward.grow(interner.len());
+                let (this, other) = match side {
+                    Side::Forward => (&mut forward, &backward),
+                    Side::Backward => (&mut backward, &forward),
+                };
+                if this.g(id).is_some() {
+                    continue;
+                }
+                this.push(id, 0, 0, None);
+                recorder.generate();
+                if other.g(id).is_some() {
+                    best = Some((0, id));
+                }
+            }
+        }
+
+        let heap_bytes = problem.state_heap_bytes(&problem.initial_state());
+        let memory_kb = |interner: &StateInterner<P::State>,
+                         forward: &Frontier<P::Action>,
+                         backward: &Frontier<P::Action>| {
+            memory::kb(
+                interner.memory_bytes(heap_bytes)
+                    + forward.memory_bytes()
+                    + backward.memory_bytes(),
+            )
+        };
+
+        let mut successors: Successors<P> = Vec::new();
+        let mut neighbors = Vec::new();
+
+        while let (Some(forward_f), Some(backward_f)) = (forward.min_f(), backward.min_f()) {
+            if best.is_some_and(|(cost, _)| cost <= forward_f.max(backward_f)) {
+                break;
+            }
+
+            let side = if forward.open.len() <= backward.open.len() {
+                Side::Forward
+            } else {
+                Side::Backward
+            };
+            let (this, other) = match side {
+                Side::Forward => (&mut forward, &mut backward),
+                Side::Backward => (&mut backward, &mut forward),
+            };
+            let Some((id, g, f)) = this.pop() else {
+                break;
+            };
+            recorder.visit();
+            recorder.sample_frontier(f, this.open.len() + other.open.len());
+
+            let state = interner.get(id).clone();
+            neighbors.clear();
+            match side {
+                Side::Forward => {
+                    problem.successors_into(&state, &mut successors);
+                    neighbors.extend(
+                        successors
+                            .drain(..)
+                            .map(|(action, next, cost)| (next, cost, Some(action))),
+                    );
+                }
+                Side::Backward => neighbors.extend(
+                    problem
+                        .predecessors(&state)
+                        .into_iter()
+                        .map(|(previous, cost)| (previous, cost, None)),
+                ),
+            }
+
+            for (next, cost, action) in neighbors.drain(..) {
+                let tentative_g = g + cost;
+                if self.cost_bound.is_some_and(|bound| tentative_g > bound) {
+                    continue;
+                }
+                let (next_id, _) = interner.intern(next);
+                this.grow(interner.len());
+                other.grow(interner.len());
+                if this.g(next_id).is_some_and(|known| tentative_g >= known) {
+                    continue;
+                }
+
+                let h =
+                    Self::front_to_front(problem, &interner, side, interner.get(next_id), other);
+                if this.push(
+                    next_id,
+                    tentative_g,
+                    tentative_g + h,
+                    Some((id, cost, action)),
+                ) {
+                    recorder.reexpand();
+                }
+                recorder.generate();
+                if let Some(other_g) = other.g(next_id) {
+                    let cost = tentative_g + other_g;
+                    if best.is_none_or(|(known, _)| cost < known) {
+                        best = Some((cost, next_id));
+                    }
+                }
+            }
+
+            recorder.observe_frontier(forward.open.len() + backward.open.len());
+        }
+
+        recorder.set_memory_kb(memory_kb(&interner, &forward, &backward));
+        let (_, meeting) = best?;
+        let cost = forward.g(meeting)? + backward.g(meeting)?;
+        let actions = Self::plan(problem, &interner, &forward, &backward, meeting)?;
+        recorder.set_solution_length(actions.len());
+        recorder.set_solution_cost(cost);
+        Some(actions)
+    }
+}
+
+impl Default for BidirectionalAStar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::astar::AStar;
+    use crate::algorithms::validation;
+    use crate::algorithms::SearchAlgorithm;
+    use crate::problems::taquin::{hardest_eight_puzzle, HeuristicType};
+    use crate::problems::{PackedTaquin, ShortestPath};
+
+    #[test]
+    fn test_matches_astar_cost_and_meets_consistently() {
+        let grid = ShortestPath::generate_grid_between(12, 9, 3, 100);
+        let taquin = hardest_eight_puzzle();
+        let mut weighted = ShortestPath::new(0, 3);
+        weighted.add_edge(0, 1, 4);
+        weighted.add_edge(0, 2, 1);
+        weighted.add_edge(2, 1, 1);
+        weighted.add_edge(1, 3, 10);
+        weighted.add_edge(0, 3, 20);
+
+        fn check<P: BackwardProblem>(problem: &P) {
+            let expected = AStar::new().search(problem);
+            let result =
+                BidirectionalAStar::new().search_with_recorder(problem, MetricsRecorder::new());
+            assert!(result.status.is_solved());
+            assert_eq!(result.metrics.solution_cost, expected.metrics.solution_cost);
+            let actions = result.solution.unwrap();
+            validation::validate_solution(problem, &actions, &result.metrics).unwrap();
+        }
+        check(&grid);
+        check(&taquin);
+        check(&PackedTaquin::from_taquin(
+            &taquin,
+            HeuristicType::Manhattan,
+        ));
+        check(&weighted);
+
+        let mut unreachable = ShortestPath::new(0, 2);
+        unreachable.add_edge(0, 1, 1);
+        let result =
+            BidirectionalAStar::new().search_with_recorder(&unreachable, MetricsRecorder::new());
+        assert!(!result.status.is_solved());
+    }
+}
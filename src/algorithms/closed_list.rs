@@ -0,0 +1,117 @@
+use super::memory;
+use crate::utils::hashing::StateMap;
+use std::collections::VecDeque;
+use std::hash::Hash;
+
+pub struct BoundedClosedList<S, V = ()> {
+    entries: StateMap<S, (V, u64)>,
+    insertion_order: VecDeque<(S, u64)>,
+    capacity: usize,
+    next_stamp: u64,
+    evictions: usize,
+}
+
+impl<S: Clone + Eq + Hash, V> BoundedClosedList<S, V> {
+    pub fn with_capacity_limit(capacity: usize) -> Self {
+        BoundedClosedList {
+            entries: StateMap::default(),
+            insertion_order: VecDeque::new(),
+            capacity: capacity.max(1),
+            next_stamp: 0,
+            evictions: 0,
+        }
+    }
+
+    pub fn with_memory_limit(limit_kb: usize) -> Self {
+        let entry_size = 2 * std::mem::size_of::<(S, u64)>() + std::mem::size_of::<V>();
+        Self::with_capacity_limit(limit_kb * 1024 / entry_size.max(1))
+    }
+
+    pub fn get(&self, state: &S) -> Option<&V> {
+        self.entries.get(state).map(|(value, _)| value)
+    }
+
+    pub fn get_mut(&mut self, state: &S) -> Option<&mut V> {
+        self.entries.get_mut(state).map(|(value, _)| value)
+    }
+
+    pub fn contains(&self, state: &S) -> bool {
+        self.entries.contains_key(state)
+    }
+
+    pub fn insert(&mut self, state: S, value: V) -> usize {
+        let stamp = self.next_stamp;
+        self.next_stamp += 1;
+        self.insertion_order.push_back((state.clone(), stamp));
+        self.entries.insert(state, (value, stamp));
+
+        let mut evicted = 0;
+        while self.entries.len() > self.capacity {
+            let Some((oldest, oldest_stamp)) = self.insertion_order.pop_front() else {
+                break;
+            };
+            if self
+                .entries
+                .get(&oldest)
+                .is_some_and(|(_, current)| *current == oldest_stamp)
+            {
+                self.entries.remove(&oldest);
+                evicted += 1;
+            }
+        }
+        self.evictions += evicted;
+        evicted
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn memory_bytes(&self, state_heap_bytes: usize) -> usize {
+        memory::hash_table_bytes::<(S, (V, u64))>(self.entries.len())
+            + memory::vec_bytes::<(S, u64)>(self.insertion_order.capacity())
+            + (self.entries.len() + self.insertion_order.len()) * state_heap_bytes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn evictions(&self) -> usize {
+        self.evictions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evicts_oldest_entries_first() {
+        let mut closed = BoundedClosedList::with_capacity_limit(2);
+        assert_eq!(closed.insert(1, ()), 0);
+        assert_eq!(closed.insert(2, ()), 0);
+        assert_eq!(closed.insert(3, ()), 1);
+
+        assert!(!closed.contains(&1));
+        assert!(closed.contains(&2) && closed.contains(&3));
+        assert_eq!(closed.evictions(), 1);
+    }
+
+    #[test]
+    fn test_reinsertion_refreshes_entry() {
+        let mut closed = BoundedClosedList::with_capacity_limit(2);
+        closed.insert(1, 10);
+        closed.insert(2, 20);
+        closed.insert(1, 5);
+        closed.insert(3, 30);
+
+        assert_eq!(closed.get(&1), Some(&5));
+        assert!(!closed.contains(&2));
+        assert_eq!(closed.len(), 2);
+    }
+}
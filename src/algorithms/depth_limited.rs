@@ -0,0 +1,233 @@
+use super::memory;
+use super::{order_by_heuristic, Problem, SearchAlgorithm, Successors};
+use crate::benchmarking::MetricsRecorder;
+use crate::utils::hashing::StateSet;
+use std::mem::size_of;
+
+pub struct DepthLimited {
+    pub depth_limit: usize,
+    pub track_solutions: bool,
+    pub cost_bound: Option<usize>,
+    pub order_successors: bool,
+}
+
+pub(crate) enum LimitedOutcome<A> {
+    Found(Vec<A>, usize),
+    Cutoff,
+    Exhausted,
+}
+
+struct Frame<P: Problem> {
+    state: P::State,
+    g: usize,
+    successors: Successors<P>,
+}
+
+impl DepthLimited {
+    pub fn new(depth_limit: usize) -> Self {
+        DepthLimited {
+            depth_limit,
+            track_solutions: true,
+            cost_bound: None,
+            order_successors: false,
+        }
+    }
+
+    pub fn with_solution_tracking(mut self, track_solutions: bool) -> Self {
+        self.track_solutions = track_solutions;
+        self
+    }
+
+    pub fn with_cost_bound(mut self, cost_bound: Option<usize>) -> Self {
+        self.cost_bound = cost_bound;
+        self
+    }
+
+    pub fn with_successor_ordering(mut self, order_successors: bool) -> Self {
+        self.order_successors = order_successors;
+        self
+    }
+}
+
+pub(crate) fn depth_limited<P: Problem>(
+    problem: &P,
+    initial_state: &P::State,
+    limit: usize,
+    cost_bound: Option<usize>,
+    order_successors: bool,
+    recorder: &mut MetricsRecorder,
+    peak_bytes: &mut usize,
+) -> LimitedOutcome<P::Action> {
+    recorder.visit();
+    if problem.is_goal(initial_state) {
+        return LimitedOutcome::Found(Vec::new(), 0);
+    }
+    if limit == 0 {
+        return LimitedOutcome::Cutoff;
+    }
+
+    let mut on_path = StateSet::default();
+    let mut path = Vec::new();
+    let mut spare_buffers: Vec<Successors<P>> = Vec::new();
+    let mut cutoff = false;
+    let heap_bytes = problem.state_heap_bytes(initial_state);
+    let frame_bytes = |frame: &Frame<P>| {
+        size_of::<Frame<P>>()
+            + heap_bytes
+            + memory::vec_bytes::<(P::Action, P::State, usize)>(frame.successors.capacity())
+            + frame.successors.capacity() * heap_bytes
+    };
+
+    let mut successors = Vec::new();
+    problem.successors_into(initial_state, &mut successors);
+    successors.reverse();
+    if order_successors {
+        order_by_heuristic(problem, &mut successors);
+    }
+    on_path.insert(initial_state.clone());
+    let mut stack = vec![Frame::<P> {
+        state: initial_state.clone(),
+        g: 0,
+        successors,
+    }];
+    let mut stack_bytes = frame_bytes(&stack[0]);
+
+    while let Some(frame) = stack.last_mut() {
+        let Some((action, successor_state, cost)) = frame.successors.pop() else {
+            let frame = stack.pop().unwrap();
+            stack_bytes -= frame_bytes(&frame);
+            on_path.remove(&frame.state);
+            spare_buffers.push(frame.successors);
+            path.pop();
+            continue;
+        };
+
+        if on_path.contains(&successor_state) {
+            continue;
+        }
+
+        let g = frame.g + cost;
+        if cost_bound.is_some_and(|bound| g > bound) {
+            continue;
+        }
+        recorder.generate();
+        recorder.visit();
+        path.push(action);
+
+        if problem.is_goal(&successor_state) {
+            return LimitedOutcome::Found(path, g);
+        }
+
+        if stack.len() >= limit {
+            cutoff = true;
+            path.pop();
+            continue;
+        }
+
+        let mut successors = spare_buffers.pop().unwrap_or_default();
+        problem.successors_into(&successor_state, &mut successors);
+        successors.reverse();
+        if order_successors {
+            order_by_heuristic(problem, &mut successors);
+        }
+        on_path.insert(successor_state.clone());
+        let frame = Frame {
+            state: successor_state,
+            g,
+            successors,
+        };
+        stack_bytes += frame_bytes(&frame);
+        stack.push(frame);
+        recorder.observe_frontier(stack.len());
+
+        let bytes = stack_bytes
+            + memory::hash_table_bytes::<P::State>(on_path.len())
+            + on_path.len() * heap_bytes
+            + memory::vec_bytes::<P::Action>(path.capacity());
+        *peak_bytes = (*peak_bytes).max(bytes);
+    }
+
+    if cutoff {
+        LimitedOutcome::Cutoff
+    } else {
+        LimitedOutcome::Exhausted
+    }
+}
+
+impl SearchAlgorithm for DepthLimited {
+    fn search_recorded<P: Problem>(
+        &self,
+        problem: &P,
+        recorder: &mut MetricsRecorder,
+    ) -> Option<Vec<P::Action>> {
+        let initial_states = problem.initial_states();
+        for _ in &initial_states {
+            recorder.generate();
+        }
+
+        let mut peak_bytes = 0;
+        let mut cutoff = false;
+        for initial_state in &initial_states {
+            let outcome = depth_limited(
+                problem,
+                initial_state,
+                self.depth_limit,
+                self.cost_bound,
+                self.order_successors,
+                recorder,
+                &mut peak_bytes,
+            );
+            recorder.set_memory_kb(memory::kb(peak_bytes));
+            match outcome {
+                LimitedOutcome::Found(solution, cost) => {
+                    recorder.set_solution_length(solution.len());
+                    recorder.set_solution_cost(cost);
+                    return Some(if self.track_solutions {
+                        solution
+                    } else {
+                        Vec::new()
+                    });
+                }
+                LimitedOutcome::Exhausted => {}
+                LimitedOutcome::Cutoff => cutoff = true,
+            }
+        }
+
+        if cutoff {
+            recorder.mark_cutoff();
+        }
+        None
+    }
+
+    fn name(&self) -> &str {
+        "Depth-Limited Search"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::SearchStatus;
+    use crate::problems::ShortestPath;
+
+    #[test]
+    fn test_reports_cutoff_only_when_the_limit_pruned_something() {
+        let mut chain = ShortestPath::new(0, 4);
+        for node in 0..4 {
+            chain.add_edge(node, node + 1, 1);
+        }
+        let result = DepthLimited::new(4).search(&chain);
+        assert!(result.status.is_solved());
+        assert_eq!(result.metrics.solution_cost, 4);
+
+        let result = DepthLimited::new(3).search(&chain);
+        assert_eq!(result.status, SearchStatus::DepthLimitReached);
+
+        let mut dead_end = ShortestPath::new(0, 5);
+        for node in 0..3 {
+            dead_end.add_edge(node, node + 1, 1);
+        }
+        let result = DepthLimited::new(5).search(&dead_end);
+        assert_eq!(result.status, SearchStatus::NoSolution);
+    }
+}
@@ -1,135 +1,191 @@
-use super::{Node, Problem, SearchAlgorithm, SearchResult};
-use crate::benchmarking::{Metrics, SharedMetrics};
-use std::collections::HashSet;
-use std::time::Instant;
+use super::memory;
+use super::{order_by_heuristic, NodePool, NodeStore, Problem, SearchAlgorithm, SearchTree};
+use crate::benchmarking::MetricsRecorder;
+use crate::utils::hashing::{StateMap, StateSet};
 
 pub struct DFS {
     pub max_depth: Option<usize>,
+    pub track_solutions: bool,
+    pub reexpand: bool,
+    pub early_goal_test: bool,
+    pub cost_bound: Option<usize>,
+    pub order_successors: bool,
 }
 
 impl DFS {
     pub fn new() -> Self {
-        DFS { max_depth: None }
+        DFS {
+            max_depth: None,
+            track_solutions: true,
+            reexpand: false,
+            early_goal_test: false,
+            cost_bound: None,
+            order_successors: false,
+        }
     }
 
     pub fn with_max_depth(max_depth: usize) -> Self {
         DFS {
             max_depth: Some(max_depth),
+            ..Self::new()
         }
     }
-}
 
-impl SearchAlgorithm for DFS {
-    fn search<P: Problem>(&self, problem: &P) -> SearchResult {
-        let start = Instant::now();
-        let mut metrics = Metrics::default();
+    pub fn with_solution_tracking(mut self, track_solutions: bool) -> Self {
+        self.track_solutions = track_solutions;
+        self
+    }
 
-        let initial_node = Node::new(problem.initial_state());
-        let mut frontier = vec![initial_node];
-        let mut explored = HashSet::new();
+    pub fn with_reexpansion(mut self, reexpand: bool) -> Self {
+        self.reexpand = reexpand;
+        self
+    }
 
-        metrics.nodes_generated = 1;
+    pub fn with_early_goal_test(mut self, early_goal_test: bool) -> Self {
+        self.early_goal_test = early_goal_test;
+        self
+    }
 
-        while let Some(node) = frontier.pop() {
-            metrics.nodes_visited += 1;
+    pub fn with_cost_bound(mut self, cost_bound: Option<usize>) -> Self {
+        self.cost_bound = cost_bound;
+        self
+    }
 
-            if let Some(max_depth) = self.max_depth {
-                if node.depth > max_depth {
-                    continue;
-                }
-            }
+    pub fn with_successor_ordering(mut self, order_successors: bool) -> Self {
+        self.order_successors = order_successors;
+        self
+    }
 
-            if problem.is_goal(&node.state) {
-                let solution = node.extract_solution();
-                metrics.solution_length = solution.len();
-                metrics.time_ms = start.elapsed().as_millis() as f64;
-                metrics.memory_kb =
-                    (explored.len() + frontier.len()) * std::mem::size_of::<P::State>() / 1024;
-
-                return SearchResult {
-                    solution: Some(solution),
-                    metrics,
-                    status: 0,
-                };
+    fn search_with_store<P: Problem, T: NodeStore<P::State, P::Action>>(
+        &self,
+        problem: &P,
+        recorder: &mut MetricsRecorder,
+        mut tree: T,
+    ) -> Option<Vec<P::Action>> {
+        let mut frontier = Vec::new();
+        let mut explored = StateSet::default();
+        let mut best_g = StateMap::default();
+        for state in problem.initial_states().into_iter().rev() {
+            if self.reexpand {
+                best_g.insert(state.clone(), 0);
             }
+            frontier.push(tree.add_root(state));
+            recorder.generate();
+        }
 
-            explored.insert(node.state.clone());
+        let heap_bytes = problem.state_heap_bytes(&problem.initial_state());
+        let memory_kb = |explored: &StateSet<P::State>,
+                         best_g: &StateMap<P::State, usize>,
+                         tree: &T,
+                         frontier: &Vec<usize>| {
+            memory::kb(
+                memory::hash_table_bytes::<P::State>(explored.len())
+                    + memory::hash_table_bytes::<(P::State, usize)>(best_g.len())
+                    + (explored.len() + best_g.len()) * heap_bytes
+                    + tree.memory_bytes(heap_bytes)
+                    + memory::vec_bytes::<usize>(frontier.capacity()),
+            )
+        };
+
+        let mut successors = Vec::new();
+
+        while let Some(id) = frontier.pop() {
+            recorder.visit();
 
-            for (successor_state, cost) in problem.successors(&node.state) {
-                if !explored.contains(&successor_state) {
-                    let child = node.child(successor_state, metrics.nodes_generated, cost);
-                    frontier.push(child);
-                    metrics.nodes_generated += 1;
+            if let Some(max_depth) = self.max_depth {
+                if tree.get(id).depth > max_depth {
+                    recorder.mark_cutoff();
+                    tree.release(id);
+                    continue;
                 }
             }
 
-            metrics.max_frontier_size = metrics.max_frontier_size.max(frontier.len());
-        }
-
-        metrics.time_ms = start.elapsed().as_millis() as f64;
-        metrics.memory_kb = explored.len() * std::mem::size_of::<P::State>() / 1024;
-
-        SearchResult {
-            solution: None,
-            metrics,
-            status: 2,
-        }
-    }
+            let state = tree.get(id).state.clone();
+            let path_cost = tree.get(id).path_cost;
 
-    fn search_with_shared_metrics<P: Problem>(
-        &self,
-        problem: &P,
-        shared: SharedMetrics,
-    ) -> SearchResult {
-        let initial_node = Node::new(problem.initial_state());
-        let mut frontier = vec![initial_node];
-        let mut explored = HashSet::new();
-
-        shared.update(|m| m.nodes_generated = 1);
+            if self.reexpand && best_g.get(&state).is_some_and(|&g| g < path_cost) {
+                tree.release(id);
+                continue;
+            }
 
-        while let Some(node) = frontier.pop() {
-            shared.increment_visited();
+            if problem.is_goal(&state) {
+                recorder.set_memory_kb(memory_kb(&explored, &best_g, &tree, &frontier));
+                recorder.set_solution_length(tree.get(id).depth);
+                recorder.set_solution_cost(tree.get(id).path_cost);
+                return Some(tree.extract_solution(id));
+            }
 
-            if let Some(max_depth) = self.max_depth {
-                if node.depth > max_depth {
+            if !explored.insert(state.clone()) {
+                if !self.reexpand {
+                    recorder.skip_duplicate();
+                    tree.release(id);
                     continue;
                 }
+                recorder.reexpand();
             }
 
-            if problem.is_goal(&node.state) {
-                let solution = node.extract_solution();
-                shared.set_solution_length(solution.len());
-                shared.set_memory_kb(
-                    (explored.len() + frontier.len()) * std::mem::size_of::<P::State>() / 1024,
-                );
-
-                return SearchResult {
-                    solution: Some(solution),
-                    metrics: shared.get(),
-                    status: 0,
-                };
+            let early_goal_test = self.early_goal_test
+                && self
+                    .max_depth
+                    .is_none_or(|max_depth| tree.get(id).depth < max_depth);
+            problem.successors_into(&state, &mut successors);
+            if self.order_successors {
+                order_by_heuristic(problem, &mut successors);
             }
+            for (action, successor_state, cost) in successors.drain(..) {
+                let tentative_g = path_cost + cost;
+                if self.cost_bound.is_some_and(|bound| tentative_g > bound) {
+                    continue;
+                }
+                if self.reexpand {
+                    if best_g
+                        .get(&successor_state)
+                        .is_some_and(|&g| g <= tentative_g)
+                    {
+                        continue;
+                    }
+                    best_g.insert(successor_state.clone(), tentative_g);
+                } else if explored.contains(&successor_state) {
+                    continue;
+                }
 
-            explored.insert(node.state.clone());
-
-            for (successor_state, cost) in problem.successors(&node.state) {
-                if !explored.contains(&successor_state) {
-                    let generated = shared.get().nodes_generated;
-                    let child = node.child(successor_state, generated, cost);
-                    frontier.push(child);
-                    shared.increment_generated();
+                let reached_goal = early_goal_test && problem.is_goal(&successor_state);
+                let child = tree.add_child(id, successor_state, action, cost);
+                recorder.generate();
+                if reached_goal {
+                    recorder.set_memory_kb(memory_kb(&explored, &best_g, &tree, &frontier));
+                    recorder.set_solution_length(tree.get(child).depth);
+                    recorder.set_solution_cost(tree.get(child).path_cost);
+                    return Some(tree.extract_solution(child));
                 }
+                frontier.push(child);
             }
+            tree.release(id);
 
-            shared.update_max_frontier(frontier.len());
+            recorder.observe_frontier(frontier.len());
         }
 
-        shared.set_memory_kb(explored.len() * std::mem::size_of::<P::State>() / 1024);
+        recorder.set_memory_kb(memory_kb(&explored, &best_g, &tree, &frontier));
+        None
+    }
+}
+
+impl Default for DFS {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-        SearchResult {
-            solution: None,
-            metrics: shared.get(),
-            status: 2,
+impl SearchAlgorithm for DFS {
+    fn search_recorded<P: Problem>(
+        &self,
+        problem: &P,
+        recorder: &mut MetricsRecorder,
+    ) -> Option<Vec<P::Action>> {
+        if self.track_solutions {
+            self.search_with_store(problem, recorder, SearchTree::new())
+        } else {
+            self.search_with_store(problem, recorder, NodePool::new())
         }
     }
 
@@ -137,3 +193,33 @@ impl SearchAlgorithm for DFS {
         "DFS"
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::problems::ShortestPath;
+
+    #[test]
+    fn test_duplicate_pops_are_skipped_unless_reexpanding() {
+        let mut graph = ShortestPath::new(0, 5);
+        graph.add_edge(0, 3, 1);
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(1, 3, 1);
+        graph.add_edge(3, 4, 1);
+
+        let plain = DFS::new().search(&graph);
+        assert_eq!(plain.metrics.nodes_visited, 5);
+        assert_eq!(plain.metrics.duplicates, 1);
+        assert_eq!(plain.metrics.reexpansions, 0);
+
+        let mut detour = ShortestPath::new(0, 5);
+        detour.add_edge(0, 1, 1);
+        detour.add_edge(0, 3, 5);
+        detour.add_edge(1, 3, 1);
+        detour.add_edge(3, 4, 1);
+        assert_eq!(DFS::new().search(&detour).metrics.reexpansions, 0);
+        let reexpanded = DFS::new().with_reexpansion(true).search(&detour);
+        assert_eq!(reexpanded.metrics.reexpansions, 2);
+        assert_eq!(reexpanded.metrics.duplicates, 0);
+    }
+}
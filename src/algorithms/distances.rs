@@ -0,0 +1,87 @@
+use super::interner::StateInterner;
+use super::BackwardProblem;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+pub fn goal_distances<P: BackwardProblem>(
+    problem: &P,
+    max_states: usize,
+) -> Vec<(P::State, usize)> {
+    let mut interner = StateInterner::new();
+    let mut best = Vec::new();
+    let mut closed = Vec::new();
+    let mut frontier = BinaryHeap::new();
+
+    for goal in problem.goal_states() {
+        let (id, is_new) = interner.intern(goal);
+        if is_new {
+            best.push(0);
+            closed.push(false);
+            frontier.push(Reverse((0, id)));
+        }
+    }
+
+    let mut settled = Vec::new();
+    while let Some(Reverse((distance, id))) = frontier.pop() {
+        if closed[id.index()] {
+            continue;
+        }
+        closed[id.index()] = true;
+
+        let state = interner.get(id).clone();
+        let predecessors = problem.predecessors(&state);
+        settled.push((state, distance));
+        if settled.len() >= max_states {
+            break;
+        }
+
+        for (predecessor, cost) in predecessors {
+            let tentative = distance + cost;
+            let (predecessor_id, is_new) = interner.intern(predecessor);
+            if is_new {
+                best.push(tentative);
+                closed.push(false);
+            } else if tentative >= best[predecessor_id.index()] {
+                continue;
+            } else {
+                best[predecessor_id.index()] = tentative;
+            }
+            frontier.push(Reverse((tentative, predecessor_id)));
+        }
+    }
+
+    settled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::Problem;
+    use crate::problems::taquin::HeuristicType;
+    use crate::problems::{ShortestPath, Taquin};
+
+    #[test]
+    fn test_distances_follow_reversed_edges() {
+        let mut graph = ShortestPath::new(0, 2);
+        graph.add_edge(0, 2, 10);
+        graph.add_edge(0, 1, 2);
+        graph.add_edge(1, 2, 3);
+        graph.add_edge(2, 3, 1);
+
+        let mut distances = goal_distances(&graph, usize::MAX);
+        distances.sort_unstable();
+        assert_eq!(distances, vec![(0, 5), (1, 3), (2, 0)]);
+    }
+
+    #[test]
+    fn test_taquin_distances_bound_manhattan() {
+        let taquin = Taquin::new(3, HeuristicType::Manhattan);
+        let distances = goal_distances(&taquin, 2000);
+
+        assert_eq!(distances.len(), 2000);
+        assert_eq!(distances[0].1, 0);
+        assert!(distances
+            .iter()
+            .all(|(state, distance)| taquin.heuristic(state) <= *distance));
+    }
+}
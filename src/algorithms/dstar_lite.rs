@@ -0,0 +1,238 @@
+use super::memory;
+use super::{BackwardProblem, SearchResult, SearchStatus, Successors};
+use crate::benchmarking::MetricsRecorder;
+use crate::utils::hashing::StateMap;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+const INFINITY: usize = usize::MAX;
+
+type Key = (usize, usize);
+
+pub struct DStarLite<S> {
+    start: Option<S>,
+    goals: Vec<S>,
+    km: usize,
+    g: StateMap<S, usize>,
+    rhs: StateMap<S, usize>,
+    open: StateMap<S, Key>,
+    queue: BinaryHeap<Reverse<(Key, usize)>>,
+    pending: StateMap<usize, S>,
+    order: usize,
+    changed: Vec<S>,
+}
+
+impl<S: Clone + Eq + std::hash::Hash> Default for DStarLite<S> {
+    fn default() -> Self {
+        DStarLite {
+            start: None,
+            goals: Vec::new(),
+            km: 0,
+            g: StateMap::default(),
+            rhs: StateMap::default(),
+            open: StateMap::default(),
+            queue: BinaryHeap::new(),
+            pending: StateMap::default(),
+            order: 0,
+            changed: Vec::new(),
+        }
+    }
+}
+
+impl<S: Clone + Eq + std::hash::Hash> DStarLite<S> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn edge_changed(&mut self, from: S) {
+        if self.start.is_some() {
+            self.changed.push(from);
+        }
+    }
+
+    fn g(&self, state: &S) -> usize {
+        self.g.get(state).copied().unwrap_or(INFINITY)
+    }
+
+    fn rhs(&self, state: &S) -> usize {
+        self.rhs.get(state).copied().unwrap_or(INFINITY)
+    }
+
+    fn key<P: BackwardProblem<State = S>>(&self, problem: &P, start: &S, state: &S) -> Key {
+        let best = self.g(state).min(self.rhs(state));
+        (
+            best.saturating_add(problem.heuristic_between(start, state))
+                .saturating_add(self.km),
+            best,
+        )
+    }
+
+    fn push(&mut self, state: S, key: Key) {
+        self.open.insert(state.clone(), key);
+        self.pending.insert(self.order, state);
+        self.queue.push(Reverse((key, self.order)));
+        self.order += 1;
+    }
+
+    fn top(&mut self) -> Option<(Key, usize)> {
+        while let Some(&Reverse((key, id))) = self.queue.peek() {
+            if self.open.get(&self.pending[&id]) == Some(&key) {
+                return Some((key, id));
+            }
+            self.queue.pop();
+            self.pending.remove(&id);
+        }
+        None
+    }
+
+    fn update_vertex<P: BackwardProblem<State = S>>(
+        &mut self,
+        problem: &P,
+        start: &S,
+        state: &S,
+        recorder: &mut MetricsRecorder,
+    ) {
+        recorder.generate();
+        if !self.goals.contains(state) {
+            let rhs = problem
+                .successors(state)
+                .into_iter()
+                .map(|(next, cost)| self.g(&next).saturating_add(cost))
+                .min()
+                .unwrap_or(INFINITY);
+            self.rhs.insert(state.clone(), rhs);
+        }
+        self.open.remove(state);
+        if self.g(state) != self.rhs(state) {
+            let key = self.key(problem, start, state);
+            self.push(state.clone(), key);
+        }
+    }
+
+    fn compute<P: BackwardProblem<State = S>>(
+        &mut self,
+        problem: &P,
+        start: &S,
+        recorder: &mut MetricsRecorder,
+    ) {
+        while let Some((key, id)) = self.top() {
+            if key >= self.key(problem, start, start) && self.rhs(start) == self.g(start) {
+                break;
+            }
+            self.queue.pop();
+            let state = self.pending.remove(&id).expect("état en attente");
+            let current = self.key(problem, start, &state);
+            if key < current {
+                self.push(state, current);
+                continue;
+            }
+            self.open.remove(&state);
+            recorder.visit();
+
+            let (g, rhs) = (self.g(&state), self.rhs(&state));
+            if g > rhs {
+                self.g.insert(state.clone(), rhs);
+            } else {
+                recorder.expand_underconsistent();
+                self.g.insert(state.clone(), INFINITY);
+                self.update_vertex(problem, start, &state, recorder);
+            }
+            for (previous, _) in problem.predecessors(&state) {
+                self.update_vertex(problem, start, &previous, recorder);
+            }
+            recorder.observe_frontier(self.open.len());
+        }
+    }
+
+    fn path<P: BackwardProblem<State = S>>(
+        &self,
+        problem: &P,
+        start: &S,
+    ) -> Option<Vec<P::Action>> {
+        if self.g(start) == INFINITY {
+            return None;
+        }
+        let mut actions = Vec::new();
+        let mut state = start.clone();
+        let mut successors: Successors<P> = Vec::new();
+        while !problem.is_goal(&state) && actions.len() <= self.g.len() {
+            problem.successors_into(&state, &mut successors);
+            let (action, next, _) = successors
+                .drain(..)
+                .filter(|(_, next, _)| self.g(next) != INFINITY)
+                .min_by_key(|(_, next, cost)| self.g(next).saturating_add(*cost))?;
+            actions.push(action);
+            state = next;
+        }
+        problem.is_goal(&state).then_some(actions)
+    }
+
+    fn memory_kb(&self) -> usize {
+        memory::kb(
+            2 * memory::hash_table_bytes::<(S, usize)>(self.g.len().max(self.rhs.len()))
+                + memory::hash_table_bytes::<(S, Key)>(self.open.len())
+                + memory::hash_table_bytes::<(usize, S)>(self.pending.len())
+                + memory::vec_bytes::<Reverse<(Key, usize)>>(self.queue.capacity()),
+        )
+    }
+
+    pub fn search<P: BackwardProblem<State = S>>(
+        &mut self,
+        problem: &P,
+    ) -> SearchResult<P::Action> {
+        let mut recorder = MetricsRecorder::new();
+        let start = problem.initial_state();
+        match self.start.replace(start.clone()) {
+            None => {
+                self.goals = problem.goal_states();
+                if self.goals.is_empty() {
+                    self.start = None;
+                    return SearchResult {
+                        solution: None,
+                        metrics: recorder.finish(),
+                        status: SearchStatus::Error(
+                            "D* Lite: aucun état but explicite".to_string(),
+                        ),
+                        profile: None,
+                        improvements: Vec::new(),
+                    };
+                }
+                for goal in self.goals.clone() {
+                    self.rhs.insert(goal.clone(), 0);
+                    let key = self.key(problem, &start, &goal);
+                    self.push(goal, key);
+                }
+            }
+            Some(last) if last != start => {
+                self.km += problem.heuristic_between(&last, &start);
+            }
+            Some(_) => {}
+        }
+
+        for state in std::mem::take(&mut self.changed) {
+            self.update_vertex(problem, &start, &state, &mut recorder);
+        }
+        self.compute(problem, &start, &mut recorder);
+        recorder.set_memory_kb(self.memory_kb());
+
+        let solution = self.path(problem, &start);
+        let status = match &solution {
+            Some(actions) => {
+                recorder.set_solution_length(actions.len());
+                recorder.set_solution_cost(self.g(&start));
+                SearchStatus::Solved
+            }
+            None if self.g(&start) != INFINITY => {
+                SearchStatus::Error("D* Lite: chemin incohérent avec les valeurs g".to_string())
+            }
+            None => SearchStatus::NoSolution,
+        };
+        SearchResult {
+            solution,
+            metrics: recorder.finish(),
+            status,
+            profile: None,
+            improvements: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,280 @@
+use super::interner::{StateId, StateInterner};
+use super::memory;
+use super::{NodeStore, Problem, SearchAlgorithm, SearchTree};
+use crate::benchmarking::MetricsRecorder;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::mem::size_of;
+
+const UNKNOWN: usize = usize::MAX;
+
+type Queue = BinaryHeap<Reverse<(usize, usize)>>;
+
+struct Estimates {
+    f: usize,
+    d_hat: usize,
+    f_hat: usize,
+    open: bool,
+}
+
+#[derive(Default)]
+struct OnlineError {
+    h: f64,
+    d: f64,
+    samples: usize,
+}
+
+impl OnlineError {
+    fn observe(&mut self, h_error: f64, d_error: f64) {
+        self.samples += 1;
+        self.h += (h_error.max(0.0) - self.h) / self.samples as f64;
+        self.d += (d_error.max(0.0) - self.d) / self.samples as f64;
+    }
+
+    fn estimate(&self, g: usize, h: usize) -> Estimates {
+        let d_hat = h as f64 / (1.0 - self.d.min(0.99));
+        Estimates {
+            f: g + h,
+            d_hat: d_hat.ceil() as usize,
+            f_hat: g + h + (d_hat * self.h).ceil() as usize,
+            open: true,
+        }
+    }
+}
+
+fn top(queue: &mut Queue, is_open: impl Fn(usize) -> bool) -> Option<(usize, usize)> {
+    while let Some(&Reverse((key, id))) = queue.peek() {
+        if is_open(id) {
+            return Some((key, id));
+        }
+        queue.pop();
+    }
+    None
+}
+
+pub struct ExplicitEstimationSearch {
+    pub bound: f64,
+    pub track_solutions: bool,
+}
+
+impl ExplicitEstimationSearch {
+    pub fn new(bound: f64) -> Self {
+        ExplicitEstimationSearch {
+            bound: bound.max(1.0),
+            track_solutions: true,
+        }
+    }
+
+    pub fn with_solution_tracking(mut self, track_solutions: bool) -> Self {
+        self.track_solutions = track_solutions;
+        self
+    }
+}
+
+impl SearchAlgorithm for ExplicitEstimationSearch {
+    fn search_recorded<P: Problem>(
+        &self,
+        problem: &P,
+        recorder: &mut MetricsRecorder,
+    ) -> Option<Vec<P::Action>> {
+        let mut interner = StateInterner::new();
+        let mut tree = SearchTree::new();
+        let mut estimates: Vec<Estimates> = Vec::new();
+        let mut g_scores = Vec::new();
+        let mut h_scores = Vec::new();
+        let mut expanded = Vec::new();
+        let mut errors = OnlineError::default();
+        let (mut open_f, mut open_f_hat, mut waiting) = (Queue::new(), Queue::new(), Queue::new());
+        let mut focal: BinaryHeap<Reverse<(usize, usize, usize)>> = BinaryHeap::new();
+
+        for initial_state in problem.initial_states() {
+            let h = problem.heuristic(&initial_state);
+            let (initial_id, is_new) = interner.intern(initial_state);
+            if is_new {
+                g_scores.push(0);
+                h_scores.push(h);
+                expanded.push(false);
+                let id = tree.add_root(initial_id);
+                let node = errors.estimate(0, h);
+                open_f.push(Reverse((node.f, id)));
+                open_f_hat.push(Reverse((node.f_hat, id)));
+                waiting.push(Reverse((node.f_hat, id)));
+                estimates.push(node);
+                recorder.generate();
+            }
+        }
+
+        let heap_bytes = problem.state_heap_bytes(&problem.initial_state());
+        let memory_kb = |interner: &StateInterner<P::State>,
+                         tree: &SearchTree<StateId, P::Action>,
+                         estimates: &Vec<Estimates>,
+                         queues: usize| {
+            memory::kb(
+                interner.memory_bytes(heap_bytes)
+                    + interner.len() * (3 * size_of::<usize>() + size_of::<bool>())
+                    + tree.memory_bytes(0)
+                    + memory::vec_bytes::<Estimates>(estimates.capacity())
+                    + queues * size_of::<Reverse<(usize, usize, usize)>>(),
+            )
+        };
+        let mut successors = Vec::new();
+
+        loop {
+            let is_open = |id: usize| {
+                estimates[id].open && tree.get(id).path_cost == g_scores[tree.get(id).state.index()]
+            };
+            let Some((best_f, best_f_id)) = top(&mut open_f, is_open) else {
+                break;
+            };
+            let (best_f_hat, best_f_hat_id) =
+                top(&mut open_f_hat, is_open).expect("liste ouverte non vide");
+            let focal_limit = self.bound * best_f_hat as f64;
+            while let Some((f_hat, id)) = top(&mut waiting, is_open) {
+                if f_hat as f64 > focal_limit {
+                    break;
+                }
+                waiting.pop();
+                focal.push(Reverse((estimates[id].d_hat, f_hat, id)));
+            }
+            while let Some(&Reverse((_, f_hat, id))) = focal.peek() {
+                if !is_open(id) {
+                    focal.pop();
+                } else if f_hat as f64 > focal_limit {
+                    focal.pop();
+                    waiting.push(Reverse((f_hat, id)));
+                } else {
+                    break;
+                }
+            }
+
+            let limit = self.bound * best_f as f64;
+            let id = match focal.peek() {
+                Some(&Reverse((_, _, id))) if estimates[id].f as f64 <= limit => id,
+                _ if estimates[best_f_hat_id].f as f64 <= limit => best_f_hat_id,
+                _ => best_f_id,
+            };
+            estimates[id].open = false;
+
+            let state_id = tree.get(id).state;
+            let path_cost = tree.get(id).path_cost;
+            let state = interner.get(state_id).clone();
+            recorder.visit();
+            recorder.sample_frontier(best_f, open_f.len());
+
+            if problem.is_goal(&state) {
+                recorder.set_memory_kb(memory_kb(
+                    &interner,
+                    &tree,
+                    &estimates,
+                    open_f.len() + open_f_hat.len() + waiting.len() + focal.len(),
+                ));
+                recorder.set_solution_length(tree.get(id).depth);
+                recorder.set_solution_cost(path_cost);
+                let actions = tree.extract_solution(id);
+                return Some(if self.track_solutions {
+                    actions
+                } else {
+                    Vec::new()
+                });
+            }
+
+            if expanded[state_id.index()] {
+                recorder.reexpand();
+            }
+            expanded[state_id.index()] = true;
+
+            let h = h_scores[state_id.index()];
+            let mut best_child: Option<(usize, usize, usize)> = None;
+            problem.successors_with_heuristic_into(&state, h, &mut successors);
+            for (action, successor_state, cost, successor_h) in successors.drain(..) {
+                if best_child.is_none_or(|(f, _, _)| cost + successor_h < f) {
+                    best_child = Some((cost + successor_h, cost, successor_h));
+                }
+                let tentative_g = path_cost + cost;
+                let (successor_id, is_new) = interner.intern(successor_state);
+                if is_new {
+                    g_scores.push(UNKNOWN);
+                    h_scores.push(successor_h);
+                    expanded.push(false);
+                }
+                if tentative_g >= g_scores[successor_id.index()] {
+                    continue;
+                }
+                g_scores[successor_id.index()] = tentative_g;
+
+                let child = tree.add_child(id, successor_id, action, cost);
+                let node = errors.estimate(tentative_g, successor_h);
+                open_f.push(Reverse((node.f, child)));
+                open_f_hat.push(Reverse((node.f_hat, child)));
+                if node.f_hat as f64 <= focal_limit {
+                    focal.push(Reverse((node.d_hat, node.f_hat, child)));
+                } else {
+                    waiting.push(Reverse((node.f_hat, child)));
+                }
+                estimates.push(node);
+                recorder.generate();
+            }
+            if let Some((_, cost, successor_h)) = best_child {
+                errors.observe(
+                    (cost + successor_h) as f64 - h as f64,
+                    (1 + successor_h) as f64 - h as f64,
+                );
+            }
+
+            recorder.observe_frontier(open_f.len());
+        }
+
+        recorder.set_memory_kb(memory_kb(
+            &interner,
+            &tree,
+            &estimates,
+            open_f.len() + open_f_hat.len() + waiting.len() + focal.len(),
+        ));
+        None
+    }
+
+    fn name(&self) -> &str {
+        "EES"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::astar::AStar;
+    use crate::algorithms::validation;
+    use crate::problems::taquin::hardest_eight_puzzle;
+    use crate::problems::ShortestPath;
+
+    #[test]
+    fn test_taquin_cost_stays_within_the_bound() {
+        let taquin = hardest_eight_puzzle();
+        let optimal = AStar::new().search(&taquin).metrics.solution_cost;
+        for bound in [1.5, 3.0] {
+            let result = ExplicitEstimationSearch::new(bound).search(&taquin);
+            assert!(result.status.is_solved());
+            assert!(result.metrics.solution_cost as f64 <= bound * optimal as f64);
+            let actions = result.solution.unwrap();
+            validation::validate_solution(&taquin, &actions, &result.metrics).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_graph_cost_stays_within_the_bound() {
+        let graph = ShortestPath::generate_connected_with_seed(200, 800, 0, 199, 5);
+        let optimal = AStar::new().search(&graph).metrics.solution_cost;
+        for bound in [1.0, 1.5, 3.0] {
+            let result = ExplicitEstimationSearch::new(bound).search(&graph);
+            assert!(result.metrics.solution_cost as f64 <= bound * optimal as f64);
+        }
+    }
+
+    #[test]
+    fn test_unit_bound_is_optimal() {
+        let taquin = hardest_eight_puzzle();
+        let optimal = AStar::new().search(&taquin).metrics.solution_cost;
+        let result = ExplicitEstimationSearch::new(1.0).search(&taquin);
+        assert!(result.status.is_solved());
+        assert_eq!(result.metrics.solution_cost, optimal);
+    }
+}
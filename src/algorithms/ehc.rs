@@ -0,0 +1,201 @@
+use super::memory;
+use super::{Problem, SearchAlgorithm};
+use crate::benchmarking::MetricsRecorder;
+use crate::utils::hashing::StateSet;
+use std::collections::VecDeque;
+use std::mem::size_of;
+
+struct LocalNode<S, A> {
+    state: S,
+    parent: Option<(usize, A)>,
+    h: usize,
+    depth: usize,
+}
+
+pub struct EnforcedHillClimbing {
+    pub track_solutions: bool,
+}
+
+impl EnforcedHillClimbing {
+    pub fn new() -> Self {
+        EnforcedHillClimbing {
+            track_solutions: true,
+        }
+    }
+
+    pub fn with_solution_tracking(mut self, track_solutions: bool) -> Self {
+        self.track_solutions = track_solutions;
+        self
+    }
+
+    fn improve<P: Problem>(
+        problem: &P,
+        current: &P::State,
+        h: usize,
+        recorder: &mut MetricsRecorder,
+        peak_bytes: &mut usize,
+    ) -> Option<(Vec<P::Action>, usize)> {
+        let mut nodes = vec![LocalNode {
+            state: current.clone(),
+            parent: None,
+            h,
+            depth: 0,
+        }];
+        let mut seen = StateSet::default();
+        seen.insert(current.clone());
+        let mut queue = VecDeque::from([0]);
+        let heap_bytes = problem.state_heap_bytes(current);
+        let mut successors = Vec::new();
+        let mut record_memory = |nodes: &Vec<LocalNode<P::State, P::Action>>,
+                                 seen: &StateSet<P::State>,
+                                 queue: &VecDeque<usize>| {
+            *peak_bytes = (*peak_bytes).max(
+                memory::vec_bytes::<LocalNode<P::State, P::Action>>(nodes.capacity())
+                    + nodes.len() * heap_bytes
+                    + memory::hash_table_bytes::<P::State>(seen.len())
+                    + seen.len() * heap_bytes
+                    + memory::vec_bytes::<usize>(queue.capacity()),
+            );
+        };
+
+        while let Some(id) = queue.pop_front() {
+            recorder.visit();
+            let (state, parent_h, depth) = {
+                let node = &nodes[id];
+                (node.state.clone(), node.h, node.depth)
+            };
+            problem.successors_with_heuristic_into(&state, parent_h, &mut successors);
+            for (action, next, _, next_h) in successors.drain(..) {
+                if !seen.insert(next.clone()) {
+                    continue;
+                }
+                recorder.generate();
+                if next_h < h || problem.is_goal(&next) {
+                    record_memory(&nodes, &seen, &queue);
+                    if depth > 0 {
+                        recorder.escape_plateau();
+                    }
+                    let mut actions = vec![action];
+                    let mut node = id;
+                    while let Some((parent, action)) = &nodes[node].parent {
+                        actions.push(action.clone());
+                        node = *parent;
+                    }
+                    actions.reverse();
+                    return Some((actions, next_h));
+                }
+                queue.push_back(nodes.len());
+                nodes.push(LocalNode {
+                    state: next,
+                    parent: Some((id, action)),
+                    h: next_h,
+                    depth: depth + 1,
+                });
+            }
+            recorder.observe_frontier(queue.len());
+        }
+        record_memory(&nodes, &seen, &queue);
+        None
+    }
+}
+
+impl Default for EnforcedHillClimbing {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SearchAlgorithm for EnforcedHillClimbing {
+    fn search_recorded<P: Problem>(
+        &self,
+        problem: &P,
+        recorder: &mut MetricsRecorder,
+    ) -> Option<Vec<P::Action>> {
+        let start = problem.initial_state();
+        let heap_bytes = problem.state_heap_bytes(&start);
+        let mut h = problem.heuristic(&start);
+        let mut current = start;
+        let mut trajectory = Vec::new();
+        let mut cost = 0;
+        let mut peak_bytes = 0;
+        recorder.generate();
+
+        while !problem.is_goal(&current) {
+            let Some((actions, next_h)) =
+                Self::improve(problem, &current, h, recorder, &mut peak_bytes)
+            else {
+                recorder.set_memory_kb(memory::kb(peak_bytes));
+                return None;
+            };
+            for action in actions {
+                let (state, step_cost) = problem.apply(&current, &action);
+                cost += step_cost;
+                current = state;
+                trajectory.push(action);
+            }
+            h = next_h;
+            recorder.sample_frontier(h, trajectory.len());
+        }
+
+        recorder.set_memory_kb(memory::kb(
+            peak_bytes
+                + trajectory.len() * size_of::<P::Action>()
+                + heap_bytes
+                + size_of::<P::State>(),
+        ));
+        recorder.set_solution_length(trajectory.len());
+        recorder.set_solution_cost(cost);
+        Some(if self.track_solutions {
+            trajectory
+        } else {
+            Vec::new()
+        })
+    }
+
+    fn name(&self) -> &str {
+        "EHC"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::validation;
+    use crate::algorithms::SearchStatus;
+    use crate::problems::taquin::HeuristicType;
+    use crate::problems::{ShortestPath, Taquin};
+
+    #[test]
+    fn test_escapes_plateaus_and_gives_up_on_dead_ends() {
+        let taquin =
+            Taquin::from_state(3, vec![1, 2, 5, 3, 4, 0, 6, 7, 8], HeuristicType::Manhattan);
+        let result = EnforcedHillClimbing::new().search(&taquin);
+        assert!(result.status.is_solved());
+        assert_eq!(result.metrics.solution_length, 3);
+        assert_eq!(result.metrics.plateau_escapes, 0);
+
+        let mut plateau = ShortestPath::new(0, 4);
+        for (from, to) in [(0, 1), (1, 2), (2, 3), (3, 4)] {
+            plateau.add_edge(from, to, 1);
+        }
+        for node in 0..4 {
+            plateau.set_heuristic(node, 3);
+        }
+        let result = EnforcedHillClimbing::new().search(&plateau);
+        assert!(result.status.is_solved());
+        assert_eq!(result.metrics.solution_cost, 4);
+        assert_eq!(result.metrics.plateau_escapes, 1);
+        let actions = result.solution.unwrap();
+        validation::validate_solution(&plateau, &actions, &result.metrics).unwrap();
+
+        let mut dead_end = ShortestPath::new(0, 3);
+        dead_end.add_edge(0, 1, 1);
+        dead_end.add_edge(0, 2, 5);
+        dead_end.add_edge(2, 3, 1);
+        dead_end.set_heuristic(0, 2);
+        dead_end.set_heuristic(1, 1);
+        dead_end.set_heuristic(2, 2);
+        let result = EnforcedHillClimbing::new().search(&dead_end);
+        assert_eq!(result.status, SearchStatus::NoSolution);
+    }
+}
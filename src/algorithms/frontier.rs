@@ -0,0 +1,254 @@
+use super::memory;
+use super::{Problem, SearchAlgorithm};
+use crate::benchmarking::MetricsRecorder;
+use crate::utils::hashing::StateMap;
+
+type Layer<S> = StateMap<S, (usize, usize)>;
+
+struct Sweep<'a> {
+    recorder: &'a mut MetricsRecorder,
+    peak_bytes: &'a mut usize,
+    pruned: Option<usize>,
+}
+
+pub struct FrontierSearch {
+    pub max_depth: usize,
+    pub track_solutions: bool,
+    pub upper_bound: Option<usize>,
+}
+
+impl FrontierSearch {
+    pub fn new(max_depth: usize) -> Self {
+        FrontierSearch {
+            max_depth,
+            track_solutions: true,
+            upper_bound: None,
+        }
+    }
+
+    pub fn with_solution_tracking(mut self, track_solutions: bool) -> Self {
+        self.track_solutions = track_solutions;
+        self
+    }
+
+    pub fn with_upper_bound(mut self, upper_bound: Option<usize>) -> Self {
+        self.upper_bound = upper_bound;
+        self
+    }
+
+    fn reaches<P: Problem>(problem: &P, state: &P::State, target: Option<&P::State>) -> bool {
+        target.map_or_else(|| problem.is_goal(state), |target| state == target)
+    }
+
+    fn layered<P: Problem>(
+        &self,
+        problem: &P,
+        starts: Vec<(P::State, usize)>,
+        target: Option<&P::State>,
+        anchor_depth: usize,
+        sweep: &mut Sweep,
+    ) -> Option<(usize, P::State, usize)> {
+        let heap_bytes = problem.state_heap_bytes(&problem.initial_state());
+        let layer_bytes = |layer: &Layer<P::State>| {
+            memory::hash_table_bytes::<(P::State, (usize, usize))>(layer.len())
+                + layer.len() * heap_bytes
+        };
+        let mut anchors = Vec::new();
+        let mut previous: Layer<P::State> = Layer::default();
+        let mut current: Layer<P::State> = Layer::default();
+        for (state, g) in starts {
+            if current.contains_key(&state) {
+                continue;
+            }
+            let anchor = if anchor_depth == 0 {
+                anchors.push((state.clone(), g));
+                anchors.len() - 1
+            } else {
+                0
+            };
+            current.insert(state, (anchor, g));
+            sweep.recorder.generate();
+        }
+
+        let mut successors = Vec::new();
+        let mut depth = 0;
+        while !current.is_empty() {
+            if depth > self.max_depth {
+                sweep.recorder.mark_cutoff();
+                return None;
+            }
+            let mut next: Layer<P::State> = Layer::default();
+            for (state, &(anchor, g)) in &current {
+                sweep.recorder.visit();
+                if Self::reaches(problem, state, target) {
+                    let (anchor, anchor_g) = anchors[anchor].clone();
+                    return Some((depth, anchor, anchor_g));
+                }
+                problem.successors_into(state, &mut successors);
+                for (_, child, cost) in successors.drain(..) {
+                    if previous.contains_key(&child) || current.contains_key(&child) {
+                        continue;
+                    }
+                    let child_g = g + cost;
+                    if next.get(&child).is_some_and(|&(_, best)| best <= child_g) {
+                        continue;
+                    }
+                    if let Some(bound) = self.upper_bound {
+                        let f = child_g + problem.heuristic(&child);
+                        if f > bound {
+                            sweep.pruned = Some(sweep.pruned.map_or(f, |min| min.min(f)));
+                            continue;
+                        }
+                    }
+                    let child_anchor = if depth + 1 == anchor_depth {
+                        anchors.push((child.clone(), child_g));
+                        anchors.len() - 1
+                    } else {
+                        anchor
+                    };
+                    if next.insert(child, (child_anchor, child_g)).is_none() {
+                        sweep.recorder.generate();
+                    }
+                }
+            }
+
+            *sweep.peak_bytes = (*sweep.peak_bytes).max(
+                layer_bytes(&previous)
+                    + layer_bytes(&current)
+                    + layer_bytes(&next)
+                    + memory::vec_bytes::<(P::State, usize)>(anchors.capacity())
+                    + anchors.len() * heap_bytes,
+            );
+            previous = std::mem::replace(&mut current, next);
+            depth += 1;
+            sweep.recorder.sample_frontier(depth, current.len());
+            sweep.recorder.observe_frontier(current.len());
+        }
+        None
+    }
+
+    fn reconstruct<P: Problem>(
+        &self,
+        problem: &P,
+        from: (P::State, usize),
+        target: Option<&P::State>,
+        depth: usize,
+        sweep: &mut Sweep,
+    ) -> Vec<P::Action> {
+        match depth {
+            0 => Vec::new(),
+            1 => {
+                let mut successors = Vec::new();
+                problem.successors_into(&from.0, &mut successors);
+                sweep.recorder.visit();
+                successors
+                    .into_iter()
+                    .filter(|(_, next, _)| Self::reaches(problem, next, target))
+                    .min_by_key(|&(_, _, cost)| cost)
+                    .map(|(action, _, _)| vec![action])
+                    .expect("successeur à distance 1")
+            }
+            _ => {
+                let half = depth / 2;
+                let (_, middle, middle_g) = self
+                    .layered(problem, vec![from.clone()], target, half, sweep)
+                    .expect("cible à distance connue");
+                let mut actions = self.reconstruct(problem, from, Some(&middle), half, sweep);
+                actions.extend(self.reconstruct(
+                    problem,
+                    (middle, middle_g),
+                    target,
+                    depth - half,
+                    sweep,
+                ));
+                actions
+            }
+        }
+    }
+
+    pub(crate) fn search_within<P: Problem>(
+        &self,
+        problem: &P,
+        recorder: &mut MetricsRecorder,
+        peak_bytes: &mut usize,
+    ) -> Result<Vec<P::Action>, Option<usize>> {
+        let mut sweep = Sweep {
+            recorder,
+            peak_bytes,
+            pruned: None,
+        };
+        let starts = problem
+            .initial_states()
+            .into_iter()
+            .map(|state| (state, 0))
+            .collect();
+        let Some((depth, source, source_g)) = self.layered(problem, starts, None, 0, &mut sweep)
+        else {
+            return Err(sweep.pruned.filter(|_| !sweep.recorder.cutoff_occurred()));
+        };
+
+        let actions =
+            self.reconstruct(problem, (source.clone(), source_g), None, depth, &mut sweep);
+        let mut state = source;
+        let mut cost = 0;
+        for action in &actions {
+            let (next, step_cost) = problem.apply(&state, action);
+            cost += step_cost;
+            state = next;
+        }
+        sweep.recorder.set_solution_length(actions.len());
+        sweep.recorder.set_solution_cost(cost);
+        Ok(if self.track_solutions {
+            actions
+        } else {
+            Vec::new()
+        })
+    }
+}
+
+impl SearchAlgorithm for FrontierSearch {
+    fn search_recorded<P: Problem>(
+        &self,
+        problem: &P,
+        recorder: &mut MetricsRecorder,
+    ) -> Option<Vec<P::Action>> {
+        let mut peak_bytes = 0;
+        let result = self.search_within(problem, recorder, &mut peak_bytes);
+        recorder.set_memory_kb(memory::kb(peak_bytes));
+        result.ok()
+    }
+
+    fn name(&self) -> &str {
+        "Frontier"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::bfs::BFS;
+    use crate::algorithms::validation;
+    use crate::problems::taquin::hardest_eight_puzzle;
+    use crate::problems::ShortestPath;
+
+    #[test]
+    fn test_matches_bfs_depth_with_less_memory() {
+        let taquin = hardest_eight_puzzle();
+        let bfs = BFS::new().search(&taquin);
+        let result = FrontierSearch::new(100).search(&taquin);
+        assert!(result.status.is_solved());
+        assert_eq!(result.metrics.solution_length, bfs.metrics.solution_length);
+        assert!(result.metrics.memory_kb < bfs.metrics.memory_kb);
+        let actions = result.solution.unwrap();
+        validation::validate_solution(&taquin, &actions, &result.metrics).unwrap();
+    }
+
+    #[test]
+    fn test_reconstructs_a_shortest_grid_path() {
+        let grid = ShortestPath::generate_grid_between(12, 12, 0, 143);
+        let result = FrontierSearch::new(100).search(&grid);
+        assert_eq!(result.metrics.solution_length, 22);
+        let actions = result.solution.unwrap();
+        validation::validate_solution(&grid, &actions, &result.metrics).unwrap();
+    }
+}
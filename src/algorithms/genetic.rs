@@ -0,0 +1,215 @@
+use super::memory;
+use super::{Problem, SearchAlgorithm};
+use crate::benchmarking::MetricsRecorder;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::mem::size_of;
+
+const TOURNAMENT: usize = 3;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Fitness {
+    h: usize,
+    length: usize,
+}
+
+pub struct GeneticAlgorithm {
+    pub population: usize,
+    pub generations: usize,
+    pub genome_length: usize,
+    pub seed: u64,
+    pub track_solutions: bool,
+}
+
+impl GeneticAlgorithm {
+    pub fn new(population: usize, generations: usize, genome_length: usize) -> Self {
+        GeneticAlgorithm {
+            population: population.max(2),
+            generations: generations.max(1),
+            genome_length: genome_length.max(1),
+            seed: 0,
+            track_solutions: true,
+        }
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn with_solution_tracking(mut self, track_solutions: bool) -> Self {
+        self.track_solutions = track_solutions;
+        self
+    }
+
+    fn decode<P: Problem>(
+        problem: &P,
+        genome: &[u8],
+        recorder: &mut MetricsRecorder,
+    ) -> (Fitness, Vec<P::Action>, usize) {
+        let mut state = problem.initial_state();
+        let mut previous: Option<P::State> = None;
+        let mut best = Fitness {
+            h: problem.heuristic(&state),
+            length: 0,
+        };
+        let mut actions = Vec::with_capacity(genome.len());
+        let mut costs = Vec::with_capacity(genome.len());
+        let mut successors = Vec::new();
+
+        for &gene in genome {
+            if problem.is_goal(&state) {
+                break;
+            }
+            problem.successors_into(&state, &mut successors);
+            if successors.is_empty() {
+                break;
+            }
+            let mut choice = gene as usize % successors.len();
+            if successors.len() > 1 && previous.as_ref() == Some(&successors[choice].1) {
+                choice = (choice + 1) % successors.len();
+            }
+            let (action, next, cost) = successors.swap_remove(choice);
+            recorder.visit();
+            actions.push(action);
+            costs.push(cost);
+            previous = Some(std::mem::replace(&mut state, next));
+            let fitness = Fitness {
+                h: if problem.is_goal(&state) {
+                    0
+                } else {
+                    problem.heuristic(&state).max(1)
+                },
+                length: actions.len(),
+            };
+            if fitness < best {
+                best = fitness;
+            }
+        }
+
+        actions.truncate(best.length);
+        let cost = costs[..best.length].iter().sum();
+        (best, actions, cost)
+    }
+
+    fn select<'a>(rng: &mut StdRng, population: &'a [(Fitness, Vec<u8>)]) -> &'a [u8] {
+        let winner = (0..TOURNAMENT)
+            .map(|_| &population[rng.gen_range(0..population.len())])
+            .min_by_key(|(fitness, _)| *fitness)
+            .expect("tournoi non vide");
+        &winner.1
+    }
+}
+
+impl SearchAlgorithm for GeneticAlgorithm {
+    fn search_recorded<P: Problem>(
+        &self,
+        problem: &P,
+        recorder: &mut MetricsRecorder,
+    ) -> Option<Vec<P::Action>> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mutation = 1.0 / self.genome_length as f64;
+        let mut genomes: Vec<Vec<u8>> = (0..self.population)
+            .map(|_| (0..self.genome_length).map(|_| rng.gen()).collect())
+            .collect();
+        let heap_bytes = problem.state_heap_bytes(&problem.initial_state());
+        recorder.set_memory_kb(memory::kb(
+            2 * self.population
+                * (memory::vec_bytes::<u8>(self.genome_length) + size_of::<Fitness>())
+                + self.genome_length * (size_of::<P::Action>() + size_of::<usize>())
+                + 2 * (size_of::<P::State>() + heap_bytes),
+        ));
+
+        let mut best: Option<(Fitness, Vec<P::Action>, usize)> = None;
+        for _ in 0..self.generations {
+            let mut scored = Vec::with_capacity(genomes.len());
+            for genome in genomes {
+                let (fitness, actions, cost) = Self::decode(problem, &genome, recorder);
+                recorder.generate();
+                if best
+                    .as_ref()
+                    .is_none_or(|(current, _, _)| fitness < *current)
+                {
+                    best = Some((fitness, actions, cost));
+                }
+                scored.push((fitness, genome));
+            }
+            recorder.next_generation();
+            let (best_fitness, actions, cost) = best.as_ref().expect("population non vide");
+            recorder.sample_frontier(best_fitness.h, scored.len());
+            recorder.observe_frontier(scored.len());
+
+            if best_fitness.h == 0 {
+                recorder.set_solution_length(actions.len());
+                recorder.set_solution_cost(*cost);
+                return Some(if self.track_solutions {
+                    actions.clone()
+                } else {
+                    Vec::new()
+                });
+            }
+
+            let elite = scored
+                .iter()
+                .min_by_key(|(fitness, _)| *fitness)
+                .map(|(_, genome)| genome.clone())
+                .expect("population non vide");
+            genomes = std::iter::once(elite)
+                .chain((1..self.population).map(|_| {
+                    let (mother, father) = (
+                        Self::select(&mut rng, &scored),
+                        Self::select(&mut rng, &scored),
+                    );
+                    let cut = rng.gen_range(0..=self.genome_length);
+                    mother[..cut]
+                        .iter()
+                        .chain(&father[cut..])
+                        .map(|&gene| {
+                            if rng.gen_bool(mutation) {
+                                rng.gen()
+                            } else {
+                                gene
+                            }
+                        })
+                        .collect()
+                }))
+                .collect();
+        }
+
+        recorder.mark_cutoff();
+        None
+    }
+
+    fn name(&self) -> &str {
+        "GA"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::validation;
+    use crate::algorithms::SearchStatus;
+    use crate::problems::taquin::{hardest_eight_puzzle, HeuristicType};
+    use crate::problems::Taquin;
+
+    #[test]
+    fn test_evolves_a_valid_move_sequence() {
+        let taquin =
+            Taquin::from_state(3, vec![1, 4, 2, 3, 0, 5, 6, 7, 8], HeuristicType::Manhattan);
+        let search = GeneticAlgorithm::new(50, 100, 30).with_seed(3);
+        let result = search.search(&taquin);
+        assert!(result.status.is_solved());
+        assert!(result.metrics.generations >= 1);
+        let actions = result.solution.unwrap();
+        validation::validate_solution(&taquin, &actions, &result.metrics).unwrap();
+        let again = search.search(&taquin);
+        assert_eq!(again.metrics.solution_cost, result.metrics.solution_cost);
+        assert_eq!(again.metrics.nodes_visited, result.metrics.nodes_visited);
+
+        let hard = hardest_eight_puzzle();
+        let result = GeneticAlgorithm::new(4, 2, 5).search(&hard);
+        assert_eq!(result.status, SearchStatus::DepthLimitReached);
+        assert_eq!(result.metrics.generations, 2);
+    }
+}
@@ -1,203 +1,274 @@
-use super::{Node, Problem, SearchAlgorithm, SearchResult};
-use crate::benchmarking::{Metrics, SharedMetrics};
-use std::collections::HashSet;
-use std::time::Instant;
+use super::memory;
+use super::{HeuristicSuccessors, Problem, SearchAlgorithm};
+use crate::benchmarking::MetricsRecorder;
+use crate::utils::hashing::StateSet;
+use std::cmp::Reverse;
+use std::mem::size_of;
+
+type CostedPath<A> = (Vec<A>, usize);
 
 pub struct IDAStar {
     pub max_bound: usize,
+    pub cost_bound: Option<usize>,
+    pub order_successors: bool,
 }
 
 impl IDAStar {
     pub fn new(max_bound: usize) -> Self {
-        IDAStar { max_bound }
+        IDAStar {
+            max_bound,
+            cost_bound: None,
+            order_successors: false,
+        }
+    }
+
+    pub fn with_cost_bound(mut self, cost_bound: Option<usize>) -> Self {
+        self.cost_bound = cost_bound;
+        self
     }
 
-    fn search_recursive<P: Problem>(
+    pub fn with_successor_ordering(mut self, order_successors: bool) -> Self {
+        self.order_successors = order_successors;
+        self
+    }
+
+    fn bounded_search<P: Problem>(
         &self,
         problem: &P,
-        node: &Node<P::State>,
+        initial_state: &P::State,
         bound: usize,
-        explored: &mut HashSet<P::State>,
-        metrics: &mut Metrics,
-    ) -> (Option<Vec<usize>>, usize) {
-        metrics.nodes_visited += 1;
-
-        let f = node.path_cost + problem.heuristic(&node.state);
-
-        if f > bound {
-            return (None, f);
+        explored: &mut StateSet<P::State>,
+        recorder: &mut MetricsRecorder,
+        peak_bytes: &mut usize,
+    ) -> (Option<CostedPath<P::Action>>, usize) {
+        recorder.visit();
+
+        let initial_h = problem.heuristic(initial_state);
+        if initial_h > bound {
+            return (None, initial_h);
         }
 
-        if problem.is_goal(&node.state) {
-            return (Some(node.extract_solution()), 0);
+        if problem.is_goal(initial_state) {
+            return (Some((Vec::new(), 0)), 0);
         }
 
-        explored.insert(node.state.clone());
-
         let mut min_bound = usize::MAX;
+        let mut path = Vec::new();
+        let mut spare_buffers: Vec<HeuristicSuccessors<P>> = Vec::new();
+
+        let heap_bytes = problem.state_heap_bytes(initial_state);
+        let frame_bytes = |frame: &Frame<P::State, P::Action>| {
+            size_of::<Frame<P::State, P::Action>>()
+                + heap_bytes
+                + memory::vec_bytes::<(P::Action, P::State, usize, usize)>(
+                    frame.successors.capacity(),
+                )
+                + frame.successors.capacity() * heap_bytes
+        };
+
+        let mut successors = Vec::new();
+        problem.successors_with_heuristic_into(initial_state, initial_h, &mut successors);
+        successors.reverse();
+        if self.order_successors {
+            successors.sort_by_key(|&(_, _, _, h)| Reverse(h));
+        }
+        let mut stack = vec![Frame {
+            state: initial_state.clone(),
+            g: 0,
+            successors,
+        }];
+        explored.insert(initial_state.clone());
+        let mut stack_bytes = frame_bytes(&stack[0]);
+
+        while let Some(frame) = stack.last_mut() {
+            let Some((action, successor_state, cost, h)) = frame.successors.pop() else {
+                let frame = stack.pop().unwrap();
+                stack_bytes -= frame_bytes(&frame);
+                explored.remove(&frame.state);
+                spare_buffers.push(frame.successors);
+                path.pop();
+                continue;
+            };
 
-        for (successor_state, cost) in problem.successors(&node.state) {
             if explored.contains(&successor_state) {
                 continue;
             }
 
-            let child = node.child(successor_state, metrics.nodes_generated, cost);
-            metrics.nodes_generated += 1;
-
-            let (result, new_bound) =
-                self.search_recursive(problem, &child, bound, explored, metrics);
-
-            if result.is_some() {
-                explored.remove(&node.state);
-                return (result, 0);
-            }
-
-            if new_bound < min_bound {
-                min_bound = new_bound;
+            let g = frame.g + cost;
+            if self.cost_bound.is_some_and(|cost_bound| g > cost_bound) {
+                continue;
             }
-        }
-
-        explored.remove(&node.state);
-        (None, min_bound)
-    }
-
-    fn search_recursive_shared<P: Problem>(
-        &self,
-        problem: &P,
-        node: &Node<P::State>,
-        bound: usize,
-        explored: &mut HashSet<P::State>,
-        shared: &SharedMetrics,
-    ) -> (Option<Vec<usize>>, usize) {
-        shared.increment_visited();
-
-        let f = node.path_cost + problem.heuristic(&node.state);
-
-        if f > bound {
-            return (None, f);
-        }
-
-        if problem.is_goal(&node.state) {
-            return (Some(node.extract_solution()), 0);
-        }
-
-        explored.insert(node.state.clone());
-
-        let mut min_bound = usize::MAX;
+            recorder.generate();
+            recorder.visit();
 
-        for (successor_state, cost) in problem.successors(&node.state) {
-            if explored.contains(&successor_state) {
+            let f = g + h;
+            if f > bound {
+                min_bound = min_bound.min(f);
                 continue;
             }
 
-            let generated = shared.get().nodes_generated;
-            let child = node.child(successor_state, generated, cost);
-            shared.increment_generated();
-
-            let (result, new_bound) =
-                self.search_recursive_shared(problem, &child, bound, explored, shared);
+            path.push(action);
 
-            if result.is_some() {
-                explored.remove(&node.state);
-                return (result, 0);
+            if problem.is_goal(&successor_state) {
+                return (Some((path, g)), 0);
             }
 
-            if new_bound < min_bound {
-                min_bound = new_bound;
+            let mut successors = spare_buffers.pop().unwrap_or_default();
+            problem.successors_with_heuristic_into(&successor_state, h, &mut successors);
+            successors.reverse();
+            if self.order_successors {
+                successors.sort_by_key(|&(_, _, _, h)| Reverse(h));
             }
+            explored.insert(successor_state.clone());
+            let frame = Frame {
+                state: successor_state,
+                g,
+                successors,
+            };
+            stack_bytes += frame_bytes(&frame);
+            stack.push(frame);
+
+            let bytes = stack_bytes
+                + memory::hash_table_bytes::<P::State>(explored.len())
+                + explored.len() * heap_bytes
+                + memory::vec_bytes::<P::Action>(path.capacity());
+            *peak_bytes = (*peak_bytes).max(bytes);
         }
 
-        explored.remove(&node.state);
         (None, min_bound)
     }
 }
 
-impl SearchAlgorithm for IDAStar {
-    fn search<P: Problem>(&self, problem: &P) -> SearchResult {
-        let start = Instant::now();
-        let mut metrics = Metrics::default();
-
-        let initial_state = problem.initial_state();
-        let mut bound = problem.heuristic(&initial_state);
-        let initial_node = Node::new(initial_state);
+struct Frame<S, A> {
+    state: S,
+    g: usize,
+    successors: Vec<(A, S, usize, usize)>,
+}
 
-        metrics.nodes_generated = 1;
+impl SearchAlgorithm for IDAStar {
+    fn search_recorded<P: Problem>(
+        &self,
+        problem: &P,
+        recorder: &mut MetricsRecorder,
+    ) -> Option<Vec<P::Action>> {
+        let initial_states = problem.initial_states();
+        let mut bound = initial_states
+            .iter()
+            .map(|state| problem.heuristic(state))
+            .min()
+            .unwrap_or(0);
+        for _ in &initial_states {
+            recorder.generate();
+        }
 
+        let mut peak_bytes = 0;
         loop {
-            let mut explored = HashSet::new();
-            let (result, new_bound) =
-                self.search_recursive(problem, &initial_node, bound, &mut explored, &mut metrics);
-
-            if let Some(solution) = result {
-                metrics.solution_length = solution.len();
-                metrics.time_ms = start.elapsed().as_millis() as f64;
-                metrics.memory_kb = explored.len() * std::mem::size_of::<P::State>() / 1024;
-
-                return SearchResult {
-                    solution: Some(solution),
-                    metrics,
-                    status: 0,
-                };
+            recorder.sample_bound(bound);
+            let mut new_bound = usize::MAX;
+            for initial_state in &initial_states {
+                let mut explored = StateSet::default();
+                let (result, next_bound) = self.bounded_search(
+                    problem,
+                    initial_state,
+                    bound,
+                    &mut explored,
+                    recorder,
+                    &mut peak_bytes,
+                );
+                recorder.set_memory_kb(memory::kb(peak_bytes));
+
+                if let Some((solution, cost)) = result {
+                    recorder.set_solution_cost(cost);
+                    return Some(solution);
+                }
+                new_bound = new_bound.min(next_bound);
             }
 
-            if new_bound == usize::MAX || bound >= self.max_bound {
-                break;
+            if new_bound == usize::MAX {
+                return None;
+            }
+            if bound >= self.max_bound {
+                recorder.mark_cutoff();
+                return None;
             }
 
             bound = new_bound;
         }
-
-        metrics.time_ms = start.elapsed().as_millis() as f64;
-
-        SearchResult {
-            solution: None,
-            metrics,
-            status: 2,
-        }
     }
 
-    fn search_with_shared_metrics<P: Problem>(
-        &self,
-        problem: &P,
-        shared: SharedMetrics,
-    ) -> SearchResult {
-        let initial_state = problem.initial_state();
-        let mut bound = problem.heuristic(&initial_state);
-        let initial_node = Node::new(initial_state);
+    fn name(&self) -> &str {
+        "IDA*"
+    }
+}
 
-        shared.update(|m| m.nodes_generated = 1);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::problems::taquin::{hardest_eight_puzzle, HeuristicType};
+    use crate::problems::ShortestPath;
+    use crate::problems::Taquin;
+
+    #[test]
+    fn test_deep_chain_does_not_overflow_stack() {
+        let length = 100_000;
+        let mut chain = ShortestPath::new(0, length);
+        for node in 0..length {
+            chain.add_edge(node, node + 1, 1);
+            chain.set_heuristic(node, length - node);
+        }
 
-        loop {
-            let mut explored = HashSet::new();
-            let (result, new_bound) =
-                self.search_recursive_shared(problem, &initial_node, bound, &mut explored, &shared);
-
-            if let Some(solution) = result {
-                shared.set_solution_length(solution.len());
-                shared.set_memory_kb(explored.len() * std::mem::size_of::<P::State>() / 1024);
-
-                return SearchResult {
-                    solution: Some(solution),
-                    metrics: shared.get(),
-                    status: 0,
-                };
-            }
+        let result = IDAStar::new(length * 2).search(&chain);
+        assert!(result.status.is_solved());
+        assert_eq!(result.solution.unwrap().len(), length);
+    }
 
-            if new_bound == usize::MAX || bound >= self.max_bound {
-                break;
-            }
+    #[test]
+    fn test_successor_ordering_changes_expansions_not_cost() {
+        let taquin = hardest_eight_puzzle();
+        let plain = IDAStar::new(100).search(&taquin);
+        let ordered = IDAStar::new(100)
+            .with_successor_ordering(true)
+            .search(&taquin);
+        assert_eq!(ordered.metrics.solution_cost, plain.metrics.solution_cost);
+        assert_ne!(ordered.metrics.nodes_visited, plain.metrics.nodes_visited);
+    }
 
-            bound = new_bound;
-        }
+    #[test]
+    fn test_optimal_on_taquin() {
+        let taquin =
+            Taquin::from_state(3, vec![1, 2, 5, 3, 4, 0, 6, 7, 8], HeuristicType::Manhattan);
+        let result = IDAStar::new(100).search(&taquin);
+        assert!(result.status.is_solved());
+        assert_eq!(result.metrics.solution_length, 3);
+        assert_eq!(result.profile, None);
+
+        let recorder = MetricsRecorder::new().with_profile();
+        let profiled = IDAStar::new(100).search_with_recorder(&taquin, recorder);
+        let bounds: Vec<usize> = profiled
+            .profile
+            .unwrap()
+            .iter()
+            .map(|sample| sample.f_bound)
+            .collect();
+        assert_eq!(
+            bounds.first(),
+            Some(&taquin.heuristic(&taquin.initial_state()))
+        );
+        assert!(bounds.windows(2).all(|pair| pair[0] < pair[1]));
+    }
 
-        SearchResult {
-            solution: None,
-            metrics: shared.get(),
-            status: 2,
+    #[test]
+    fn test_bound_limit_is_reported_as_cutoff() {
+        let mut chain = ShortestPath::new(0, 10);
+        for node in 0..10 {
+            chain.add_edge(node, node + 1, 1);
         }
-    }
 
-    fn name(&self) -> &str {
-        "IDA*"
+        let result = IDAStar::new(4).search(&chain);
+        assert_eq!(
+            result.status,
+            crate::algorithms::SearchStatus::DepthLimitReached
+        );
+        assert!(IDAStar::new(10).search(&chain).status.is_solved());
     }
 }
@@ -0,0 +1,105 @@
+use super::memory;
+use crate::utils::hashing::{StateHasher, StateMap};
+use std::hash::{BuildHasher, Hash};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct StateId(u32);
+
+impl StateId {
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+pub struct StateInterner<S> {
+    states: Vec<S>,
+    heads: StateMap<u64, StateId>,
+    next_with_same_hash: Vec<Option<StateId>>,
+    hasher: StateHasher,
+}
+
+impl<S: Eq + Hash> StateInterner<S> {
+    pub fn new() -> Self {
+        StateInterner {
+            states: Vec::new(),
+            heads: StateMap::default(),
+            next_with_same_hash: Vec::new(),
+            hasher: StateHasher::default(),
+        }
+    }
+
+    pub fn intern(&mut self, state: S) -> (StateId, bool) {
+        let hash = self.hasher.hash_one(&state);
+        let head = self.heads.get(&hash).copied();
+        if let Some(id) = self.find_in_chain(head, &state) {
+            return (id, false);
+        }
+
+        let id = StateId(self.states.len() as u32);
+        self.states.push(state);
+        self.next_with_same_hash.push(head);
+        self.heads.insert(hash, id);
+        (id, true)
+    }
+
+    pub fn lookup(&self, state: &S) -> Option<StateId> {
+        let hash = self.hasher.hash_one(state);
+        self.find_in_chain(self.heads.get(&hash).copied(), state)
+    }
+
+    fn find_in_chain(&self, mut current: Option<StateId>, state: &S) -> Option<StateId> {
+        while let Some(id) = current {
+            if self.states[id.index()] == *state {
+                return Some(id);
+            }
+            current = self.next_with_same_hash[id.index()];
+        }
+        None
+    }
+
+    pub fn get(&self, id: StateId) -> &S {
+        &self.states[id.index()]
+    }
+
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    pub fn memory_bytes(&self, state_heap_bytes: usize) -> usize {
+        memory::vec_bytes::<S>(self.states.capacity())
+            + self.states.len() * state_heap_bytes
+            + memory::hash_table_bytes::<(u64, StateId)>(self.heads.len())
+            + memory::vec_bytes::<Option<StateId>>(self.next_with_same_hash.capacity())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+}
+
+impl<S: Eq + Hash> Default for StateInterner<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intern_deduplicates() {
+        let mut interner = StateInterner::new();
+        let (a, new_a) = interner.intern(vec![1u8, 2, 3]);
+        let (b, new_b) = interner.intern(vec![3u8, 2, 1]);
+        let (again, new_again) = interner.intern(vec![1u8, 2, 3]);
+
+        assert!(new_a && new_b && !new_again);
+        assert_eq!(a, again);
+        assert_ne!(a, b);
+        assert_eq!(interner.len(), 2);
+        assert_eq!(interner.get(b), &vec![3u8, 2, 1]);
+        assert_eq!(interner.lookup(&vec![3u8, 2, 1]), Some(b));
+        assert_eq!(interner.lookup(&vec![0u8]), None);
+    }
+}
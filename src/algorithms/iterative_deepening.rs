@@ -1,81 +1,126 @@
-use super::dfs::DFS;
-use super::{Problem, SearchAlgorithm, SearchResult};
-use crate::benchmarking::{Metrics, SharedMetrics};
-use std::time::Instant;
+use super::depth_limited::{depth_limited, LimitedOutcome};
+use super::memory;
+use super::{Problem, SearchAlgorithm};
+use crate::benchmarking::MetricsRecorder;
 
 pub struct IterativeDeepening {
     pub max_depth: usize,
+    pub track_solutions: bool,
+    pub cost_bound: Option<usize>,
+    pub order_successors: bool,
 }
 
 impl IterativeDeepening {
     pub fn new(max_depth: usize) -> Self {
-        IterativeDeepening { max_depth }
+        IterativeDeepening {
+            max_depth,
+            track_solutions: true,
+            cost_bound: None,
+            order_successors: false,
+        }
     }
-}
-
-impl SearchAlgorithm for IterativeDeepening {
-    fn search<P: Problem>(&self, problem: &P) -> SearchResult {
-        let start = Instant::now();
-        let mut total_metrics = Metrics::default();
-
-        for depth in 0..=self.max_depth {
-            let dfs = DFS::with_max_depth(depth);
-            let result = dfs.search(problem);
-
-            total_metrics.nodes_visited += result.metrics.nodes_visited;
-            total_metrics.nodes_generated += result.metrics.nodes_generated;
-            total_metrics.max_frontier_size = total_metrics
-                .max_frontier_size
-                .max(result.metrics.max_frontier_size);
-
-            if result.status == 0 {
-                total_metrics.solution_length = result.metrics.solution_length;
-                total_metrics.time_ms = start.elapsed().as_millis() as f64;
-                total_metrics.memory_kb = result.metrics.memory_kb;
 
-                return SearchResult {
-                    solution: result.solution,
-                    metrics: total_metrics,
-                    status: 0,
-                };
-            }
-        }
+    pub fn with_solution_tracking(mut self, track_solutions: bool) -> Self {
+        self.track_solutions = track_solutions;
+        self
+    }
 
-        total_metrics.time_ms = start.elapsed().as_millis() as f64;
+    pub fn with_cost_bound(mut self, cost_bound: Option<usize>) -> Self {
+        self.cost_bound = cost_bound;
+        self
+    }
 
-        SearchResult {
-            solution: None,
-            metrics: total_metrics,
-            status: 2,
-        }
+    pub fn with_successor_ordering(mut self, order_successors: bool) -> Self {
+        self.order_successors = order_successors;
+        self
     }
+}
 
-    fn search_with_shared_metrics<P: Problem>(
+impl SearchAlgorithm for IterativeDeepening {
+    fn search_recorded<P: Problem>(
         &self,
         problem: &P,
-        shared: SharedMetrics,
-    ) -> SearchResult {
-        for depth in 0..=self.max_depth {
-            let dfs = DFS::with_max_depth(depth);
-            let result = dfs.search_with_shared_metrics(problem, shared.clone());
+        recorder: &mut MetricsRecorder,
+    ) -> Option<Vec<P::Action>> {
+        let initial_states = problem.initial_states();
+        for _ in &initial_states {
+            recorder.generate();
+        }
 
-            if result.status == 0 {
-                return SearchResult {
-                    solution: result.solution,
-                    metrics: shared.get(),
-                    status: 0,
-                };
+        let mut peak_bytes = 0;
+        for limit in 0..=self.max_depth {
+            let mut exhausted = true;
+            for initial_state in &initial_states {
+                let outcome = depth_limited(
+                    problem,
+                    initial_state,
+                    limit,
+                    self.cost_bound,
+                    self.order_successors,
+                    recorder,
+                    &mut peak_bytes,
+                );
+                recorder.set_memory_kb(memory::kb(peak_bytes));
+                match outcome {
+                    LimitedOutcome::Found(solution, cost) => {
+                        recorder.set_solution_length(solution.len());
+                        recorder.set_solution_cost(cost);
+                        return Some(if self.track_solutions {
+                            solution
+                        } else {
+                            Vec::new()
+                        });
+                    }
+                    LimitedOutcome::Exhausted => {}
+                    LimitedOutcome::Cutoff => exhausted = false,
+                }
+            }
+            if exhausted {
+                return None;
             }
         }
 
-        SearchResult {
-            solution: None,
-            metrics: shared.get(),
-            status: 2,
-        }
+        recorder.mark_cutoff();
+        None
     }
 
     fn name(&self) -> &str {
         "Iterative Deepening"
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::SearchStatus;
+    use crate::problems::ShortestPath;
+
+    #[test]
+    fn test_finds_shallow_path_behind_deep_detour() {
+        let mut graph = ShortestPath::new(0, 3);
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(1, 2, 1);
+        graph.add_edge(0, 2, 1);
+        graph.add_edge(2, 3, 1);
+
+        let result = IterativeDeepening::new(5).search(&graph);
+        assert!(result.status.is_solved());
+        assert_eq!(result.metrics.solution_length, 2);
+    }
+
+    #[test]
+    fn test_cutoff_is_distinct_from_no_solution() {
+        let mut chain = ShortestPath::new(0, 10);
+        for node in 0..10 {
+            chain.add_edge(node, node + 1, 1);
+        }
+        let result = IterativeDeepening::new(3).search(&chain);
+        assert_eq!(result.status, SearchStatus::DepthLimitReached);
+
+        let mut cycle = ShortestPath::new(0, 5);
+        cycle.add_edge(0, 1, 1);
+        cycle.add_edge(1, 0, 1);
+        let result = IterativeDeepening::new(10).search(&cycle);
+        assert_eq!(result.status, SearchStatus::NoSolution);
+    }
+}
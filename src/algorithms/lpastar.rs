@@ -0,0 +1,218 @@
+use super::memory;
+use super::{BackwardProblem, Problem, SearchResult, SearchStatus};
+use crate::benchmarking::MetricsRecorder;
+use crate::utils::hashing::StateMap;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+const INFINITY: usize = usize::MAX;
+
+type Key = (usize, usize);
+
+pub struct LpaStar<S> {
+    start: Option<S>,
+    goal: Option<S>,
+    g: StateMap<S, usize>,
+    rhs: StateMap<S, usize>,
+    open: StateMap<S, Key>,
+    queue: BinaryHeap<Reverse<(Key, usize)>>,
+    pending: StateMap<usize, S>,
+    order: usize,
+    changed: Vec<S>,
+}
+
+impl<S: Clone + Eq + std::hash::Hash> Default for LpaStar<S> {
+    fn default() -> Self {
+        LpaStar {
+            start: None,
+            goal: None,
+            g: StateMap::default(),
+            rhs: StateMap::default(),
+            open: StateMap::default(),
+            queue: BinaryHeap::new(),
+            pending: StateMap::default(),
+            order: 0,
+            changed: Vec::new(),
+        }
+    }
+}
+
+impl<S: Clone + Eq + std::hash::Hash> LpaStar<S> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn edge_changed(&mut self, to: S) {
+        if self.start.is_some() {
+            self.changed.push(to);
+        }
+    }
+
+    fn g(&self, state: &S) -> usize {
+        self.g.get(state).copied().unwrap_or(INFINITY)
+    }
+
+    fn rhs(&self, state: &S) -> usize {
+        self.rhs.get(state).copied().unwrap_or(INFINITY)
+    }
+
+    fn key<P: Problem<State = S>>(&self, problem: &P, state: &S) -> Key {
+        let best = self.g(state).min(self.rhs(state));
+        (best.saturating_add(problem.heuristic(state)), best)
+    }
+
+    fn push(&mut self, state: S, key: Key) {
+        self.open.insert(state.clone(), key);
+        self.pending.insert(self.order, state);
+        self.queue.push(Reverse((key, self.order)));
+        self.order += 1;
+    }
+
+    fn top(&mut self) -> Option<(Key, usize)> {
+        while let Some(&Reverse((key, id))) = self.queue.peek() {
+            if self.open.get(&self.pending[&id]) == Some(&key) {
+                return Some((key, id));
+            }
+            self.queue.pop();
+            self.pending.remove(&id);
+        }
+        None
+    }
+
+    fn update_vertex<P: BackwardProblem<State = S>>(
+        &mut self,
+        problem: &P,
+        state: &S,
+        recorder: &mut MetricsRecorder,
+    ) {
+        recorder.generate();
+        if self.start.as_ref() != Some(state) {
+            let rhs = problem
+                .predecessors(state)
+                .into_iter()
+                .map(|(predecessor, cost)| self.g(&predecessor).saturating_add(cost))
+                .min()
+                .unwrap_or(INFINITY);
+            self.rhs.insert(state.clone(), rhs);
+        }
+        self.open.remove(state);
+        if self.g(state) != self.rhs(state) {
+            let key = self.key(problem, state);
+            self.push(state.clone(), key);
+        }
+    }
+
+    fn compute<P: BackwardProblem<State = S>>(
+        &mut self,
+        problem: &P,
+        goal: &S,
+        recorder: &mut MetricsRecorder,
+    ) {
+        while let Some((key, id)) = self.top() {
+            if key >= self.key(problem, goal) && self.rhs(goal) == self.g(goal) {
+                break;
+            }
+            self.queue.pop();
+            let state = self.pending.remove(&id).expect("état en attente");
+            self.open.remove(&state);
+            recorder.visit();
+
+            let (g, rhs) = (self.g(&state), self.rhs(&state));
+            if g > rhs {
+                self.g.insert(state.clone(), rhs);
+            } else {
+                recorder.expand_underconsistent();
+                self.g.insert(state.clone(), INFINITY);
+                self.update_vertex(problem, &state, recorder);
+            }
+            for (next, _) in problem.successors(&state) {
+                self.update_vertex(problem, &next, recorder);
+            }
+            recorder.observe_frontier(self.open.len());
+        }
+    }
+
+    fn path<P: BackwardProblem<State = S>>(&self, problem: &P, goal: &S) -> Option<Vec<P::Action>> {
+        let start = self.start.as_ref()?;
+        if self.g(goal) == INFINITY {
+            return None;
+        }
+        let mut actions = Vec::new();
+        let mut state = goal.clone();
+        while &state != start && actions.len() <= self.g.len() {
+            let g = self.g(&state);
+            let (previous, cost) = problem
+                .predecessors(&state)
+                .into_iter()
+                .find(|(predecessor, cost)| self.g(predecessor).saturating_add(*cost) == g)?;
+            let action = problem
+                .actions(&previous)
+                .into_iter()
+                .find(|action| problem.apply(&previous, action) == (state.clone(), cost))?;
+            actions.push(action);
+            state = previous;
+        }
+        actions.reverse();
+        Some(actions)
+    }
+
+    fn memory_kb(&self) -> usize {
+        memory::kb(
+            2 * memory::hash_table_bytes::<(S, usize)>(self.g.len().max(self.rhs.len()))
+                + memory::hash_table_bytes::<(S, Key)>(self.open.len())
+                + memory::hash_table_bytes::<(usize, S)>(self.pending.len())
+                + memory::vec_bytes::<Reverse<(Key, usize)>>(self.queue.capacity()),
+        )
+    }
+
+    pub fn search<P: BackwardProblem<State = S>>(
+        &mut self,
+        problem: &P,
+    ) -> SearchResult<P::Action> {
+        let mut recorder = MetricsRecorder::new();
+        if self.start.is_none() {
+            let Some(goal) = problem.goal_states().into_iter().next() else {
+                return SearchResult {
+                    solution: None,
+                    metrics: recorder.finish(),
+                    status: SearchStatus::Error("LPA*: aucun état but explicite".to_string()),
+                    profile: None,
+                    improvements: Vec::new(),
+                };
+            };
+            let start = problem.initial_state();
+            self.rhs.insert(start.clone(), 0);
+            self.start = Some(start.clone());
+            self.goal = Some(goal);
+            let key = self.key(problem, &start);
+            self.push(start, key);
+        }
+
+        let goal = self.goal.clone().expect("but fixé à l'initialisation");
+        for state in std::mem::take(&mut self.changed) {
+            self.update_vertex(problem, &state, &mut recorder);
+        }
+        self.compute(problem, &goal, &mut recorder);
+        recorder.set_memory_kb(self.memory_kb());
+
+        let solution = self.path(problem, &goal);
+        let status = match &solution {
+            Some(actions) => {
+                recorder.set_solution_length(actions.len());
+                recorder.set_solution_cost(self.g(&goal));
+                SearchStatus::Solved
+            }
+            None if self.g(&goal) != INFINITY => {
+                SearchStatus::Error("LPA*: chemin incohérent avec les valeurs g".to_string())
+            }
+            None => SearchStatus::NoSolution,
+        };
+        SearchResult {
+            solution,
+            metrics: recorder.finish(),
+            status,
+            profile: None,
+            improvements: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,211 @@
+use super::memory;
+use super::{Problem, SearchAlgorithm};
+use crate::benchmarking::MetricsRecorder;
+use crate::utils::hashing::StateMap;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::mem::size_of;
+use std::time::Instant;
+
+struct LocalNode<S, A> {
+    state: S,
+    parent: Option<(usize, A)>,
+    g: usize,
+    depth: usize,
+}
+
+pub struct LRTAStar {
+    pub lookahead: usize,
+    pub step_budget: usize,
+    pub max_steps: usize,
+}
+
+impl LRTAStar {
+    pub fn new(lookahead: usize, step_budget: usize) -> Self {
+        LRTAStar {
+            lookahead: lookahead.max(1),
+            step_budget: step_budget.max(1),
+            max_steps: usize::MAX,
+        }
+    }
+
+    pub fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    fn first_action<S, A: Clone>(nodes: &[LocalNode<S, A>], mut id: usize) -> Option<A> {
+        let mut first = None;
+        while let Some((parent, action)) = &nodes[id].parent {
+            first = Some(action.clone());
+            id = *parent;
+        }
+        first
+    }
+
+    fn plan<P: Problem>(
+        &self,
+        problem: &P,
+        learned: &StateMap<P::State, usize>,
+        current: &P::State,
+        recorder: &mut MetricsRecorder,
+    ) -> (Option<P::Action>, usize, usize) {
+        let h = |state: &P::State| {
+            learned
+                .get(state)
+                .copied()
+                .unwrap_or_else(|| problem.heuristic(state))
+        };
+        let mut nodes = vec![LocalNode {
+            state: current.clone(),
+            parent: None,
+            g: 0,
+            depth: 0,
+        }];
+        let mut best_g = StateMap::default();
+        best_g.insert(current.clone(), 0);
+        let mut open = BinaryHeap::new();
+        open.push(Reverse((h(current), 0)));
+        let mut successors = Vec::new();
+        let mut expansions = 0;
+
+        while let Some(&Reverse((f, id))) = open.peek() {
+            let node = &nodes[id];
+            if best_g.get(&node.state).is_some_and(|&g| g < node.g) {
+                open.pop();
+                continue;
+            }
+            if id != 0
+                && (problem.is_goal(&node.state)
+                    || node.depth >= self.lookahead
+                    || expansions >= self.step_budget)
+            {
+                let local = nodes.len();
+                return (Self::first_action(&nodes, id), f, local);
+            }
+            open.pop();
+            expansions += 1;
+            recorder.visit();
+
+            let (state, g, depth) = (node.state.clone(), node.g, node.depth);
+            problem.successors_into(&state, &mut successors);
+            for (action, next, cost) in successors.drain(..) {
+                let tentative_g = g + cost;
+                if best_g.get(&next).is_some_and(|&known| known <= tentative_g) {
+                    continue;
+                }
+                best_g.insert(next.clone(), tentative_g);
+                open.push(Reverse((tentative_g + h(&next), nodes.len())));
+                nodes.push(LocalNode {
+                    state: next,
+                    parent: Some((id, action)),
+                    g: tentative_g,
+                    depth: depth + 1,
+                });
+                recorder.generate();
+            }
+            recorder.observe_frontier(open.len());
+        }
+        (None, usize::MAX, nodes.len())
+    }
+}
+
+impl SearchAlgorithm for LRTAStar {
+    fn search_recorded<P: Problem>(
+        &self,
+        problem: &P,
+        recorder: &mut MetricsRecorder,
+    ) -> Option<Vec<P::Action>> {
+        let mut learned: StateMap<P::State, usize> = StateMap::default();
+        let mut current = problem.initial_state();
+        let mut trajectory = Vec::new();
+        let mut cost = 0;
+        let mut peak_local = 0;
+        let heap_bytes = problem.state_heap_bytes(&current);
+        let memory_kb = |learned: &StateMap<P::State, usize>, peak_local: usize| {
+            memory::kb(
+                memory::hash_table_bytes::<(P::State, usize)>(learned.len())
+                    + learned.len() * heap_bytes
+                    + peak_local
+                        * (size_of::<LocalNode<P::State, P::Action>>()
+                            + heap_bytes
+                            + 2 * size_of::<usize>()),
+            )
+        };
+
+        while !problem.is_goal(&current) {
+            if trajectory.len() >= self.max_steps {
+                recorder.mark_cutoff();
+                recorder.set_memory_kb(memory_kb(&learned, peak_local));
+                return None;
+            }
+            let step_start = Instant::now();
+            let (action, estimate, local) = self.plan(problem, &learned, &current, recorder);
+            peak_local = peak_local.max(local);
+            let Some(action) = action else {
+                recorder.record_step(step_start.elapsed().as_micros() as f64 / 1000.0);
+                recorder.set_memory_kb(memory_kb(&learned, peak_local));
+                return None;
+            };
+            let known = learned
+                .get(&current)
+                .copied()
+                .unwrap_or_else(|| problem.heuristic(&current));
+            learned.insert(current.clone(), known.max(estimate));
+            recorder.sample_frontier(estimate, local);
+            recorder.record_step(step_start.elapsed().as_micros() as f64 / 1000.0);
+
+            let (next, step_cost) = problem.apply(&current, &action);
+            cost += step_cost;
+            trajectory.push(action);
+            current = next;
+        }
+
+        recorder.set_memory_kb(memory_kb(&learned, peak_local));
+        recorder.set_solution_cost(cost);
+        Some(trajectory)
+    }
+
+    fn name(&self) -> &str {
+        "LRTA*"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::astar::AStar;
+    use crate::algorithms::validation;
+    use crate::algorithms::SearchStatus;
+    use crate::problems::ShortestPath;
+
+    #[test]
+    fn test_reaches_the_goal_step_by_step_and_learns() {
+        let grid = ShortestPath::generate_grid_between(10, 10, 0, 99);
+        let optimal = AStar::new().search(&grid).metrics.solution_cost;
+
+        let result = LRTAStar::new(1, 10).search(&grid);
+        assert!(result.status.is_solved());
+        assert_eq!(result.metrics.solution_cost, optimal);
+        assert_eq!(result.metrics.steps, optimal);
+        assert!(result.metrics.max_step_ms >= result.metrics.mean_step_ms);
+        let actions = result.solution.unwrap();
+        validation::validate_solution(&grid, &actions, &result.metrics).unwrap();
+
+        let mut trap = ShortestPath::new(0, 4);
+        for (from, to) in [(0, 1), (1, 0), (0, 2), (2, 0), (2, 3), (3, 4)] {
+            trap.add_edge(from, to, 1);
+        }
+        for (node, h) in [(0, 1), (1, 0), (2, 2), (3, 1)] {
+            trap.set_heuristic(node, h);
+        }
+        let result = LRTAStar::new(1, 10).search(&trap);
+        assert!(result.status.is_solved());
+        assert!(result.metrics.solution_cost > 3);
+        let actions = result.solution.unwrap();
+        validation::validate_solution(&trap, &actions, &result.metrics).unwrap();
+
+        let capped = LRTAStar::new(1, 10).with_max_steps(2).search(&trap);
+        assert_eq!(capped.status, SearchStatus::DepthLimitReached);
+    }
+}
@@ -0,0 +1,37 @@
+use std::mem::size_of;
+
+const GROUP_WIDTH: usize = 16;
+
+pub fn hash_table_bytes<T>(len: usize) -> usize {
+    if len == 0 {
+        return 0;
+    }
+    let buckets = match len {
+        0..=3 => 4,
+        4..=7 => 8,
+        _ => (len * 8 / 7).next_power_of_two(),
+    };
+    buckets * (size_of::<T>() + 1) + GROUP_WIDTH
+}
+
+pub fn vec_bytes<T>(capacity: usize) -> usize {
+    capacity * size_of::<T>()
+}
+
+pub fn kb(bytes: usize) -> usize {
+    bytes.div_ceil(1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_table_rounds_to_power_of_two_buckets() {
+        assert_eq!(hash_table_bytes::<u64>(0), 0);
+        assert_eq!(hash_table_bytes::<u64>(3), 4 * 9 + GROUP_WIDTH);
+        assert_eq!(hash_table_bytes::<u64>(100), 128 * 9 + GROUP_WIDTH);
+        assert_eq!(hash_table_bytes::<u64>(112), 128 * 9 + GROUP_WIDTH);
+        assert_eq!(hash_table_bytes::<u64>(113), 256 * 9 + GROUP_WIDTH);
+    }
+}
@@ -1,88 +1,512 @@
+pub mod all_pairs;
+pub mod anytime;
 pub mod astar;
+pub mod backward;
+pub mod bellman_ford;
+pub mod best_first;
+pub mod bfhs;
 pub mod bfs;
+pub mod bidirectional;
+pub mod closed_list;
+pub mod depth_limited;
 pub mod dfs;
+pub mod distances;
+pub mod dstar_lite;
+pub mod ees;
+pub mod ehc;
+pub mod frontier;
+pub mod genetic;
 pub mod idastar;
+pub mod interner;
 pub mod iterative_deepening;
+pub mod lpastar;
+pub mod lrta;
+pub mod memory;
+pub mod perimeter;
+pub mod reference;
+pub mod sma;
+pub mod stepper;
+pub mod trace;
+pub mod uniform_cost;
+pub mod validation;
+pub mod yen;
 
-use crate::benchmarking::{Metrics, SharedMetrics};
+use crate::benchmarking::{Improvement, Metrics, MetricsRecorder, ProfileSample, SharedMetrics};
+use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
+use std::cmp::Reverse;
+use std::fmt;
 
 pub trait SearchAlgorithm {
-    fn search<P: Problem>(&self, problem: &P) -> SearchResult;
+    fn search_recorded<P: Problem>(
+        &self,
+        problem: &P,
+        recorder: &mut MetricsRecorder,
+    ) -> Option<Vec<P::Action>>;
+
+    fn search<P: Problem>(&self, problem: &P) -> SearchResult<P::Action> {
+        run_recorded(self, problem, MetricsRecorder::new())
+    }
 
     fn search_with_shared_metrics<P: Problem>(
         &self,
         problem: &P,
         shared: SharedMetrics,
-    ) -> SearchResult {
-        let result = self.search(problem);
-        shared.update(|m| *m = result.metrics.clone());
-        result
+    ) -> SearchResult<P::Action> {
+        run_recorded(self, problem, MetricsRecorder::with_shared(shared))
+    }
+
+    fn search_with_recorder<P: Problem>(
+        &self,
+        problem: &P,
+        recorder: MetricsRecorder,
+    ) -> SearchResult<P::Action> {
+        run_recorded(self, problem, recorder)
     }
 
     fn name(&self) -> &str;
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchStatus {
+    Solved,
+    Timeout,
+    NoSolution,
+    DepthLimitReached,
+    MemoryExceeded,
+    NegativeCycle,
+    Skipped,
+    Error(String),
+}
+
+impl SearchStatus {
+    pub fn is_solved(&self) -> bool {
+        *self == SearchStatus::Solved
+    }
+
+    pub fn from_legacy_code(code: u8) -> Self {
+        match code {
+            0 => SearchStatus::Solved,
+            1 => SearchStatus::Timeout,
+            3 => SearchStatus::Error("Solution invalide".to_string()),
+            _ => SearchStatus::NoSolution,
+        }
+    }
+}
+
+impl fmt::Display for SearchStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SearchStatus::Solved => write!(f, "Résolu"),
+            SearchStatus::Timeout => write!(f, "Timeout"),
+            SearchStatus::NoSolution => write!(f, "Pas de solution trouvée"),
+            SearchStatus::DepthLimitReached => write!(f, "Limite de profondeur atteinte"),
+            SearchStatus::MemoryExceeded => write!(f, "Limite mémoire dépassée"),
+            SearchStatus::NegativeCycle => write!(f, "Cycle de poids négatif"),
+            SearchStatus::Skipped => write!(f, "Non exécuté"),
+            SearchStatus::Error(message) => write!(f, "{}", message),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
-pub struct SearchResult {
-    pub solution: Option<Vec<usize>>,
+pub struct SearchResult<A> {
+    pub solution: Option<Vec<A>>,
     pub metrics: Metrics,
-    pub status: u8,
+    pub status: SearchStatus,
+    pub profile: Option<Vec<ProfileSample>>,
+    pub improvements: Vec<Improvement>,
+}
+
+pub trait NodeStore<S, A> {
+    fn add_root(&mut self, state: S) -> usize;
+    fn add_child(&mut self, parent: usize, state: S, action: A, step_cost: usize) -> usize;
+    fn get(&self, id: usize) -> &Node<S, A>;
+    fn release(&mut self, id: usize);
+    fn extract_solution(&self, id: usize) -> Vec<A>;
+    fn memory_bytes(&self, state_heap_bytes: usize) -> usize;
+}
+
+fn run_recorded<T, P>(
+    algorithm: &T,
+    problem: &P,
+    mut recorder: MetricsRecorder,
+) -> SearchResult<P::Action>
+where
+    T: SearchAlgorithm + ?Sized,
+    P: Problem,
+{
+    let solution = algorithm.search_recorded(problem, &mut recorder);
+    finish_search(solution, recorder)
+}
+
+pub(crate) fn finish_search<A>(
+    solution: Option<Vec<A>>,
+    mut recorder: MetricsRecorder,
+) -> SearchResult<A> {
+    if let Some(actions) = solution.as_ref().filter(|actions| !actions.is_empty()) {
+        recorder.set_solution_length(actions.len());
+    }
+    let status = if solution.is_some() {
+        SearchStatus::Solved
+    } else if recorder.negative_cycle_found() {
+        SearchStatus::NegativeCycle
+    } else if recorder.memory_exceeded() {
+        SearchStatus::MemoryExceeded
+    } else if recorder.cutoff_occurred() {
+        SearchStatus::DepthLimitReached
+    } else {
+        SearchStatus::NoSolution
+    };
+
+    SearchResult {
+        solution,
+        profile: recorder.take_profile(),
+        improvements: recorder.take_improvements(),
+        metrics: recorder.finish(),
+        status,
+    }
+}
+
+pub type ActionList<A> = SmallVec<[A; 8]>;
+pub type SuccessorList<S> = SmallVec<[(S, usize); 8]>;
+pub type Successors<P> = Vec<(<P as Problem>::Action, <P as Problem>::State, usize)>;
+pub type HeuristicSuccessors<P> =
+    Vec<(<P as Problem>::Action, <P as Problem>::State, usize, usize)>;
+
+pub(crate) fn order_by_heuristic<P: Problem>(problem: &P, successors: &mut Successors<P>) {
+    successors.sort_by_cached_key(|(_, state, _)| Reverse(problem.heuristic(state)));
 }
 
 pub trait Problem: Clone {
-    type State: Clone + Eq + std::hash::Hash;
+    type State: Clone + Eq + std::hash::Hash + fmt::Debug;
+    type Action: Clone + PartialEq + fmt::Debug + fmt::Display;
     fn initial_state(&self) -> Self::State;
+    fn initial_states(&self) -> Vec<Self::State> {
+        vec![self.initial_state()]
+    }
     fn is_goal(&self, state: &Self::State) -> bool;
-    fn successors(&self, state: &Self::State) -> Vec<(Self::State, usize)>;
+    fn actions(&self, state: &Self::State) -> ActionList<Self::Action>;
+    fn apply(&self, state: &Self::State, action: &Self::Action) -> (Self::State, usize);
+    fn successors(&self, state: &Self::State) -> SuccessorList<Self::State> {
+        self.actions(state)
+            .iter()
+            .map(|action| self.apply(state, action))
+            .collect()
+    }
+    fn successors_into(&self, state: &Self::State, buffer: &mut Successors<Self>) {
+        buffer.clear();
+        for action in self.actions(state) {
+            let (next, cost) = self.apply(state, &action);
+            buffer.push((action, next, cost));
+        }
+    }
+    fn successors_with_heuristic_into(
+        &self,
+        state: &Self::State,
+        heuristic: usize,
+        buffer: &mut HeuristicSuccessors<Self>,
+    ) {
+        let _ = heuristic;
+        buffer.clear();
+        for action in self.actions(state) {
+            let (next, cost) = self.apply(state, &action);
+            let h = self.heuristic(&next);
+            buffer.push((action, next, cost, h));
+        }
+    }
     fn description(&self) -> String;
     fn heuristic(&self, state: &Self::State) -> usize {
         let _ = state;
         0
     }
+    fn state_heap_bytes(&self, state: &Self::State) -> usize {
+        let _ = state;
+        0
+    }
+    fn signed_cost(&self, state: &Self::State, action: &Self::Action) -> i64 {
+        self.apply(state, action).1 as i64
+    }
+    fn encode_state(&self, state: &Self::State) -> String {
+        format!("{:?}", state)
+    }
+    fn take_external_calls(&self) -> Option<ExternalCalls> {
+        None
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ExternalCalls {
+    pub calls: usize,
+    pub time_ms: f64,
+}
+
+pub trait BackwardProblem: Problem {
+    fn goal_states(&self) -> Vec<Self::State>;
+    fn predecessors(&self, state: &Self::State) -> SuccessorList<Self::State>;
+    fn heuristic_between(&self, from: &Self::State, to: &Self::State) -> usize {
+        if self.is_goal(to) {
+            self.heuristic(from)
+        } else {
+            0
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
-pub struct Node<S> {
+pub struct Node<S, A> {
     pub state: S,
-    pub parent: Option<Box<Node<S>>>,
-    pub action: Option<usize>,
+    pub parent: Option<usize>,
+    pub action: Option<A>,
     pub path_cost: usize,
     pub depth: usize,
 }
 
-impl<S: Clone> Node<S> {
-    pub fn new(state: S) -> Self {
-        Node {
+const NODE_CHUNK_SIZE: usize = 4096;
+
+pub struct SearchTree<S, A> {
+    chunks: Vec<Vec<Node<S, A>>>,
+    len: usize,
+}
+
+impl<S: Clone, A: Clone> SearchTree<S, A> {
+    pub fn new() -> Self {
+        SearchTree {
+            chunks: Vec::new(),
+            len: 0,
+        }
+    }
+
+    fn push(&mut self, node: Node<S, A>) -> usize {
+        let chunk_index = self.len / NODE_CHUNK_SIZE;
+        if chunk_index == self.chunks.len() {
+            self.chunks.push(Vec::with_capacity(NODE_CHUNK_SIZE));
+        }
+        self.chunks[chunk_index].push(node);
+        self.len += 1;
+        self.len - 1
+    }
+
+    pub fn add_root(&mut self, state: S) -> usize {
+        self.push(Node {
             state,
             parent: None,
             action: None,
             path_cost: 0,
             depth: 0,
-        }
+        })
     }
 
-    pub fn child(&self, state: S, action: usize, step_cost: usize) -> Self {
-        Node {
+    pub fn add_child(&mut self, parent: usize, state: S, action: A, step_cost: usize) -> usize {
+        let (path_cost, depth) = {
+            let parent_node = self.get(parent);
+            (parent_node.path_cost + step_cost, parent_node.depth + 1)
+        };
+        self.push(Node {
             state,
-            parent: Some(Box::new(self.clone())),
+            parent: Some(parent),
             action: Some(action),
-            path_cost: self.path_cost + step_cost,
-            depth: self.depth + 1,
+            path_cost,
+            depth,
+        })
+    }
+
+    pub fn get(&self, id: usize) -> &Node<S, A> {
+        &self.chunks[id / NODE_CHUNK_SIZE][id % NODE_CHUNK_SIZE]
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        for chunk in &mut self.chunks {
+            chunk.clear();
         }
+        self.len = 0;
     }
 
-    pub fn extract_solution(&self) -> Vec<usize> {
+    pub fn extract_solution(&self, id: usize) -> Vec<A> {
         let mut actions = Vec::new();
-        let mut current = Some(self);
+        let mut current = Some(id);
 
-        while let Some(node) = current {
-            if let Some(action) = node.action {
-                actions.push(action);
+        while let Some(index) = current {
+            let node = self.get(index);
+            if let Some(action) = &node.action {
+                actions.push(action.clone());
             }
-            current = node.parent.as_ref().map(|p| p.as_ref());
+            current = node.parent;
         }
 
         actions.reverse();
         actions
     }
 }
+
+impl<S: Clone, A: Clone> Default for SearchTree<S, A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Clone, A: Clone> NodeStore<S, A> for SearchTree<S, A> {
+    fn add_root(&mut self, state: S) -> usize {
+        SearchTree::add_root(self, state)
+    }
+
+    fn add_child(&mut self, parent: usize, state: S, action: A, step_cost: usize) -> usize {
+        SearchTree::add_child(self, parent, state, action, step_cost)
+    }
+
+    fn get(&self, id: usize) -> &Node<S, A> {
+        SearchTree::get(self, id)
+    }
+
+    fn release(&mut self, _id: usize) {}
+
+    fn extract_solution(&self, id: usize) -> Vec<A> {
+        SearchTree::extract_solution(self, id)
+    }
+
+    fn memory_bytes(&self, state_heap_bytes: usize) -> usize {
+        self.chunks.len() * memory::vec_bytes::<Node<S, A>>(NODE_CHUNK_SIZE)
+            + memory::vec_bytes::<Vec<Node<S, A>>>(self.chunks.capacity())
+            + self.len * state_heap_bytes
+    }
+}
+
+pub struct NodePool<S, A> {
+    slots: Vec<Node<S, A>>,
+    free: Vec<usize>,
+}
+
+impl<S, A> NodePool<S, A> {
+    pub fn new() -> Self {
+        NodePool {
+            slots: Vec::new(),
+            free: Vec::new(),
+        }
+    }
+
+    pub fn live(&self) -> usize {
+        self.slots.len() - self.free.len()
+    }
+
+    fn insert(&mut self, node: Node<S, A>) -> usize {
+        match self.free.pop() {
+            Some(id) => {
+                self.slots[id] = node;
+                id
+            }
+            None => {
+                self.slots.push(node);
+                self.slots.len() - 1
+            }
+        }
+    }
+}
+
+impl<S, A> Default for NodePool<S, A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S, A> NodeStore<S, A> for NodePool<S, A> {
+    fn add_root(&mut self, state: S) -> usize {
+        self.insert(Node {
+            state,
+            parent: None,
+            action: None,
+            path_cost: 0,
+            depth: 0,
+        })
+    }
+
+    fn add_child(&mut self, parent: usize, state: S, _action: A, step_cost: usize) -> usize {
+        let parent_node = &self.slots[parent];
+        let path_cost = parent_node.path_cost + step_cost;
+        let depth = parent_node.depth + 1;
+        self.insert(Node {
+            state,
+            parent: None,
+            action: None,
+            path_cost,
+            depth,
+        })
+    }
+
+    fn get(&self, id: usize) -> &Node<S, A> {
+        &self.slots[id]
+    }
+
+    fn release(&mut self, id: usize) {
+        self.free.push(id);
+    }
+
+    fn extract_solution(&self, _id: usize) -> Vec<A> {
+        Vec::new()
+    }
+
+    fn memory_bytes(&self, state_heap_bytes: usize) -> usize {
+        memory::vec_bytes::<Node<S, A>>(self.slots.capacity())
+            + memory::vec_bytes::<usize>(self.free.capacity())
+            + self.live() * state_heap_bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_tree_extract_solution() {
+        let mut tree: SearchTree<u32, char> = SearchTree::new();
+        let root = tree.add_root(0);
+        let a = tree.add_child(root, 1, 'a', 2);
+        let _sibling = tree.add_child(root, 5, 'x', 1);
+        let b = tree.add_child(a, 2, 'b', 3);
+
+        assert_eq!(tree.extract_solution(b), vec!['a', 'b']);
+        assert_eq!(tree.get(b).path_cost, 5);
+        assert_eq!(tree.get(b).depth, 2);
+        assert!(tree.extract_solution(root).is_empty());
+    }
+
+    #[test]
+    fn test_node_pool_recycles_released_slots() {
+        let mut pool: NodePool<u32, char> = NodePool::new();
+        let root = pool.add_root(0);
+        let child = pool.add_child(root, 1, 'a', 3);
+        pool.release(root);
+
+        let grandchild = pool.add_child(child, 2, 'b', 4);
+        assert_eq!(grandchild, root);
+        assert_eq!(pool.get(grandchild).path_cost, 7);
+        assert_eq!(pool.get(grandchild).depth, 2);
+        assert_eq!(pool.live(), 2);
+        assert!(pool.extract_solution(grandchild).is_empty());
+    }
+
+    #[test]
+    fn test_search_tree_spans_chunks() {
+        let mut tree: SearchTree<usize, usize> = SearchTree::new();
+        let mut last = tree.add_root(0);
+        for step in 1..=NODE_CHUNK_SIZE * 2 + 1 {
+            last = tree.add_child(last, step, step, 1);
+        }
+
+        assert_eq!(tree.len(), NODE_CHUNK_SIZE * 2 + 2);
+        assert_eq!(tree.get(last).depth, NODE_CHUNK_SIZE * 2 + 1);
+        assert_eq!(tree.extract_solution(last).len(), NODE_CHUNK_SIZE * 2 + 1);
+
+        tree.clear();
+        assert!(tree.is_empty());
+        assert_eq!(tree.add_root(7), 0);
+    }
+}
@@ -0,0 +1,266 @@
+use super::memory;
+use super::validation;
+use super::{
+    ActionList, BackwardProblem, ExternalCalls, HeuristicSuccessors, Problem, SearchResult,
+    SearchStatus, Successors,
+};
+use crate::utils::hashing::StateMap;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::time::Instant;
+
+pub struct Perimeter<S> {
+    radius: usize,
+    interior: StateMap<S, (usize, Option<S>)>,
+    build_ms: f64,
+}
+
+impl<S: Clone + Eq + std::hash::Hash> Perimeter<S> {
+    pub fn build<P: BackwardProblem<State = S>>(problem: &P, radius: usize) -> Self {
+        let start = Instant::now();
+        let mut interior: StateMap<S, (usize, Option<S>)> = StateMap::default();
+        let mut settled = StateMap::default();
+        let mut frontier = BinaryHeap::new();
+        let mut order = 0usize;
+        let mut pending: StateMap<usize, S> = StateMap::default();
+
+        for goal in problem.goal_states() {
+            if !interior.contains_key(&goal) {
+                interior.insert(goal.clone(), (0, None));
+                pending.insert(order, goal);
+                frontier.push(Reverse((0, order)));
+                order += 1;
+            }
+        }
+
+        while let Some(Reverse((distance, id))) = frontier.pop() {
+            let state = pending.remove(&id).expect("état en attente");
+            if settled.insert(state.clone(), ()).is_some() || interior[&state].0 < distance {
+                continue;
+            }
+            for (predecessor, cost) in problem.predecessors(&state) {
+                let tentative = distance + cost;
+                if tentative > radius
+                    || interior
+                        .get(&predecessor)
+                        .is_some_and(|&(known, _)| known <= tentative)
+                {
+                    continue;
+                }
+                interior.insert(predecessor.clone(), (tentative, Some(state.clone())));
+                pending.insert(order, predecessor);
+                frontier.push(Reverse((tentative, order)));
+                order += 1;
+            }
+        }
+
+        Perimeter {
+            radius,
+            interior,
+            build_ms: start.elapsed().as_micros() as f64 / 1000.0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.interior.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.interior.len()
+    }
+
+    pub fn build_ms(&self) -> f64 {
+        self.build_ms
+    }
+
+    pub fn distance(&self, state: &S) -> Option<usize> {
+        self.interior.get(state).map(|&(distance, _)| distance)
+    }
+
+    pub fn memory_kb(&self, state_heap_bytes: usize) -> usize {
+        memory::kb(
+            memory::hash_table_bytes::<(S, (usize, Option<S>))>(self.interior.len())
+                + self.interior.len() * 2 * state_heap_bytes,
+        )
+    }
+
+    fn path_to_goal<P: Problem<State = S>>(
+        &self,
+        problem: &P,
+        mut state: S,
+    ) -> Result<Vec<P::Action>, String> {
+        let mut path = Vec::new();
+        while let Some((distance, Some(next))) = self.interior.get(&state) {
+            let step = distance - self.distance(next).unwrap_or(0);
+            let action = problem
+                .actions(&state)
+                .into_iter()
+                .find(|action| problem.apply(&state, action) == (next.clone(), step))
+                .ok_or("prédécesseur sans action avant")?;
+            path.push(action);
+            state = next.clone();
+        }
+        Ok(path)
+    }
+
+    pub fn complete<P: Problem<State = S>>(
+        &self,
+        problem: &P,
+        mut result: SearchResult<P::Action>,
+    ) -> SearchResult<P::Action> {
+        result.metrics.precompute_ms = self.build_ms;
+        result.metrics.time_ms += self.build_ms;
+        result.metrics.memory_kb +=
+            self.memory_kb(problem.state_heap_bytes(&problem.initial_state()));
+
+        let Some(actions) = result.solution.take() else {
+            return result;
+        };
+        let bounded = PerimeterProblem::new(problem, self);
+        let end = problem
+            .initial_states()
+            .into_iter()
+            .find_map(|start| validation::replay(&bounded, start, &actions).ok());
+        let completed = end.ok_or_else(|| "chemin vers le périmètre invalide".to_string());
+        match completed.and_then(|(state, cost)| {
+            let distance = self.distance(&state).unwrap_or(0);
+            self.path_to_goal(problem, state)
+                .map(|tail| (tail, cost + distance))
+        }) {
+            Ok((tail, cost)) => {
+                let mut actions = actions;
+                actions.extend(tail);
+                result.metrics.solution_length = actions.len();
+                result.metrics.solution_cost = cost;
+                result.solution = Some(actions);
+            }
+            Err(e) => result.status = SearchStatus::Error(format!("Périmètre: {}", e)),
+        }
+        result
+    }
+}
+
+#[derive(Clone)]
+pub struct PerimeterProblem<'a, P: Problem> {
+    problem: &'a P,
+    perimeter: &'a Perimeter<P::State>,
+}
+
+impl<'a, P: Problem> PerimeterProblem<'a, P> {
+    pub fn new(problem: &'a P, perimeter: &'a Perimeter<P::State>) -> Self {
+        PerimeterProblem { problem, perimeter }
+    }
+
+    fn corrected(&self, state: &P::State, h: usize) -> usize {
+        match self.perimeter.distance(state) {
+            Some(distance) => distance,
+            None => h.max(self.perimeter.radius + 1),
+        }
+    }
+}
+
+impl<P: Problem> Problem for PerimeterProblem<'_, P> {
+    type State = P::State;
+    type Action = P::Action;
+
+    fn initial_state(&self) -> Self::State {
+        self.problem.initial_state()
+    }
+
+    fn initial_states(&self) -> Vec<Self::State> {
+        self.problem.initial_states()
+    }
+
+    fn is_goal(&self, state: &Self::State) -> bool {
+        self.perimeter.distance(state).is_some()
+    }
+
+    fn actions(&self, state: &Self::State) -> ActionList<Self::Action> {
+        self.problem.actions(state)
+    }
+
+    fn apply(&self, state: &Self::State, action: &Self::Action) -> (Self::State, usize) {
+        self.problem.apply(state, action)
+    }
+
+    fn successors_into(&self, state: &Self::State, buffer: &mut Successors<Self>) {
+        self.problem.successors_into(state, buffer)
+    }
+
+    fn successors_with_heuristic_into(
+        &self,
+        state: &Self::State,
+        _heuristic: usize,
+        buffer: &mut HeuristicSuccessors<Self>,
+    ) {
+        let heuristic = self.problem.heuristic(state);
+        self.problem
+            .successors_with_heuristic_into(state, heuristic, buffer);
+        for (_, next, _, h) in buffer.iter_mut() {
+            *h = self.corrected(next, *h);
+        }
+    }
+
+    fn heuristic(&self, state: &Self::State) -> usize {
+        self.corrected(state, self.problem.heuristic(state))
+    }
+
+    fn description(&self) -> String {
+        self.problem.description()
+    }
+
+    fn state_heap_bytes(&self, state: &Self::State) -> usize {
+        self.problem.state_heap_bytes(state)
+    }
+
+    fn encode_state(&self, state: &Self::State) -> String {
+        self.problem.encode_state(state)
+    }
+
+    fn take_external_calls(&self) -> Option<ExternalCalls> {
+        self.problem.take_external_calls()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::idastar::IDAStar;
+    use crate::algorithms::SearchAlgorithm;
+    use crate::problems::taquin::hardest_eight_puzzle;
+    use crate::problems::ShortestPath;
+
+    #[test]
+    fn test_perimeter_search_matches_idastar() {
+        let taquin = hardest_eight_puzzle();
+        let reference = IDAStar::new(100).search(&taquin);
+        for radius in [0, 3, 12] {
+            let perimeter = Perimeter::build(&taquin, radius);
+            let bounded = PerimeterProblem::new(&taquin, &perimeter);
+            let result = perimeter.complete(&taquin, IDAStar::new(100).search(&bounded));
+            assert!(result.status.is_solved());
+            assert_eq!(
+                result.metrics.solution_cost,
+                reference.metrics.solution_cost
+            );
+            let solution = result.solution.unwrap();
+            assert!(validation::validate_solution(&taquin, &solution, &result.metrics).is_ok());
+            if radius == 12 {
+                assert!(result.metrics.nodes_visited < reference.metrics.nodes_visited);
+            }
+        }
+
+        let mut graph = ShortestPath::new(0, 3);
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(1, 2, 1);
+        graph.add_edge(2, 3, 4);
+        graph.add_edge(1, 3, 7);
+        let perimeter = Perimeter::build(&graph, 5);
+        assert_eq!(perimeter.distance(&1), Some(5));
+        assert_eq!(perimeter.distance(&0), None);
+        let bounded = PerimeterProblem::new(&graph, &perimeter);
+        let result = perimeter.complete(&graph, IDAStar::new(100).search(&bounded));
+        assert_eq!(result.metrics.solution_cost, 6);
+        assert_eq!(result.metrics.solution_length, 3);
+    }
+}
@@ -0,0 +1,34 @@
+use super::bfs::BFS;
+use super::uniform_cost::UniformCost;
+use super::{Problem, SearchAlgorithm};
+
+pub fn optimal_cost<P: Problem>(problem: &P, unit_costs: bool) -> Option<usize> {
+    let result = if unit_costs {
+        BFS::new().with_solution_tracking(false).search(problem)
+    } else {
+        UniformCost::new()
+            .with_solution_tracking(false)
+            .search(problem)
+    };
+    result
+        .status
+        .is_solved()
+        .then_some(result.metrics.solution_cost)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::problems::ShortestPath;
+
+    #[test]
+    fn test_uniform_cost_prefers_cheaper_longer_path() {
+        let mut graph = ShortestPath::new(0, 2);
+        graph.add_edge(0, 2, 10);
+        graph.add_edge(0, 1, 2);
+        graph.add_edge(1, 2, 3);
+
+        assert_eq!(optimal_cost(&graph, false), Some(5));
+        assert_eq!(optimal_cost(&graph, true), Some(10));
+    }
+}
@@ -0,0 +1,335 @@
+use super::memory;
+use super::{Problem, SearchAlgorithm};
+use crate::benchmarking::MetricsRecorder;
+use std::cmp::Reverse;
+use std::collections::BTreeSet;
+use std::mem::size_of;
+
+const UNREACHABLE: usize = usize::MAX;
+
+type OpenKey = (usize, Reverse<usize>, usize);
+
+struct SmaNode<S, A> {
+    state: S,
+    parent: Option<usize>,
+    action: Option<A>,
+    g: usize,
+    f: usize,
+    depth: usize,
+    children: Vec<usize>,
+    forgotten: usize,
+    expanded: bool,
+    queued: Option<OpenKey>,
+}
+
+impl<S, A> SmaNode<S, A> {
+    fn priority(&self) -> Option<usize> {
+        if self.children.is_empty() {
+            Some(self.f)
+        } else {
+            (self.forgotten != UNREACHABLE).then_some(self.forgotten)
+        }
+    }
+}
+
+pub struct SMAStar {
+    pub memory_budget: usize,
+    pub cost_bound: Option<usize>,
+}
+
+impl SMAStar {
+    pub fn new(memory_budget: usize) -> Self {
+        SMAStar {
+            memory_budget: memory_budget.max(2),
+            cost_bound: None,
+        }
+    }
+
+    pub fn with_cost_bound(mut self, cost_bound: Option<usize>) -> Self {
+        self.cost_bound = cost_bound;
+        self
+    }
+}
+
+struct Arena<S, A> {
+    nodes: Vec<Option<SmaNode<S, A>>>,
+    free: Vec<usize>,
+    live: usize,
+}
+
+impl<S: PartialEq, A: Clone> Arena<S, A> {
+    fn insert(&mut self, node: SmaNode<S, A>) -> usize {
+        self.live += 1;
+        match self.free.pop() {
+            Some(id) => {
+                self.nodes[id] = Some(node);
+                id
+            }
+            None => {
+                self.nodes.push(Some(node));
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    fn remove(&mut self, id: usize) -> SmaNode<S, A> {
+        self.live -= 1;
+        self.free.push(id);
+        self.nodes[id].take().expect("nœud SMA* présent")
+    }
+
+    fn get(&self, id: usize) -> &SmaNode<S, A> {
+        self.nodes[id].as_ref().expect("nœud SMA* présent")
+    }
+
+    fn get_mut(&mut self, id: usize) -> &mut SmaNode<S, A> {
+        self.nodes[id].as_mut().expect("nœud SMA* présent")
+    }
+
+    fn on_path(&self, mut id: usize, state: &S) -> bool {
+        loop {
+            let node = self.get(id);
+            if node.state == *state {
+                return true;
+            }
+            match node.parent {
+                Some(parent) => id = parent,
+                None => return false,
+            }
+        }
+    }
+
+    fn solution(&self, mut id: usize) -> Vec<A> {
+        let mut actions = Vec::new();
+        while let Some(action) = &self.get(id).action {
+            actions.push(action.clone());
+            id = self.get(id).parent.expect("parent d'un nœud avec action");
+        }
+        actions.reverse();
+        actions
+    }
+
+    fn requeue(&mut self, open: &mut BTreeSet<OpenKey>, id: usize) {
+        let node = self.get_mut(id);
+        if let Some(key) = node.queued.take() {
+            open.remove(&key);
+        }
+        if let Some(priority) = node.priority() {
+            let key = (priority, Reverse(node.depth), id);
+            node.queued = Some(key);
+            open.insert(key);
+        }
+    }
+
+    fn backup(&mut self, mut id: usize) {
+        loop {
+            let node = self.get(id);
+            if node.children.is_empty() {
+                return;
+            }
+            let best = node
+                .children
+                .iter()
+                .map(|&child| self.get(child).f)
+                .fold(node.forgotten, usize::min);
+            if best == node.f {
+                return;
+            }
+            let node = self.get_mut(id);
+            node.f = best;
+            match node.parent {
+                Some(parent) => id = parent,
+                None => return,
+            }
+        }
+    }
+}
+
+impl SearchAlgorithm for SMAStar {
+    fn search_recorded<P: Problem>(
+        &self,
+        problem: &P,
+        recorder: &mut MetricsRecorder,
+    ) -> Option<Vec<P::Action>> {
+        let mut arena: Arena<P::State, P::Action> = Arena {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            live: 0,
+        };
+        let mut open = BTreeSet::new();
+        for state in problem.initial_states() {
+            let id = arena.insert(SmaNode {
+                f: problem.heuristic(&state),
+                state,
+                parent: None,
+                action: None,
+                g: 0,
+                depth: 0,
+                children: Vec::new(),
+                forgotten: UNREACHABLE,
+                expanded: false,
+                queued: None,
+            });
+            arena.requeue(&mut open, id);
+            recorder.generate();
+        }
+
+        let heap_bytes = problem.state_heap_bytes(&problem.initial_state());
+        let memory_kb = |arena: &Arena<P::State, P::Action>, open: &BTreeSet<OpenKey>| {
+            memory::kb(
+                memory::vec_bytes::<Option<SmaNode<P::State, P::Action>>>(arena.nodes.capacity())
+                    + arena.live * (heap_bytes + size_of::<usize>())
+                    + memory::vec_bytes::<usize>(arena.free.capacity())
+                    + open.len() * size_of::<OpenKey>() * 2,
+            )
+        };
+
+        let mut successors = Vec::new();
+
+        while let Some(&(f, _, id)) = open.first() {
+            if f == UNREACHABLE {
+                recorder.mark_memory_exceeded();
+                break;
+            }
+            open.pop_first();
+            recorder.visit();
+            recorder.sample_frontier(f, open.len());
+
+            let node = arena.get_mut(id);
+            node.queued = None;
+            if problem.is_goal(&node.state) {
+                let (depth, g) = (node.depth, node.g);
+                recorder.set_memory_kb(memory_kb(&arena, &open));
+                recorder.set_solution_length(depth);
+                recorder.set_solution_cost(g);
+                return Some(arena.solution(id));
+            }
+            if node.expanded {
+                recorder.reexpand();
+            }
+            node.expanded = true;
+            node.forgotten = UNREACHABLE;
+            let (state, g, depth) = (node.state.clone(), node.g, node.depth);
+
+            problem.successors_into(&state, &mut successors);
+            for (action, next, cost) in successors.drain(..) {
+                let child_g = g + cost;
+                if self.cost_bound.is_some_and(|bound| child_g > bound)
+                    || arena.on_path(id, &next)
+                    || arena
+                        .get(id)
+                        .children
+                        .iter()
+                        .any(|&child| arena.get(child).state == next)
+                {
+                    continue;
+                }
+                let child_f = if depth + 1 >= self.memory_budget - 1 && !problem.is_goal(&next) {
+                    UNREACHABLE
+                } else {
+                    f.max(child_g + problem.heuristic(&next))
+                };
+                let child = arena.insert(SmaNode {
+                    state: next,
+                    parent: Some(id),
+                    action: Some(action),
+                    g: child_g,
+                    f: child_f,
+                    depth: depth + 1,
+                    children: Vec::new(),
+                    forgotten: UNREACHABLE,
+                    expanded: false,
+                    queued: None,
+                });
+                arena.get_mut(id).children.push(child);
+                arena.requeue(&mut open, child);
+                recorder.generate();
+            }
+
+            if arena.get(id).children.is_empty() {
+                arena.get_mut(id).f = UNREACHABLE;
+                arena.requeue(&mut open, id);
+                if let Some(parent) = arena.get(id).parent {
+                    arena.backup(parent);
+                }
+            } else {
+                arena.backup(id);
+            }
+
+            while arena.live > self.memory_budget {
+                let Some(&(_, _, worst)) = open.iter().rev().find(|&&(_, _, candidate)| {
+                    let node = arena.get(candidate);
+                    node.parent.is_some() && node.children.is_empty()
+                }) else {
+                    break;
+                };
+                let pruned = arena.remove(worst);
+                open.remove(&pruned.queued.expect("feuille dans la liste ouverte"));
+                let parent = pruned.parent.expect("nœud non racine");
+                recorder.add_evictions(1);
+
+                let parent_node = arena.get_mut(parent);
+                parent_node.children.retain(|&child| child != worst);
+                parent_node.forgotten = parent_node.forgotten.min(pruned.f);
+                if parent_node.children.is_empty() {
+                    parent_node.f = parent_node.forgotten;
+                }
+                arena.requeue(&mut open, parent);
+            }
+
+            recorder.observe_frontier(open.len());
+        }
+
+        recorder.set_memory_kb(memory_kb(&arena, &open));
+        None
+    }
+
+    fn name(&self) -> &str {
+        "SMA*"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::astar::AStar;
+    use crate::algorithms::SearchStatus;
+    use crate::problems::taquin::hardest_eight_puzzle;
+    use crate::problems::ShortestPath;
+
+    fn chain(length: usize) -> ShortestPath {
+        let mut chain = ShortestPath::new(0, length);
+        for node in 0..length {
+            chain.add_edge(node, node + 1, 1);
+        }
+        chain
+    }
+
+    #[test]
+    fn test_stays_optimal_without_evictions_when_memory_is_roomy() {
+        let taquin = hardest_eight_puzzle();
+        let optimal = AStar::new().search(&taquin).metrics.solution_cost;
+        let result = SMAStar::new(1_000_000).search(&taquin);
+        assert!(result.status.is_solved());
+        assert_eq!(result.metrics.solution_cost, optimal);
+        assert_eq!(result.metrics.evictions, 0);
+    }
+
+    #[test]
+    fn test_evicts_but_stays_optimal_when_memory_is_tight() {
+        let taquin = hardest_eight_puzzle();
+        let optimal = AStar::new().search(&taquin).metrics.solution_cost;
+        let result = SMAStar::new(200).search(&taquin);
+        assert!(result.status.is_solved());
+        assert_eq!(result.metrics.solution_cost, optimal);
+        assert!(result.metrics.evictions > 0);
+    }
+
+    #[test]
+    fn test_reports_memory_exceeded_when_the_path_does_not_fit() {
+        let chain = chain(8);
+        assert!(SMAStar::new(10).search(&chain).status.is_solved());
+        let result = SMAStar::new(5).search(&chain);
+        assert_eq!(result.status, SearchStatus::MemoryExceeded);
+    }
+}
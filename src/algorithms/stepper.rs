@@ -0,0 +1,250 @@
+use super::{Problem, Successors};
+use crate::utils::hashing::{StateMap, StateSet};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StepOrder {
+    AStar,
+    BreadthFirst,
+    DepthFirst,
+}
+
+#[derive(Clone, Debug)]
+pub struct StepNode<S, A> {
+    pub state: S,
+    pub parent: Option<usize>,
+    pub action: Option<A>,
+    pub g: usize,
+    pub h: usize,
+    pub depth: usize,
+}
+
+impl<S, A> StepNode<S, A> {
+    pub fn f(&self) -> usize {
+        self.g + self.h
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Step {
+    Expanded { node: usize, generated: usize },
+    Skipped { node: usize },
+    Goal { node: usize },
+}
+
+impl Step {
+    pub fn node(&self) -> usize {
+        match *self {
+            Step::Expanded { node, .. } | Step::Skipped { node } | Step::Goal { node } => node,
+        }
+    }
+}
+
+type FrontierKey = Reverse<(usize, usize, usize)>;
+
+pub struct Stepper<'a, P: Problem> {
+    problem: &'a P,
+    order: StepOrder,
+    nodes: Vec<StepNode<P::State, P::Action>>,
+    frontier: BinaryHeap<FrontierKey>,
+    best_g: StateMap<P::State, usize>,
+    closed: StateSet<P::State>,
+    successors: Successors<P>,
+    expansions: usize,
+    finished: bool,
+}
+
+impl<'a, P: Problem> Stepper<'a, P> {
+    pub fn new(problem: &'a P, order: StepOrder) -> Self {
+        let state = problem.initial_state();
+        let mut stepper = Stepper {
+            problem,
+            order,
+            nodes: Vec::new(),
+            frontier: BinaryHeap::new(),
+            best_g: StateMap::default(),
+            closed: StateSet::default(),
+            successors: Vec::new(),
+            expansions: 0,
+            finished: false,
+        };
+        stepper.best_g.insert(state.clone(), 0);
+        stepper.push(StepNode {
+            h: problem.heuristic(&state),
+            state,
+            parent: None,
+            action: None,
+            g: 0,
+            depth: 0,
+        });
+        stepper
+    }
+
+    fn push(&mut self, node: StepNode<P::State, P::Action>) {
+        let id = self.nodes.len();
+        let key = match self.order {
+            StepOrder::AStar => (node.f(), node.h, id),
+            StepOrder::BreadthFirst => (node.depth, 0, id),
+            StepOrder::DepthFirst => (0, 0, usize::MAX - id),
+        };
+        self.nodes.push(node);
+        self.frontier.push(Reverse(key));
+    }
+
+    fn id(key: &FrontierKey, order: StepOrder) -> usize {
+        let Reverse((_, _, tie)) = *key;
+        match order {
+            StepOrder::DepthFirst => usize::MAX - tie,
+            _ => tie,
+        }
+    }
+
+    pub fn node(&self, id: usize) -> &StepNode<P::State, P::Action> {
+        &self.nodes[id]
+    }
+
+    pub fn path(&self, id: usize) -> Vec<P::Action> {
+        let mut actions = Vec::new();
+        let mut current = Some(id);
+        while let Some(node) = current.map(|id| &self.nodes[id]) {
+            actions.extend(node.action.clone());
+            current = node.parent;
+        }
+        actions.reverse();
+        actions
+    }
+
+    pub fn frontier(&self, count: usize) -> Vec<usize> {
+        let mut keys: Vec<&FrontierKey> = self.frontier.iter().collect();
+        keys.sort_unstable_by(|a, b| b.cmp(a));
+        keys.into_iter()
+            .take(count)
+            .map(|key| Self::id(key, self.order))
+            .collect()
+    }
+
+    pub fn frontier_len(&self) -> usize {
+        self.frontier.len()
+    }
+
+    pub fn expansions(&self) -> usize {
+        self.expansions
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+}
+
+impl<P: Problem> Iterator for Stepper<'_, P> {
+    type Item = Step;
+
+    fn next(&mut self) -> Option<Step> {
+        if self.finished {
+            return None;
+        }
+        let Some(key) = self.frontier.pop() else {
+            self.finished = true;
+            return None;
+        };
+        let id = Self::id(&key, self.order);
+        let (state, g) = (self.nodes[id].state.clone(), self.nodes[id].g);
+
+        if self.closed.contains(&state) || self.best_g.get(&state).is_some_and(|&best| best < g) {
+            return Some(Step::Skipped { node: id });
+        }
+        if self.problem.is_goal(&state) {
+            self.finished = true;
+            return Some(Step::Goal { node: id });
+        }
+
+        self.expansions += 1;
+        self.closed.insert(state.clone());
+        let mut successors = std::mem::take(&mut self.successors);
+        self.problem.successors_into(&state, &mut successors);
+        let mut generated = 0;
+        for (action, child, cost) in successors.drain(..) {
+            let child_g = g + cost;
+            if self.closed.contains(&child)
+                || self.best_g.get(&child).is_some_and(|&best| best <= child_g)
+            {
+                continue;
+            }
+            self.best_g.insert(child.clone(), child_g);
+            generated += 1;
+            self.push(StepNode {
+                h: self.problem.heuristic(&child),
+                state: child,
+                parent: Some(id),
+                action: Some(action),
+                g: child_g,
+                depth: self.nodes[id].depth + 1,
+            });
+        }
+        self.successors = successors;
+
+        Some(Step::Expanded {
+            node: id,
+            generated,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::problems::ShortestPath;
+
+    fn diamond() -> ShortestPath {
+        let mut graph = ShortestPath::new(0, 3);
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(1, 3, 5);
+        graph.add_edge(0, 2, 2);
+        graph.add_edge(2, 3, 1);
+        graph.set_heuristic(0, 3);
+        graph.set_heuristic(1, 1);
+        graph.set_heuristic(2, 1);
+        graph
+    }
+
+    #[test]
+    fn test_astar_steps_in_f_order() {
+        let graph = diamond();
+        let mut stepper = Stepper::new(&graph, StepOrder::AStar);
+
+        assert_eq!(
+            stepper.next(),
+            Some(Step::Expanded {
+                node: 0,
+                generated: 2
+            })
+        );
+        let top = stepper.frontier(10);
+        assert_eq!(
+            top.iter()
+                .map(|&id| stepper.node(id).state)
+                .collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+
+        let steps: Vec<Step> = stepper.by_ref().collect();
+        let Some(&Step::Goal { node }) = steps.last() else {
+            panic!("but non atteint: {:?}", steps);
+        };
+        assert_eq!(stepper.node(node).g, 3);
+        assert_eq!(stepper.path(node).len(), 2);
+        assert!(stepper.is_finished());
+        assert_eq!(stepper.next(), None);
+    }
+
+    #[test]
+    fn test_depth_first_is_last_in_first_out() {
+        let graph = diamond();
+        let mut stepper = Stepper::new(&graph, StepOrder::DepthFirst);
+        stepper.next();
+        let top = stepper.frontier(1)[0];
+        assert_eq!(stepper.node(top).state, 2);
+        assert_eq!(stepper.next().map(|step| step.node()), Some(top));
+    }
+}
@@ -0,0 +1,146 @@
+use super::{ActionList, BackwardProblem, HeuristicSuccessors, Problem, SuccessorList, Successors};
+use std::sync::{Arc, Mutex};
+
+pub struct SearchTrace<S> {
+    pub expansions: Vec<S>,
+    pub edges: Vec<(S, S, usize)>,
+    pub truncated: bool,
+}
+
+impl<S> Default for SearchTrace<S> {
+    fn default() -> Self {
+        SearchTrace {
+            expansions: Vec::new(),
+            edges: Vec::new(),
+            truncated: false,
+        }
+    }
+}
+
+pub type SharedTrace<S> = Arc<Mutex<SearchTrace<S>>>;
+
+#[derive(Clone)]
+pub struct Traced<'a, P: Problem> {
+    problem: &'a P,
+    trace: SharedTrace<P::State>,
+    max_expansions: usize,
+    record_edges: bool,
+}
+
+impl<'a, P: Problem> Traced<'a, P> {
+    pub fn new(problem: &'a P) -> Self {
+        Traced {
+            problem,
+            trace: SharedTrace::default(),
+            max_expansions: usize::MAX,
+            record_edges: true,
+        }
+    }
+
+    pub fn with_shared_trace(mut self, trace: SharedTrace<P::State>) -> Self {
+        self.trace = trace;
+        self
+    }
+
+    pub fn with_edges(mut self, record_edges: bool) -> Self {
+        self.record_edges = record_edges;
+        self
+    }
+
+    pub fn with_max_expansions(mut self, max_expansions: usize) -> Self {
+        self.max_expansions = max_expansions;
+        self
+    }
+
+    pub fn take_trace(&self) -> SearchTrace<P::State> {
+        std::mem::take(&mut *self.trace.lock().unwrap())
+    }
+
+    fn record<'b>(&self, state: &P::State, successors: impl Iterator<Item = (&'b P::State, usize)>)
+    where
+        P::State: 'b,
+    {
+        let mut trace = self.trace.lock().unwrap();
+        if trace.expansions.len() >= self.max_expansions {
+            trace.truncated = true;
+            return;
+        }
+        trace.expansions.push(state.clone());
+        if self.record_edges {
+            for (next, cost) in successors {
+                trace.edges.push((state.clone(), next.clone(), cost));
+            }
+        }
+    }
+}
+
+impl<P: Problem> Problem for Traced<'_, P> {
+    type State = P::State;
+    type Action = P::Action;
+
+    fn initial_state(&self) -> Self::State {
+        self.problem.initial_state()
+    }
+
+    fn initial_states(&self) -> Vec<Self::State> {
+        self.problem.initial_states()
+    }
+
+    fn is_goal(&self, state: &Self::State) -> bool {
+        self.problem.is_goal(state)
+    }
+
+    fn actions(&self, state: &Self::State) -> ActionList<Self::Action> {
+        self.problem.actions(state)
+    }
+
+    fn apply(&self, state: &Self::State, action: &Self::Action) -> (Self::State, usize) {
+        self.problem.apply(state, action)
+    }
+
+    fn successors_into(&self, state: &Self::State, buffer: &mut Successors<Self>) {
+        self.problem.successors_into(state, buffer);
+        self.record(state, buffer.iter().map(|(_, next, cost)| (next, *cost)));
+    }
+
+    fn successors_with_heuristic_into(
+        &self,
+        state: &Self::State,
+        heuristic: usize,
+        buffer: &mut HeuristicSuccessors<Self>,
+    ) {
+        self.problem
+            .successors_with_heuristic_into(state, heuristic, buffer);
+        self.record(state, buffer.iter().map(|(_, next, cost, _)| (next, *cost)));
+    }
+
+    fn description(&self) -> String {
+        self.problem.description()
+    }
+
+    fn heuristic(&self, state: &Self::State) -> usize {
+        self.problem.heuristic(state)
+    }
+
+    fn state_heap_bytes(&self, state: &Self::State) -> usize {
+        self.problem.state_heap_bytes(state)
+    }
+
+    fn encode_state(&self, state: &Self::State) -> String {
+        self.problem.encode_state(state)
+    }
+}
+
+impl<P: BackwardProblem> BackwardProblem for Traced<'_, P> {
+    fn goal_states(&self) -> Vec<Self::State> {
+        self.problem.goal_states()
+    }
+
+    fn predecessors(&self, state: &Self::State) -> SuccessorList<Self::State> {
+        self.problem.predecessors(state)
+    }
+
+    fn heuristic_between(&self, from: &Self::State, to: &Self::State) -> usize {
+        self.problem.heuristic_between(from, to)
+    }
+}
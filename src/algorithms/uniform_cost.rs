@@ -0,0 +1,173 @@
+use super::interner::{StateId, StateInterner};
+use super::memory;
+use super::{NodePool, NodeStore, Problem, SearchAlgorithm, SearchTree};
+use crate::benchmarking::MetricsRecorder;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::mem::size_of;
+
+pub struct UniformCost {
+    pub track_solutions: bool,
+    pub cost_bound: Option<usize>,
+}
+
+impl UniformCost {
+    pub fn new() -> Self {
+        UniformCost {
+            track_solutions: true,
+            cost_bound: None,
+        }
+    }
+
+    pub fn with_solution_tracking(mut self, track_solutions: bool) -> Self {
+        self.track_solutions = track_solutions;
+        self
+    }
+
+    pub fn with_cost_bound(mut self, cost_bound: Option<usize>) -> Self {
+        self.cost_bound = cost_bound;
+        self
+    }
+
+    fn search_tree<P: Problem, T: NodeStore<StateId, P::Action>>(
+        &self,
+        problem: &P,
+        recorder: &mut MetricsRecorder,
+        mut tree: T,
+    ) -> Option<Vec<P::Action>> {
+        let mut interner = StateInterner::new();
+        let mut frontier = BinaryHeap::new();
+        for initial_state in problem.initial_states() {
+            let (initial_id, is_new) = interner.intern(initial_state);
+            if is_new {
+                frontier.push(Reverse((0, tree.add_root(initial_id))));
+                recorder.generate();
+            }
+        }
+
+        let mut closed = vec![false; interner.len()];
+        let mut g_scores = vec![0; interner.len()];
+        let heap_bytes = problem.state_heap_bytes(&problem.initial_state());
+        let memory_kb = |interner: &StateInterner<P::State>,
+                         tree: &T,
+                         frontier: &BinaryHeap<Reverse<(usize, usize)>>| {
+            memory::kb(
+                interner.memory_bytes(heap_bytes)
+                    + interner.len() * (size_of::<bool>() + size_of::<usize>())
+                    + tree.memory_bytes(0)
+                    + memory::vec_bytes::<Reverse<(usize, usize)>>(frontier.capacity()),
+            )
+        };
+
+        let mut successors = Vec::new();
+
+        while let Some(Reverse((path_cost, id))) = frontier.pop() {
+            let state_id = tree.get(id).state;
+            if closed[state_id.index()] {
+                tree.release(id);
+                continue;
+            }
+            closed[state_id.index()] = true;
+
+            let state = interner.get(state_id).clone();
+            recorder.visit();
+            recorder.sample_frontier(path_cost, frontier.len());
+
+            if problem.is_goal(&state) {
+                recorder.set_memory_kb(memory_kb(&interner, &tree, &frontier));
+                recorder.set_solution_length(tree.get(id).depth);
+                recorder.set_solution_cost(path_cost);
+                return Some(tree.extract_solution(id));
+            }
+
+            problem.successors_into(&state, &mut successors);
+            for (action, successor_state, cost) in successors.drain(..) {
+                let tentative_g = path_cost + cost;
+                if self.cost_bound.is_some_and(|bound| tentative_g > bound) {
+                    continue;
+                }
+
+                let (successor_id, is_new) = interner.intern(successor_state);
+                if is_new {
+                    closed.push(false);
+                    g_scores.push(tentative_g);
+                } else if tentative_g >= g_scores[successor_id.index()] {
+                    continue;
+                } else {
+                    g_scores[successor_id.index()] = tentative_g;
+                }
+
+                let child = tree.add_child(id, successor_id, action, cost);
+                frontier.push(Reverse((tentative_g, child)));
+                recorder.generate();
+            }
+            tree.release(id);
+
+            recorder.observe_frontier(frontier.len());
+        }
+
+        recorder.set_memory_kb(memory_kb(&interner, &tree, &frontier));
+        None
+    }
+}
+
+impl Default for UniformCost {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SearchAlgorithm for UniformCost {
+    fn search_recorded<P: Problem>(
+        &self,
+        problem: &P,
+        recorder: &mut MetricsRecorder,
+    ) -> Option<Vec<P::Action>> {
+        if self.track_solutions {
+            self.search_tree(problem, recorder, SearchTree::new())
+        } else {
+            self.search_tree(problem, recorder, NodePool::new())
+        }
+    }
+
+    fn name(&self) -> &str {
+        "UCS"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::bfs::BFS;
+    use crate::algorithms::reference;
+    use crate::problems::ShortestPath;
+
+    #[test]
+    fn test_finds_cheapest_path_where_bfs_does_not() {
+        let mut graph = ShortestPath::new(0, 3);
+        graph.add_edge(0, 1, 4);
+        graph.add_edge(0, 2, 1);
+        graph.add_edge(2, 1, 1);
+        graph.add_edge(1, 3, 10);
+        graph.add_edge(0, 3, 20);
+
+        let bfs = BFS::new().search(&graph);
+        assert_eq!(bfs.metrics.solution_cost, 20);
+        assert_eq!(reference::optimal_cost(&graph, false), Some(12));
+
+        for ucs in [
+            UniformCost::new(),
+            UniformCost::new().with_solution_tracking(false),
+        ] {
+            let result = ucs.search(&graph);
+            assert!(result.status.is_solved());
+            assert_eq!(result.metrics.solution_cost, 12);
+        }
+        assert_eq!(UniformCost::new().search(&graph).solution.unwrap().len(), 3);
+        assert!(!UniformCost::new()
+            .with_cost_bound(Some(11))
+            .search(&graph)
+            .status
+            .is_solved());
+    }
+}
@@ -0,0 +1,108 @@
+use super::Problem;
+use crate::benchmarking::Metrics;
+
+pub(crate) fn replay<P: Problem>(
+    problem: &P,
+    mut state: P::State,
+    actions: &[P::Action],
+) -> Result<(P::State, usize), String> {
+    let mut cost = 0;
+
+    for (step, action) in actions.iter().enumerate() {
+        if !problem.actions(&state).contains(action) {
+            return Err(format!("action {} illégale à l'étape {}", action, step + 1));
+        }
+        let (next, step_cost) = problem.apply(&state, action);
+        state = next;
+        cost += step_cost;
+    }
+
+    if !problem.is_goal(&state) {
+        return Err("la séquence d'actions n'atteint pas le but".to_string());
+    }
+    Ok((state, cost))
+}
+
+pub fn decode_actions<P: Problem>(problem: &P, labels: &[String]) -> Option<Vec<P::Action>> {
+    problem.initial_states().into_iter().find_map(|mut state| {
+        let mut actions = Vec::with_capacity(labels.len());
+        for label in labels {
+            let action = problem
+                .actions(&state)
+                .into_iter()
+                .find(|action| action.to_string() == *label)?;
+            state = problem.apply(&state, &action).0;
+            actions.push(action);
+        }
+        problem.is_goal(&state).then_some(actions)
+    })
+}
+
+pub fn validate_solution<P: Problem>(
+    problem: &P,
+    actions: &[P::Action],
+    metrics: &Metrics,
+) -> Result<(), String> {
+    let mut starts = problem.initial_states().into_iter();
+    let mut replayed = replay(
+        problem,
+        starts.next().expect("au moins un état initial"),
+        actions,
+    );
+    for start in starts {
+        if replayed.is_ok() {
+            break;
+        }
+        replayed = replay(problem, start, actions).or(replayed);
+    }
+    let (_, cost) = replayed?;
+    if metrics.solution_length != actions.len() {
+        return Err(format!(
+            "longueur annoncée {} mais {} actions rejouées",
+            metrics.solution_length,
+            actions.len()
+        ));
+    }
+    if metrics.solution_cost != cost {
+        return Err(format!(
+            "coût annoncé {} mais coût rejoué {}",
+            metrics.solution_cost, cost
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::problems::taquin::{HeuristicType, Move};
+    use crate::problems::Taquin;
+
+    fn metrics(length: usize, cost: usize) -> Metrics {
+        Metrics {
+            solution_length: length,
+            solution_cost: cost,
+            ..Metrics::default()
+        }
+    }
+
+    #[test]
+    fn test_detects_invalid_solutions() {
+        let taquin =
+            Taquin::from_state(3, vec![1, 0, 2, 3, 4, 5, 6, 7, 8], HeuristicType::Manhattan);
+
+        assert!(validate_solution(&taquin, &[Move::Left], &metrics(1, 1)).is_ok());
+        assert!(validate_solution(&taquin, &[Move::Right], &metrics(1, 1)).is_err());
+        assert!(validate_solution(&taquin, &[Move::Up], &metrics(1, 1)).is_err());
+        assert!(validate_solution(&taquin, &[Move::Left], &metrics(1, 2)).is_err());
+        assert!(validate_solution(&taquin, &[Move::Left], &metrics(2, 1)).is_err());
+
+        let labels = |moves: &[Move]| moves.iter().map(|m| m.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            decode_actions(&taquin, &labels(&[Move::Left])),
+            Some(vec![Move::Left])
+        );
+        assert_eq!(decode_actions(&taquin, &labels(&[Move::Right])), None);
+    }
+}
@@ -0,0 +1,258 @@
+use super::memory;
+use super::{Problem, SearchAlgorithm};
+use crate::benchmarking::MetricsRecorder;
+use crate::utils::hashing::{StateMap, StateSet};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::mem::size_of;
+
+struct Path<S, A> {
+    states: Vec<S>,
+    actions: Vec<A>,
+    step_costs: Vec<usize>,
+    cost: usize,
+}
+
+impl<S, A> Path<S, A> {
+    fn bytes(&self, heap_bytes: usize) -> usize {
+        self.states.len() * (size_of::<S>() + heap_bytes)
+            + self.actions.len() * size_of::<A>()
+            + self.step_costs.len() * size_of::<usize>()
+    }
+}
+
+struct SpurNode<S, A> {
+    state: S,
+    parent: Option<(usize, A, usize)>,
+}
+
+pub struct Yen {
+    pub k: usize,
+    pub track_solutions: bool,
+}
+
+impl Yen {
+    pub fn new(k: usize) -> Self {
+        Yen {
+            k: k.max(1),
+            track_solutions: true,
+        }
+    }
+
+    pub fn with_solution_tracking(mut self, track_solutions: bool) -> Self {
+        self.track_solutions = track_solutions;
+        self
+    }
+
+    fn spur_path<P: Problem>(
+        problem: &P,
+        from: &P::State,
+        banned_states: &StateSet<P::State>,
+        banned_actions: &[P::Action],
+        recorder: &mut MetricsRecorder,
+        peak_bytes: &mut usize,
+    ) -> Option<Path<P::State, P::Action>> {
+        let mut nodes: Vec<SpurNode<P::State, P::Action>> = vec![SpurNode {
+            state: from.clone(),
+            parent: None,
+        }];
+        let mut best_g = StateMap::default();
+        best_g.insert(from.clone(), 0);
+        let mut closed = StateSet::default();
+        let mut frontier = BinaryHeap::new();
+        frontier.push(Reverse((0, 0)));
+        recorder.generate();
+        let heap_bytes = problem.state_heap_bytes(from);
+        let mut successors = Vec::new();
+
+        while let Some(Reverse((g, id))) = frontier.pop() {
+            let state = nodes[id].state.clone();
+            if !closed.insert(state.clone()) {
+                continue;
+            }
+            recorder.visit();
+            recorder.sample_frontier(g, frontier.len());
+
+            if problem.is_goal(&state) {
+                *peak_bytes = (*peak_bytes).max(
+                    memory::vec_bytes::<SpurNode<P::State, P::Action>>(nodes.capacity())
+                        + nodes.len() * heap_bytes
+                        + memory::hash_table_bytes::<(P::State, usize)>(best_g.len())
+                        + memory::hash_table_bytes::<P::State>(closed.len())
+                        + memory::vec_bytes::<Reverse<(usize, usize)>>(frontier.capacity()),
+                );
+                let mut path = Path {
+                    states: vec![state],
+                    actions: Vec::new(),
+                    step_costs: Vec::new(),
+                    cost: g,
+                };
+                let mut current = id;
+                while let Some((parent, action, cost)) = &nodes[current].parent {
+                    path.states.push(nodes[*parent].state.clone());
+                    path.actions.push(action.clone());
+                    path.step_costs.push(*cost);
+                    current = *parent;
+                }
+                path.states.reverse();
+                path.actions.reverse();
+                path.step_costs.reverse();
+                return Some(path);
+            }
+
+            problem.successors_into(&state, &mut successors);
+            for (action, next, cost) in successors.drain(..) {
+                if (id == 0 && banned_actions.contains(&action)) || banned_states.contains(&next) {
+                    continue;
+                }
+                let tentative_g = g + cost;
+                if best_g.get(&next).is_some_and(|&known| known <= tentative_g) {
+                    continue;
+                }
+                best_g.insert(next.clone(), tentative_g);
+                frontier.push(Reverse((tentative_g, nodes.len())));
+                nodes.push(SpurNode {
+                    state: next,
+                    parent: Some((id, action, cost)),
+                });
+                recorder.generate();
+            }
+            recorder.observe_frontier(frontier.len());
+        }
+        None
+    }
+}
+
+impl SearchAlgorithm for Yen {
+    fn search_recorded<P: Problem>(
+        &self,
+        problem: &P,
+        recorder: &mut MetricsRecorder,
+    ) -> Option<Vec<P::Action>> {
+        let start = problem.initial_state();
+        let heap_bytes = problem.state_heap_bytes(&start);
+        let mut peak_bytes = 0;
+        let first = Self::spur_path(
+            problem,
+            &start,
+            &StateSet::default(),
+            &[],
+            recorder,
+            &mut peak_bytes,
+        )?;
+        recorder.generate_path();
+
+        let mut found = vec![first];
+        let mut candidates: Vec<Path<P::State, P::Action>> = Vec::new();
+        let mut candidate_bytes = 0;
+        let mut peak_candidates = 0;
+
+        while found.len() < self.k {
+            let last = found.last().expect("au moins un chemin");
+            for spur in 0..last.actions.len() {
+                let root = &last.actions[..spur];
+                let banned_actions: Vec<P::Action> = found
+                    .iter()
+                    .filter(|path| path.actions.len() > spur && path.actions[..spur] == *root)
+                    .map(|path| path.actions[spur].clone())
+                    .collect();
+                let banned_states: StateSet<P::State> =
+                    last.states[..spur].iter().cloned().collect();
+
+                let Some(tail) = Self::spur_path(
+                    problem,
+                    &last.states[spur],
+                    &banned_states,
+                    &banned_actions,
+                    recorder,
+                    &mut peak_bytes,
+                ) else {
+                    continue;
+                };
+                recorder.generate_path();
+
+                let mut path = Path {
+                    states: last.states[..spur].to_vec(),
+                    actions: root.to_vec(),
+                    step_costs: last.step_costs[..spur].to_vec(),
+                    cost: last.step_costs[..spur].iter().sum::<usize>() + tail.cost,
+                };
+                path.states.extend(tail.states);
+                path.actions.extend(tail.actions);
+                path.step_costs.extend(tail.step_costs);
+                if candidates
+                    .iter()
+                    .chain(&found)
+                    .any(|known| known.actions == path.actions)
+                {
+                    continue;
+                }
+                candidate_bytes += path.bytes(heap_bytes);
+                candidates.push(path);
+            }
+            peak_candidates = peak_candidates.max(candidate_bytes);
+
+            let Some(best) = (0..candidates.len()).min_by_key(|&i| candidates[i].cost) else {
+                break;
+            };
+            let path = candidates.swap_remove(best);
+            candidate_bytes -= path.bytes(heap_bytes);
+            found.push(path);
+        }
+
+        let found_bytes: usize = found.iter().map(|path| path.bytes(heap_bytes)).sum();
+        recorder.set_memory_kb(memory::kb(peak_bytes + peak_candidates + found_bytes));
+        recorder.set_paths_found(found.len());
+
+        let best = found.swap_remove(0);
+        recorder.set_solution_length(best.actions.len());
+        recorder.set_solution_cost(best.cost);
+        Some(if self.track_solutions {
+            best.actions
+        } else {
+            Vec::new()
+        })
+    }
+
+    fn name(&self) -> &str {
+        "Yen"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::uniform_cost::UniformCost;
+    use crate::algorithms::validation;
+    use crate::problems::ShortestPath;
+
+    #[test]
+    fn test_enumerates_loopless_paths_in_cost_order() {
+        let mut graph = ShortestPath::new(0, 3);
+        for (from, to, cost) in [
+            (0, 1, 1),
+            (0, 2, 2),
+            (1, 2, 1),
+            (1, 3, 5),
+            (2, 3, 1),
+            (2, 1, 1),
+            (2, 3, 4),
+        ] {
+            graph.add_edge(from, to, cost);
+        }
+
+        let result = Yen::new(10).search(&graph);
+        assert!(result.status.is_solved());
+        assert_eq!(result.metrics.solution_cost, 3);
+        assert_eq!(result.metrics.paths_found, 6);
+        assert!(result.metrics.paths_generated >= result.metrics.paths_found);
+        let actions = result.solution.unwrap();
+        validation::validate_solution(&graph, &actions, &result.metrics).unwrap();
+
+        let random = ShortestPath::generate_connected_with_seed(30, 120, 0, 29, 7);
+        let optimal = UniformCost::new().search(&random).metrics.solution_cost;
+        let result = Yen::new(5).search(&random);
+        assert_eq!(result.metrics.solution_cost, optimal);
+        assert_eq!(result.metrics.paths_found, 5);
+    }
+}
@@ -0,0 +1,133 @@
+use super::metrics::{BenchmarkResult, MetricsRecorder};
+use super::progress::Progress;
+use super::runner::{BenchmarkConfig, BenchmarkRunner, ProblemRun};
+use super::scenario;
+use crate::algorithms::all_pairs::{self, DistanceMatrix};
+use crate::algorithms::SearchStatus;
+use crate::problems::registry::ProblemFactory;
+use crate::problems::shortest_path::RandomGraphFactory;
+use crate::problems::ShortestPath;
+use crate::utils::hashing::HASHER_NAME;
+use crate::utils::seeding;
+use std::time::Instant;
+
+type AllPairs = fn(&ShortestPath, &[usize], &mut MetricsRecorder) -> DistanceMatrix;
+
+const ALGORITHMS: [(&str, AllPairs); 2] = [
+    ("Floyd-Warshall", all_pairs::floyd_warshall::<ShortestPath>),
+    (
+        "Repeated-Dijkstra",
+        all_pairs::repeated_dijkstra::<ShortestPath>,
+    ),
+];
+
+pub fn enabled(config: &BenchmarkConfig) -> bool {
+    config.all_pairs
+}
+
+pub fn validate(config: &BenchmarkConfig) -> Result<(), String> {
+    if !config.all_pairs {
+        return Ok(());
+    }
+    if config.problem != "shortest-path-random" {
+        return Err(format!(
+            "--all-pairs ne s'applique qu'à shortest-path-random (problème: {})",
+            config.problem
+        ));
+    }
+    if scenario::enabled(config) {
+        return Err(
+            "--all-pairs n'est pas compatible avec --scenario ni --dynamic-obstacles".to_string(),
+        );
+    }
+    if config.instances.is_some() {
+        return Err("--all-pairs génère ses instances (--instances non supporté)".to_string());
+    }
+    if config.negative_edges > 0.0 {
+        return Err(
+            "--all-pairs: Dijkstra suppose des coûts positifs (--negative-edges non supporté)"
+                .to_string(),
+        );
+    }
+    Ok(())
+}
+
+pub fn benchmark(config: &BenchmarkConfig, progress: &Progress) -> Result<ProblemRun, String> {
+    let base_label = RandomGraphFactory::label(config);
+    let label = format!("{}-all-pairs", base_label);
+    let nodes: Vec<usize> = (0..config.size).collect();
+    progress.log(format!(
+        "Plus courts chemins toutes paires: {} nœuds, Floyd-Warshall (O(n³)) contre Dijkstra depuis chaque source",
+        config.size
+    ));
+    let start = Instant::now();
+    let mut generation_ms = 0.0;
+    let mut results = Vec::new();
+
+    for instance_id in 0..config.iterations {
+        let generation_start = Instant::now();
+        let seed = seeding::instance_seed(config.seed, &base_label, config.size, instance_id);
+        let problem = RandomGraphFactory::generate(config, seed);
+        let properties = RandomGraphFactory::properties(&problem);
+        generation_ms += generation_start.elapsed().as_micros() as f64 / 1000.0;
+
+        let mut matrices: Vec<DistanceMatrix> = Vec::new();
+        for (algorithm, solve) in ALGORITHMS {
+            let mut recorder = MetricsRecorder::new();
+            let matrix = solve(&problem, &nodes, &mut recorder);
+            recorder.set_solution_cost(matrix.checksum());
+            let mut result = BenchmarkResult {
+                algorithm: algorithm.to_string(),
+                problem: label.clone(),
+                problem_size: config.size,
+                instance_id,
+                seed,
+                status: SearchStatus::Solved,
+                metrics: recorder.finish(),
+                timestamp: chrono::Local::now().to_rfc3339(),
+                hasher: HASHER_NAME.to_string(),
+                initial_state: Some(problem.initial_state_string()),
+                solution: None,
+                error: None,
+                optimal: None,
+                cost_optimal: None,
+                properties: properties.clone(),
+                profile: None,
+                improvements: Vec::new(),
+                host: None,
+            };
+            result.properties.insert(
+                "reachable_pairs".to_string(),
+                matrix.reachable_pairs().into(),
+            );
+            if matrices
+                .first()
+                .is_some_and(|reference| *reference != matrix)
+            {
+                result.status =
+                    SearchStatus::Error(format!("distances différentes de {}", ALGORITHMS[0].0));
+                result.error = Some(result.status.to_string());
+            }
+            if progress.is_console() {
+                println!(
+                    "  Instance {}\t {}/{}\t {}",
+                    algorithm,
+                    instance_id + 1,
+                    config.iterations,
+                    BenchmarkRunner::outcome(&result)
+                );
+            }
+            matrices.push(matrix);
+            results.push(result);
+        }
+    }
+
+    Ok(scenario::problem_run(
+        config,
+        label,
+        "all-pairs",
+        start,
+        generation_ms,
+        results,
+    ))
+}
@@ -0,0 +1,341 @@
+use super::metrics::BenchmarkResult;
+use super::progress::ProgressEvent;
+use super::runner::{BenchmarkConfig, BenchmarkRunner};
+use super::server::{self, Filters, LoadedFile};
+use crate::problems::registry;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+pub struct ApiConfig {
+    pub address: String,
+    pub output_dir: String,
+    pub max_threads: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum JobStatus {
+    Queued,
+    Running,
+    Done,
+    Failed,
+}
+
+#[derive(Serialize)]
+struct Job {
+    id: usize,
+    status: JobStatus,
+    submitted_at: String,
+    started_at: Option<String>,
+    finished_at: Option<String>,
+    tasks: usize,
+    completed: usize,
+    error: Option<String>,
+    config: BenchmarkConfig,
+}
+
+struct Api {
+    jobs: Mutex<Vec<Job>>,
+    queue: Sender<usize>,
+    output_dir: PathBuf,
+    max_threads: usize,
+}
+
+fn now() -> String {
+    chrono::Local::now().to_rfc3339()
+}
+
+fn error(status: u16, message: impl Into<String>) -> (u16, Value) {
+    (status, json!({ "error": message.into() }))
+}
+
+impl Api {
+    fn new(config: &ApiConfig) -> (Arc<Self>, Receiver<usize>) {
+        let (queue, jobs) = channel();
+        let api = Api {
+            jobs: Mutex::new(Vec::new()),
+            queue,
+            output_dir: PathBuf::from(&config.output_dir),
+            max_threads: config.max_threads.max(1),
+        };
+        (Arc::new(api), jobs)
+    }
+
+    fn submit(&self, body: &str) -> Result<Value, String> {
+        let mut config: BenchmarkConfig =
+            serde_json::from_str(body).map_err(|e| format!("configuration invalide: {}", e))?;
+        config.validate()?;
+
+        let mut jobs = self.jobs.lock().unwrap();
+        let id = jobs.len() + 1;
+        config.output_file = self
+            .output_dir
+            .join(format!("job-{}.json", id))
+            .to_string_lossy()
+            .into_owned();
+        config.threads = config.threads.clamp(1, self.max_threads);
+        jobs.push(Job {
+            id,
+            status: JobStatus::Queued,
+            submitted_at: now(),
+            started_at: None,
+            finished_at: None,
+            tasks: 0,
+            completed: 0,
+            error: None,
+            config,
+        });
+        let job = json!(jobs[id - 1]);
+        drop(jobs);
+
+        self.queue
+            .send(id)
+            .map_err(|_| "file d'exécution arrêtée".to_string())?;
+        Ok(job)
+    }
+
+    fn update(&self, id: usize, change: impl FnOnce(&mut Job)) {
+        if let Some(job) = self.jobs.lock().unwrap().get_mut(id.wrapping_sub(1)) {
+            change(job);
+        }
+    }
+
+    fn results(&self, id: usize) -> Result<Vec<BenchmarkResult>, (u16, Value)> {
+        let jobs = self.jobs.lock().unwrap();
+        let job = jobs
+            .get(id.wrapping_sub(1))
+            .ok_or_else(|| error(404, format!("tâche inconnue: {}", id)))?;
+        if job.status != JobStatus::Done {
+            return Err((
+                409,
+                json!({ "error": "résultats pas encore disponibles", "status": job.status }),
+            ));
+        }
+        let path = job.config.output_file.clone();
+        drop(jobs);
+
+        fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|contents| serde_json::from_str(&contents).map_err(|e| e.to_string()))
+            .map_err(|e| error(500, format!("{}: {}", path, e)))
+    }
+
+    fn route(&self, method: &Method, path: &str, query: &str, body: &str) -> (u16, Value) {
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+        match (method, segments.as_slice()) {
+            (Method::Get, ["api", "problems"]) => (
+                200,
+                registry::PROBLEMS
+                    .iter()
+                    .map(|entry| {
+                        json!({
+                            "name": entry.name,
+                            "title": entry.title,
+                            "params": entry.params.iter().map(|param| {
+                                json!({ "name": param.name, "description": param.description })
+                            }).collect::<Vec<_>>(),
+                        })
+                    })
+                    .collect(),
+            ),
+            (Method::Post, ["api", "jobs"]) => match self.submit(body) {
+                Ok(job) => (201, job),
+                Err(e) => error(400, e),
+            },
+            (Method::Get, ["api", "jobs"]) => (200, json!(*self.jobs.lock().unwrap())),
+            (Method::Get, ["api", "jobs", id, rest @ ..]) => {
+                let Ok(id) = id.parse::<usize>() else {
+                    return error(404, format!("tâche inconnue: {}", id));
+                };
+                let filters = Filters::from_query(query);
+                match rest {
+                    [] => match self.jobs.lock().unwrap().get(id.wrapping_sub(1)) {
+                        Some(job) => (200, json!(job)),
+                        None => error(404, format!("tâche inconnue: {}", id)),
+                    },
+                    ["results"] => match self.results(id) {
+                        Ok(results) => {
+                            let results: Vec<&BenchmarkResult> = results
+                                .iter()
+                                .filter(|result| filters.matches("", result))
+                                .collect();
+                            (200, json!(results))
+                        }
+                        Err(response) => response,
+                    },
+                    ["summary"] => match self.results(id) {
+                        Ok(results) => (
+                            200,
+                            server::summary(
+                                &[LoadedFile {
+                                    name: format!("job-{}", id),
+                                    results: Ok(results),
+                                }],
+                                &filters,
+                            ),
+                        ),
+                        Err(response) => response,
+                    },
+                    _ => error(404, "introuvable"),
+                }
+            }
+            _ => error(404, "introuvable"),
+        }
+    }
+}
+
+fn run_job(api: &Arc<Api>, id: usize, config: BenchmarkConfig) -> Result<(), String> {
+    fs::create_dir_all(&api.output_dir).map_err(|e| e.to_string())?;
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(config.threads)
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut runner = BenchmarkRunner::new(config);
+    runner.progress_mut().set_console(false);
+    let events = runner.progress_mut().subscribe();
+    let tracker = {
+        let api = Arc::clone(api);
+        thread::spawn(move || {
+            for event in events {
+                match event {
+                    ProgressEvent::ProblemStarted { tasks, .. } => {
+                        api.update(id, |job| job.tasks += tasks)
+                    }
+                    ProgressEvent::TaskFinished { .. } => api.update(id, |job| job.completed += 1),
+                    _ => {}
+                }
+            }
+        })
+    };
+
+    let outcome = pool.install(move || runner.run().map_err(|e| e.to_string()));
+    let _ = tracker.join();
+    outcome
+}
+
+fn execute(api: Arc<Api>, queue: Receiver<usize>) {
+    for id in queue {
+        let mut config = None;
+        api.update(id, |job| {
+            job.status = JobStatus::Running;
+            job.started_at = Some(now());
+            config = Some(job.config.clone());
+        });
+        let Some(config) = config else {
+            continue;
+        };
+
+        let outcome = run_job(&api, id, config);
+        api.update(id, |job| {
+            job.finished_at = Some(now());
+            match outcome {
+                Ok(()) => job.status = JobStatus::Done,
+                Err(e) => {
+                    job.status = JobStatus::Failed;
+                    job.error = Some(e);
+                }
+            }
+        });
+    }
+}
+
+fn respond(api: &Api, mut request: Request) -> std::io::Result<()> {
+    let url = request.url().to_string();
+    let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+    let mut body = String::new();
+    let (status, value) = match request.as_reader().read_to_string(&mut body) {
+        Ok(_) => api.route(request.method(), path, query, &body),
+        Err(e) => error(400, format!("corps de requête illisible: {}", e)),
+    };
+    let response = Response::from_string(value.to_string())
+        .with_status_code(status)
+        .with_header(
+            Header::from_bytes("Content-Type", "application/json; charset=utf-8")
+                .expect("en-tête valide"),
+        );
+    request.respond(response)
+}
+
+pub fn run(config: &ApiConfig) -> Result<(), Box<dyn Error>> {
+    let server = Server::http(&config.address)
+        .map_err(|e| format!("Impossible d'écouter sur {}: {}", config.address, e))?;
+    let (api, queue) = Api::new(config);
+    {
+        let api = Arc::clone(&api);
+        thread::spawn(move || execute(api, queue));
+    }
+
+    println!("API REST: http://{}/api/jobs", server.server_addr());
+    println!("  Résultats dans {}", config.output_dir);
+    println!("  Threads max par tâche: {}", api.max_threads);
+    println!("Ctrl-C pour arrêter");
+
+    for request in server.incoming_requests() {
+        if let Err(e) = respond(&api, request) {
+            eprintln!("Erreur de réponse: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_submitted_job_runs_and_serves_results() {
+        let output_dir = std::env::temp_dir().join("benchmark_api_test");
+        let (api, queue) = Api::new(&ApiConfig {
+            address: String::new(),
+            output_dir: output_dir.to_string_lossy().into_owned(),
+            max_threads: 2,
+        });
+
+        let (status, body) = api.route(&Method::Post, "/api/jobs", "", "{\"problem\": \"nope\"}");
+        assert_eq!(status, 400, "{}", body);
+
+        let submission = r#"{"algorithm": "astar", "problem": "shortest-path", "iterations": 2, "threads": 8, "seed": 5}"#;
+        let (status, job) = api.route(&Method::Post, "/api/jobs", "", submission);
+        assert_eq!(status, 201, "{}", job);
+        assert_eq!(
+            (job["id"].as_u64(), job["status"].as_str()),
+            (Some(1), Some("queued"))
+        );
+        assert_eq!(job["config"]["threads"], 2);
+        assert_eq!(
+            api.route(&Method::Get, "/api/jobs/1/results", "", "").0,
+            409
+        );
+
+        {
+            let api = Arc::clone(&api);
+            thread::spawn(move || execute(api, queue));
+        }
+        while api.route(&Method::Get, "/api/jobs/1", "", "").1["status"] != "done" {
+            assert_ne!(api.jobs.lock().unwrap()[0].status, JobStatus::Failed);
+            thread::sleep(std::time::Duration::from_millis(10));
+        }
+
+        let (_, job) = api.route(&Method::Get, "/api/jobs/1", "", "");
+        assert_eq!(
+            (job["tasks"].as_u64(), job["completed"].as_u64()),
+            (Some(2), Some(2))
+        );
+        let (status, results) = api.route(&Method::Get, "/api/jobs/1/results", "", "");
+        assert_eq!(status, 200);
+        assert_eq!(results.as_array().map(Vec::len), Some(2));
+        let (_, summary) = api.route(&Method::Get, "/api/jobs/1/summary", "", "");
+        assert_eq!(summary[0]["successful_instances"], 2);
+        assert_eq!(api.route(&Method::Get, "/api/jobs/9", "", "").0, 404);
+        let _ = fs::remove_dir_all(output_dir);
+    }
+}
@@ -0,0 +1,277 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const MIN_ITERATIONS: usize = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SkipReason {
+    ReducedIterations,
+    SlowestAlgorithm,
+    Exhausted,
+    TooLong,
+}
+
+impl SkipReason {
+    pub fn code(self) -> &'static str {
+        match self {
+            SkipReason::ReducedIterations => "iterations",
+            SkipReason::SlowestAlgorithm => "slowest",
+            SkipReason::Exhausted => "exhausted",
+            SkipReason::TooLong => "estimate",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SkipReason::ReducedIterations => "itérations réduites pour tenir le budget",
+            SkipReason::SlowestAlgorithm => "algorithme le plus lent écarté pour tenir le budget",
+            SkipReason::Exhausted => "budget épuisé",
+            SkipReason::TooLong => "durée estimée au-delà du budget restant",
+        }
+    }
+}
+
+pub fn parse(spec: &str) -> Result<u64, String> {
+    let invalid = || {
+        format!(
+            "durée attendue comme 2h, 1h30m, 45m, 90s ou 3600 (reçu: {})",
+            spec
+        )
+    };
+    if let Ok(secs) = spec.parse::<u64>() {
+        return Ok(secs);
+    }
+    let mut total = 0;
+    let mut digits = String::new();
+    for c in spec.chars() {
+        let unit = match c {
+            '0'..='9' => {
+                digits.push(c);
+                continue;
+            }
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return Err(invalid()),
+        };
+        total += digits.parse::<u64>().map_err(|_| invalid())? * unit;
+        digits.clear();
+    }
+    if !digits.is_empty() || total == 0 {
+        return Err(invalid());
+    }
+    Ok(total)
+}
+
+pub fn format(secs: u64) -> String {
+    match (secs / 3600, secs % 3600 / 60, secs % 60) {
+        (0, 0, s) => format!("{}s", s),
+        (0, m, 0) => format!("{}m", m),
+        (0, m, s) => format!("{}m{}s", m, s),
+        (h, 0, 0) => format!("{}h", h),
+        (h, m, _) => format!("{}h{:02}m", h, m),
+    }
+}
+
+pub struct Plan {
+    pub skips: Vec<Option<SkipReason>>,
+    pub iterations: usize,
+    pub dropped: Vec<String>,
+    pub estimated_ms: f64,
+}
+
+pub struct Budget {
+    limit: Duration,
+    start: Instant,
+    problems_left: AtomicUsize,
+    observed: Mutex<HashMap<(String, String), (f64, usize)>>,
+}
+
+impl Budget {
+    pub fn new(secs: u64) -> Self {
+        Budget {
+            limit: Duration::from_secs(secs),
+            start: Instant::now(),
+            problems_left: AtomicUsize::new(1),
+            observed: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn set_problems(&self, count: usize) {
+        self.problems_left.store(count.max(1), Ordering::Relaxed);
+    }
+
+    pub fn finish_problem(&self) {
+        let _ = self
+            .problems_left
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |left| {
+                Some(left.saturating_sub(1).max(1))
+            });
+    }
+
+    pub fn remaining(&self) -> Duration {
+        self.limit.saturating_sub(self.start.elapsed())
+    }
+
+    pub fn problem_deadline(&self) -> Instant {
+        Instant::now() + self.remaining() / self.problems_left.load(Ordering::Relaxed) as u32
+    }
+
+    pub fn record(&self, problem: &str, algorithm: &str, time_ms: f64) {
+        let mut observed = self.observed.lock().unwrap();
+        let entry = observed
+            .entry((problem.to_string(), algorithm.to_string()))
+            .or_default();
+        entry.0 += time_ms;
+        entry.1 += 1;
+    }
+
+    pub fn observed_ms(&self, problem: &str, algorithm: &str) -> Option<f64> {
+        self.observed
+            .lock()
+            .unwrap()
+            .get(&(problem.to_string(), algorithm.to_string()))
+            .map(|(total, count)| total / *count as f64)
+    }
+
+    pub fn plan(
+        &self,
+        deadline: Instant,
+        tasks: &[(usize, &str)],
+        threads: usize,
+        estimate: impl Fn(&str) -> Option<f64>,
+    ) -> Option<Plan> {
+        let costs: Vec<f64> = tasks
+            .iter()
+            .map(|(_, algo_name)| estimate(algo_name))
+            .collect::<Option<_>>()?;
+        let allotted = deadline
+            .saturating_duration_since(Instant::now())
+            .as_secs_f64()
+            * 1000.0;
+        let threads = threads.max(1) as f64;
+        let total_of = |skips: &[Option<SkipReason>]| {
+            costs
+                .iter()
+                .zip(skips)
+                .filter(|(_, skip)| skip.is_none())
+                .map(|(cost, _)| cost)
+                .sum::<f64>()
+                / threads
+        };
+
+        let mut skips = vec![None; tasks.len()];
+        let full = total_of(&skips);
+        let iterations = tasks.iter().map(|(id, _)| id + 1).max().unwrap_or(0);
+        if full <= allotted {
+            return Some(Plan {
+                skips,
+                iterations,
+                dropped: Vec::new(),
+                estimated_ms: full,
+            });
+        }
+
+        let reduced = ((iterations as f64 * allotted / full) as usize)
+            .max(MIN_ITERATIONS)
+            .min(iterations);
+        for (skip, (instance_id, _)) in skips.iter_mut().zip(tasks) {
+            if *instance_id >= reduced {
+                *skip = Some(SkipReason::ReducedIterations);
+            }
+        }
+
+        let mut per_algorithm: Vec<(&str, f64)> = Vec::new();
+        for ((_, algo_name), cost) in tasks.iter().zip(&costs) {
+            match per_algorithm.iter_mut().find(|(name, _)| name == algo_name) {
+                Some(entry) => entry.1 = entry.1.max(*cost),
+                None => per_algorithm.push((algo_name, *cost)),
+            }
+        }
+        per_algorithm.sort_by(|a, b| b.1.total_cmp(&a.1));
+        let mut dropped = Vec::new();
+        for (slowest, _) in per_algorithm.iter().take(per_algorithm.len() - 1) {
+            if total_of(&skips) <= allotted {
+                break;
+            }
+            for (skip, (_, algo_name)) in skips.iter_mut().zip(tasks) {
+                if algo_name == slowest {
+                    *skip = Some(SkipReason::SlowestAlgorithm);
+                }
+            }
+            dropped.push(slowest.to_string());
+        }
+
+        Some(Plan {
+            estimated_ms: total_of(&skips),
+            skips,
+            iterations: reduced,
+            dropped,
+        })
+    }
+
+    pub fn check(&self, deadline: Instant, estimate: Option<f64>) -> Option<SkipReason> {
+        let left = deadline.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return Some(SkipReason::Exhausted);
+        }
+        estimate
+            .filter(|&ms| ms > left.as_secs_f64() * 1000.0)
+            .map(|_| SkipReason::TooLong)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_reduces_iterations_then_drops_slowest() {
+        assert_eq!(parse("1h30m"), Ok(5400));
+        assert_eq!(parse("45s"), Ok(45));
+        assert_eq!(parse("120"), Ok(120));
+        assert!(parse("2j").is_err() && parse("h").is_err() && parse("5m3").is_err());
+        assert_eq!(format(5400), "1h30m");
+
+        let budget = Budget::new(3600);
+        let deadline = Instant::now() + Duration::from_millis(1000);
+        let tasks: Vec<(usize, &str)> = (0..10).flat_map(|id| [(id, "A*"), (id, "BFS")]).collect();
+        let estimate = |cost_bfs: f64| {
+            move |algo_name: &str| match algo_name {
+                "A*" => Some(10.0),
+                _ => Some(cost_bfs),
+            }
+        };
+
+        let fits = budget.plan(deadline, &tasks, 1, estimate(50.0)).unwrap();
+        assert!(fits.skips.iter().all(Option::is_none));
+
+        let trimmed = budget.plan(deadline, &tasks, 1, estimate(180.0)).unwrap();
+        assert_eq!(trimmed.iterations, 5);
+        assert!(trimmed.dropped.is_empty());
+        assert_eq!(
+            trimmed.skips[10..12],
+            [Some(SkipReason::ReducedIterations); 2]
+        );
+
+        let dropped = budget.plan(deadline, &tasks, 1, estimate(900.0)).unwrap();
+        assert_eq!(dropped.iterations, MIN_ITERATIONS);
+        assert_eq!(dropped.dropped, vec!["BFS"]);
+        assert_eq!(
+            dropped.skips[0..2],
+            [None, Some(SkipReason::SlowestAlgorithm)]
+        );
+
+        assert!(budget.plan(deadline, &tasks, 1, |_| None).is_none());
+        budget.record("Taquin", "A*", 4.0);
+        budget.record("Taquin", "A*", 6.0);
+        assert_eq!(budget.observed_ms("Taquin", "A*"), Some(5.0));
+        assert_eq!(
+            budget.check(Instant::now(), None),
+            Some(SkipReason::Exhausted)
+        );
+        assert_eq!(budget.check(deadline, Some(1e6)), Some(SkipReason::TooLong));
+    }
+}
@@ -0,0 +1,421 @@
+use super::import::{self, ImportOptions};
+use super::metrics::{AggregatedResults, BenchmarkResult};
+use super::options::{CompareFormat, ImportFormat};
+use super::runs;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+
+pub struct CompareConfig {
+    pub baseline: String,
+    pub candidate: String,
+    pub format: CompareFormat,
+    pub threshold: f64,
+    pub runs_dir: String,
+}
+
+struct MetricSpec {
+    label: &'static str,
+    unit: &'static str,
+    value: fn(&AggregatedResults) -> f64,
+    higher_is_better: bool,
+}
+
+const METRICS: &[MetricSpec] = &[
+    MetricSpec {
+        label: "Temps",
+        unit: " ms",
+        value: |a| a.avg_time_ms,
+        higher_is_better: false,
+    },
+    MetricSpec {
+        label: "Nœuds",
+        unit: "",
+        value: |a| a.avg_nodes_visited,
+        higher_is_better: false,
+    },
+    MetricSpec {
+        label: "Mémoire",
+        unit: " Ko",
+        value: |a| a.avg_memory_kb,
+        higher_is_better: false,
+    },
+    MetricSpec {
+        label: "Coût",
+        unit: "",
+        value: |a| a.avg_solution_cost,
+        higher_is_better: false,
+    },
+    MetricSpec {
+        label: "Succès",
+        unit: " %",
+        value: |a| {
+            if a.total_instances == 0 {
+                0.0
+            } else {
+                a.successful_instances as f64 * 100.0 / a.total_instances as f64
+            }
+        },
+        higher_is_better: true,
+    },
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verdict {
+    Regression,
+    Improvement,
+    Added,
+    Removed,
+    Unchanged,
+}
+
+impl Verdict {
+    fn marker(self) -> &'static str {
+        match self {
+            Verdict::Regression => "🔴",
+            Verdict::Improvement => "🟢",
+            Verdict::Added => "🆕",
+            Verdict::Removed => "➖",
+            Verdict::Unchanged => "⚪",
+        }
+    }
+}
+
+pub struct Row {
+    pub problem: String,
+    pub algorithm: String,
+    pub baseline: Option<AggregatedResults>,
+    pub candidate: Option<AggregatedResults>,
+    pub verdict: Verdict,
+}
+
+fn relative_change(before: f64, after: f64) -> f64 {
+    if before == after {
+        0.0
+    } else if before == 0.0 {
+        f64::INFINITY.copysign(after)
+    } else {
+        (after - before) * 100.0 / before.abs()
+    }
+}
+
+fn worsening(spec: &MetricSpec, change: f64) -> f64 {
+    if spec.higher_is_better {
+        -change
+    } else {
+        change
+    }
+}
+
+fn aggregate(results: &[BenchmarkResult]) -> BTreeMap<(String, String), AggregatedResults> {
+    let mut groups: BTreeMap<(String, String), Vec<BenchmarkResult>> = BTreeMap::new();
+    for result in results {
+        groups
+            .entry((result.problem.clone(), result.algorithm.clone()))
+            .or_default()
+            .push(result.clone());
+    }
+    groups
+        .into_iter()
+        .map(|(key, group)| (key, AggregatedResults::from_results(&group)))
+        .collect()
+}
+
+pub fn compare(
+    baseline: &[BenchmarkResult],
+    candidate: &[BenchmarkResult],
+    threshold: f64,
+) -> Vec<Row> {
+    let mut before = aggregate(baseline);
+    let after = aggregate(candidate);
+    let mut rows = Vec::new();
+
+    for ((problem, algorithm), candidate) in after {
+        let baseline = before.remove(&(problem.clone(), algorithm.clone()));
+        let verdict = match &baseline {
+            None => Verdict::Added,
+            Some(baseline) => {
+                let worsenings: Vec<f64> = METRICS
+                    .iter()
+                    .map(|spec| {
+                        let change =
+                            relative_change((spec.value)(baseline), (spec.value)(&candidate));
+                        worsening(spec, change)
+                    })
+                    .collect();
+                let worst = worsenings.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                let best = worsenings.iter().copied().fold(f64::INFINITY, f64::min);
+                if worst > threshold {
+                    Verdict::Regression
+                } else if best < -threshold {
+                    Verdict::Improvement
+                } else {
+                    Verdict::Unchanged
+                }
+            }
+        };
+        rows.push(Row {
+            problem,
+            algorithm,
+            baseline,
+            candidate: Some(candidate),
+            verdict,
+        });
+    }
+    rows.extend(
+        before
+            .into_iter()
+            .map(|((problem, algorithm), baseline)| Row {
+                problem,
+                algorithm,
+                baseline: Some(baseline),
+                candidate: None,
+                verdict: Verdict::Removed,
+            }),
+    );
+    rows.sort_by(|a, b| {
+        (a.verdict, &a.problem, &a.algorithm).cmp(&(b.verdict, &b.problem, &b.algorithm))
+    });
+    rows
+}
+
+fn format_value(value: f64, unit: &str) -> String {
+    if value.abs() >= 100.0 {
+        format!("{:.0}{}", value, unit)
+    } else {
+        format!("{:.2}{}", value, unit)
+    }
+}
+
+fn format_change(change: f64) -> String {
+    if change.is_infinite() {
+        format!("{}∞%", if change > 0.0 { "+" } else { "-" })
+    } else {
+        format!("{:+.1}%", change)
+    }
+}
+
+fn cell(spec: &MetricSpec, row: &Row, threshold: f64, markdown: bool) -> String {
+    match (&row.baseline, &row.candidate) {
+        (Some(baseline), Some(candidate)) => {
+            let (before, after) = ((spec.value)(baseline), (spec.value)(candidate));
+            let change = relative_change(before, after);
+            let mut text = format_change(change);
+            if markdown && worsening(spec, change).abs() > threshold {
+                text = format!("**{}**", text);
+            }
+            format!(
+                "{} → {} ({})",
+                format_value(before, ""),
+                format_value(after, spec.unit),
+                text
+            )
+        }
+        (Some(only), None) | (None, Some(only)) => format_value((spec.value)(only), spec.unit),
+        (None, None) => String::new(),
+    }
+}
+
+fn count(rows: &[Row], verdict: Verdict) -> usize {
+    rows.iter().filter(|row| row.verdict == verdict).count()
+}
+
+fn headline(rows: &[Row], threshold: f64) -> String {
+    let mut parts = vec![
+        format!(
+            "{} {} régression(s)",
+            Verdict::Regression.marker(),
+            count(rows, Verdict::Regression)
+        ),
+        format!(
+            "{} {} amélioration(s)",
+            Verdict::Improvement.marker(),
+            count(rows, Verdict::Improvement)
+        ),
+        format!(
+            "{} {} inchangé(s)",
+            Verdict::Unchanged.marker(),
+            count(rows, Verdict::Unchanged)
+        ),
+    ];
+    for verdict in [Verdict::Added, Verdict::Removed] {
+        let n = count(rows, verdict);
+        if n > 0 {
+            let label = if verdict == Verdict::Added {
+                "ajouté(s)"
+            } else {
+                "retiré(s)"
+            };
+            parts.push(format!("{} {} {}", verdict.marker(), n, label));
+        }
+    }
+    format!("{} (seuil ±{}%)", parts.join(", "), threshold)
+}
+
+fn markdown_table<'a>(rows: impl Iterator<Item = &'a Row>, threshold: f64) -> String {
+    let mut table = String::from("| | Problème | Algorithme |");
+    for spec in METRICS {
+        let _ = write!(table, " {} |", spec.label);
+    }
+    table.push_str("\n|---|---|---|");
+    table.push_str(&"---|".repeat(METRICS.len()));
+    for row in rows {
+        let _ = write!(
+            table,
+            "\n| {} | {} | {} |",
+            row.verdict.marker(),
+            row.problem,
+            row.algorithm
+        );
+        for spec in METRICS {
+            let _ = write!(table, " {} |", cell(spec, row, threshold, true));
+        }
+    }
+    table
+}
+
+pub fn render_markdown_comment(rows: &[Row], threshold: f64, config: &CompareConfig) -> String {
+    let mut out = format!("### Benchmarks : {}\n\n", headline(rows, threshold));
+    let notable: Vec<&Row> = rows
+        .iter()
+        .filter(|row| row.verdict != Verdict::Unchanged)
+        .collect();
+    if notable.is_empty() {
+        out.push_str("Aucun écart au-delà du seuil.\n");
+    } else {
+        out.push_str(&markdown_table(notable.into_iter(), threshold));
+        out.push('\n');
+    }
+
+    let _ = write!(
+        out,
+        "\n<details>\n<summary>Détails ({} comparaisons, référence <code>{}</code>, candidat <code>{}</code>)</summary>\n\n{}\n\n</details>\n",
+        rows.len(),
+        config.baseline,
+        config.candidate,
+        markdown_table(rows.iter(), threshold)
+    );
+    out
+}
+
+pub fn render_text(rows: &[Row], threshold: f64) -> String {
+    let mut out = format!("{}\n", headline(rows, threshold));
+    for row in rows {
+        let _ = write!(
+            out,
+            "\n{} {} sur {}",
+            row.verdict.marker(),
+            row.algorithm,
+            row.problem
+        );
+        for spec in METRICS {
+            let _ = write!(
+                out,
+                "\n  {}: {}",
+                spec.label,
+                cell(spec, row, threshold, false)
+            );
+        }
+    }
+    out.push('\n');
+    out
+}
+
+fn load(spec: &str, runs_dir: &str) -> Result<Vec<BenchmarkResult>, String> {
+    let path = runs::resolve(spec, Path::new(runs_dir))?;
+    import::load(&path, ImportFormat::Auto, &ImportOptions::default())
+}
+
+pub fn run(config: &CompareConfig) -> Result<usize, String> {
+    let rows = compare(
+        &load(&config.baseline, &config.runs_dir)?,
+        &load(&config.candidate, &config.runs_dir)?,
+        config.threshold,
+    );
+    let output = match config.format {
+        CompareFormat::Text => render_text(&rows, config.threshold),
+        CompareFormat::MarkdownComment => render_markdown_comment(&rows, config.threshold, config),
+    };
+    print!("{}", output);
+    Ok(count(&rows, Verdict::Regression))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::SearchStatus;
+    use crate::benchmarking::Metrics;
+
+    fn result(algorithm: &str, time_ms: f64, nodes_visited: usize) -> BenchmarkResult {
+        BenchmarkResult {
+            algorithm: algorithm.to_string(),
+            problem: "Taquin-3x3".to_string(),
+            problem_size: 3,
+            instance_id: 0,
+            seed: 0,
+            status: SearchStatus::Solved,
+            metrics: Metrics {
+                time_ms,
+                nodes_visited,
+                ..Metrics::default()
+            },
+            timestamp: String::new(),
+            hasher: String::new(),
+            initial_state: None,
+            solution: None,
+            error: None,
+            optimal: None,
+            cost_optimal: None,
+            properties: Default::default(),
+            profile: None,
+            improvements: Vec::new(),
+            host: None,
+        }
+    }
+
+    #[test]
+    fn test_markdown_comment_lists_only_notable_rows() {
+        let baseline = vec![
+            result("A*", 10.0, 100),
+            result("BFS", 10.0, 100),
+            result("DFS", 10.0, 100),
+            result("ID", 10.0, 100),
+        ];
+        let candidate = vec![
+            result("A*", 15.0, 100),
+            result("BFS", 10.2, 80),
+            result("DFS", 10.1, 101),
+            result("IDA*", 5.0, 50),
+        ];
+        let rows = compare(&baseline, &candidate, 5.0);
+        let verdicts: Vec<(&str, Verdict)> = rows
+            .iter()
+            .map(|row| (row.algorithm.as_str(), row.verdict))
+            .collect();
+        assert_eq!(
+            verdicts,
+            vec![
+                ("A*", Verdict::Regression),
+                ("BFS", Verdict::Improvement),
+                ("IDA*", Verdict::Added),
+                ("ID", Verdict::Removed),
+                ("DFS", Verdict::Unchanged),
+            ]
+        );
+
+        let config = CompareConfig {
+            baseline: "avant.json".to_string(),
+            candidate: "après.json".to_string(),
+            format: CompareFormat::MarkdownComment,
+            threshold: 5.0,
+            runs_dir: runs::DEFAULT_DIR.to_string(),
+        };
+        let comment = render_markdown_comment(&rows, 5.0, &config);
+        let (summary, details) = comment.split_once("<details>").unwrap();
+        assert!(summary.starts_with("### Benchmarks : 🔴 1 régression(s), 🟢 1 amélioration(s)"));
+        assert!(summary.contains("| 🔴 | Taquin-3x3 | A* | 10.00 → 15.00 ms (**+50.0%**) |"));
+        assert!(!summary.contains("| DFS |"));
+        assert!(details.contains("| ⚪ | Taquin-3x3 | DFS |"));
+        assert!(details.trim_end().ends_with("</details>"));
+    }
+}
@@ -0,0 +1,157 @@
+use super::metrics::BenchmarkResult;
+use super::runner::{BACKWARD_SUFFIX, EARLY_GOAL_SUFFIX};
+use std::collections::BTreeMap;
+
+pub fn is_optimal_algorithm(algo_name: &str, unit_costs: bool, admissible: bool) -> bool {
+    suboptimality_bound(algo_name, unit_costs, admissible) == Some(1.0)
+}
+
+pub fn suboptimality_bound(algo_name: &str, unit_costs: bool, admissible: bool) -> Option<f64> {
+    let base = algo_name.strip_suffix(BACKWARD_SUFFIX).unwrap_or(algo_name);
+    let base = base.strip_suffix(EARLY_GOAL_SUFFIX).unwrap_or(base);
+    let optimal = match base {
+        "BFS" | "ID" | "Frontier" => unit_costs,
+        name if name.starts_with("BFHS") => unit_costs && admissible,
+        "UCS" | "Yen" | "Bellman-Ford" => true,
+        name => {
+            admissible
+                && (name.starts_with("A*")
+                    || name.starts_with("IDA*")
+                    || name.starts_with("BiA*")
+                    || name.starts_with("AWA*")
+                    || name.starts_with("Perimeter"))
+        }
+    };
+    optimal.then_some(1.0)
+}
+
+pub fn optimality_gap(solution_cost: usize, optimal_cost: usize) -> f64 {
+    if optimal_cost == 0 {
+        1.0
+    } else {
+        solution_cost as f64 / optimal_cost as f64
+    }
+}
+
+pub struct Disagreement {
+    pub instance_id: usize,
+    pub costs: Vec<(String, usize)>,
+}
+
+impl Disagreement {
+    pub fn summary(&self) -> String {
+        self.costs
+            .iter()
+            .map(|(algo_name, cost)| format!("{}={}", algo_name, cost))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+pub fn find_disagreement<'a>(
+    results: impl IntoIterator<Item = &'a BenchmarkResult>,
+    is_optimal: impl Fn(&str) -> bool,
+) -> Option<Disagreement> {
+    let mut costs: BTreeMap<usize, Vec<(String, usize)>> = BTreeMap::new();
+    for result in results {
+        if result.status.is_solved() && is_optimal(&result.algorithm) {
+            costs
+                .entry(result.instance_id)
+                .or_default()
+                .push((result.algorithm.clone(), result.metrics.solution_cost));
+        }
+    }
+
+    costs
+        .into_iter()
+        .find(|(_, costs)| costs.iter().any(|(_, cost)| *cost != costs[0].1))
+        .map(|(instance_id, costs)| Disagreement { instance_id, costs })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::SearchStatus;
+    use crate::benchmarking::Metrics;
+
+    fn result(
+        algorithm: &str,
+        instance_id: usize,
+        status: SearchStatus,
+        cost: usize,
+    ) -> BenchmarkResult {
+        BenchmarkResult {
+            algorithm: algorithm.to_string(),
+            problem: "Taquin-3x3".to_string(),
+            problem_size: 3,
+            instance_id,
+            seed: 0,
+            status,
+            metrics: Metrics {
+                solution_cost: cost,
+                ..Metrics::default()
+            },
+            timestamp: String::new(),
+            hasher: String::new(),
+            initial_state: None,
+            solution: None,
+            error: None,
+            optimal: None,
+            cost_optimal: None,
+            properties: Default::default(),
+            profile: None,
+            improvements: Vec::new(),
+            host: None,
+        }
+    }
+
+    #[test]
+    fn test_reports_first_cost_disagreement() {
+        let results = [
+            result("BFS", 0, SearchStatus::Solved, 20),
+            result("A*-Manhattan", 0, SearchStatus::Solved, 20),
+            result("DFS", 0, SearchStatus::Solved, 86),
+            result("BFS", 1, SearchStatus::Solved, 12),
+            result("IDA*-Manhattan", 1, SearchStatus::Timeout, 0),
+            result("A*-Manhattan", 1, SearchStatus::Solved, 14),
+        ];
+        let is_optimal = |name: &str| is_optimal_algorithm(name, true, true);
+
+        let disagreement = find_disagreement(&results, is_optimal).unwrap();
+        assert_eq!(disagreement.instance_id, 1);
+        assert_eq!(disagreement.summary(), "BFS=12, A*-Manhattan=14");
+        assert!(find_disagreement(&results[..4], is_optimal).is_none());
+    }
+
+    #[test]
+    fn test_optimal_algorithms_depend_on_problem() {
+        assert!(is_optimal_algorithm("BFS", true, false));
+        assert!(!is_optimal_algorithm("BFS", false, true));
+        assert!(is_optimal_algorithm("IDA*-Manhattan", false, true));
+        assert!(!is_optimal_algorithm("A*", true, false));
+        assert!(!is_optimal_algorithm("DFS", true, true));
+        assert_eq!(suboptimality_bound("A*-Manhattan", false, true), Some(1.0));
+        assert_eq!(suboptimality_bound("DFS", true, true), None);
+        assert!(is_optimal_algorithm("UCS", false, false));
+        assert!(is_optimal_algorithm("UCS-backward", false, false));
+        assert!(!is_optimal_algorithm("A*-backward", false, false));
+        assert!(is_optimal_algorithm("A*-backward", false, true));
+    }
+
+    #[test]
+    fn test_early_goal_and_backward_variants_keep_base_optimality() {
+        for name in [
+            "BFS-early",
+            "BFS-early-backward",
+            "ID-backward",
+            "Frontier-backward",
+        ] {
+            assert!(is_optimal_algorithm(name, true, false), "{}", name);
+            assert!(!is_optimal_algorithm(name, false, true), "{}", name);
+        }
+        assert!(!is_optimal_algorithm("DFS-early", true, true));
+        assert!(!is_optimal_algorithm("DFS-early-backward", true, true));
+        assert_eq!(optimality_gap(15, 12), 1.25);
+        assert_eq!(optimality_gap(0, 0), 1.0);
+    }
+}
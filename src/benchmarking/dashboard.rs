@@ -0,0 +1,388 @@
+use super::metrics::SharedMetrics;
+use super::progress::ProgressEvent;
+use super::runner::BenchmarkRunner;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Gauge, Paragraph, Row, Table};
+use ratatui::{DefaultTerminal, Frame};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::io::{self, IsTerminal};
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::thread;
+use std::time::Duration;
+
+const REFRESH: Duration = Duration::from_millis(100);
+const MAX_LOG_LINES: usize = 1000;
+
+struct RunningTask {
+    algorithm: String,
+    instance: usize,
+    thread: Option<usize>,
+    metrics: SharedMetrics,
+}
+
+#[derive(Default)]
+struct AlgorithmRow {
+    algorithm: String,
+    problem: String,
+    finished: usize,
+    solved: usize,
+    time_ms: f64,
+    nodes_visited: usize,
+}
+
+#[derive(Default)]
+struct Dashboard {
+    threads: usize,
+    problem: String,
+    tasks: usize,
+    finished: usize,
+    running: BTreeMap<usize, RunningTask>,
+    rows: Vec<AlgorithmRow>,
+    log: Vec<String>,
+    done: bool,
+}
+
+impl Dashboard {
+    fn new(threads: usize) -> Self {
+        Dashboard {
+            threads,
+            ..Dashboard::default()
+        }
+    }
+
+    fn apply(&mut self, event: ProgressEvent) {
+        match event {
+            ProgressEvent::ProblemStarted { problem, tasks } => {
+                self.problem = problem;
+                self.tasks = tasks;
+                self.finished = 0;
+                self.running.clear();
+            }
+            ProgressEvent::TaskStarted {
+                task,
+                algorithm,
+                instance,
+                thread,
+                metrics,
+            } => {
+                self.running.insert(
+                    task,
+                    RunningTask {
+                        algorithm,
+                        instance,
+                        thread,
+                        metrics,
+                    },
+                );
+            }
+            ProgressEvent::TaskFinished {
+                task,
+                result,
+                time_ms,
+            } => {
+                self.running.remove(&task);
+                self.finished += 1;
+                let index = match self.rows.iter().position(|row| {
+                    row.algorithm == result.algorithm && row.problem == result.problem
+                }) {
+                    Some(index) => index,
+                    None => {
+                        self.rows.push(AlgorithmRow {
+                            algorithm: result.algorithm.clone(),
+                            problem: result.problem.clone(),
+                            ..AlgorithmRow::default()
+                        });
+                        self.rows.len() - 1
+                    }
+                };
+                let row = &mut self.rows[index];
+                row.finished += 1;
+                row.time_ms += time_ms;
+                row.nodes_visited += result.metrics.nodes_visited;
+                if result.status.is_solved() {
+                    row.solved += 1;
+                }
+            }
+            ProgressEvent::Log(line) => {
+                self.log.extend(line.lines().map(str::to_string));
+            }
+        }
+    }
+
+    fn busy_threads(&self) -> usize {
+        self.running.len().min(self.threads.max(1))
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [progress_area, threads_area, running_area, results_area, log_area, help_area] =
+            Layout::vertical([
+                Constraint::Length(3),
+                Constraint::Length(3),
+                Constraint::Min(5),
+                Constraint::Min(5),
+                Constraint::Length(8),
+                Constraint::Length(1),
+            ])
+            .areas(frame.area());
+
+        let ratio = if self.tasks == 0 {
+            0.0
+        } else {
+            self.finished as f64 / self.tasks as f64
+        };
+        frame.render_widget(
+            Gauge::default()
+                .block(Block::bordered().title(format!(" {} ", self.problem)))
+                .gauge_style(Style::default().fg(Color::Green))
+                .ratio(ratio.min(1.0))
+                .label(format!("{}/{} tâches", self.finished, self.tasks)),
+            progress_area,
+        );
+
+        let threads = self.threads.max(1);
+        let busy = self.busy_threads();
+        frame.render_widget(
+            Gauge::default()
+                .block(Block::bordered().title(" Utilisation des threads "))
+                .gauge_style(Style::default().fg(Color::Cyan))
+                .ratio(busy as f64 / threads as f64)
+                .label(format!("{}/{} occupés", busy, threads)),
+            threads_area,
+        );
+
+        self.draw_running(frame, running_area);
+        self.draw_results(frame, results_area);
+
+        let visible = log_area.height.saturating_sub(2) as usize;
+        let log: Vec<Line> = self.log[self.log.len().saturating_sub(visible)..]
+            .iter()
+            .map(|line| Line::from(line.as_str()))
+            .collect();
+        frame.render_widget(
+            Paragraph::new(log).block(Block::bordered().title(" Journal ")),
+            log_area,
+        );
+
+        let help = if self.done {
+            "Terminé - q pour quitter"
+        } else {
+            "En cours... (Ctrl-C pour interrompre)"
+        };
+        frame.render_widget(Line::from(help).dim(), help_area);
+    }
+
+    fn draw_running(&self, frame: &mut Frame, area: Rect) {
+        let rows = self.running.values().map(|task| {
+            let metrics = task.metrics.get();
+            let elapsed = task.metrics.elapsed().as_secs_f64();
+            let rate = if elapsed > 0.0 {
+                metrics.nodes_visited as f64 / elapsed
+            } else {
+                0.0
+            };
+            Row::new(vec![
+                task.algorithm.clone(),
+                (task.instance + 1).to_string(),
+                task.thread
+                    .map_or("-".to_string(), |thread| thread.to_string()),
+                format!("{:.1} s", elapsed),
+                metrics.nodes_visited.to_string(),
+                format!("{:.0}", rate),
+            ])
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Fill(2),
+                Constraint::Length(9),
+                Constraint::Length(7),
+                Constraint::Length(10),
+                Constraint::Length(12),
+                Constraint::Length(12),
+            ],
+        )
+        .header(
+            Row::new(vec![
+                "Algorithme",
+                "Instance",
+                "Thread",
+                "Temps",
+                "Nœuds",
+                "Nœuds/s",
+            ])
+            .bold(),
+        )
+        .block(Block::bordered().title(format!(" En cours ({}) ", self.running.len())));
+        frame.render_widget(table, area);
+    }
+
+    fn draw_results(&self, frame: &mut Frame, area: Rect) {
+        let rows = self.rows.iter().map(|row| {
+            let n = row.finished.max(1) as f64;
+            Row::new(vec![
+                row.algorithm.clone(),
+                row.problem.clone(),
+                format!("{}/{}", row.solved, row.finished),
+                format!("{:.2}", row.time_ms / n),
+                format!("{:.0}", row.nodes_visited as f64 / n),
+            ])
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Fill(2),
+                Constraint::Fill(2),
+                Constraint::Length(9),
+                Constraint::Length(14),
+                Constraint::Length(14),
+            ],
+        )
+        .header(
+            Row::new(vec![
+                "Algorithme",
+                "Problème",
+                "Succès",
+                "Temps moy. ms",
+                "Nœuds moy.",
+            ])
+            .bold(),
+        )
+        .block(Block::bordered().title(" Résultats "));
+        frame.render_widget(table, area);
+    }
+
+    fn event_loop(
+        &mut self,
+        terminal: &mut DefaultTerminal,
+        events: &Receiver<ProgressEvent>,
+    ) -> io::Result<bool> {
+        loop {
+            while !self.done {
+                match events.try_recv() {
+                    Ok(event) => self.apply(event),
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => self.done = true,
+                }
+            }
+            if self.log.len() > MAX_LOG_LINES {
+                self.log.drain(..self.log.len() - MAX_LOG_LINES);
+            }
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if !event::poll(REFRESH)? {
+                continue;
+            }
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                match key.code {
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        return Ok(false)
+                    }
+                    KeyCode::Char('q') | KeyCode::Esc if self.done => return Ok(true),
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+pub fn run(mut runner: BenchmarkRunner) -> Result<(), Box<dyn Error>> {
+    if !io::stdout().is_terminal() {
+        return Err("--tui nécessite un terminal interactif".into());
+    }
+
+    let mut dashboard = Dashboard::new(runner.config().threads);
+    let events = runner.progress_mut().subscribe();
+    runner.progress_mut().set_console(false);
+    let worker = thread::spawn(move || runner.run().map_err(|e| e.to_string()));
+
+    let mut terminal = ratatui::init();
+    let completed = dashboard.event_loop(&mut terminal, &events);
+    ratatui::restore();
+
+    for line in &dashboard.log {
+        println!("{}", line);
+    }
+    if !completed? {
+        return Err("benchmark interrompu".into());
+    }
+
+    worker
+        .join()
+        .map_err(|_| "le thread de benchmark a paniqué")?
+        .map_err(|e| e.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::SearchStatus;
+    use crate::benchmarking::{BenchmarkResult, Metrics};
+
+    fn finished(task: usize, algorithm: &str, status: SearchStatus) -> ProgressEvent {
+        ProgressEvent::TaskFinished {
+            task,
+            result: Box::new(BenchmarkResult {
+                algorithm: algorithm.to_string(),
+                problem: "Taquin 3x3".to_string(),
+                problem_size: 3,
+                instance_id: task,
+                seed: 0,
+                status,
+                metrics: Metrics {
+                    nodes_visited: 10,
+                    ..Metrics::default()
+                },
+                timestamp: String::new(),
+                hasher: String::new(),
+                initial_state: None,
+                solution: None,
+                error: None,
+                optimal: None,
+                cost_optimal: None,
+                properties: Default::default(),
+                profile: None,
+                improvements: Vec::new(),
+                host: None,
+            }),
+            time_ms: 2.0,
+        }
+    }
+
+    #[test]
+    fn test_results_fill_in_as_tasks_finish() {
+        let mut dashboard = Dashboard::new(2);
+        dashboard.apply(ProgressEvent::ProblemStarted {
+            problem: "Taquin 3x3".to_string(),
+            tasks: 3,
+        });
+        for task in 0..3 {
+            dashboard.apply(ProgressEvent::TaskStarted {
+                task,
+                algorithm: "BFS".to_string(),
+                instance: task,
+                thread: Some(task % 2),
+                metrics: SharedMetrics::new(),
+            });
+        }
+        assert_eq!(dashboard.busy_threads(), 2);
+
+        dashboard.apply(finished(0, "BFS", SearchStatus::Solved));
+        dashboard.apply(finished(1, "BFS", SearchStatus::Timeout));
+
+        assert_eq!(dashboard.finished, 2);
+        assert_eq!(dashboard.running.len(), 1);
+        assert_eq!(dashboard.rows.len(), 1);
+        assert_eq!(
+            (dashboard.rows[0].solved, dashboard.rows[0].finished),
+            (1, 2)
+        );
+        assert_eq!(dashboard.rows[0].nodes_visited, 20);
+    }
+}
@@ -0,0 +1,338 @@
+use super::options::AlgorithmChoice;
+use super::runner::BenchmarkConfig;
+use crate::algorithms::stepper::{Step, StepOrder, Stepper};
+use crate::algorithms::Problem;
+use crate::problems::registry::ProblemFactory;
+use crate::utils::seeding;
+use std::io::{self, BufRead, Write};
+
+const DEFAULT_FRONTIER: usize = 10;
+const HELP: &str = "\
+Commandes :
+  s [n]                 développer n nœuds (1 par défaut)
+  c                     continuer jusqu'à un point d'arrêt, au but ou à l'épuisement
+  f [n]                 afficher les n premières entrées de la frontière (10 par défaut)
+  p [i]                 g/h/f, profondeur, chemin et état de l'entrée i de la frontière
+                        (sans argument : dernier nœud développé)
+  b depth <d>           s'arrêter quand un nœud de profondeur d est développé
+  b state <texte>       s'arrêter quand l'état développé contient <texte> (forme Debug)
+  b                     lister les points d'arrêt
+  d <n>                 supprimer le point d'arrêt n
+  h                     afficher cette aide
+  q                     quitter
+Une ligne vide répète la dernière commande.";
+
+pub struct DebugConfig {
+    pub instance: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Breakpoint {
+    Depth(usize),
+    State(String),
+}
+
+impl Breakpoint {
+    fn matches<S: std::fmt::Debug>(&self, state: &S, depth: usize) -> bool {
+        match self {
+            Breakpoint::Depth(target) => depth == *target,
+            Breakpoint::State(text) => format!("{:?}", state).contains(text.as_str()),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Breakpoint::Depth(depth) => format!("profondeur {}", depth),
+            Breakpoint::State(text) => format!("état contenant \"{}\"", text),
+        }
+    }
+}
+
+pub fn step_order(algorithm: AlgorithmChoice) -> Result<StepOrder, String> {
+    match algorithm {
+        AlgorithmChoice::Astar => Ok(StepOrder::AStar),
+        AlgorithmChoice::Bfs => Ok(StepOrder::BreadthFirst),
+        AlgorithmChoice::Dfs => Ok(StepOrder::DepthFirst),
+        other => Err(format!(
+            "{} n'est pas disponible en mode pas à pas (astar, bfs ou dfs)",
+            other
+        )),
+    }
+}
+
+struct Session<'a, P: Problem, W: Write> {
+    stepper: Stepper<'a, P>,
+    breakpoints: Vec<Breakpoint>,
+    last: Option<usize>,
+    output: W,
+}
+
+impl<P: Problem, W: Write> Session<'_, P, W> {
+    fn describe(&mut self, id: usize) -> io::Result<()> {
+        let node = self.stepper.node(id);
+        writeln!(
+            self.output,
+            "  g={} h={} f={} profondeur={}",
+            node.g,
+            node.h,
+            node.f(),
+            node.depth
+        )?;
+        let path: Vec<String> = self
+            .stepper
+            .path(id)
+            .iter()
+            .map(|action| action.to_string())
+            .collect();
+        writeln!(self.output, "  chemin: [{}]", path.join(", "))?;
+        writeln!(self.output, "  état: {:?}", self.stepper.node(id).state)
+    }
+
+    fn advance(&mut self, limit: usize) -> io::Result<()> {
+        let mut expanded = 0;
+        while expanded < limit {
+            let Some(step) = self.stepper.next() else {
+                writeln!(
+                    self.output,
+                    "Frontière vide : pas de solution ({} expansions)",
+                    self.stepper.expansions()
+                )?;
+                return Ok(());
+            };
+            let node = self.stepper.node(step.node());
+            match step {
+                Step::Skipped { .. } => continue,
+                Step::Goal { node: id } => {
+                    self.last = Some(id);
+                    writeln!(
+                        self.output,
+                        "But atteint après {} expansions, coût {}",
+                        self.stepper.expansions(),
+                        self.stepper.node(id).g
+                    )?;
+                    return self.describe(id);
+                }
+                Step::Expanded {
+                    node: id,
+                    generated,
+                } => {
+                    expanded += 1;
+                    self.last = Some(id);
+                    writeln!(
+                        self.output,
+                        "#{} développé: g={} h={} f={} profondeur={} (+{} générés, frontière {})",
+                        self.stepper.expansions(),
+                        node.g,
+                        node.h,
+                        node.f(),
+                        node.depth,
+                        generated,
+                        self.stepper.frontier_len()
+                    )?;
+                    let hit = self
+                        .breakpoints
+                        .iter()
+                        .position(|b| b.matches(&node.state, node.depth));
+                    if let Some(index) = hit {
+                        writeln!(
+                            self.output,
+                            "Point d'arrêt {} ({})",
+                            index + 1,
+                            self.breakpoints[index].describe()
+                        )?;
+                        return self.describe(id);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn frontier(&mut self, count: usize) -> io::Result<()> {
+        let top = self.stepper.frontier(count);
+        writeln!(
+            self.output,
+            "Frontière: {} entrées (les {} premières)",
+            self.stepper.frontier_len(),
+            top.len()
+        )?;
+        for (index, id) in top.into_iter().enumerate() {
+            let node = self.stepper.node(id);
+            writeln!(
+                self.output,
+                "  [{}] g={} h={} f={} profondeur={} {:?}",
+                index,
+                node.g,
+                node.h,
+                node.f(),
+                node.depth,
+                node.state
+            )?;
+        }
+        Ok(())
+    }
+
+    fn execute(&mut self, command: &str, argument: &str) -> io::Result<bool> {
+        let number = |default: usize| {
+            if argument.is_empty() {
+                Ok(default)
+            } else {
+                argument
+                    .parse::<usize>()
+                    .map_err(|_| format!("nombre invalide: {}", argument))
+            }
+        };
+
+        let outcome: Result<(), String> = match command {
+            "q" | "quit" => return Ok(false),
+            "h" | "help" => writeln!(self.output, "{}", HELP).map_err(|e| e.to_string()),
+            "s" | "step" => {
+                number(1).and_then(|count| self.advance(count).map_err(|e| e.to_string()))
+            }
+            "c" | "continue" => self.advance(usize::MAX).map_err(|e| e.to_string()),
+            "f" | "frontier" => number(DEFAULT_FRONTIER)
+                .and_then(|count| self.frontier(count).map_err(|e| e.to_string())),
+            "p" | "print" => {
+                let target = if argument.is_empty() {
+                    self.last.ok_or_else(|| "aucun nœud développé".to_string())
+                } else {
+                    number(0).and_then(|index| {
+                        self.stepper
+                            .frontier(index + 1)
+                            .get(index)
+                            .copied()
+                            .ok_or_else(|| format!("pas d'entrée {} dans la frontière", index))
+                    })
+                };
+                target.and_then(|id| self.describe(id).map_err(|e| e.to_string()))
+            }
+            "b" | "break" => {
+                let (kind, value) = argument.split_once(' ').unwrap_or((argument, ""));
+                let breakpoint = match (kind, value.trim()) {
+                    ("", _) => None,
+                    ("depth", value) => Some(
+                        value
+                            .parse()
+                            .map(Breakpoint::Depth)
+                            .map_err(|_| format!("profondeur invalide: {}", value)),
+                    ),
+                    ("state", value) if !value.is_empty() => {
+                        Some(Ok(Breakpoint::State(value.to_string())))
+                    }
+                    _ => Some(Err("usage: b depth <d> | b state <texte>".to_string())),
+                };
+                match breakpoint {
+                    Some(Ok(breakpoint)) => {
+                        self.breakpoints.push(breakpoint);
+                        writeln!(
+                            self.output,
+                            "Point d'arrêt {} ajouté",
+                            self.breakpoints.len()
+                        )
+                        .map_err(|e| e.to_string())
+                    }
+                    Some(Err(e)) => Err(e),
+                    None => self
+                        .breakpoints
+                        .iter()
+                        .enumerate()
+                        .try_for_each(|(index, breakpoint)| {
+                            writeln!(self.output, "  {}: {}", index + 1, breakpoint.describe())
+                        })
+                        .map_err(|e| e.to_string()),
+                }
+            }
+            "d" | "delete" => number(0).and_then(|index| {
+                if index == 0 || index > self.breakpoints.len() {
+                    return Err(format!("point d'arrêt inconnu: {}", argument));
+                }
+                self.breakpoints.remove(index - 1);
+                Ok(())
+            }),
+            other => Err(format!("commande inconnue: {} (h pour l'aide)", other)),
+        };
+
+        if let Err(e) = outcome {
+            writeln!(self.output, "Erreur: {}", e)?;
+        }
+        Ok(true)
+    }
+}
+
+pub fn run_session<P: Problem, R: BufRead, W: Write>(
+    problem: &P,
+    order: StepOrder,
+    input: R,
+    output: W,
+) -> io::Result<()> {
+    let mut session = Session {
+        stepper: Stepper::new(problem, order),
+        breakpoints: Vec::new(),
+        last: None,
+        output,
+    };
+    writeln!(session.output, "h pour l'aide")?;
+    session.frontier(1)?;
+
+    let mut previous = String::new();
+    for line in input.lines() {
+        let line = line?;
+        let line = if line.trim().is_empty() {
+            previous.clone()
+        } else {
+            line.trim().to_string()
+        };
+        let (command, argument) = line.split_once(' ').unwrap_or((&line, ""));
+        if !session.execute(command, argument.trim())? {
+            break;
+        }
+        previous = line;
+    }
+    Ok(())
+}
+
+pub fn debug_problem<F: ProblemFactory>(
+    config: &BenchmarkConfig,
+    debug: &DebugConfig,
+) -> Result<(), String> {
+    let order = step_order(config.algorithm)?;
+    let label = F::label(config);
+    let seed = seeding::instance_seed(config.seed, &label, config.size, debug.instance);
+    let problem = F::generate(config, seed);
+
+    println!(
+        "{} (instance {}, graine {}), algorithme {}",
+        label,
+        debug.instance + 1,
+        seed,
+        config.algorithm
+    );
+    println!("  {}", F::describe(&problem));
+    run_session(&problem, order, io::stdin().lock(), io::stdout().lock()).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::problems::ShortestPath;
+
+    #[test]
+    fn test_scripted_session_stops_on_breakpoints() {
+        let mut graph = ShortestPath::new(0, 4);
+        for node in 0..4 {
+            graph.add_edge(node, node + 1, 1);
+            graph.set_heuristic(node, 4 - node);
+        }
+
+        let script = "b depth 2\nc\nf\np 0\n\nx\nd 1\nc\nq\ns\n";
+        let mut output = Vec::new();
+        run_session(&graph, StepOrder::AStar, script.as_bytes(), &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(output.contains("Point d'arrêt 1 (profondeur 2)"));
+        assert!(output.contains("  [0] g=3 h=1 f=4 profondeur=3 3"));
+        assert!(output.contains("Erreur: commande inconnue: x"));
+        assert!(output.contains("But atteint après 4 expansions, coût 4"));
+        assert_eq!(output.matches("#").count(), 4);
+    }
+}
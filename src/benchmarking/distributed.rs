@@ -0,0 +1,422 @@
+use super::metrics::{BenchmarkResult, HostReport};
+use super::runner::{BenchmarkConfig, BenchmarkRunner};
+use crate::problems::registry;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{self, TcpListener, TcpStream};
+use std::sync::mpsc::{channel, Sender};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RemoteTask {
+    pub task: usize,
+    pub problem: String,
+    pub algorithm: String,
+    pub instance_id: usize,
+    pub seed: u64,
+    pub total: usize,
+    pub verified: bool,
+    pub optimal_cost: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Message {
+    Hello {
+        host: String,
+        threads: usize,
+    },
+    Configure {
+        config: Box<BenchmarkConfig>,
+    },
+    Task(RemoteTask),
+    Result {
+        task: usize,
+        result: Box<BenchmarkResult>,
+        time_ms: f64,
+    },
+    Shutdown,
+}
+
+fn send(stream: &mut impl Write, message: &Message) -> io::Result<()> {
+    let line = serde_json::to_string(message).map_err(io::Error::other)?;
+    writeln!(stream, "{}", line)?;
+    stream.flush()
+}
+
+fn receive(reader: &mut impl BufRead) -> io::Result<Option<Message>> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    serde_json::from_str(&line)
+        .map(Some)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+type Completed = (RemoteTask, BenchmarkResult, f64);
+
+struct Worker {
+    report: HostReport,
+    in_flight: HashMap<usize, RemoteTask>,
+    connected: bool,
+}
+
+#[derive(Default)]
+struct Dispatch {
+    pending: VecDeque<RemoteTask>,
+    workers: Vec<Worker>,
+    results: Option<Sender<Completed>>,
+    shutdown: bool,
+}
+
+struct Shared {
+    config: BenchmarkConfig,
+    dispatch: Mutex<Dispatch>,
+    changed: Condvar,
+}
+
+pub struct Coordinator {
+    address: String,
+    shared: Arc<Shared>,
+}
+
+impl Coordinator {
+    pub fn bind(address: &str, config: BenchmarkConfig) -> Result<Self, String> {
+        let listener = TcpListener::bind(address)
+            .map_err(|e| format!("Impossible d'écouter sur {}: {}", address, e))?;
+        let address = listener
+            .local_addr()
+            .map_or(address.to_string(), |local| local.to_string());
+        let shared = Arc::new(Shared {
+            config,
+            dispatch: Mutex::new(Dispatch::default()),
+            changed: Condvar::new(),
+        });
+        {
+            let shared = Arc::clone(&shared);
+            thread::spawn(move || accept(listener, shared));
+        }
+        Ok(Coordinator { address, shared })
+    }
+
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    pub fn threads(&self) -> usize {
+        let dispatch = self.shared.dispatch.lock().unwrap();
+        dispatch
+            .workers
+            .iter()
+            .filter(|worker| worker.connected)
+            .map(|worker| worker.report.threads)
+            .sum()
+    }
+
+    pub fn hosts(&self) -> Vec<HostReport> {
+        let dispatch = self.shared.dispatch.lock().unwrap();
+        dispatch
+            .workers
+            .iter()
+            .map(|worker| worker.report.clone())
+            .collect()
+    }
+
+    pub fn execute(
+        &self,
+        tasks: Vec<RemoteTask>,
+        mut on_result: impl FnMut(&RemoteTask, &BenchmarkResult, f64),
+    ) -> Result<Vec<(usize, BenchmarkResult, f64)>, String> {
+        let count = tasks.len();
+        let (sender, receiver) = channel();
+        {
+            let mut dispatch = self.shared.dispatch.lock().unwrap();
+            dispatch.pending.extend(tasks);
+            dispatch.results = Some(sender);
+        }
+        self.shared.changed.notify_all();
+
+        let mut results = Vec::with_capacity(count);
+        for _ in 0..count {
+            let (task, result, time_ms) = receiver
+                .recv()
+                .map_err(|_| "Coordinateur interrompu".to_string())?;
+            on_result(&task, &result, time_ms);
+            results.push((task.task, result, time_ms));
+        }
+        self.shared.dispatch.lock().unwrap().results = None;
+        Ok(results)
+    }
+
+    pub fn shutdown(&self) {
+        self.shared.dispatch.lock().unwrap().shutdown = true;
+        self.shared.changed.notify_all();
+    }
+}
+
+fn accept(listener: TcpListener, shared: Arc<Shared>) {
+    for stream in listener.incoming().flatten() {
+        let shared = Arc::clone(&shared);
+        thread::spawn(move || {
+            if let Err(e) = handle(stream, &shared) {
+                eprintln!("Worker déconnecté: {}", e);
+            }
+        });
+    }
+}
+
+fn handle(stream: TcpStream, shared: &Arc<Shared>) -> io::Result<()> {
+    let peer = stream.peer_addr()?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = stream;
+    let Some(Message::Hello { host, threads }) = receive(&mut reader)? else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: message hello attendu", peer),
+        ));
+    };
+    let host = if host.is_empty() {
+        peer.to_string()
+    } else {
+        host
+    };
+    send(
+        &mut writer,
+        &Message::Configure {
+            config: Box::new(shared.config.clone()),
+        },
+    )?;
+
+    let id = {
+        let mut dispatch = shared.dispatch.lock().unwrap();
+        dispatch.workers.push(Worker {
+            report: HostReport {
+                host: host.clone(),
+                threads: threads.max(1),
+                tasks: 0,
+                busy_ms: 0.0,
+            },
+            in_flight: HashMap::new(),
+            connected: true,
+        });
+        dispatch.workers.len() - 1
+    };
+    println!("Worker connecté: {} ({} threads)", host, threads.max(1));
+    shared.changed.notify_all();
+
+    let dispatcher = {
+        let shared = Arc::clone(shared);
+        thread::spawn(move || dispatch(writer, id, &shared))
+    };
+    let outcome = collect(reader, id, &host, shared);
+
+    let lost = {
+        let mut dispatch = shared.dispatch.lock().unwrap();
+        let lost: Vec<RemoteTask> = dispatch.workers[id]
+            .in_flight
+            .drain()
+            .map(|(_, task)| task)
+            .collect();
+        dispatch.workers[id].connected = false;
+        let count = lost.len();
+        dispatch.pending.extend(lost);
+        count
+    };
+    shared.changed.notify_all();
+    if lost > 0 {
+        println!("Worker perdu: {} ({} tâches remises en file)", host, lost);
+    }
+    let _ = dispatcher.join();
+    outcome
+}
+
+fn dispatch(mut writer: TcpStream, id: usize, shared: &Shared) {
+    let mut dispatch = shared.dispatch.lock().unwrap();
+    loop {
+        if !dispatch.workers[id].connected {
+            return;
+        }
+        if dispatch.shutdown {
+            drop(dispatch);
+            let _ = send(&mut writer, &Message::Shutdown);
+            return;
+        }
+        let worker = &dispatch.workers[id];
+        if worker.in_flight.len() < worker.report.threads {
+            if let Some(task) = dispatch.pending.pop_front() {
+                dispatch.workers[id]
+                    .in_flight
+                    .insert(task.task, task.clone());
+                drop(dispatch);
+                if send(&mut writer, &Message::Task(task)).is_err() {
+                    let _ = writer.shutdown(net::Shutdown::Both);
+                    return;
+                }
+                dispatch = shared.dispatch.lock().unwrap();
+                continue;
+            }
+        }
+        dispatch = shared.changed.wait(dispatch).unwrap();
+    }
+}
+
+fn collect(
+    mut reader: BufReader<TcpStream>,
+    id: usize,
+    host: &str,
+    shared: &Shared,
+) -> io::Result<()> {
+    while let Some(message) = receive(&mut reader)? {
+        let Message::Result {
+            task,
+            result,
+            time_ms,
+        } = message
+        else {
+            continue;
+        };
+        let mut dispatch = shared.dispatch.lock().unwrap();
+        let Some(remote) = dispatch.workers[id].in_flight.remove(&task) else {
+            continue;
+        };
+        let report = &mut dispatch.workers[id].report;
+        report.tasks += 1;
+        report.busy_ms += time_ms;
+        let mut result = *result;
+        result.host = Some(host.to_string());
+        if let Some(results) = &dispatch.results {
+            let _ = results.send((remote, result, time_ms));
+        }
+        drop(dispatch);
+        shared.changed.notify_all();
+    }
+    Ok(())
+}
+
+pub struct WorkerConfig {
+    pub address: String,
+    pub threads: usize,
+    pub name: Option<String>,
+}
+
+pub fn run_worker(worker: &WorkerConfig) -> Result<usize, String> {
+    let stream = TcpStream::connect(&worker.address)
+        .map_err(|e| format!("Connexion à {} impossible: {}", worker.address, e))?;
+    let host = worker
+        .name
+        .clone()
+        .or_else(|| stream.local_addr().ok().map(|local| local.ip().to_string()))
+        .unwrap_or_default();
+    let mut reader = BufReader::new(stream.try_clone().map_err(|e| e.to_string())?);
+    let writer = Arc::new(Mutex::new(stream));
+
+    send(
+        &mut *writer.lock().unwrap(),
+        &Message::Hello {
+            host: host.clone(),
+            threads: worker.threads,
+        },
+    )
+    .map_err(|e| e.to_string())?;
+    let Some(Message::Configure { config }) = receive(&mut reader).map_err(|e| e.to_string())?
+    else {
+        return Err("Configuration attendue du coordinateur".to_string());
+    };
+    let config = BenchmarkConfig {
+        threads: worker.threads,
+        ..*config
+    };
+    println!(
+        "Worker {} connecté à {}: {} sur {}, taille {}, {} threads",
+        host, worker.address, config.algorithm, config.problem, config.size, worker.threads
+    );
+
+    let runner = Arc::new(BenchmarkRunner::new(config));
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(worker.threads)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let mut received = 0;
+    while let Some(message) = receive(&mut reader).map_err(|e| e.to_string())? {
+        match message {
+            Message::Task(task) => {
+                received += 1;
+                let runner = Arc::clone(&runner);
+                let writer = Arc::clone(&writer);
+                pool.spawn(move || {
+                    let outcome = registry::PROBLEMS
+                        .iter()
+                        .find_map(|entry| (entry.run_task)(&runner, &task));
+                    let mut writer = writer.lock().unwrap();
+                    let Some((result, time_ms)) = outcome else {
+                        eprintln!("Problème inconnu: {}", task.problem);
+                        let _ = writer.shutdown(net::Shutdown::Both);
+                        return;
+                    };
+                    let message = Message::Result {
+                        task: task.task,
+                        result: Box::new(result),
+                        time_ms,
+                    };
+                    if let Err(e) = send(&mut *writer, &message) {
+                        eprintln!("Envoi du résultat impossible: {}", e);
+                    }
+                });
+            }
+            Message::Shutdown => break,
+            _ => {}
+        }
+    }
+    Ok(received)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarking::AlgorithmChoice;
+    use crate::problems::shortest_path::GridFactory;
+
+    #[test]
+    fn test_tasks_round_trip_through_a_worker() {
+        let config = BenchmarkConfig {
+            algorithm: AlgorithmChoice::Astar,
+            problem: "shortest-path".to_string(),
+            iterations: 3,
+            output_file: std::env::temp_dir()
+                .join("distributed_round_trip.json")
+                .to_string_lossy()
+                .into_owned(),
+            seed: 7,
+            ..BenchmarkConfig::default()
+        };
+        let coordinator = Arc::new(Coordinator::bind("127.0.0.1:0", config.clone()).unwrap());
+        let worker = {
+            let address = coordinator.address().to_string();
+            thread::spawn(move || {
+                run_worker(&WorkerConfig {
+                    address,
+                    threads: 2,
+                    name: Some("test".to_string()),
+                })
+            })
+        };
+
+        let mut runner = BenchmarkRunner::new(config);
+        runner.progress_mut().set_console(false);
+        runner.set_coordinator(Arc::clone(&coordinator));
+        let run = runner.benchmark_problem::<GridFactory>().unwrap();
+        coordinator.shutdown();
+
+        assert_eq!(worker.join().unwrap(), Ok(3));
+        assert_eq!(run.results.len(), 3);
+        assert!(run
+            .results
+            .iter()
+            .all(|result| result.status.is_solved() && result.host.as_deref() == Some("test")));
+        let hosts = coordinator.hosts();
+        assert_eq!((hosts[0].threads, hosts[0].tasks), (2, 3));
+    }
+}
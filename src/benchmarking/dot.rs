@@ -0,0 +1,303 @@
+use super::runner::{AlgorithmSettings, BenchmarkConfig, BenchmarkRunner};
+use crate::algorithms::trace::{SearchTrace, Traced};
+use crate::algorithms::{Problem, SearchStatus};
+use crate::problems::registry::{self, ProblemFactory};
+use crate::utils::seeding;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+pub struct DotConfig {
+    pub instance: usize,
+    pub max_nodes: usize,
+    pub output_dir: String,
+}
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+pub(crate) fn file_stem(name: &str) -> String {
+    name.replace('*', "star")
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+pub fn to_dot<P: Problem>(
+    problem: &P,
+    title: &str,
+    trace: &SearchTrace<P::State>,
+    solution: Option<&[P::Action]>,
+    max_nodes: usize,
+) -> String {
+    let start = problem.initial_state();
+    let mut ids: HashMap<P::State, usize> = HashMap::from([(start.clone(), 0)]);
+    let mut states = vec![start.clone()];
+    let mut g = vec![0];
+    let mut edges = Vec::new();
+    let mut seen_edges = HashSet::new();
+    let mut dropped = HashSet::new();
+
+    for (parent, child, cost) in &trace.edges {
+        let Some(&from) = ids.get(parent) else {
+            continue;
+        };
+        let to = match ids.get(child) {
+            Some(&to) => to,
+            None if states.len() < max_nodes => {
+                ids.insert(child.clone(), states.len());
+                states.push(child.clone());
+                g.push(usize::MAX);
+                states.len() - 1
+            }
+            None => {
+                dropped.insert(child);
+                continue;
+            }
+        };
+        g[to] = g[to].min(g[from] + cost);
+        if seen_edges.insert((from, to)) {
+            edges.push((from, to, *cost));
+        }
+    }
+
+    let mut expansion_order = vec![None; states.len()];
+    let mut expanded = 0;
+    for state in &trace.expansions {
+        if let Some(&id) = ids.get(state) {
+            if expansion_order[id].is_none() {
+                expanded += 1;
+                expansion_order[id] = Some(expanded);
+            }
+        }
+    }
+
+    let mut path_nodes = HashSet::from([0]);
+    let mut path_edges = HashSet::new();
+    let mut state = start;
+    for action in solution.unwrap_or_default() {
+        let (next, _) = problem.apply(&state, action);
+        if let (Some(&from), Some(&to)) = (ids.get(&state), ids.get(&next)) {
+            path_nodes.insert(to);
+            path_edges.insert((from, to));
+        }
+        state = next;
+    }
+    if solution.is_none() {
+        path_nodes.clear();
+    }
+
+    let mut note = format!("{} états", states.len());
+    if !dropped.is_empty() || trace.truncated {
+        let _ = write!(
+            note,
+            ", tronqué à {} nœuds ({} états non affichés)",
+            max_nodes,
+            dropped.len()
+        );
+    }
+
+    let mut dot = String::new();
+    let _ = writeln!(dot, "digraph \"{}\" {{", escape(title));
+    let _ = writeln!(
+        dot,
+        "  label=\"{}\\n{}\"; labelloc=t;",
+        escape(title),
+        escape(&note)
+    );
+    let _ = writeln!(
+        dot,
+        "  node [shape=box, fontname=\"monospace\", fontsize=10];"
+    );
+
+    for (id, state) in states.iter().enumerate() {
+        let h = problem.heuristic(state);
+        let mut label = format!(
+            "{}\\ng={} h={} f={}",
+            escape(&format!("{:?}", state)),
+            g[id],
+            h,
+            g[id] + h
+        );
+        let mut attributes = Vec::new();
+        match expansion_order[id] {
+            Some(order) => {
+                let _ = write!(label, "\\n#{}", order);
+                attributes.push("style=filled, fillcolor=lightblue".to_string());
+            }
+            None => attributes.push("style=dashed".to_string()),
+        }
+        if path_nodes.contains(&id) {
+            attributes.push("color=red, penwidth=2".to_string());
+        }
+        if problem.is_goal(state) {
+            attributes.push("peripheries=2".to_string());
+        }
+        let _ = writeln!(
+            dot,
+            "  n{} [label=\"{}\", {}];",
+            id,
+            label,
+            attributes.join(", ")
+        );
+    }
+
+    for (from, to, cost) in edges {
+        let highlight = if path_edges.contains(&(from, to)) {
+            ", color=red, penwidth=2"
+        } else {
+            ""
+        };
+        let _ = writeln!(
+            dot,
+            "  n{} -> n{} [label=\"{}\"{}];",
+            from, to, cost, highlight
+        );
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+pub fn export_problem<F: ProblemFactory>(
+    config: &BenchmarkConfig,
+    dot: &DotConfig,
+) -> Result<Vec<String>, String> {
+    let label = F::label(config);
+    let seed = seeding::instance_seed(config.seed, &label, config.size, dot.instance);
+    let problem = F::generate(config, seed);
+    let settings = AlgorithmSettings {
+        max_depth: F::max_depth(config),
+        memory_limit_kb: None,
+        track_solutions: true,
+        reexpand: config.reexpand,
+        cost_bound: config.cost_bound,
+        depth_limit: config.depth_limit,
+        perimeter_radius: config.perimeter_radius,
+        memory_budget: config.memory_budget,
+        weight: config.weight,
+        bound: config.bound,
+        lookahead: config.lookahead,
+        step_budget: config.step_budget,
+        k: config.k,
+        population: config.population,
+        generations: config.generations,
+        seed: config.seed,
+        tie_breaking: config.tie_breaking,
+        lazy_duplicates: config.lazy_duplicates,
+        order_successors: config.order_successors,
+        profile: false,
+    };
+    fs::create_dir_all(&dot.output_dir).map_err(|e| format!("{}: {}", dot.output_dir, e))?;
+
+    let mut written = Vec::new();
+    for algo_name in BenchmarkRunner::algorithm_names(config, &F::heuristic_labels(config)) {
+        let traced = Traced::new(&problem).with_max_expansions(dot.max_nodes);
+        let result = BenchmarkRunner::execute_algorithm(&algo_name, &traced, settings, None);
+        let trace = traced.take_trace();
+
+        let title = match &result.status {
+            SearchStatus::Solved => format!(
+                "{} sur {} (instance {}, coût {})",
+                algo_name,
+                label,
+                dot.instance + 1,
+                result.metrics.solution_cost
+            ),
+            status => format!(
+                "{} sur {} (instance {}, {})",
+                algo_name,
+                label,
+                dot.instance + 1,
+                status
+            ),
+        };
+        let graph = to_dot(
+            &problem,
+            &title,
+            &trace,
+            result.solution.as_deref(),
+            dot.max_nodes,
+        );
+
+        let path = Path::new(&dot.output_dir).join(format!(
+            "{}-{}.dot",
+            file_stem(&label),
+            file_stem(&algo_name)
+        ));
+        fs::write(&path, graph).map_err(|e| format!("{}: {}", path.display(), e))?;
+        written.push(path.display().to_string());
+    }
+
+    Ok(written)
+}
+
+pub fn run(config: &BenchmarkConfig, dot: &DotConfig) -> Result<(), Box<dyn Error>> {
+    let entries: Vec<_> = registry::PROBLEMS
+        .iter()
+        .filter(|entry| config.problem == "all" || entry.name == config.problem)
+        .collect();
+    if entries.is_empty() {
+        return Err(format!(
+            "Problème inconnu: {} (disponibles: {})",
+            config.problem,
+            registry::names().join(", ")
+        )
+        .into());
+    }
+
+    for entry in entries {
+        if let Err(e) = (entry.validate)(config) {
+            println!("{} ignoré: {}", entry.title, e);
+            continue;
+        }
+        for path in (entry.export_dot)(config, dot)? {
+            println!("  {}", path);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::astar::AStar;
+    use crate::algorithms::SearchAlgorithm;
+    use crate::problems::ShortestPath;
+
+    #[test]
+    fn test_dot_highlights_solution_and_bounds_nodes() {
+        let mut graph = ShortestPath::new(0, 3);
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(1, 3, 1);
+        graph.add_edge(0, 2, 1);
+        graph.add_edge(2, 3, 5);
+        graph.set_heuristic(0, 2);
+        graph.set_heuristic(1, 1);
+        graph.set_heuristic(2, 4);
+
+        let traced = Traced::new(&graph);
+        let result = AStar::new().search(&traced);
+        let trace = traced.take_trace();
+
+        let dot = to_dot(&graph, "A*", &trace, result.solution.as_deref(), 10);
+        assert!(dot.contains("n0 [label=\"0\\ng=0 h=2 f=2\\n#1\""));
+        assert!(dot.contains("n0 -> n1 [label=\"1\", color=red, penwidth=2];"));
+        assert!(dot.contains("n0 -> n2 [label=\"1\"];"));
+        assert!(dot.contains("peripheries=2"));
+
+        let bounded = to_dot(&graph, "A*", &trace, result.solution.as_deref(), 2);
+        assert!(!bounded.contains("n2 ["));
+        assert!(bounded.contains("tronqué à 2 nœuds"));
+    }
+}
@@ -0,0 +1,245 @@
+use super::consistency;
+use super::options::ShuffleMode;
+use super::runner::{AlgorithmSettings, BenchmarkConfig, BenchmarkRunner};
+use crate::algorithms::{
+    reference, validation, ActionList, HeuristicSuccessors, Problem, Successors,
+};
+use crate::problems::registry::{self, ProblemFactory};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::error::Error;
+
+const ALGORITHMS: &[&str] = &["BFS", "DFS", "ID", "UCS", "A*", "IDA*"];
+const MAX_WALK: usize = 10;
+
+pub struct FuzzConfig {
+    pub cases: usize,
+    pub seed: u64,
+    pub max_size: usize,
+    pub samples: usize,
+}
+
+#[derive(Clone)]
+struct Rooted<'a, P: Problem> {
+    problem: &'a P,
+    root: P::State,
+}
+
+impl<P: Problem> Problem for Rooted<'_, P> {
+    type State = P::State;
+    type Action = P::Action;
+
+    fn initial_state(&self) -> Self::State {
+        self.root.clone()
+    }
+
+    fn is_goal(&self, state: &Self::State) -> bool {
+        self.problem.is_goal(state)
+    }
+
+    fn actions(&self, state: &Self::State) -> ActionList<Self::Action> {
+        self.problem.actions(state)
+    }
+
+    fn apply(&self, state: &Self::State, action: &Self::Action) -> (Self::State, usize) {
+        self.problem.apply(state, action)
+    }
+
+    fn successors_into(&self, state: &Self::State, buffer: &mut Successors<Self>) {
+        self.problem.successors_into(state, buffer)
+    }
+
+    fn successors_with_heuristic_into(
+        &self,
+        state: &Self::State,
+        heuristic: usize,
+        buffer: &mut HeuristicSuccessors<Self>,
+    ) {
+        self.problem
+            .successors_with_heuristic_into(state, heuristic, buffer)
+    }
+
+    fn description(&self) -> String {
+        self.problem.description()
+    }
+
+    fn heuristic(&self, state: &Self::State) -> usize {
+        self.problem.heuristic(state)
+    }
+
+    fn state_heap_bytes(&self, state: &Self::State) -> usize {
+        self.problem.state_heap_bytes(state)
+    }
+}
+
+fn case_config(size: usize, shuffle: ShuffleMode) -> BenchmarkConfig {
+    BenchmarkConfig {
+        size,
+        shuffle,
+        ..BenchmarkConfig::default()
+    }
+}
+
+fn check_instance<F: ProblemFactory>(
+    config: &BenchmarkConfig,
+    problem: &F::Instance,
+    samples: usize,
+    rng: &mut StdRng,
+) -> Result<(), String> {
+    let unit_costs = F::unit_costs(config);
+    let admissible = F::admissible_heuristic(config);
+    let settings = AlgorithmSettings {
+        max_depth: F::max_depth(config),
+        memory_limit_kb: None,
+        track_solutions: true,
+        reexpand: config.reexpand,
+        cost_bound: None,
+        depth_limit: None,
+        perimeter_radius: config.perimeter_radius,
+        memory_budget: config.memory_budget,
+        weight: config.weight,
+        bound: config.bound,
+        lookahead: config.lookahead,
+        step_budget: config.step_budget,
+        k: config.k,
+        population: config.population,
+        generations: config.generations,
+        seed: config.seed,
+        tie_breaking: config.tie_breaking,
+        lazy_duplicates: config.lazy_duplicates,
+        order_successors: config.order_successors,
+        profile: false,
+    };
+    let optimum = reference::optimal_cost(problem, unit_costs);
+
+    for &algo_name in ALGORITHMS {
+        let result = BenchmarkRunner::execute_algorithm(algo_name, problem, settings, None);
+        if !result.status.is_solved() {
+            continue;
+        }
+
+        let actions = result.solution.as_deref().unwrap_or_default();
+        validation::validate_solution(problem, actions, &result.metrics)
+            .map_err(|e| format!("solution invalide de {}: {}", algo_name, e))?;
+
+        let cost = result.metrics.solution_cost;
+        if consistency::is_optimal_algorithm(algo_name, unit_costs, admissible)
+            && Some(cost) != optimum
+        {
+            return Err(format!(
+                "{} trouve un coût {} alors que l'optimum est {}",
+                algo_name,
+                cost,
+                optimum.map_or("introuvable".to_string(), |c| c.to_string())
+            ));
+        }
+    }
+
+    if !admissible {
+        return Ok(());
+    }
+
+    let mut state = problem.initial_state();
+    for _ in 0..samples {
+        for _ in 0..rng.gen_range(0..=MAX_WALK) {
+            let successors = problem.successors(&state);
+            if successors.is_empty() {
+                break;
+            }
+            state = successors[rng.gen_range(0..successors.len())].0.clone();
+        }
+
+        let rooted = Rooted {
+            problem,
+            root: state.clone(),
+        };
+        if let Some(cost) = reference::optimal_cost(&rooted, unit_costs) {
+            let h = problem.heuristic(&state);
+            if h > cost {
+                return Err(format!(
+                    "heuristique non admissible: h = {} pour un coût optimal de {}",
+                    h, cost
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn fuzz_problem<F: ProblemFactory>(config: &FuzzConfig) -> Result<usize, String> {
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let mut checked = 0;
+
+    for case in 0..config.cases {
+        let size = rng.gen_range(2..=config.max_size.max(2));
+        let shuffle = if rng.gen_bool(0.5) {
+            ShuffleMode::Uniform
+        } else {
+            ShuffleMode::Walk
+        };
+        let case_config = case_config(size, shuffle);
+        if F::validate(&case_config).is_err() {
+            continue;
+        }
+
+        let seed = rng.gen();
+        let problem = F::generate(&case_config, seed);
+        check_instance::<F>(&case_config, &problem, config.samples, &mut rng).map_err(|e| {
+            format!(
+                "{} (cas {}, graine {}): {}\nInstance pour reproduction:\n{}",
+                F::label(&case_config),
+                case + 1,
+                seed,
+                e,
+                F::serialize(&problem)
+            )
+        })?;
+        checked += 1;
+    }
+
+    Ok(checked)
+}
+
+pub fn run(problem: &str, config: &FuzzConfig) -> Result<(), Box<dyn Error>> {
+    let entries: Vec<_> = registry::PROBLEMS
+        .iter()
+        .filter(|entry| problem == "all" || entry.name == problem)
+        .collect();
+    if entries.is_empty() {
+        return Err(format!(
+            "Problème inconnu: {} (disponibles: {})",
+            problem,
+            registry::names().join(", ")
+        )
+        .into());
+    }
+
+    for entry in entries {
+        let checked = (entry.fuzz)(config)?;
+        println!("{}: {} cas vérifiés", entry.title, checked);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::problems::shortest_path::{GridFactory, RandomGraphFactory};
+    use crate::problems::taquin::TaquinFactory;
+
+    #[test]
+    fn test_small_instances_pass_all_checks() {
+        let config = FuzzConfig {
+            cases: 4,
+            seed: 7,
+            max_size: 2,
+            samples: 2,
+        };
+
+        assert_eq!(fuzz_problem::<TaquinFactory>(&config), Ok(4));
+        assert_eq!(fuzz_problem::<GridFactory>(&config), Ok(4));
+        assert_eq!(fuzz_problem::<RandomGraphFactory>(&config), Ok(4));
+    }
+}
@@ -0,0 +1,535 @@
+use super::metrics::{BenchmarkResult, Metrics};
+use super::progress::ProgressEvent;
+use super::runner::{BenchmarkConfig, BenchmarkRunner};
+use super::AlgorithmChoice;
+use crate::algorithms::SearchStatus;
+use crate::problems::registry;
+use clap::ValueEnum;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Request, Response};
+
+include!(concat!(env!("OUT_DIR"), "/benchmark.Benchmarking.rs"));
+
+pub use benchmarking_client::BenchmarkingClient;
+use benchmarking_server::{Benchmarking, BenchmarkingServer};
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct SolveRequest {
+    #[prost(string, tag = "1")]
+    pub problem: String,
+    #[prost(string, tag = "2")]
+    pub algorithm: String,
+    #[prost(uint32, optional, tag = "3")]
+    pub size: Option<u32>,
+    #[prost(uint64, optional, tag = "4")]
+    pub seed: Option<u64>,
+    #[prost(uint32, optional, tag = "5")]
+    pub instance: Option<u32>,
+    #[prost(uint64, optional, tag = "6")]
+    pub timeout_secs: Option<u64>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct SolveResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub results: Vec<TaskResult>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct BenchmarkRequest {
+    #[prost(string, tag = "1")]
+    pub problem: String,
+    #[prost(string, tag = "2")]
+    pub algorithm: String,
+    #[prost(uint32, optional, tag = "3")]
+    pub size: Option<u32>,
+    #[prost(uint32, optional, tag = "4")]
+    pub iterations: Option<u32>,
+    #[prost(uint32, optional, tag = "5")]
+    pub threads: Option<u32>,
+    #[prost(uint64, optional, tag = "6")]
+    pub timeout_secs: Option<u64>,
+    #[prost(uint64, optional, tag = "7")]
+    pub memory_limit_mb: Option<u64>,
+    #[prost(uint64, optional, tag = "8")]
+    pub seed: Option<u64>,
+    #[prost(bool, tag = "9")]
+    pub verify_optimal: bool,
+    #[prost(bool, tag = "10")]
+    pub check_consistency: bool,
+    #[prost(bool, tag = "11")]
+    pub no_solutions: bool,
+    #[prost(bool, tag = "12")]
+    pub profile: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum Status {
+    Solved = 0,
+    Timeout = 1,
+    NoSolution = 2,
+    DepthLimitReached = 3,
+    MemoryExceeded = 4,
+    Error = 5,
+    Skipped = 6,
+    NegativeCycle = 7,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct SearchMetrics {
+    #[prost(double, tag = "1")]
+    pub time_ms: f64,
+    #[prost(uint64, tag = "2")]
+    pub memory_kb: u64,
+    #[prost(uint64, tag = "3")]
+    pub nodes_visited: u64,
+    #[prost(uint64, tag = "4")]
+    pub nodes_generated: u64,
+    #[prost(uint64, tag = "5")]
+    pub max_frontier_size: u64,
+    #[prost(uint64, tag = "6")]
+    pub solution_length: u64,
+    #[prost(uint64, tag = "7")]
+    pub solution_cost: u64,
+    #[prost(uint64, tag = "8")]
+    pub evictions: u64,
+    #[prost(uint64, tag = "9")]
+    pub reexpansions: u64,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct TaskResult {
+    #[prost(string, tag = "1")]
+    pub algorithm: String,
+    #[prost(string, tag = "2")]
+    pub problem: String,
+    #[prost(uint64, tag = "3")]
+    pub problem_size: u64,
+    #[prost(uint64, tag = "4")]
+    pub instance_id: u64,
+    #[prost(uint64, tag = "5")]
+    pub seed: u64,
+    #[prost(enumeration = "Status", tag = "6")]
+    pub status: i32,
+    #[prost(message, optional, tag = "7")]
+    pub metrics: Option<SearchMetrics>,
+    #[prost(string, repeated, tag = "8")]
+    pub solution: Vec<String>,
+    #[prost(string, optional, tag = "9")]
+    pub error: Option<String>,
+    #[prost(string, optional, tag = "10")]
+    pub initial_state: Option<String>,
+    #[prost(bool, optional, tag = "11")]
+    pub optimal: Option<bool>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ProblemStarted {
+    #[prost(string, tag = "1")]
+    pub problem: String,
+    #[prost(uint64, tag = "2")]
+    pub tasks: u64,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct TaskStarted {
+    #[prost(uint64, tag = "1")]
+    pub task: u64,
+    #[prost(string, tag = "2")]
+    pub algorithm: String,
+    #[prost(uint64, tag = "3")]
+    pub instance: u64,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct TaskFinished {
+    #[prost(uint64, tag = "1")]
+    pub task: u64,
+    #[prost(message, optional, tag = "2")]
+    pub result: Option<TaskResult>,
+    #[prost(double, tag = "3")]
+    pub time_ms: f64,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Finished {
+    #[prost(string, tag = "1")]
+    pub output_file: String,
+    #[prost(string, optional, tag = "2")]
+    pub error: Option<String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct BenchmarkUpdate {
+    #[prost(oneof = "benchmark_update::Event", tags = "1, 2, 3, 4, 5")]
+    pub event: Option<benchmark_update::Event>,
+}
+
+pub mod benchmark_update {
+    #[derive(Clone, PartialEq, prost::Oneof)]
+    pub enum Event {
+        #[prost(message, tag = "1")]
+        ProblemStarted(super::ProblemStarted),
+        #[prost(message, tag = "2")]
+        TaskStarted(super::TaskStarted),
+        #[prost(message, tag = "3")]
+        TaskFinished(super::TaskFinished),
+        #[prost(string, tag = "4")]
+        Log(String),
+        #[prost(message, tag = "5")]
+        Finished(super::Finished),
+    }
+}
+
+impl From<&Metrics> for SearchMetrics {
+    fn from(metrics: &Metrics) -> Self {
+        SearchMetrics {
+            time_ms: metrics.time_ms,
+            memory_kb: metrics.memory_kb as u64,
+            nodes_visited: metrics.nodes_visited as u64,
+            nodes_generated: metrics.nodes_generated as u64,
+            max_frontier_size: metrics.max_frontier_size as u64,
+            solution_length: metrics.solution_length as u64,
+            solution_cost: metrics.solution_cost as u64,
+            evictions: metrics.evictions as u64,
+            reexpansions: metrics.reexpansions as u64,
+        }
+    }
+}
+
+impl From<&BenchmarkResult> for TaskResult {
+    fn from(result: &BenchmarkResult) -> Self {
+        let status = match result.status {
+            SearchStatus::Solved => Status::Solved,
+            SearchStatus::Timeout => Status::Timeout,
+            SearchStatus::NoSolution => Status::NoSolution,
+            SearchStatus::DepthLimitReached => Status::DepthLimitReached,
+            SearchStatus::MemoryExceeded => Status::MemoryExceeded,
+            SearchStatus::NegativeCycle => Status::NegativeCycle,
+            SearchStatus::Skipped => Status::Skipped,
+            SearchStatus::Error(_) => Status::Error,
+        };
+        TaskResult {
+            algorithm: result.algorithm.clone(),
+            problem: result.problem.clone(),
+            problem_size: result.problem_size as u64,
+            instance_id: result.instance_id as u64,
+            seed: result.seed,
+            status: status as i32,
+            metrics: Some(SearchMetrics::from(&result.metrics)),
+            solution: result.solution.clone().unwrap_or_default(),
+            error: result.error.clone(),
+            initial_state: result.initial_state.clone(),
+            optimal: result.optimal,
+        }
+    }
+}
+
+fn update(event: ProgressEvent) -> BenchmarkUpdate {
+    use benchmark_update::Event;
+
+    let event = match event {
+        ProgressEvent::ProblemStarted { problem, tasks } => Event::ProblemStarted(ProblemStarted {
+            problem,
+            tasks: tasks as u64,
+        }),
+        ProgressEvent::TaskStarted {
+            task,
+            algorithm,
+            instance,
+            ..
+        } => Event::TaskStarted(TaskStarted {
+            task: task as u64,
+            algorithm,
+            instance: instance as u64,
+        }),
+        ProgressEvent::TaskFinished {
+            task,
+            result,
+            time_ms,
+        } => Event::TaskFinished(TaskFinished {
+            task: task as u64,
+            result: Some(TaskResult::from(result.as_ref())),
+            time_ms,
+        }),
+        ProgressEvent::Log(line) => Event::Log(line),
+    };
+    BenchmarkUpdate { event: Some(event) }
+}
+
+fn algorithm(name: &str) -> Result<AlgorithmChoice, tonic::Status> {
+    if name.is_empty() {
+        return Ok(AlgorithmChoice::All);
+    }
+    AlgorithmChoice::from_str(name, true)
+        .map_err(|_| tonic::Status::invalid_argument(format!("Algorithme inconnu: {}", name)))
+}
+
+pub struct GrpcConfig {
+    pub address: String,
+    pub output_dir: String,
+    pub max_threads: usize,
+}
+
+pub struct BenchmarkingService {
+    output_dir: PathBuf,
+    max_threads: usize,
+    runs: AtomicUsize,
+}
+
+impl BenchmarkingService {
+    pub fn new(config: &GrpcConfig) -> Self {
+        BenchmarkingService {
+            output_dir: PathBuf::from(&config.output_dir),
+            max_threads: config.max_threads.max(1),
+            runs: AtomicUsize::new(0),
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl Benchmarking for BenchmarkingService {
+    async fn solve(
+        &self,
+        request: Request<SolveRequest>,
+    ) -> Result<Response<SolveResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let defaults = BenchmarkConfig::default();
+        let config = BenchmarkConfig {
+            algorithm: algorithm(&request.algorithm)?,
+            problem: request.problem,
+            size: request.size.map_or(defaults.size, |size| size as usize),
+            timeout_secs: request.timeout_secs.unwrap_or(defaults.timeout_secs),
+            seed: request.seed.unwrap_or(defaults.seed),
+            ..defaults
+        };
+        let entry = registry::find(&config.problem).ok_or_else(|| {
+            tonic::Status::invalid_argument(format!(
+                "Problème inconnu: {} (disponibles: {})",
+                config.problem,
+                registry::names().join(", ")
+            ))
+        })?;
+        config.validate().map_err(tonic::Status::invalid_argument)?;
+        let instance = request.instance.unwrap_or(1).saturating_sub(1) as usize;
+
+        let results = tokio::task::spawn_blocking(move || {
+            let mut runner = BenchmarkRunner::new(config);
+            runner.progress_mut().set_console(false);
+            (entry.solve)(&runner, instance)
+        })
+        .await
+        .map_err(|e| tonic::Status::internal(e.to_string()))?;
+
+        Ok(Response::new(SolveResponse {
+            results: results.iter().map(TaskResult::from).collect(),
+        }))
+    }
+
+    type BenchmarkStream = ReceiverStream<Result<BenchmarkUpdate, tonic::Status>>;
+
+    async fn benchmark(
+        &self,
+        request: Request<BenchmarkRequest>,
+    ) -> Result<Response<Self::BenchmarkStream>, tonic::Status> {
+        let request = request.into_inner();
+        let run = self.runs.fetch_add(1, Ordering::Relaxed) + 1;
+        let defaults = BenchmarkConfig::default();
+        let config = BenchmarkConfig {
+            algorithm: algorithm(&request.algorithm)?,
+            problem: if request.problem.is_empty() {
+                defaults.problem.clone()
+            } else {
+                request.problem
+            },
+            size: request.size.map_or(defaults.size, |size| size as usize),
+            iterations: request
+                .iterations
+                .map_or(defaults.iterations, |iterations| iterations as usize),
+            output_file: self
+                .output_dir
+                .join(format!("run-{}.json", run))
+                .to_string_lossy()
+                .into_owned(),
+            threads: request
+                .threads
+                .map_or(1, |threads| threads as usize)
+                .clamp(1, self.max_threads),
+            timeout_secs: request.timeout_secs.unwrap_or(defaults.timeout_secs),
+            memory_limit_mb: request
+                .memory_limit_mb
+                .map_or(defaults.memory_limit_mb, |limit| limit as usize),
+            track_solutions: !request.no_solutions,
+            verify_optimal: request.verify_optimal,
+            check_consistency: request.check_consistency,
+            seed: request.seed.unwrap_or(defaults.seed),
+            profile: request.profile,
+            ..defaults
+        };
+        config.validate().map_err(tonic::Status::invalid_argument)?;
+        fs::create_dir_all(&self.output_dir).map_err(|e| tonic::Status::internal(e.to_string()))?;
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(config.threads)
+            .build()
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+
+        let (sender, receiver) = mpsc::channel(64);
+        thread::spawn(move || {
+            let output_file = config.output_file.clone();
+            let mut runner = BenchmarkRunner::new(config);
+            runner.progress_mut().set_console(false);
+            let events = runner.progress_mut().subscribe();
+            let forward = {
+                let sender = sender.clone();
+                thread::spawn(move || {
+                    for event in events {
+                        if sender.blocking_send(Ok(update(event))).is_err() {
+                            break;
+                        }
+                    }
+                })
+            };
+
+            let outcome = pool.install(move || runner.run().map_err(|e| e.to_string()));
+            let _ = forward.join();
+            let finished = Finished {
+                output_file,
+                error: outcome.err(),
+            };
+            let _ = sender.blocking_send(Ok(BenchmarkUpdate {
+                event: Some(benchmark_update::Event::Finished(finished)),
+            }));
+        });
+
+        Ok(Response::new(ReceiverStream::new(receiver)))
+    }
+}
+
+pub fn run(config: &GrpcConfig) -> Result<(), Box<dyn Error>> {
+    let address = config
+        .address
+        .parse()
+        .map_err(|e| format!("Adresse invalide {}: {}", config.address, e))?;
+    let service = BenchmarkingService::new(config);
+
+    println!("Service gRPC benchmark.Benchmarking: {}", address);
+    println!("  Définition: proto/benchmark.proto");
+    println!("  Résultats dans {}", config.output_dir);
+    println!("  Threads max par benchmark: {}", service.max_threads);
+    println!("Ctrl-C pour arrêter");
+
+    tokio::runtime::Runtime::new()?.block_on(async {
+        tonic::transport::Server::builder()
+            .add_service(BenchmarkingServer::new(service))
+            .serve(address)
+            .await
+    })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio_stream::StreamExt;
+
+    #[tokio::test]
+    async fn test_solve_and_benchmark_stream() {
+        let output_dir = std::env::temp_dir().join("benchmark_grpc_test");
+        let service = BenchmarkingService::new(&GrpcConfig {
+            address: String::new(),
+            output_dir: output_dir.to_string_lossy().into_owned(),
+            max_threads: 2,
+        });
+
+        let solved = service
+            .solve(Request::new(SolveRequest {
+                problem: "shortest-path".to_string(),
+                algorithm: "all".to_string(),
+                seed: Some(3),
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(solved.results.len() > 1);
+        assert!(solved
+            .results
+            .iter()
+            .all(|result| result.status() == Status::Solved));
+
+        let unknown = service
+            .solve(Request::new(SolveRequest {
+                problem: "nope".to_string(),
+                ..Default::default()
+            }))
+            .await;
+        assert_eq!(unknown.unwrap_err().code(), tonic::Code::InvalidArgument);
+
+        let updates: Vec<BenchmarkUpdate> = service
+            .benchmark(Request::new(BenchmarkRequest {
+                problem: "shortest-path".to_string(),
+                algorithm: "astar".to_string(),
+                iterations: Some(2),
+                seed: Some(3),
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        let finished = updates
+            .iter()
+            .filter(|update| matches!(update.event, Some(benchmark_update::Event::TaskFinished(_))))
+            .count();
+        assert_eq!(finished, 2);
+        let Some(benchmark_update::Event::Finished(last)) =
+            updates.last().and_then(|update| update.event.clone())
+        else {
+            panic!("message Finished attendu en dernier");
+        };
+        assert_eq!(last.error, None);
+        let _ = fs::remove_dir_all(output_dir);
+    }
+
+    #[tokio::test]
+    async fn test_client_reaches_server_over_transport() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let service = BenchmarkingService::new(&GrpcConfig {
+            address: address.to_string(),
+            output_dir: std::env::temp_dir().to_string_lossy().into_owned(),
+            max_threads: 1,
+        });
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(BenchmarkingServer::new(service))
+                .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener)),
+        );
+
+        let mut client = BenchmarkingClient::connect(format!("http://{}", address))
+            .await
+            .unwrap();
+        let response = client
+            .solve(SolveRequest {
+                problem: "taquin".to_string(),
+                algorithm: "astar".to_string(),
+                seed: Some(1),
+                ..Default::default()
+            })
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(response.results.len(), 1);
+        assert_eq!(response.results[0].algorithm, "A*-Manhattan");
+        assert_eq!(response.results[0].status(), Status::Solved);
+    }
+}
@@ -0,0 +1,139 @@
+use super::options::HeatmapMode;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+const TARGET_PIXELS: usize = 512;
+const UNVISITED: [u8; 3] = [235, 235, 235];
+const OBSTACLE: [u8; 3] = [20, 20, 20];
+const PATH: [u8; 3] = [255, 255, 255];
+const START: [u8; 3] = [40, 200, 80];
+const GOAL: [u8; 3] = [230, 40, 40];
+const RAMP: [[f64; 3]; 5] = [
+    [253.0, 231.0, 37.0],
+    [94.0, 201.0, 98.0],
+    [33.0, 145.0, 140.0],
+    [59.0, 82.0, 139.0],
+    [68.0, 1.0, 84.0],
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cell {
+    Unvisited,
+    Obstacle,
+    Heat(usize),
+    Path,
+    Start,
+    Goal,
+}
+
+pub struct Heatmap {
+    pub width: usize,
+    pub height: usize,
+    pub cells: Vec<Cell>,
+}
+
+impl Heatmap {
+    pub fn new(width: usize, height: usize) -> Self {
+        Heatmap {
+            width,
+            height,
+            cells: vec![Cell::Unvisited; width * height],
+        }
+    }
+
+    pub fn record_expansions(&mut self, expansions: &[usize], mode: HeatmapMode) {
+        for (order, &cell) in expansions.iter().enumerate() {
+            let Some(slot) = self.cells.get_mut(cell) else {
+                continue;
+            };
+            *slot = match (mode, *slot) {
+                (HeatmapMode::Order, Cell::Heat(first)) => Cell::Heat(first),
+                (HeatmapMode::Order, _) => Cell::Heat(order + 1),
+                (HeatmapMode::Density, Cell::Heat(count)) => Cell::Heat(count + 1),
+                (HeatmapMode::Density, _) => Cell::Heat(1),
+            };
+        }
+    }
+
+    pub fn colors(&self) -> Vec<[u8; 3]> {
+        let max = self
+            .cells
+            .iter()
+            .filter_map(|cell| match cell {
+                Cell::Heat(value) => Some(*value),
+                _ => None,
+            })
+            .max()
+            .unwrap_or(1);
+        self.cells
+            .iter()
+            .map(|cell| match *cell {
+                Cell::Unvisited => UNVISITED,
+                Cell::Obstacle => OBSTACLE,
+                Cell::Path => PATH,
+                Cell::Start => START,
+                Cell::Goal => GOAL,
+                Cell::Heat(value) if max > 1 => ramp((value - 1) as f64 / (max - 1) as f64),
+                Cell::Heat(_) => ramp(0.0),
+            })
+            .collect()
+    }
+
+    pub fn write_png(&self, path: &Path) -> Result<(), String> {
+        let scale = (TARGET_PIXELS / self.width.max(self.height).max(1)).max(1);
+        let (width, height) = (self.width * scale, self.height * scale);
+        let colors = self.colors();
+
+        let mut pixels = Vec::with_capacity(width * height * 3);
+        for y in 0..height {
+            for x in 0..width {
+                pixels.extend_from_slice(&colors[(y / scale) * self.width + x / scale]);
+            }
+        }
+
+        let file = File::create(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let mut encoder = png::Encoder::new(BufWriter::new(file), width as u32, height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&pixels))
+            .map_err(|e| format!("{}: {}", path.display(), e))
+    }
+}
+
+fn ramp(t: f64) -> [u8; 3] {
+    let position = t.clamp(0.0, 1.0) * (RAMP.len() - 1) as f64;
+    let index = (position as usize).min(RAMP.len() - 2);
+    let local = position - index as f64;
+    let (from, to) = (RAMP[index], RAMP[index + 1]);
+    [0, 1, 2].map(|channel| (from[channel] + (to[channel] - from[channel]) * local).round() as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_density_counts_repeated_expansions() {
+        let mut order = Heatmap::new(3, 1);
+        order.record_expansions(&[0, 1, 0, 0], HeatmapMode::Order);
+        assert_eq!(
+            order.cells,
+            vec![Cell::Heat(1), Cell::Heat(2), Cell::Unvisited]
+        );
+
+        let mut density = Heatmap::new(3, 1);
+        density.record_expansions(&[0, 1, 0, 0], HeatmapMode::Density);
+        assert_eq!(
+            density.cells,
+            vec![Cell::Heat(3), Cell::Heat(1), Cell::Unvisited]
+        );
+        let colors = density.colors();
+        assert_eq!(
+            (colors[0], colors[1], colors[2]),
+            (ramp(1.0), ramp(0.0), UNVISITED)
+        );
+    }
+}
@@ -0,0 +1,279 @@
+use super::runner::BenchmarkConfig;
+use crate::algorithms::distances;
+use crate::algorithms::{BackwardProblem, Problem};
+use crate::problems::registry::{self, ProblemFactory};
+use crate::utils::seeding;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::error::Error;
+
+const MAX_EXAMPLES: usize = 3;
+const WALK_RESTART: usize = 50;
+
+pub struct HeuristicCheckConfig {
+    pub instances: usize,
+    pub samples: usize,
+    pub max_states: usize,
+}
+
+pub struct Overestimate {
+    pub state: String,
+    pub heuristic: usize,
+    pub distance: usize,
+}
+
+pub struct Inconsistency {
+    pub from: String,
+    pub to: String,
+    pub cost: usize,
+    pub heuristic: usize,
+    pub successor_heuristic: usize,
+}
+
+#[derive(Default)]
+pub struct HeuristicReport {
+    pub problem: String,
+    pub sampled: usize,
+    pub overestimates: usize,
+    pub max_excess: usize,
+    pub examples: Vec<Overestimate>,
+    pub edges: usize,
+    pub inconsistencies: usize,
+    pub max_gap: usize,
+    pub inconsistent_edges: Vec<Inconsistency>,
+}
+
+fn rate(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        100.0 * count as f64 / total as f64
+    }
+}
+
+impl HeuristicReport {
+    pub fn print(&self) {
+        println!("{}:", self.problem);
+
+        if self.overestimates == 0 {
+            println!(
+                "  Admissibilité: {} états échantillonnés, aucune surestimation",
+                self.sampled
+            );
+        } else {
+            println!(
+                "  Admissibilité: {} états échantillonnés, {} surestimations ({:.1}%), excès max {}",
+                self.sampled,
+                self.overestimates,
+                rate(self.overestimates, self.sampled),
+                self.max_excess
+            );
+        }
+        for example in &self.examples {
+            println!(
+                "    état {}: h = {}, distance réelle = {}",
+                example.state, example.heuristic, example.distance
+            );
+        }
+
+        if self.inconsistencies == 0 {
+            println!(
+                "  Cohérence: {} arêtes échantillonnées, aucune violation",
+                self.edges
+            );
+        } else {
+            println!(
+                "  Cohérence: {} arêtes échantillonnées, {} violations ({:.1}%), écart max {}",
+                self.edges,
+                self.inconsistencies,
+                rate(self.inconsistencies, self.edges),
+                self.max_gap
+            );
+        }
+        for edge in &self.inconsistent_edges {
+            println!(
+                "    {} -> {} (coût {}): h = {} > {} + {}",
+                edge.from, edge.to, edge.cost, edge.heuristic, edge.cost, edge.successor_heuristic
+            );
+        }
+    }
+}
+
+pub fn check_admissibility<P: BackwardProblem>(
+    problem: &P,
+    check: &HeuristicCheckConfig,
+    rng: &mut StdRng,
+    report: &mut HeuristicReport,
+) {
+    let settled = distances::goal_distances(problem, check.max_states);
+    if settled.is_empty() {
+        return;
+    }
+
+    let samples: Vec<&(P::State, usize)> = if settled.len() <= check.samples {
+        settled.iter().collect()
+    } else {
+        (0..check.samples)
+            .map(|_| &settled[rng.gen_range(0..settled.len())])
+            .collect()
+    };
+
+    for (state, distance) in samples {
+        report.sampled += 1;
+        let heuristic = problem.heuristic(state);
+        if heuristic <= *distance {
+            continue;
+        }
+
+        report.overestimates += 1;
+        report.max_excess = report.max_excess.max(heuristic - distance);
+        if report.examples.len() < MAX_EXAMPLES {
+            report.examples.push(Overestimate {
+                state: format!("{:?}", state),
+                heuristic,
+                distance: *distance,
+            });
+        }
+    }
+}
+
+pub fn check_consistency<P: Problem>(
+    problem: &P,
+    check: &HeuristicCheckConfig,
+    rng: &mut StdRng,
+    report: &mut HeuristicReport,
+) {
+    let mut state = problem.initial_state();
+    for step in 0..check.samples {
+        if step % WALK_RESTART == 0 {
+            state = problem.initial_state();
+        }
+        let successors = problem.successors(&state);
+        if successors.is_empty() {
+            state = problem.initial_state();
+            continue;
+        }
+
+        let (next, cost) = successors[rng.gen_range(0..successors.len())].clone();
+        let heuristic = problem.heuristic(&state);
+        let successor_heuristic = problem.heuristic(&next);
+        report.edges += 1;
+
+        if heuristic > cost + successor_heuristic {
+            report.inconsistencies += 1;
+            report.max_gap = report.max_gap.max(heuristic - cost - successor_heuristic);
+            let (from, to) = (format!("{:?}", state), format!("{:?}", next));
+            let already_reported = report
+                .inconsistent_edges
+                .iter()
+                .any(|edge| edge.from == from && edge.to == to);
+            if report.inconsistent_edges.len() < MAX_EXAMPLES && !already_reported {
+                report.inconsistent_edges.push(Inconsistency {
+                    from,
+                    to,
+                    cost,
+                    heuristic,
+                    successor_heuristic,
+                });
+            }
+        }
+        state = next;
+    }
+}
+
+pub fn check_problem<F>(config: &BenchmarkConfig, check: &HeuristicCheckConfig) -> HeuristicReport
+where
+    F: ProblemFactory,
+    F::Instance: BackwardProblem,
+{
+    let label = F::label(config);
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let mut report = HeuristicReport {
+        problem: label.clone(),
+        ..HeuristicReport::default()
+    };
+
+    for instance_id in 0..check.instances {
+        let seed = seeding::instance_seed(config.seed, &label, config.size, instance_id);
+        let problem = F::generate(config, seed);
+        check_admissibility(&problem, check, &mut rng, &mut report);
+        check_consistency(&problem, check, &mut rng, &mut report);
+    }
+
+    report
+}
+
+pub fn run(config: &BenchmarkConfig, check: &HeuristicCheckConfig) -> Result<(), Box<dyn Error>> {
+    let entries: Vec<_> = registry::PROBLEMS
+        .iter()
+        .filter(|entry| config.problem == "all" || entry.name == config.problem)
+        .collect();
+    if entries.is_empty() {
+        return Err(format!(
+            "Problème inconnu: {} (disponibles: {})",
+            config.problem,
+            registry::names().join(", ")
+        )
+        .into());
+    }
+
+    for entry in entries {
+        if let Err(e) = (entry.validate)(config) {
+            println!("{} ignoré: {}", entry.title, e);
+            continue;
+        }
+        (entry.check_heuristic)(config, check).print();
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::problems::ShortestPath;
+
+    #[test]
+    fn test_reports_overestimating_states() {
+        let mut graph = ShortestPath::new(0, 2);
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(1, 2, 1);
+        graph.set_heuristic(0, 2);
+        graph.set_heuristic(1, 4);
+
+        let check = HeuristicCheckConfig {
+            instances: 1,
+            samples: 10,
+            max_states: 10,
+        };
+        let mut report = HeuristicReport::default();
+        check_admissibility(&graph, &check, &mut StdRng::seed_from_u64(0), &mut report);
+
+        assert_eq!(report.sampled, 3);
+        assert_eq!(report.overestimates, 1);
+        assert_eq!(report.max_excess, 3);
+        assert_eq!(report.examples[0].state, "1");
+    }
+
+    #[test]
+    fn test_counts_inconsistent_edges() {
+        let mut graph = ShortestPath::new(0, 2);
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(1, 2, 1);
+        graph.set_heuristic(0, 5);
+        graph.set_heuristic(1, 1);
+
+        let check = HeuristicCheckConfig {
+            instances: 1,
+            samples: 2,
+            max_states: 10,
+        };
+        let mut report = HeuristicReport::default();
+        check_consistency(&graph, &check, &mut StdRng::seed_from_u64(0), &mut report);
+
+        assert_eq!(report.edges, 2);
+        assert_eq!(report.inconsistencies, 1);
+        assert_eq!(report.max_gap, 3);
+        assert_eq!(report.inconsistent_edges[0].to, "1");
+    }
+}
@@ -0,0 +1,373 @@
+use super::metrics::{BenchmarkResult, InstanceProperties, Metrics};
+use super::options::ImportFormat;
+use crate::algorithms::SearchStatus;
+use serde::Deserialize;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub struct ImportOptions {
+    pub problem: String,
+    pub size: usize,
+    pub label: Option<String>,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        ImportOptions {
+            problem: "externe".to_string(),
+            size: 0,
+            label: None,
+        }
+    }
+}
+
+pub struct ImportConfig {
+    pub sources: Vec<String>,
+    pub format: ImportFormat,
+    pub options: ImportOptions,
+    pub output: String,
+}
+
+#[derive(Deserialize)]
+struct HyperfineReport {
+    results: Vec<HyperfineRun>,
+}
+
+#[derive(Deserialize)]
+struct HyperfineRun {
+    command: String,
+    mean: f64,
+    #[serde(default)]
+    times: Vec<f64>,
+    #[serde(default)]
+    exit_codes: Vec<Option<i32>>,
+}
+
+#[derive(Deserialize)]
+struct CriterionBenchmark {
+    full_id: String,
+}
+
+#[derive(Deserialize)]
+struct CriterionSample {
+    iters: Vec<f64>,
+    times: Vec<f64>,
+}
+
+#[derive(Deserialize)]
+struct CriterionEstimate {
+    estimate: f64,
+    unit: String,
+}
+
+#[derive(Deserialize)]
+struct CriterionMessage {
+    reason: String,
+    #[serde(default)]
+    id: String,
+    typical: Option<CriterionEstimate>,
+    #[serde(default)]
+    iteration_count: Vec<f64>,
+    #[serde(default)]
+    measured_values: Vec<f64>,
+    #[serde(default)]
+    unit: Option<String>,
+}
+
+fn external_result(
+    options: &ImportOptions,
+    name: &str,
+    instance_id: usize,
+    time_ms: f64,
+    status: SearchStatus,
+    source: &str,
+) -> BenchmarkResult {
+    let mut properties = InstanceProperties::new();
+    properties.insert("source".to_string(), source.into());
+    BenchmarkResult {
+        algorithm: options.label.clone().unwrap_or_else(|| name.to_string()),
+        problem: options.problem.clone(),
+        problem_size: options.size,
+        instance_id,
+        seed: 0,
+        error: match &status {
+            SearchStatus::Solved => None,
+            status => Some(status.to_string()),
+        },
+        status,
+        metrics: Metrics {
+            time_ms,
+            ..Metrics::default()
+        },
+        timestamp: chrono::Local::now().to_rfc3339(),
+        hasher: String::new(),
+        initial_state: None,
+        solution: None,
+        optimal: None,
+        cost_optimal: None,
+        properties,
+        profile: None,
+        improvements: Vec::new(),
+        host: None,
+    }
+}
+
+fn to_ms(value: f64, unit: &str) -> Result<f64, String> {
+    match unit {
+        "ns" => Ok(value / 1e6),
+        "us" | "µs" => Ok(value / 1e3),
+        "ms" => Ok(value),
+        "s" => Ok(value * 1e3),
+        other => Err(format!("unité Criterion inconnue: {}", other)),
+    }
+}
+
+pub fn parse_hyperfine(
+    json: &str,
+    options: &ImportOptions,
+) -> Result<Vec<BenchmarkResult>, String> {
+    let report: HyperfineReport = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    let mut results = Vec::new();
+    for run in report.results {
+        let times = if run.times.is_empty() {
+            vec![run.mean]
+        } else {
+            run.times
+        };
+        for (instance_id, seconds) in times.into_iter().enumerate() {
+            let status = match run.exit_codes.get(instance_id).copied().flatten() {
+                None | Some(0) => SearchStatus::Solved,
+                Some(code) => SearchStatus::Error(format!("code de sortie {}", code)),
+            };
+            results.push(external_result(
+                options,
+                &run.command,
+                instance_id,
+                seconds * 1e3,
+                status,
+                "hyperfine",
+            ));
+        }
+    }
+    Ok(results)
+}
+
+pub fn parse_criterion_messages(
+    lines: &str,
+    options: &ImportOptions,
+) -> Result<Vec<BenchmarkResult>, String> {
+    let mut results = Vec::new();
+    for line in lines.lines().filter(|line| !line.trim().is_empty()) {
+        let message: CriterionMessage = serde_json::from_str(line).map_err(|e| e.to_string())?;
+        if message.reason != "benchmark-complete" {
+            continue;
+        }
+        let samples: Vec<f64> = match &message.unit {
+            Some(unit) if !message.measured_values.is_empty() => message
+                .measured_values
+                .iter()
+                .zip(&message.iteration_count)
+                .map(|(total, iters)| to_ms(total / iters, unit))
+                .collect::<Result<_, _>>()?,
+            _ => match &message.typical {
+                Some(typical) => vec![to_ms(typical.estimate, &typical.unit)?],
+                None => continue,
+            },
+        };
+        results.extend(
+            samples
+                .into_iter()
+                .enumerate()
+                .map(|(instance_id, time_ms)| {
+                    external_result(
+                        options,
+                        &message.id,
+                        instance_id,
+                        time_ms,
+                        SearchStatus::Solved,
+                        "criterion",
+                    )
+                }),
+        );
+    }
+    Ok(results)
+}
+
+fn criterion_benchmarks(directory: &Path, found: &mut Vec<PathBuf>) {
+    let mut entries: Vec<PathBuf> = fs::read_dir(directory)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    entries.sort();
+    for entry in entries {
+        if entry.file_name().is_some_and(|name| name == "new") {
+            if entry.join("benchmark.json").is_file() {
+                found.push(entry);
+            }
+        } else {
+            criterion_benchmarks(&entry, found);
+        }
+    }
+}
+
+pub fn parse_criterion_directory(
+    directory: &Path,
+    options: &ImportOptions,
+) -> Result<Vec<BenchmarkResult>, String> {
+    let read =
+        |path: PathBuf| fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e));
+    let mut found = Vec::new();
+    criterion_benchmarks(directory, &mut found);
+    if found.is_empty() {
+        return Err(format!(
+            "{}: aucun benchmark Criterion (*/new/benchmark.json)",
+            directory.display()
+        ));
+    }
+
+    let mut results = Vec::new();
+    for run in found {
+        let benchmark: CriterionBenchmark =
+            serde_json::from_str(&read(run.join("benchmark.json"))?).map_err(|e| e.to_string())?;
+        let samples: Vec<f64> = match read(run.join("sample.json")) {
+            Ok(json) => {
+                let sample: CriterionSample =
+                    serde_json::from_str(&json).map_err(|e| e.to_string())?;
+                sample
+                    .times
+                    .iter()
+                    .zip(&sample.iters)
+                    .map(|(total, iters)| total / iters / 1e6)
+                    .collect()
+            }
+            Err(_) => {
+                let estimates: Value = serde_json::from_str(&read(run.join("estimates.json"))?)
+                    .map_err(|e| e.to_string())?;
+                let mean = estimates["mean"]["point_estimate"]
+                    .as_f64()
+                    .ok_or_else(|| format!("{}: estimation moyenne absente", run.display()))?;
+                vec![mean / 1e6]
+            }
+        };
+        results.extend(
+            samples
+                .into_iter()
+                .enumerate()
+                .map(|(instance_id, time_ms)| {
+                    external_result(
+                        options,
+                        &benchmark.full_id,
+                        instance_id,
+                        time_ms,
+                        SearchStatus::Solved,
+                        "criterion",
+                    )
+                }),
+        );
+    }
+    Ok(results)
+}
+
+fn detect(path: &Path, contents: &str) -> ImportFormat {
+    let trimmed = contents.trim_start();
+    if path.is_dir() {
+        ImportFormat::Criterion
+    } else if trimmed.starts_with('[') {
+        ImportFormat::Native
+    } else if trimmed
+        .lines()
+        .next()
+        .is_some_and(|line| line.contains("\"reason\""))
+    {
+        ImportFormat::Criterion
+    } else {
+        ImportFormat::Hyperfine
+    }
+}
+
+pub fn load(
+    source: &str,
+    format: ImportFormat,
+    options: &ImportOptions,
+) -> Result<Vec<BenchmarkResult>, String> {
+    let path = Path::new(source);
+    let contents = if path.is_dir() {
+        String::new()
+    } else {
+        fs::read_to_string(path).map_err(|e| format!("{}: {}", source, e))?
+    };
+    let format = match format {
+        ImportFormat::Auto => detect(path, &contents),
+        format => format,
+    };
+    match format {
+        ImportFormat::Criterion if path.is_dir() => parse_criterion_directory(path, options),
+        ImportFormat::Criterion => parse_criterion_messages(&contents, options),
+        ImportFormat::Hyperfine => parse_hyperfine(&contents, options),
+        _ => serde_json::from_str(&contents).map_err(|e| e.to_string()),
+    }
+    .map_err(|e| format!("{} ({}): {}", source, format, e))
+}
+
+pub fn run(config: &ImportConfig) -> Result<usize, String> {
+    let mut results = Vec::new();
+    for source in &config.sources {
+        let imported = load(source, config.format, &config.options)?;
+        println!("  {}: {} résultats", source, imported.len());
+        results.extend(imported);
+    }
+    let json = serde_json::to_string_pretty(&results).map_err(|e| e.to_string())?;
+    fs::write(&config.output, json).map_err(|e| format!("{}: {}", config.output, e))?;
+    Ok(results.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_external_formats_become_results() {
+        let options = ImportOptions {
+            problem: "Taquin-3x3".to_string(),
+            size: 3,
+            label: None,
+        };
+        let hyperfine = r#"{"results": [{"command": "python solver.py", "mean": 0.5,
+            "times": [0.4, 0.6], "exit_codes": [0, 1]}]}"#;
+        let results = parse_hyperfine(hyperfine, &options).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].algorithm, "python solver.py");
+        assert_eq!(results[0].metrics.time_ms, 400.0);
+        assert!(results[0].status.is_solved());
+        assert_eq!(results[1].error.as_deref(), Some("code de sortie 1"));
+        assert_eq!(results[1].properties["source"], "hyperfine");
+
+        let messages = concat!(
+            r#"{"reason": "group-complete", "group_name": "astar"}"#,
+            "\n",
+            r#"{"reason": "benchmark-complete", "id": "astar/cpp", "unit": "ns", "#,
+            r#""iteration_count": [10, 20], "measured_values": [20000000, 60000000], "#,
+            r#""typical": {"estimate": 2500000, "unit": "ns"}}"#,
+        );
+        let labelled = ImportOptions {
+            label: Some("A*-Manhattan".to_string()),
+            ..options
+        };
+        let results = parse_criterion_messages(messages, &labelled).unwrap();
+        let times: Vec<f64> = results.iter().map(|r| r.metrics.time_ms).collect();
+        assert_eq!(times, vec![2.0, 3.0]);
+        assert!(results.iter().all(|r| r.algorithm == "A*-Manhattan"));
+        assert_eq!(
+            detect(Path::new("x.json"), messages),
+            ImportFormat::Criterion
+        );
+        assert_eq!(
+            detect(Path::new("x.json"), hyperfine),
+            ImportFormat::Hyperfine
+        );
+    }
+}
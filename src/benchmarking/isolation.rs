@@ -0,0 +1,179 @@
+use super::distributed::RemoteTask;
+use super::metrics::BenchmarkResult;
+use super::runner::{BenchmarkConfig, BenchmarkRunner};
+use crate::algorithms::SearchStatus;
+use crate::problems::registry;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{self, Read, Write};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+pub const CHILD_COMMAND: &str = "isolated-task";
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+const KILL_GRACE: Duration = Duration::from_secs(2);
+
+#[derive(Serialize, Deserialize)]
+struct IsolatedTask {
+    config: BenchmarkConfig,
+    task: RemoteTask,
+}
+
+#[derive(Serialize, Deserialize)]
+struct IsolatedResult {
+    result: BenchmarkResult,
+    time_ms: f64,
+}
+
+pub struct Failure {
+    pub status: SearchStatus,
+    pub peak_rss_kb: Option<usize>,
+}
+
+fn peak_rss_kb(process: &str) -> Option<usize> {
+    let status = fs::read_to_string(format!("/proc/{}/status", process)).ok()?;
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmHWM:"))
+        .and_then(|value| value.trim().trim_end_matches("kB").trim().parse().ok())
+}
+
+pub fn run_child() -> Result<(), String> {
+    let mut input = String::new();
+    io::stdin()
+        .read_to_string(&mut input)
+        .map_err(|e| e.to_string())?;
+    let IsolatedTask { config, task } =
+        serde_json::from_str(&input).map_err(|e| format!("tâche invalide: {}", e))?;
+    let mut runner = BenchmarkRunner::new(BenchmarkConfig {
+        threads: 1,
+        isolate: false,
+        budget_secs: None,
+        ..config
+    });
+    runner.progress_mut().set_console(false);
+    let (mut result, time_ms) = registry::PROBLEMS
+        .iter()
+        .find_map(|entry| (entry.run_task)(&runner, &task))
+        .ok_or_else(|| format!("Problème inconnu: {}", task.problem))?;
+    if let Some(peak) = peak_rss_kb("self") {
+        result
+            .properties
+            .insert("peak_rss_kb".to_string(), peak.into());
+    }
+
+    let line =
+        serde_json::to_string(&IsolatedResult { result, time_ms }).map_err(|e| e.to_string())?;
+    let mut stdout = io::stdout().lock();
+    writeln!(stdout, "{}", line)
+        .and_then(|_| stdout.flush())
+        .map_err(|e| e.to_string())
+}
+
+fn collect(mut stream: impl Read + Send + 'static) -> JoinHandle<String> {
+    thread::spawn(move || {
+        let mut contents = String::new();
+        let _ = stream.read_to_string(&mut contents);
+        contents
+    })
+}
+
+fn describe(status: ExitStatus) -> String {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        if let Some(signal) = status.signal() {
+            return format!("signal {}", signal);
+        }
+    }
+    match status.code() {
+        Some(code) => format!("code {}", code),
+        None => "statut inconnu".to_string(),
+    }
+}
+
+fn wait(
+    child: &mut Child,
+    timeout: Option<Duration>,
+    peak: &mut Option<usize>,
+) -> io::Result<Option<ExitStatus>> {
+    let start = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        *peak = peak_rss_kb(&child.id().to_string()).or(*peak);
+        if timeout.is_some_and(|limit| start.elapsed() >= limit) {
+            child.kill()?;
+            child.wait()?;
+            return Ok(None);
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+pub fn spawn(
+    config: &BenchmarkConfig,
+    task: &RemoteTask,
+) -> Result<(BenchmarkResult, f64), Failure> {
+    let mut peak = None;
+    let error = |message: String, peak: Option<usize>| Failure {
+        status: SearchStatus::Error(message),
+        peak_rss_kb: peak,
+    };
+    let input = serde_json::to_string(&IsolatedTask {
+        config: config.clone(),
+        task: task.clone(),
+    })
+    .map_err(|e| error(e.to_string(), None))?;
+
+    let mut child = std::env::current_exe()
+        .and_then(|exe| {
+            Command::new(exe)
+                .arg(CHILD_COMMAND)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()
+        })
+        .map_err(|e| error(format!("Lancement du processus isolé: {}", e), None))?;
+    let stdout = collect(child.stdout.take().expect("stdout redirigé"));
+    let stderr = collect(child.stderr.take().expect("stderr redirigé"));
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(input.as_bytes());
+    }
+
+    let timeout =
+        (config.timeout_secs > 0).then(|| Duration::from_secs(config.timeout_secs) + KILL_GRACE);
+    let status = wait(&mut child, timeout, &mut peak).map_err(|e| error(e.to_string(), peak))?;
+    let (stdout, stderr) = (
+        stdout.join().unwrap_or_default(),
+        stderr.join().unwrap_or_default(),
+    );
+    let Some(status) = status else {
+        return Err(Failure {
+            status: SearchStatus::Timeout,
+            peak_rss_kb: peak,
+        });
+    };
+    if !status.success() {
+        let reason = stderr
+            .lines()
+            .rev()
+            .find(|line| !line.trim().is_empty())
+            .map(|line| format!(": {}", line.trim()))
+            .unwrap_or_default();
+        return Err(error(
+            format!(
+                "Processus isolé interrompu ({}){}",
+                describe(status),
+                reason
+            ),
+            peak,
+        ));
+    }
+    let IsolatedResult { result, time_ms } = serde_json::from_str(stdout.trim())
+        .map_err(|e| error(format!("Réponse du processus isolé: {}", e), peak))?;
+    Ok((result, time_ms))
+}
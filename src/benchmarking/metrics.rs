@@ -1,6 +1,11 @@
-use serde::{Deserialize, Serialize};
+use super::stability::{self, StabilityReport};
+use crate::algorithms::SearchStatus;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Metrics {
@@ -10,65 +15,185 @@ pub struct Metrics {
     pub nodes_generated: usize,
     pub max_frontier_size: usize,
     pub solution_length: usize,
+    #[serde(default)]
+    pub evictions: usize,
+    #[serde(default)]
+    pub solution_cost: usize,
+    #[serde(default)]
+    pub reexpansions: usize,
+    #[serde(default)]
+    pub precompute_ms: f64,
+    #[serde(default)]
+    pub steps: usize,
+    #[serde(default)]
+    pub max_step_ms: f64,
+    #[serde(default)]
+    pub mean_step_ms: f64,
+    #[serde(default)]
+    pub paths_found: usize,
+    #[serde(default)]
+    pub paths_generated: usize,
+    #[serde(default)]
+    pub plateau_escapes: usize,
+    #[serde(default)]
+    pub generations: usize,
+    #[serde(default)]
+    pub duplicates: usize,
+    #[serde(default)]
+    pub underconsistent: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signed_cost: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProfileSample {
+    pub nodes_visited: usize,
+    pub time_ms: f64,
+    pub f_bound: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frontier: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Improvement {
+    pub time_ms: f64,
+    pub cost: usize,
+}
+
+#[derive(Default)]
+struct AtomicMetrics {
+    elapsed_us: AtomicU64,
+    memory_kb: AtomicUsize,
+    nodes_visited: AtomicUsize,
+    nodes_generated: AtomicUsize,
+    max_frontier_size: AtomicUsize,
+    solution_length: AtomicUsize,
+    snapshot: Mutex<Metrics>,
+}
+
+impl AtomicMetrics {
+    fn load(&self) -> Metrics {
+        Metrics {
+            time_ms: self.elapsed_us.load(Ordering::Relaxed) as f64 / 1000.0,
+            memory_kb: self.memory_kb.load(Ordering::Relaxed),
+            nodes_visited: self.nodes_visited.load(Ordering::Relaxed),
+            nodes_generated: self.nodes_generated.load(Ordering::Relaxed),
+            max_frontier_size: self.max_frontier_size.load(Ordering::Relaxed),
+            solution_length: self.solution_length.load(Ordering::Relaxed),
+            ..self.snapshot.lock().unwrap().clone()
+        }
+    }
+
+    fn store(&self, metrics: &Metrics) {
+        self.memory_kb.store(metrics.memory_kb, Ordering::Relaxed);
+        self.nodes_visited
+            .store(metrics.nodes_visited, Ordering::Relaxed);
+        self.nodes_generated
+            .store(metrics.nodes_generated, Ordering::Relaxed);
+        self.max_frontier_size
+            .store(metrics.max_frontier_size, Ordering::Relaxed);
+        self.solution_length
+            .store(metrics.solution_length, Ordering::Relaxed);
+        *self.snapshot.lock().unwrap() = metrics.clone();
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Incumbent {
+    pub cost: usize,
+    pub time_ms: f64,
+    pub actions: Vec<String>,
 }
 
 #[derive(Clone)]
 pub struct SharedMetrics {
-    inner: Arc<Mutex<Metrics>>,
+    inner: Arc<AtomicMetrics>,
+    incumbent: Arc<Mutex<Option<Incumbent>>>,
+    improvements: Arc<Mutex<Vec<Improvement>>>,
     start: Instant,
 }
 
 impl SharedMetrics {
     pub fn new() -> Self {
         SharedMetrics {
-            inner: Arc::new(Mutex::new(Metrics::default())),
+            inner: Arc::new(AtomicMetrics::default()),
+            incumbent: Arc::new(Mutex::new(None)),
+            improvements: Arc::new(Mutex::new(Vec::new())),
             start: Instant::now(),
         }
     }
 
+    fn touch(&self) {
+        self.inner
+            .elapsed_us
+            .store(self.start.elapsed().as_micros() as u64, Ordering::Relaxed);
+    }
+
     pub fn update<F>(&self, f: F)
     where
         F: FnOnce(&mut Metrics),
     {
-        if let Ok(mut metrics) = self.inner.lock() {
-            f(&mut metrics);
-            metrics.time_ms = self.start.elapsed().as_millis() as f64;
-        }
+        let mut metrics = self.inner.load();
+        f(&mut metrics);
+        self.inner.store(&metrics);
+        self.touch();
     }
 
     pub fn get(&self) -> Metrics {
-        self.inner.lock().map(|m| m.clone()).unwrap_or_default()
+        self.inner.load()
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
     }
 
     pub fn increment_visited(&self) {
-        if let Ok(mut metrics) = self.inner.lock() {
-            metrics.nodes_visited += 1;
-            metrics.time_ms = self.start.elapsed().as_millis() as f64;
-        }
+        self.inner.nodes_visited.fetch_add(1, Ordering::Relaxed);
+        self.touch();
     }
 
     pub fn increment_generated(&self) {
-        if let Ok(mut metrics) = self.inner.lock() {
-            metrics.nodes_generated += 1;
-        }
+        self.inner.nodes_generated.fetch_add(1, Ordering::Relaxed);
     }
 
     pub fn update_max_frontier(&self, size: usize) {
-        if let Ok(mut metrics) = self.inner.lock() {
-            metrics.max_frontier_size = metrics.max_frontier_size.max(size);
-        }
+        self.inner
+            .max_frontier_size
+            .fetch_max(size, Ordering::Relaxed);
     }
 
     pub fn set_memory_kb(&self, kb: usize) {
-        if let Ok(mut metrics) = self.inner.lock() {
-            metrics.memory_kb = kb;
-        }
+        self.inner.memory_kb.store(kb, Ordering::Relaxed);
     }
 
     pub fn set_solution_length(&self, len: usize) {
-        if let Ok(mut metrics) = self.inner.lock() {
-            metrics.solution_length = len;
+        self.inner.solution_length.store(len, Ordering::Relaxed);
+    }
+
+    pub fn offer_incumbent(&self, cost: usize, actions: Vec<String>) -> bool {
+        let mut incumbent = self.incumbent.lock().unwrap();
+        if incumbent.as_ref().is_some_and(|best| best.cost <= cost) {
+            return false;
         }
+        let time_ms = self.start.elapsed().as_micros() as f64 / 1000.0;
+        *incumbent = Some(Incumbent {
+            cost,
+            time_ms,
+            actions,
+        });
+        self.improvements
+            .lock()
+            .unwrap()
+            .push(Improvement { time_ms, cost });
+        true
+    }
+
+    pub fn incumbent(&self) -> Option<Incumbent> {
+        self.incumbent.lock().unwrap().clone()
+    }
+
+    pub fn improvements(&self) -> Vec<Improvement> {
+        self.improvements.lock().unwrap().clone()
     }
 }
 
@@ -78,6 +203,244 @@ impl Default for SharedMetrics {
     }
 }
 
+const FLUSH_INTERVAL: usize = 1024;
+const PROFILE_INTERVAL: usize = 64;
+const MAX_PROFILE_SAMPLES: usize = 1024;
+
+pub struct MetricsRecorder {
+    local: Metrics,
+    shared: Option<SharedMetrics>,
+    start: Instant,
+    pending: usize,
+    cutoff: bool,
+    negative_cycle: bool,
+    memory_exceeded: bool,
+    profile: Option<Vec<ProfileSample>>,
+    profile_interval: usize,
+    incumbent_cost: Option<usize>,
+    improvements: Vec<Improvement>,
+}
+
+impl MetricsRecorder {
+    pub fn new() -> Self {
+        MetricsRecorder {
+            local: Metrics::default(),
+            shared: None,
+            start: Instant::now(),
+            pending: 0,
+            cutoff: false,
+            negative_cycle: false,
+            memory_exceeded: false,
+            profile: None,
+            profile_interval: PROFILE_INTERVAL,
+            incumbent_cost: None,
+            improvements: Vec::new(),
+        }
+    }
+
+    pub fn with_shared(shared: SharedMetrics) -> Self {
+        MetricsRecorder {
+            shared: Some(shared),
+            ..Self::new()
+        }
+    }
+
+    pub fn with_profile(mut self) -> Self {
+        self.profile = Some(Vec::new());
+        self
+    }
+
+    fn push_sample(&mut self, f_bound: usize, frontier: Option<usize>) {
+        let sample = ProfileSample {
+            nodes_visited: self.local.nodes_visited,
+            time_ms: self.start.elapsed().as_micros() as f64 / 1000.0,
+            f_bound,
+            frontier,
+        };
+        if let Some(profile) = &mut self.profile {
+            profile.push(sample);
+        }
+    }
+
+    pub fn sample_frontier(&mut self, f_min: usize, frontier: usize) {
+        let Some(profile) = &mut self.profile else {
+            return;
+        };
+        if !self
+            .local
+            .nodes_visited
+            .is_multiple_of(self.profile_interval)
+        {
+            return;
+        }
+        if profile.len() >= MAX_PROFILE_SAMPLES {
+            let mut keep = true;
+            profile.retain(|_| {
+                keep = !keep;
+                keep
+            });
+            self.profile_interval *= 2;
+            if !self
+                .local
+                .nodes_visited
+                .is_multiple_of(self.profile_interval)
+            {
+                return;
+            }
+        }
+        self.push_sample(f_min, Some(frontier));
+    }
+
+    pub fn sample_bound(&mut self, bound: usize) {
+        self.push_sample(bound, None);
+    }
+
+    pub fn take_profile(&mut self) -> Option<Vec<ProfileSample>> {
+        self.profile.take().filter(|profile| !profile.is_empty())
+    }
+
+    pub fn take_improvements(&mut self) -> Vec<Improvement> {
+        std::mem::take(&mut self.improvements)
+    }
+
+    pub fn visit(&mut self) {
+        self.local.nodes_visited += 1;
+        self.pending += 1;
+        if self.pending >= FLUSH_INTERVAL {
+            self.flush();
+        }
+    }
+
+    pub fn generate(&mut self) {
+        self.local.nodes_generated += 1;
+    }
+
+    pub fn observe_frontier(&mut self, size: usize) {
+        self.local.max_frontier_size = self.local.max_frontier_size.max(size);
+    }
+
+    pub fn set_memory_kb(&mut self, kb: usize) {
+        self.local.memory_kb = kb;
+    }
+
+    pub fn add_evictions(&mut self, count: usize) {
+        self.local.evictions += count;
+    }
+
+    pub fn reexpand(&mut self) {
+        self.local.reexpansions += 1;
+    }
+
+    pub fn set_precompute_ms(&mut self, ms: f64) {
+        self.local.precompute_ms = ms;
+    }
+
+    pub fn record_step(&mut self, ms: f64) {
+        let metrics = &mut self.local;
+        metrics.steps += 1;
+        metrics.max_step_ms = metrics.max_step_ms.max(ms);
+        metrics.mean_step_ms += (ms - metrics.mean_step_ms) / metrics.steps as f64;
+    }
+
+    pub fn generate_path(&mut self) {
+        self.local.paths_generated += 1;
+    }
+
+    pub fn set_paths_found(&mut self, count: usize) {
+        self.local.paths_found = count;
+    }
+
+    pub fn escape_plateau(&mut self) {
+        self.local.plateau_escapes += 1;
+    }
+
+    pub fn skip_duplicate(&mut self) {
+        self.local.duplicates += 1;
+    }
+
+    pub fn expand_underconsistent(&mut self) {
+        self.local.underconsistent += 1;
+    }
+
+    pub fn next_generation(&mut self) {
+        self.local.generations += 1;
+    }
+
+    pub fn set_solution_length(&mut self, len: usize) {
+        self.local.solution_length = len;
+    }
+
+    pub fn set_solution_cost(&mut self, cost: usize) {
+        self.local.solution_cost = cost;
+    }
+
+    pub fn set_signed_cost(&mut self, cost: i64) {
+        self.local.signed_cost = Some(cost);
+    }
+
+    pub fn incumbent_cost(&self) -> Option<usize> {
+        self.incumbent_cost
+    }
+
+    pub fn improve_incumbent<A: fmt::Display>(&mut self, actions: &[A], cost: usize) -> bool {
+        if self.incumbent_cost.is_some_and(|best| best <= cost) {
+            return false;
+        }
+        self.incumbent_cost = Some(cost);
+        self.improvements.push(Improvement {
+            time_ms: self.start.elapsed().as_micros() as f64 / 1000.0,
+            cost,
+        });
+        if let Some(shared) = &self.shared {
+            shared.offer_incumbent(cost, actions.iter().map(|a| a.to_string()).collect());
+        }
+        true
+    }
+
+    pub fn mark_cutoff(&mut self) {
+        self.cutoff = true;
+    }
+
+    pub fn cutoff_occurred(&self) -> bool {
+        self.cutoff
+    }
+
+    pub fn mark_negative_cycle(&mut self) {
+        self.negative_cycle = true;
+    }
+
+    pub fn negative_cycle_found(&self) -> bool {
+        self.negative_cycle
+    }
+
+    pub fn mark_memory_exceeded(&mut self) {
+        self.memory_exceeded = true;
+    }
+
+    pub fn memory_exceeded(&self) -> bool {
+        self.memory_exceeded
+    }
+
+    pub fn flush(&mut self) {
+        self.pending = 0;
+        if let Some(shared) = &self.shared {
+            shared.update(|m| *m = self.local.clone());
+        }
+    }
+
+    pub fn finish(mut self) -> Metrics {
+        self.flush();
+        self.local.time_ms = self.start.elapsed().as_micros() as f64 / 1000.0;
+        self.local
+    }
+}
+
+impl Default for MetricsRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Metrics {
     pub fn effective_branching_factor(&self) -> f64 {
         if self.solution_length == 0 {
@@ -100,19 +463,164 @@ impl Metrics {
     }
 }
 
+pub type InstanceProperties = BTreeMap<String, serde_json::Value>;
+
+fn deserialize_status<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<SearchStatus, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StatusRepr {
+        Legacy(u8),
+        Current(SearchStatus),
+    }
+
+    Ok(match StatusRepr::deserialize(deserializer)? {
+        StatusRepr::Legacy(code) => SearchStatus::from_legacy_code(code),
+        StatusRepr::Current(status) => status,
+    })
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BenchmarkResult {
     pub algorithm: String,
     pub problem: String,
     pub problem_size: usize,
     pub instance_id: usize,
-    pub status: u8,
+    #[serde(default)]
+    pub seed: u64,
+    #[serde(deserialize_with = "deserialize_status")]
+    pub status: SearchStatus,
     pub metrics: Metrics,
     pub timestamp: String,
+    #[serde(default)]
+    pub hasher: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub initial_state: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub solution: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub optimal: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost_optimal: Option<bool>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub properties: InstanceProperties,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<Vec<ProfileSample>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub improvements: Vec<Improvement>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreparationReport {
+    pub problem: String,
+    pub instances: usize,
+    #[serde(default)]
+    pub duplicates: usize,
+    pub time_ms: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchedulingReport {
+    pub problem: String,
+    pub ordering: String,
+    pub wall_ms: f64,
+    pub busy_ms: f64,
+    pub efficiency: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostReport {
+    pub host: String,
+    pub threads: usize,
+    pub tasks: usize,
+    pub busy_ms: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResultIndexEntry {
+    pub problem: String,
+    pub first: usize,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PhaseTimings {
+    pub generation_ms: f64,
+    pub search_ms: f64,
+    pub aggregation_ms: f64,
+    pub serialization_ms: f64,
+    pub other_ms: f64,
+    pub total_ms: f64,
+}
+
+impl PhaseTimings {
+    pub fn finish(&mut self, total_ms: f64) {
+        let measured =
+            self.generation_ms + self.search_ms + self.aggregation_ms + self.serialization_ms;
+        self.total_ms = total_ms;
+        self.other_ms = (total_ms - measured).max(0.0);
+    }
+
+    pub fn lines(&self) -> Vec<String> {
+        let share = |ms: f64| {
+            if self.total_ms > 0.0 {
+                ms / self.total_ms * 100.0
+            } else {
+                0.0
+            }
+        };
+        let mut lines = vec!["\nProfil des phases:".to_string()];
+        for (label, ms) in [
+            ("Génération", self.generation_ms),
+            ("Recherche", self.search_ms),
+            ("Agrégation", self.aggregation_ms),
+            ("Sérialisation", self.serialization_ms),
+            ("Autre", self.other_ms),
+        ] {
+            lines.push(format!(
+                "  {:<14} {:>10.2} ms ({:.1}%)",
+                label,
+                ms,
+                share(ms)
+            ));
+        }
+        lines
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RunMetadata {
+    pub timestamp: String,
+    pub algorithm: String,
+    pub problem: String,
+    pub problem_size: usize,
+    pub iterations: usize,
+    pub threads: usize,
+    pub timeout_secs: u64,
+    pub memory_limit_mb: usize,
+    pub hasher: String,
+    #[serde(default)]
+    pub seed: u64,
+    #[serde(default)]
+    pub reexpand: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost_bound: Option<usize>,
+    pub preparation: Vec<PreparationReport>,
+    #[serde(default)]
+    pub scheduling: Vec<SchedulingReport>,
+    #[serde(default)]
+    pub results_index: Vec<ResultIndexEntry>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stability: Vec<StabilityReport>,
+    #[serde(default)]
+    pub phases: PhaseTimings,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hosts: Vec<HostReport>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -122,20 +630,63 @@ pub struct AggregatedResults {
     pub problem_size: usize,
     pub total_instances: usize,
     pub successful_instances: usize,
+    #[serde(default)]
+    pub limited_instances: usize,
+    #[serde(default)]
+    pub skipped_instances: usize,
     pub avg_time_ms: f64,
     pub avg_memory_kb: f64,
     pub avg_nodes_visited: f64,
     pub avg_nodes_generated: f64,
     pub avg_solution_length: f64,
+    #[serde(default)]
+    pub avg_solution_cost: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost_optimal: Option<bool>,
     pub avg_ebf: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_cv: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suboptimality_bound: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avg_gap: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_gap: Option<f64>,
+    pub total_evictions: usize,
+    #[serde(default)]
+    pub total_reexpansions: usize,
+    pub checked_instances: usize,
+    pub optimal_instances: usize,
 }
 
 impl AggregatedResults {
     pub fn from_results(results: &[BenchmarkResult]) -> Self {
-        let total = results.len();
-        let successful = results.iter().filter(|r| r.status == 0).count();
+        let skipped = results
+            .iter()
+            .filter(|r| r.status == SearchStatus::Skipped)
+            .count();
+        let total = results.len() - skipped;
+        let successful = results.iter().filter(|r| r.status.is_solved()).count();
+        let limited = results
+            .iter()
+            .filter(|r| r.status == SearchStatus::DepthLimitReached)
+            .count();
 
-        let successful_results: Vec<_> = results.iter().filter(|r| r.status == 0).collect();
+        let successful_results: Vec<_> = results.iter().filter(|r| r.status.is_solved()).collect();
+        let total_evictions = results.iter().map(|r| r.metrics.evictions).sum();
+        let total_reexpansions = results.iter().map(|r| r.metrics.reexpansions).sum();
+        let checked_instances = results.iter().filter(|r| r.optimal.is_some()).count();
+        let optimal_instances = results.iter().filter(|r| r.optimal == Some(true)).count();
+        let suboptimality_bound = results[0]
+            .properties
+            .get("suboptimality_bound")
+            .and_then(|bound| bound.as_f64());
+        let gaps: Vec<f64> = results
+            .iter()
+            .filter_map(|r| r.properties.get("optimality_gap")?.as_f64())
+            .collect();
+        let avg_gap = (!gaps.is_empty()).then(|| gaps.iter().sum::<f64>() / gaps.len() as f64);
+        let max_gap = gaps.iter().copied().reduce(f64::max);
 
         if successful_results.is_empty() {
             return AggregatedResults {
@@ -144,12 +695,24 @@ impl AggregatedResults {
                 problem_size: results[0].problem_size,
                 total_instances: total,
                 successful_instances: 0,
+                limited_instances: limited,
+                skipped_instances: skipped,
                 avg_time_ms: 0.0,
                 avg_memory_kb: 0.0,
                 avg_nodes_visited: 0.0,
                 avg_nodes_generated: 0.0,
                 avg_solution_length: 0.0,
+                avg_solution_cost: 0.0,
+                cost_optimal: results[0].cost_optimal,
                 avg_ebf: 0.0,
+                time_cv: None,
+                suboptimality_bound,
+                avg_gap,
+                max_gap,
+                total_evictions,
+                total_reexpansions,
+                checked_instances,
+                optimal_instances,
             };
         }
 
@@ -161,6 +724,8 @@ impl AggregatedResults {
             problem_size: results[0].problem_size,
             total_instances: total,
             successful_instances: successful,
+            limited_instances: limited,
+            skipped_instances: skipped,
             avg_time_ms: successful_results
                 .iter()
                 .map(|r| r.metrics.time_ms)
@@ -186,11 +751,160 @@ impl AggregatedResults {
                 .map(|r| r.metrics.solution_length)
                 .sum::<usize>() as f64
                 / n,
+            avg_solution_cost: successful_results
+                .iter()
+                .map(|r| r.metrics.solution_cost)
+                .sum::<usize>() as f64
+                / n,
+            cost_optimal: results[0].cost_optimal,
             avg_ebf: successful_results
                 .iter()
                 .map(|r| r.metrics.effective_branching_factor())
                 .sum::<f64>()
                 / n,
+            time_cv: stability::time_cv(successful_results.iter().copied()),
+            suboptimality_bound,
+            avg_gap,
+            max_gap,
+            total_evictions,
+            total_reexpansions,
+            checked_instances,
+            optimal_instances,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shared_metrics_counters() {
+        let shared = SharedMetrics::new();
+        let clone = shared.clone();
+
+        shared.update(|m| m.nodes_generated = 1);
+        clone.increment_visited();
+        clone.increment_generated();
+        shared.update_max_frontier(7);
+        shared.update_max_frontier(3);
+        shared.set_solution_length(4);
+
+        let metrics = shared.get();
+        assert_eq!(metrics.nodes_visited, 1);
+        assert_eq!(metrics.nodes_generated, 2);
+        assert_eq!(metrics.max_frontier_size, 7);
+        assert_eq!(metrics.solution_length, 4);
+    }
+
+    #[test]
+    fn test_flush_mirrors_every_metric() {
+        let shared = SharedMetrics::new();
+        let mut recorder = MetricsRecorder::with_shared(shared.clone());
+        recorder.reexpand();
+        recorder.record_step(2.0);
+        recorder.generate_path();
+        recorder.escape_plateau();
+        recorder.next_generation();
+        recorder.skip_duplicate();
+        recorder.expand_underconsistent();
+        recorder.set_precompute_ms(5.0);
+        recorder.flush();
+
+        let metrics = shared.get();
+        assert_eq!(metrics.reexpansions, 1);
+        assert_eq!(metrics.steps, 1);
+        assert_eq!(metrics.max_step_ms, 2.0);
+        assert_eq!(metrics.paths_generated, 1);
+        assert_eq!(metrics.plateau_escapes, 1);
+        assert_eq!(metrics.generations, 1);
+        assert_eq!(metrics.duplicates, 1);
+        assert_eq!(metrics.underconsistent, 1);
+        assert_eq!(metrics.precompute_ms, 5.0);
+    }
+
+    #[test]
+    fn test_incumbent_only_improves() {
+        let shared = SharedMetrics::new();
+        let mut recorder = MetricsRecorder::with_shared(shared.clone());
+        assert_eq!(shared.incumbent(), None);
+
+        assert!(recorder.improve_incumbent(&["a", "b", "c"], 12));
+        assert!(!recorder.improve_incumbent(&["d"], 12));
+        assert!(recorder.improve_incumbent(&["e", "f"], 7));
+        assert!(!shared.offer_incumbent(9, vec!["g".to_string()]));
+        assert_eq!(recorder.incumbent_cost(), Some(7));
+
+        let incumbent = shared.incumbent().unwrap();
+        assert_eq!((incumbent.cost, incumbent.actions.len()), (7, 2));
+        assert!(incumbent.time_ms <= shared.elapsed().as_secs_f64() * 1000.0);
+    }
+
+    #[test]
+    fn test_recorder_flushes_in_batches() {
+        let shared = SharedMetrics::new();
+        let mut recorder = MetricsRecorder::with_shared(shared.clone());
+
+        for _ in 0..FLUSH_INTERVAL - 1 {
+            recorder.visit();
+        }
+        assert_eq!(shared.get().nodes_visited, 0);
+
+        recorder.visit();
+        assert_eq!(shared.get().nodes_visited, FLUSH_INTERVAL);
+
+        recorder.visit();
+        recorder.generate();
+        let metrics = recorder.finish();
+        assert_eq!(metrics.nodes_visited, FLUSH_INTERVAL + 1);
+        assert_eq!(shared.get().nodes_generated, 1);
+    }
+
+    #[test]
+    fn test_profile_sampling_stays_bounded() {
+        let mut recorder = MetricsRecorder::new();
+        recorder.visit();
+        recorder.sample_frontier(1, 1);
+        assert_eq!(recorder.take_profile(), None);
+
+        let mut recorder = MetricsRecorder::new().with_profile();
+        let visits = PROFILE_INTERVAL * MAX_PROFILE_SAMPLES * 3;
+        for expansion in 0..visits {
+            recorder.visit();
+            recorder.sample_frontier(expansion / 1000, expansion % 7);
+        }
+        let profile = recorder.take_profile().unwrap();
+        assert!(profile.len() <= MAX_PROFILE_SAMPLES);
+        assert!(profile.len() > MAX_PROFILE_SAMPLES / 2);
+        assert!(profile
+            .windows(2)
+            .all(|pair| pair[0].nodes_visited < pair[1].nodes_visited));
+        assert_eq!(profile.last().unwrap().nodes_visited, visits);
+    }
+
+    #[test]
+    fn test_status_accepts_legacy_codes() {
+        let result = |status: &str| {
+            let json = format!(
+                r#"{{"algorithm":"BFS","problem":"Taquin-3x3","problem_size":3,"instance_id":0,"status":{},"metrics":{},"timestamp":""}}"#,
+                status,
+                serde_json::to_string(&Metrics::default()).unwrap()
+            );
+            serde_json::from_str::<BenchmarkResult>(&json)
+                .unwrap()
+                .status
+        };
+
+        assert_eq!(result("0"), SearchStatus::Solved);
+        assert_eq!(result("1"), SearchStatus::Timeout);
+        assert_eq!(result("2"), SearchStatus::NoSolution);
+        assert_eq!(
+            result(r#""depth_limit_reached""#),
+            SearchStatus::DepthLimitReached
+        );
+        assert_eq!(
+            result(r#"{"error":"Solution invalide"}"#),
+            SearchStatus::Error("Solution invalide".to_string())
+        );
+    }
+}
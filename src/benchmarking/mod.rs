@@ -1,5 +1,46 @@
+pub mod all_pairs;
+#[cfg(feature = "web")]
+pub mod api;
+pub mod budget;
+pub mod compare;
+pub mod consistency;
+#[cfg(feature = "tui")]
+pub mod dashboard;
+pub mod debugger;
+pub mod distributed;
+pub mod dot;
+pub mod fuzz;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+pub mod heatmap;
+pub mod heuristic_check;
+pub mod import;
+pub mod isolation;
 pub mod metrics;
+pub mod options;
+pub mod progress;
+pub mod render;
 pub mod runner;
+pub mod runs;
+pub mod scenario;
+#[cfg(feature = "web")]
+pub mod server;
+pub mod stability;
+pub mod stats;
+#[cfg(feature = "web")]
+pub mod stream;
+pub mod sweep;
+pub mod writer;
 
-pub use metrics::{Metrics, SharedMetrics};
-pub use runner::{BenchmarkConfig, BenchmarkRunner};
+pub use metrics::{
+    BenchmarkResult, HostReport, Improvement, Incumbent, InstanceProperties, Metrics,
+    MetricsRecorder, PhaseTimings, PreparationReport, ProfileSample, ResultIndexEntry, RunMetadata,
+    SchedulingReport, SharedMetrics,
+};
+pub use options::{
+    AlgorithmChoice, CompareFormat, Connectivity, Direction, GoalTest, HeatmapMode, Heuristic,
+    ImportFormat, MazeGenerator, MoveCost, ShuffleMode, StatsGroup, StatsMetric, StatusFilter,
+    TieBreaking,
+};
+pub use progress::{Progress, ProgressEvent};
+pub use runner::{BenchmarkConfig, BenchmarkRunner, ProblemRun};
@@ -0,0 +1,210 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AlgorithmChoice {
+    All,
+    Bfs,
+    Dfs,
+    Id,
+    Dls,
+    Frontier,
+    Bfhs,
+    Ucs,
+    Astar,
+    Greedy,
+    Idastar,
+    Perimeter,
+    Bidirectional,
+    Sma,
+    Awastar,
+    Ees,
+    Lrta,
+    Ehc,
+    Ga,
+    Yen,
+    BellmanFord,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ShuffleMode {
+    Walk,
+    Uniform,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MoveCost {
+    Unit,
+    Face,
+    Square,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Heuristic {
+    None,
+    Hamming,
+    Manhattan,
+    LinearConflict,
+    PatternDb,
+}
+
+impl Heuristic {
+    pub fn label(self) -> &'static str {
+        match self {
+            Heuristic::None => "None",
+            Heuristic::Hamming => "Hamming",
+            Heuristic::Manhattan => "Manhattan",
+            Heuristic::LinearConflict => "LinearConflict",
+            Heuristic::PatternDb => "PatternDB",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Connectivity {
+    None,
+    Path,
+    Resample,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MazeGenerator {
+    Backtracker,
+    Prim,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GoalTest {
+    Expansion,
+    Generation,
+    Both,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Direction {
+    Forward,
+    Backward,
+    Both,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TieBreaking {
+    HighG,
+    LowH,
+    Fifo,
+    Lifo,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SweepParameter {
+    MaxDepth,
+    DepthLimit,
+    MemoryLimit,
+    PerimeterRadius,
+    MemoryBudget,
+    Lookahead,
+    StepBudget,
+    K,
+    Population,
+    Generations,
+    CostBound,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum CompareFormat {
+    Text,
+    MarkdownComment,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ImportFormat {
+    Auto,
+    Native,
+    Hyperfine,
+    Criterion,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum StatsGroup {
+    Algorithm,
+    Problem,
+    Size,
+    Status,
+    Host,
+    Date,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum StatsMetric {
+    Time,
+    Nodes,
+    Generated,
+    Memory,
+    Length,
+    Cost,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum StatusFilter {
+    Solved,
+    Timeout,
+    NoSolution,
+    DepthLimit,
+    MemoryExceeded,
+    NegativeCycle,
+    Skipped,
+    Error,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum HeatmapMode {
+    Order,
+    Density,
+}
+
+fn write_value_name<T: ValueEnum>(value: &T, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match value.to_possible_value() {
+        Some(possible) => write!(f, "{}", possible.get_name()),
+        None => Ok(()),
+    }
+}
+
+macro_rules! display_as_value_name {
+    ($($option:ty),*) => {
+        $(impl fmt::Display for $option {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write_value_name(self, f)
+            }
+        })*
+    };
+}
+
+display_as_value_name!(
+    AlgorithmChoice,
+    ShuffleMode,
+    MoveCost,
+    Heuristic,
+    Connectivity,
+    MazeGenerator,
+    GoalTest,
+    Direction,
+    TieBreaking,
+    SweepParameter,
+    CompareFormat,
+    ImportFormat,
+    StatsGroup,
+    StatsMetric,
+    StatusFilter,
+    HeatmapMode
+);
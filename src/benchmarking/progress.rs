@@ -0,0 +1,74 @@
+use super::metrics::{BenchmarkResult, SharedMetrics};
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+#[derive(Clone)]
+pub enum ProgressEvent {
+    ProblemStarted {
+        problem: String,
+        tasks: usize,
+    },
+    TaskStarted {
+        task: usize,
+        algorithm: String,
+        instance: usize,
+        thread: Option<usize>,
+        metrics: SharedMetrics,
+    },
+    TaskFinished {
+        task: usize,
+        result: Box<BenchmarkResult>,
+        time_ms: f64,
+    },
+    Log(String),
+}
+
+#[derive(Clone)]
+pub struct Progress {
+    subscribers: Vec<Sender<ProgressEvent>>,
+    console: bool,
+}
+
+impl Default for Progress {
+    fn default() -> Self {
+        Progress {
+            subscribers: Vec::new(),
+            console: true,
+        }
+    }
+}
+
+impl Progress {
+    pub fn subscribe(&mut self) -> Receiver<ProgressEvent> {
+        let (sender, receiver) = channel();
+        self.subscribers.push(sender);
+        receiver
+    }
+
+    pub fn set_console(&mut self, console: bool) {
+        self.console = console;
+    }
+
+    pub fn is_console(&self) -> bool {
+        self.console
+    }
+
+    pub fn is_observed(&self) -> bool {
+        !self.subscribers.is_empty()
+    }
+
+    pub fn log(&self, line: String) {
+        if self.console {
+            println!("{}", line);
+        }
+        self.send(ProgressEvent::Log(line));
+    }
+
+    pub fn send(&self, event: ProgressEvent) {
+        if let Some((last, others)) = self.subscribers.split_last() {
+            for subscriber in others {
+                let _ = subscriber.send(event.clone());
+            }
+            let _ = last.send(event);
+        }
+    }
+}
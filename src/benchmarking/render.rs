@@ -0,0 +1,156 @@
+use super::heatmap::{Cell, Heatmap};
+use super::options::HeatmapMode;
+use super::runner::{AlgorithmSettings, BenchmarkConfig, BenchmarkRunner};
+use crate::algorithms::trace::Traced;
+use crate::problems::registry::ProblemFactory;
+use crate::problems::shortest_path::{CostMapFactory, GridFactory};
+use crate::problems::ShortestPath;
+use crate::utils::{cost_maps, seeding};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+pub const MAX_RENDER_SIZE: usize = 60;
+
+pub struct RenderConfig {
+    pub instance: usize,
+    pub heatmap: Option<HeatmapMode>,
+    pub output_dir: String,
+}
+
+fn heatmap(
+    grid: &ShortestPath,
+    expansions: &[usize],
+    path: &[usize],
+    mode: HeatmapMode,
+) -> Heatmap {
+    let (width, height) = grid.grid_dimensions().unwrap_or((0, 0));
+    let mut heatmap = Heatmap::new(width, height);
+    for (cell, slot) in heatmap.cells.iter_mut().enumerate() {
+        if grid.is_blocked(cell) {
+            *slot = Cell::Obstacle;
+        }
+    }
+    heatmap.record_expansions(expansions, mode);
+    for &cell in path {
+        heatmap.cells[cell] = Cell::Path;
+    }
+    for start in grid.starts() {
+        heatmap.cells[start] = Cell::Start;
+    }
+    heatmap.cells[grid.goal()] = Cell::Goal;
+    heatmap
+}
+
+pub fn run(config: &BenchmarkConfig, render: &RenderConfig) -> Result<(), String> {
+    let side = match &config.map {
+        Some(path) => {
+            let map = cost_maps::load(path)?;
+            map.width.max(map.height)
+        }
+        None => config.size,
+    };
+    let ascii = side <= MAX_RENDER_SIZE;
+    if side == 0 || (!ascii && render.heatmap.is_none()) {
+        return Err(format!(
+            "taille {} invalide pour l'affichage (1 à {}, sans limite avec --heatmap)",
+            side, MAX_RENDER_SIZE
+        ));
+    }
+
+    match config.map {
+        Some(_) => render_problem::<CostMapFactory>(config, render, ascii),
+        None => render_problem::<GridFactory>(config, render, ascii),
+    }
+}
+
+fn render_problem<F: ProblemFactory<Instance = ShortestPath>>(
+    config: &BenchmarkConfig,
+    render: &RenderConfig,
+    ascii: bool,
+) -> Result<(), String> {
+    let instance = render.instance;
+    let label = F::label(config);
+    let seed = seeding::instance_seed(config.seed, &label, config.size, instance);
+    let grid = F::generate(config, seed);
+    let settings = AlgorithmSettings {
+        max_depth: F::max_depth(config),
+        memory_limit_kb: None,
+        track_solutions: true,
+        reexpand: config.reexpand,
+        cost_bound: config.cost_bound,
+        depth_limit: config.depth_limit,
+        perimeter_radius: config.perimeter_radius,
+        memory_budget: config.memory_budget,
+        weight: config.weight,
+        bound: config.bound,
+        lookahead: config.lookahead,
+        step_budget: config.step_budget,
+        k: config.k,
+        population: config.population,
+        generations: config.generations,
+        seed: config.seed,
+        tie_breaking: config.tie_breaking,
+        lazy_duplicates: config.lazy_duplicates,
+        order_successors: config.order_successors,
+        profile: false,
+    };
+
+    println!("{} (instance {}, graine {})", label, instance + 1, seed);
+    if render.heatmap.is_some() {
+        fs::create_dir_all(&render.output_dir)
+            .map_err(|e| format!("{}: {}", render.output_dir, e))?;
+    }
+
+    for algo_name in BenchmarkRunner::algorithm_names(config, &F::heuristic_labels(config)) {
+        let traced = Traced::new(&grid);
+        let result = BenchmarkRunner::execute_algorithm(&algo_name, &traced, settings, None);
+        let trace = traced.take_trace();
+
+        let expanded: HashSet<usize> = trace.expansions.iter().copied().collect();
+        let generated: HashSet<usize> = trace.edges.iter().map(|&(_, to, _)| to).collect();
+        let path: Vec<usize> = result
+            .solution
+            .iter()
+            .flatten()
+            .map(|edge| edge.to)
+            .collect();
+
+        println!(
+            "\n{}: {}, {} cellules développées ({} expansions), {} en frontière",
+            algo_name,
+            if result.status.is_solved() {
+                format!(
+                    "chemin de longueur {} (coût {})",
+                    result.metrics.solution_length, result.metrics.solution_cost
+                )
+            } else {
+                result.status.to_string()
+            },
+            expanded.len(),
+            trace.expansions.len(),
+            generated.difference(&expanded).count()
+        );
+        if let Some(rendered) = grid
+            .render_grid(&path, &expanded, &generated)
+            .filter(|_| ascii)
+        {
+            print!("{}", rendered);
+        }
+        if let Some(mode) = render.heatmap {
+            let file = Path::new(&render.output_dir).join(format!(
+                "{}-{}-{}.png",
+                label,
+                algo_name.replace('*', "star"),
+                mode
+            ));
+            heatmap(&grid, &trace.expansions, &path, mode).write_png(&file)?;
+            println!("Carte de chaleur ({}): {}", mode, file.display());
+        }
+    }
+
+    if ascii {
+        println!("\nLégende: S départ, G but, * chemin, o développée, + frontière, # obstacle, . non visitée");
+    }
+    Ok(())
+}
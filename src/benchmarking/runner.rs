@@ -1,411 +1,2415 @@
-use super::metrics::{AggregatedResults, BenchmarkResult, SharedMetrics};
+use super::all_pairs;
+use super::budget::{Budget, SkipReason};
+use super::consistency;
+use super::distributed::{Coordinator, RemoteTask};
+use super::dot::file_stem;
+use super::isolation;
+use super::metrics::{
+    AggregatedResults, BenchmarkResult, InstanceProperties, MetricsRecorder, PhaseTimings,
+    PreparationReport, RunMetadata, SchedulingReport, SharedMetrics,
+};
+use super::options::{
+    AlgorithmChoice, Connectivity, Direction, GoalTest, Heuristic, MazeGenerator, MoveCost,
+    ShuffleMode, TieBreaking,
+};
+use super::progress::{Progress, ProgressEvent};
+use super::runs;
+use super::scenario;
+use super::stability;
+use super::sweep::{self, Sweep};
+use super::writer::{self, ResultWriter};
+use crate::algorithms::backward::Backward;
+use crate::algorithms::perimeter::{Perimeter, PerimeterProblem};
+use crate::algorithms::trace::{SharedTrace, Traced};
 use crate::algorithms::*;
-use crate::problems::*;
+use crate::problems::registry::{self, ProblemEntry, ProblemFactory};
+use crate::utils::hashing::HASHER_NAME;
+use crate::utils::seeding;
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 use serde_json;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::File;
 use std::io::Write;
+use std::path::Path;
 use std::sync::mpsc::{channel, RecvTimeoutError};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct BenchmarkConfig {
-    pub algorithm: String,
+    pub algorithm: AlgorithmChoice,
     pub problem: String,
     pub size: usize,
     pub iterations: usize,
     pub output_file: String,
     pub threads: usize,
+    pub isolate: bool,
     pub timeout_secs: u64,
+    pub memory_limit_mb: usize,
+    pub track_solutions: bool,
+    pub shuffle: ShuffleMode,
+    pub move_cost: MoveCost,
+    pub heuristics: Vec<Heuristic>,
+    pub pdb_partition: Option<String>,
+    pub pdb_dir: String,
+    pub sweeps: Vec<Sweep>,
+    pub verify_optimal: bool,
+    pub check_consistency: bool,
+    pub seed: u64,
+    pub instances: Option<String>,
+    pub connectivity: Connectivity,
+    pub maze_generator: MazeGenerator,
+    pub negative_edges: f64,
+    pub graph: Option<String>,
+    pub map: Option<String>,
+    pub scenario: Option<String>,
+    pub dynamic_obstacles: usize,
+    pub all_pairs: bool,
+    pub colors: usize,
+    pub source: Option<String>,
+    pub sources: usize,
+    pub target: Option<String>,
+    pub goal: Option<String>,
+    pub undirected: bool,
+    pub problem_script: Option<String>,
+    pub problem_command: Option<String>,
+    pub reexpand: bool,
+    pub goal_test: GoalTest,
+    pub direction: Direction,
+    pub cost_bound: Option<usize>,
+    pub depth_limit: Option<usize>,
+    pub perimeter_radius: usize,
+    pub memory_budget: usize,
+    pub weight: f64,
+    pub bound: f64,
+    pub lookahead: usize,
+    pub step_budget: usize,
+    pub k: usize,
+    pub population: usize,
+    pub generations: usize,
+    pub tie_breaking: TieBreaking,
+    pub lazy_duplicates: bool,
+    pub order_successors: bool,
+    pub profile: bool,
+    pub trace: bool,
+    pub trace_limit: Option<usize>,
+    pub stability_threshold: f64,
+    pub budget_secs: Option<u64>,
+}
+
+impl Default for BenchmarkConfig {
+    fn default() -> Self {
+        BenchmarkConfig {
+            algorithm: AlgorithmChoice::All,
+            problem: "all".to_string(),
+            size: 3,
+            iterations: 10,
+            output_file: "results/benchmark_results.json".to_string(),
+            threads: 1,
+            isolate: false,
+            timeout_secs: 60,
+            memory_limit_mb: 0,
+            track_solutions: true,
+            shuffle: ShuffleMode::Walk,
+            move_cost: MoveCost::Unit,
+            heuristics: Vec::new(),
+            pdb_partition: None,
+            pdb_dir: "results/pdb".to_string(),
+            sweeps: Vec::new(),
+            verify_optimal: false,
+            check_consistency: false,
+            seed: 0,
+            instances: None,
+            connectivity: Connectivity::None,
+            maze_generator: MazeGenerator::Backtracker,
+            negative_edges: 0.0,
+            graph: None,
+            map: None,
+            scenario: None,
+            dynamic_obstacles: 0,
+            all_pairs: false,
+            colors: 4,
+            source: None,
+            sources: 1,
+            target: None,
+            goal: None,
+            undirected: false,
+            problem_script: None,
+            problem_command: None,
+            reexpand: false,
+            goal_test: GoalTest::Expansion,
+            direction: Direction::Forward,
+            cost_bound: None,
+            depth_limit: None,
+            perimeter_radius: 10,
+            memory_budget: 100_000,
+            weight: 2.0,
+            bound: 1.5,
+            lookahead: 3,
+            step_budget: 1000,
+            k: 5,
+            population: 100,
+            generations: 200,
+            tie_breaking: TieBreaking::HighG,
+            lazy_duplicates: false,
+            order_successors: false,
+            profile: false,
+            trace: false,
+            trace_limit: None,
+            stability_threshold: 20.0,
+            budget_secs: None,
+        }
+    }
+}
+
+impl BenchmarkConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        use AlgorithmChoice::*;
+
+        if self.budget_secs.is_some() && (scenario::enabled(self) || all_pairs::enabled(self)) {
+            return Err(
+                "--budget n'est pas disponible avec --scenario, --dynamic-obstacles ni --all-pairs"
+                    .to_string(),
+            );
+        }
+        if self.stability_threshold <= 0.0 {
+            return Err("--stability-threshold doit être strictement positif".to_string());
+        }
+        if self.goal_test != GoalTest::Expansion && !matches!(self.algorithm, All | Bfs | Dfs) {
+            return Err(format!(
+                "--goal-test {} ne s'applique qu'à BFS et DFS (algorithme: {})",
+                self.goal_test, self.algorithm
+            ));
+        }
+        if self.reexpand
+            && matches!(
+                self.algorithm,
+                Id | Dls
+                    | Frontier
+                    | Bfhs
+                    | Ucs
+                    | Greedy
+                    | Idastar
+                    | Perimeter
+                    | Bidirectional
+                    | Sma
+                    | Awastar
+                    | Ees
+                    | Lrta
+                    | Ehc
+                    | Ga
+                    | Yen
+                    | BellmanFord
+            )
+        {
+            return Err(format!(
+                "--reexpand ne s'applique qu'à BFS, DFS et A* (algorithme: {})",
+                self.algorithm
+            ));
+        }
+        if self.weight < 1.0 {
+            return Err(format!("--weight doit être ≥ 1 (reçu: {})", self.weight));
+        }
+        if self.bound < 1.0 {
+            return Err(format!("--bound doit être ≥ 1 (reçu: {})", self.bound));
+        }
+        if self.lookahead == 0 || self.step_budget == 0 {
+            return Err("--lookahead et --step-budget doivent être ≥ 1".to_string());
+        }
+        if self.k == 0 {
+            return Err("--k doit être ≥ 1".to_string());
+        }
+        if self.population < 2 || self.generations == 0 {
+            return Err(
+                "--population doit être ≥ 2 et --generations ≥ 1 (algorithme génétique)"
+                    .to_string(),
+            );
+        }
+        if self.tie_breaking != TieBreaking::HighG && !matches!(self.algorithm, All | Astar) {
+            return Err(format!(
+                "--tie-breaking ne s'applique qu'à A* (algorithme: {})",
+                self.algorithm
+            ));
+        }
+        if self.lazy_duplicates && !matches!(self.algorithm, All | Astar) {
+            return Err(format!(
+                "--lazy-duplicates ne s'applique qu'à A* (algorithme: {})",
+                self.algorithm
+            ));
+        }
+        if self.lazy_duplicates && self.memory_limit_mb > 0 {
+            return Err(
+                "--lazy-duplicates et --memory-limit sont incompatibles (liste fermée non bornée)"
+                    .to_string(),
+            );
+        }
+        if self.order_successors && !matches!(self.algorithm, All | Dfs | Id | Dls | Idastar) {
+            return Err(format!(
+                "--order-successors ne s'applique qu'à DFS, ID, DLS et IDA* (algorithme: {})",
+                self.algorithm
+            ));
+        }
+        if self.depth_limit.is_some() && self.algorithm != Dls {
+            return Err(format!(
+                "--depth-limit ne s'applique qu'à DLS (algorithme: {})",
+                self.algorithm
+            ));
+        }
+        if self.algorithm == Bidirectional && self.direction != Direction::Forward {
+            return Err(
+                "--direction: la recherche bidirectionnelle explore déjà les deux sens".to_string(),
+            );
+        }
+        if self.connectivity != Connectivity::None
+            && !matches!(self.problem.as_str(), "all" | "shortest-path-random")
+        {
+            return Err(format!(
+                "--connectivity ne s'applique qu'à shortest-path-random (problème: {})",
+                self.problem
+            ));
+        }
+        if self.maze_generator != MazeGenerator::Backtracker
+            && !matches!(self.problem.as_str(), "all" | "maze")
+        {
+            return Err(format!(
+                "--maze-generator ne s'applique qu'à maze (problème: {})",
+                self.problem
+            ));
+        }
+        if self.negative_edges > 0.0 {
+            if self.problem != "shortest-path-random" {
+                return Err(format!(
+                    "--negative-edges ne s'applique qu'à shortest-path-random (problème: {})",
+                    self.problem
+                ));
+            }
+            if self.algorithm != BellmanFord {
+                return Err(format!(
+                    "--negative-edges: seul Bellman-Ford accepte des coûts négatifs (algorithme: {})",
+                    self.algorithm
+                ));
+            }
+            if self.direction != Direction::Forward {
+                return Err("--negative-edges: recherche avant uniquement".to_string());
+            }
+        }
+        if (self.source.is_some() || self.target.is_some() || self.undirected)
+            && !matches!(self.problem.as_str(), "all" | "shortest-path-file")
+        {
+            return Err(format!(
+                "--source, --target et --undirected ne s'appliquent qu'à shortest-path-file (problème: {})",
+                self.problem
+            ));
+        }
+        if self.graph.is_some()
+            && !matches!(
+                self.problem.as_str(),
+                "all" | "shortest-path-file" | "map-coloring"
+            )
+        {
+            return Err(format!(
+                "--graph ne s'applique qu'à shortest-path-file et map-coloring (problème: {})",
+                self.problem
+            ));
+        }
+        if self.colors != 4 && !matches!(self.problem.as_str(), "all" | "map-coloring") {
+            return Err(format!(
+                "--colors ne s'applique qu'à map-coloring (problème: {})",
+                self.problem
+            ));
+        }
+        if self.map.is_some() && !matches!(self.problem.as_str(), "all" | "shortest-path-map") {
+            return Err(format!(
+                "--map ne s'applique qu'à shortest-path-map (problème: {})",
+                self.problem
+            ));
+        }
+        scenario::validate(self)?;
+        all_pairs::validate(self)?;
+        sweep::validate(self)?;
+        if self.sources == 0 {
+            return Err("--sources doit être au moins 1".to_string());
+        }
+        if self.sources > 1
+            && !matches!(
+                self.problem.as_str(),
+                "all" | "shortest-path" | "shortest-path-file" | "shortest-path-map"
+            )
+        {
+            return Err(format!(
+                "--sources ne s'applique qu'à shortest-path, shortest-path-file et shortest-path-map (problème: {})",
+                self.problem
+            ));
+        }
+        if self.move_cost != MoveCost::Unit
+            && !matches!(self.problem.as_str(), "all" | "taquin" | "taquin-packed")
+        {
+            return Err(format!(
+                "--move-cost ne s'applique qu'au taquin (problème: {})",
+                self.problem
+            ));
+        }
+        if !self.heuristics.is_empty()
+            && !matches!(self.problem.as_str(), "all" | "taquin" | "taquin-packed")
+        {
+            return Err(format!(
+                "--heuristics ne s'applique qu'au taquin (problème: {})",
+                self.problem
+            ));
+        }
+        if let Some(heuristic) = self
+            .heuristics
+            .iter()
+            .enumerate()
+            .find_map(|(i, h)| self.heuristics[..i].contains(h).then_some(h))
+        {
+            return Err(format!("--heuristics: {} apparaît deux fois", heuristic));
+        }
+        if self.pdb_partition.is_some() && !self.heuristics.contains(&Heuristic::PatternDb) {
+            return Err("--pdb-partition suppose --heuristics pattern-db".to_string());
+        }
+        if self.goal.is_some()
+            && !matches!(self.problem.as_str(), "all" | "taquin" | "taquin-packed")
+        {
+            return Err(format!(
+                "--goal ne s'applique qu'au taquin (problème: {})",
+                self.problem
+            ));
+        }
+        if self.problem_script.is_some() {
+            if registry::find("script").is_none() {
+                return Err(
+                    "--problem-script nécessite la fonctionnalité script (cargo build --features script)"
+                        .to_string(),
+                );
+            }
+            if !matches!(self.problem.as_str(), "all" | "script") {
+                return Err(format!(
+                    "--problem-script ne s'applique qu'au problème script (problème: {})",
+                    self.problem
+                ));
+            }
+        }
+        if self.problem_command.is_some() && !matches!(self.problem.as_str(), "all" | "subprocess")
+        {
+            return Err(format!(
+                "--problem-command ne s'applique qu'au problème subprocess (problème: {})",
+                self.problem
+            ));
+        }
+        if self.isolate && self.instances.is_some() {
+            return Err(
+                "--isolate régénère chaque instance à partir de sa graine (--instances non supporté)"
+                    .to_string(),
+            );
+        }
+        if self.isolate && (scenario::enabled(self) || all_pairs::enabled(self)) {
+            return Err(
+                "--isolate n'est pas disponible avec --scenario, --dynamic-obstacles ni --all-pairs"
+                    .to_string(),
+            );
+        }
+        if self.instances.is_some() && self.problem == "all" {
+            return Err("--instances nécessite un problème précis (--problem)".to_string());
+        }
+        if self.instances.is_none() && self.iterations == 0 {
+            return Err("--iterations doit être au moins 1".to_string());
+        }
+
+        match self.problem.as_str() {
+            "all" => Ok(()),
+            name => match registry::find(name) {
+                Some(entry) => (entry.validate)(self),
+                None => Err(format!(
+                    "Problème inconnu: {} (disponibles: {})",
+                    name,
+                    registry::names().join(", ")
+                )),
+            },
+        }
+    }
+}
+
+const MAX_RESEEDS: usize = 16;
+pub(crate) const EARLY_GOAL_SUFFIX: &str = "-early";
+pub(crate) const BACKWARD_SUFFIX: &str = "-backward";
+
+fn elapsed_ms(start: Instant) -> f64 {
+    start.elapsed().as_micros() as f64 / 1000.0
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct AlgorithmSettings {
+    pub(crate) max_depth: usize,
+    pub(crate) memory_limit_kb: Option<usize>,
+    pub(crate) track_solutions: bool,
+    pub(crate) reexpand: bool,
+    pub(crate) cost_bound: Option<usize>,
+    pub(crate) depth_limit: Option<usize>,
+    pub(crate) perimeter_radius: usize,
+    pub(crate) memory_budget: usize,
+    pub(crate) weight: f64,
+    pub(crate) bound: f64,
+    pub(crate) lookahead: usize,
+    pub(crate) step_budget: usize,
+    pub(crate) k: usize,
+    pub(crate) population: usize,
+    pub(crate) generations: usize,
+    pub(crate) seed: u64,
+    pub(crate) tie_breaking: TieBreaking,
+    pub(crate) lazy_duplicates: bool,
+    pub(crate) order_successors: bool,
+    pub(crate) profile: bool,
+}
+
+struct PreparedInstance<P> {
+    problem: Arc<P>,
+    seed: u64,
+    description: String,
+    properties: InstanceProperties,
+    optimal_cost: Option<Option<usize>>,
+}
+
+impl<P> PreparedInstance<P> {
+    fn with_problem(&self, problem: P) -> Self {
+        PreparedInstance {
+            problem: Arc::new(problem),
+            seed: self.seed,
+            description: self.description.clone(),
+            properties: self.properties.clone(),
+            optimal_cost: self.optimal_cost,
+        }
+    }
+}
+
+struct TaskContext {
+    problem_name: String,
+    total: usize,
+    unit_costs: bool,
+    admissible: bool,
+    settings: AlgorithmSettings,
+}
+
+pub struct ProblemRun {
+    pub results: Vec<BenchmarkResult>,
+    pub preparation: PreparationReport,
+    pub scheduling: SchedulingReport,
 }
 
 pub struct BenchmarkRunner {
     config: BenchmarkConfig,
+    history: HashMap<(String, String), f64>,
+    progress: Progress,
+    coordinator: Option<Arc<Coordinator>>,
+    budget: Option<Budget>,
 }
 
 impl BenchmarkRunner {
     pub fn new(config: BenchmarkConfig) -> Self {
-        BenchmarkRunner { config }
+        let history = Self::load_history(&config);
+        BenchmarkRunner {
+            budget: config.budget_secs.map(Budget::new),
+            config,
+            history,
+            progress: Progress::default(),
+            coordinator: None,
+        }
     }
 
-    fn get_algorithm_names(&self, for_taquin: bool) -> Result<Vec<&str>, String> {
-        match self.config.algorithm.as_str() {
-            "all" => {
-                if for_taquin {
-                    Ok(vec!["BFS", "DFS", "ID", "A*-Manhattan", "IDA*-Manhattan"])
-                } else {
-                    Ok(vec!["BFS", "DFS", "ID", "A*", "IDA*"])
-                }
-            }
-            "bfs" => Ok(vec!["BFS"]),
-            "dfs" => Ok(vec!["DFS"]),
-            "id" => Ok(vec!["ID"]),
-            "astar" => {
-                if for_taquin {
-                    Ok(vec!["A*-Manhattan"])
-                } else {
-                    Ok(vec!["A*"])
-                }
-            }
-            "idastar" => {
-                if for_taquin {
-                    Ok(vec!["IDA*-Manhattan"])
-                } else {
-                    Ok(vec!["IDA*"])
+    pub fn set_coordinator(&mut self, coordinator: Arc<Coordinator>) {
+        self.coordinator = Some(coordinator);
+    }
+
+    pub fn progress_mut(&mut self) -> &mut Progress {
+        &mut self.progress
+    }
+
+    pub fn config(&self) -> &BenchmarkConfig {
+        &self.config
+    }
+
+    fn load_history(config: &BenchmarkConfig) -> HashMap<(String, String), f64> {
+        let previous: Vec<BenchmarkResult> = std::fs::read_to_string(&config.output_file)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+
+        let mut totals: HashMap<(String, String), (f64, usize)> = HashMap::new();
+        for result in previous
+            .into_iter()
+            .filter(|result| result.status != SearchStatus::Skipped)
+        {
+            let time_ms = if result.status == SearchStatus::Timeout {
+                result
+                    .metrics
+                    .time_ms
+                    .max(config.timeout_secs as f64 * 1000.0)
+            } else {
+                result.metrics.time_ms
+            };
+            let entry = totals
+                .entry((result.algorithm, result.problem))
+                .or_default();
+            entry.0 += time_ms;
+            entry.1 += 1;
+        }
+
+        totals
+            .into_iter()
+            .map(|(key, (total, count))| (key, total / count as f64))
+            .collect()
+    }
+
+    fn static_cost(algo_name: &str) -> f64 {
+        let (algo_name, _) = sweep::split(algo_name);
+        match algo_name.split('-').next().unwrap_or(algo_name) {
+            "ID" => 5.0,
+            "IDA*" | "Perimeter" => 4.0,
+            "DFS" | "DLS" => 3.0,
+            "BFS" | "Frontier" | "BFHS" | "UCS" | "Yen" => 2.0,
+            _ => 1.0,
+        }
+    }
+
+    pub(crate) fn algorithm_names(config: &BenchmarkConfig, heuristics: &[String]) -> Vec<String> {
+        let informed = |name: &str| match heuristics {
+            [] => vec![name.to_string()],
+            heuristics => heuristics
+                .iter()
+                .map(|h| format!("{}-{}", name, h))
+                .collect(),
+        };
+        let goal_tests: &[&str] = match config.goal_test {
+            GoalTest::Expansion => &[""],
+            GoalTest::Generation => &[EARLY_GOAL_SUFFIX],
+            GoalTest::Both => &["", EARLY_GOAL_SUFFIX],
+        };
+        let uninformed = |name: &str| {
+            goal_tests
+                .iter()
+                .map(|suffix| format!("{}{}", name, suffix))
+                .collect::<Vec<_>>()
+        };
+
+        let forward = match config.algorithm {
+            AlgorithmChoice::All => [
+                uninformed("BFS"),
+                uninformed("DFS"),
+                vec!["ID".to_string()],
+                vec!["UCS".to_string()],
+                informed("A*"),
+                informed("IDA*"),
+            ]
+            .concat(),
+            AlgorithmChoice::Bfs => uninformed("BFS"),
+            AlgorithmChoice::Dfs => uninformed("DFS"),
+            AlgorithmChoice::Id => vec!["ID".to_string()],
+            AlgorithmChoice::Dls => vec!["DLS".to_string()],
+            AlgorithmChoice::Frontier => vec!["Frontier".to_string()],
+            AlgorithmChoice::Yen => vec!["Yen".to_string()],
+            AlgorithmChoice::BellmanFord => vec!["Bellman-Ford".to_string()],
+            AlgorithmChoice::Ucs => vec!["UCS".to_string()],
+            AlgorithmChoice::Astar => informed("A*"),
+            AlgorithmChoice::Greedy => informed("Greedy"),
+            AlgorithmChoice::Bfhs => informed("BFHS"),
+            AlgorithmChoice::Idastar => informed("IDA*"),
+            AlgorithmChoice::Perimeter => informed("Perimeter"),
+            AlgorithmChoice::Bidirectional => informed("BiA*"),
+            AlgorithmChoice::Sma => informed("SMA*"),
+            AlgorithmChoice::Awastar => informed("AWA*"),
+            AlgorithmChoice::Ees => informed("EES"),
+            AlgorithmChoice::Lrta => informed("LRTA*"),
+            AlgorithmChoice::Ehc => informed("EHC"),
+            AlgorithmChoice::Ga => informed("GA"),
+        };
+        let backward = || {
+            let mut names: Vec<String> = Vec::new();
+            for name in &forward {
+                let base = match Self::heuristic_of(name, heuristics) {
+                    Some(h) => &name[..name.len() - h.len() - 1],
+                    None => name.as_str(),
+                };
+                let name = format!("{}{}", base, BACKWARD_SUFFIX);
+                if !names.contains(&name) {
+                    names.push(name);
                 }
             }
-            _ => Err(format!("Algorithme inconnu: {}", self.config.algorithm)),
+            names
+        };
+
+        match config.direction {
+            Direction::Forward => forward,
+            Direction::Backward => backward(),
+            Direction::Both => [forward.clone(), backward()].concat(),
         }
     }
 
-    fn execute_with_timeout<P: Problem + Clone + Send + 'static>(
+    fn has_heuristic(algo_name: &str, heuristic: &str) -> bool {
+        sweep::split(algo_name)
+            .0
+            .strip_suffix(heuristic)
+            .is_some_and(|base| base.ends_with('-'))
+    }
+
+    fn heuristic_of<'a>(algo_name: &str, heuristics: &'a [String]) -> Option<&'a str> {
+        heuristics
+            .iter()
+            .map(String::as_str)
+            .find(|h| Self::has_heuristic(algo_name, h))
+    }
+
+    fn heuristic_variant<F: ProblemFactory>(
         &self,
-        problem: &P,
+        instance: &PreparedInstance<F::Instance>,
+        algo_name: &str,
+    ) -> Option<PreparedInstance<F::Instance>> {
+        let heuristic = self
+            .config
+            .heuristics
+            .iter()
+            .copied()
+            .find(|h| Self::has_heuristic(algo_name, h.label()))?;
+        F::with_heuristic(&instance.problem, heuristic)
+            .map(|problem| instance.with_problem(problem))
+    }
+
+    fn execute_with_timeout<P>(
+        &self,
+        problem: &Arc<P>,
         algo_name: &str,
         timeout_duration: Duration,
-        max_depth: usize,
-    ) -> (SearchResult, Option<String>) {
+        settings: AlgorithmSettings,
+        live: Option<SharedMetrics>,
+        trace: Option<SharedTrace<P::State>>,
+    ) -> (SearchResult<P::Action>, Option<ExternalCalls>)
+    where
+        P: BackwardProblem + Send + Sync + 'static,
+        P::State: Send,
+        P::Action: Send,
+    {
+        let trace_limit = self.config.trace_limit.unwrap_or(usize::MAX);
         if self.config.timeout_secs > 0 {
             let (tx, rx) = channel();
-            let problem_clone = problem.clone();
+            let problem_clone = Arc::clone(problem);
             let algo = algo_name.to_string();
-            let shared_metrics = SharedMetrics::new();
+            let shared_metrics = live.unwrap_or_default();
             let shared_metrics_clone = shared_metrics.clone();
 
             std::thread::spawn(move || {
-                let res = Self::execute_algorithm_with_shared(
+                problem_clone.take_external_calls();
+                let res = Self::execute_traced(
                     &algo,
-                    &problem_clone,
-                    shared_metrics_clone,
-                    max_depth,
+                    problem_clone.as_ref(),
+                    settings,
+                    Some(shared_metrics_clone),
+                    trace.map(|trace| (trace, trace_limit)),
                 );
-                let _ = tx.send(res);
+                let _ = tx.send((res, problem_clone.take_external_calls()));
             });
 
             match rx.recv_timeout(timeout_duration) {
-                Ok(res) => (res, None),
+                Ok(res) => res,
                 Err(RecvTimeoutError::Timeout) => {
-                    let partial_metrics = shared_metrics.get();
+                    let mut metrics = shared_metrics.get();
+                    let solution = shared_metrics.incumbent().and_then(|incumbent| {
+                        let actions =
+                            validation::decode_actions(problem.as_ref(), &incumbent.actions)?;
+                        metrics.solution_length = actions.len();
+                        metrics.solution_cost = incumbent.cost;
+                        Some(actions)
+                    });
                     (
                         SearchResult {
-                            solution: None,
-                            metrics: partial_metrics,
-                            status: 1,
+                            solution,
+                            metrics,
+                            status: SearchStatus::Timeout,
+                            profile: None,
+                            improvements: shared_metrics.improvements(),
                         },
-                        Some(format!(
-                            "Timeout après {} secondes",
-                            self.config.timeout_secs
-                        )),
+                        None,
                     )
                 }
                 Err(_) => (
                     SearchResult {
                         solution: None,
                         metrics: crate::benchmarking::Metrics::default(),
-                        status: 2,
+                        status: SearchStatus::Error("Erreur de communication".to_string()),
+                        profile: None,
+                        improvements: Vec::new(),
                     },
-                    Some("Erreur de communication".to_string()),
+                    None,
                 ),
             }
         } else {
-            (Self::execute_algorithm(algo_name, problem, max_depth), None)
+            problem.take_external_calls();
+            let res = Self::execute_traced(
+                algo_name,
+                problem.as_ref(),
+                settings,
+                live,
+                trace.map(|trace| (trace, trace_limit)),
+            );
+            (res, problem.take_external_calls())
         }
     }
 
-    fn execute_algorithm<P: Problem>(
+    fn execute_traced<P: BackwardProblem>(
         algo_name: &str,
         problem: &P,
-        max_depth: usize,
-    ) -> SearchResult {
+        settings: AlgorithmSettings,
+        shared: Option<SharedMetrics>,
+        trace: Option<(SharedTrace<P::State>, usize)>,
+    ) -> SearchResult<P::Action> {
+        match algo_name.strip_suffix(BACKWARD_SUFFIX) {
+            Some(base) => {
+                let backward = Backward::new(problem);
+                let result = Self::execute_traced_forward(base, &backward, settings, shared, trace);
+                backward.into_forward(result)
+            }
+            None => Self::execute_traced_forward(algo_name, problem, settings, shared, trace),
+        }
+    }
+
+    fn execute_traced_forward<P: BackwardProblem>(
+        algo_name: &str,
+        problem: &P,
+        settings: AlgorithmSettings,
+        shared: Option<SharedMetrics>,
+        trace: Option<(SharedTrace<P::State>, usize)>,
+    ) -> SearchResult<P::Action> {
+        match trace {
+            Some((trace, limit)) => {
+                let traced = Traced::new(problem)
+                    .with_shared_trace(trace)
+                    .with_edges(false)
+                    .with_max_expansions(limit);
+                Self::execute_algorithm(algo_name, &traced, settings, shared)
+            }
+            None => Self::execute_algorithm(algo_name, problem, settings, shared),
+        }
+    }
+
+    pub(crate) fn execute_algorithm<P: BackwardProblem>(
+        algo_name: &str,
+        problem: &P,
+        settings: AlgorithmSettings,
+        shared: Option<SharedMetrics>,
+    ) -> SearchResult<P::Action> {
+        let max_depth = settings.max_depth;
+        let track = settings.track_solutions;
+        let (algo_name, early_goal_test) = match algo_name.strip_suffix(EARLY_GOAL_SUFFIX) {
+            Some(base) => (base, true),
+            None => (algo_name, false),
+        };
+        let informed = algo_name
+            .split_once('-')
+            .map_or(algo_name, |(base, _)| base);
+        let bfs = match settings.memory_limit_kb {
+            Some(limit) => bfs::BFS::with_memory_limit(limit),
+            None => bfs::BFS::new(),
+        }
+        .with_solution_tracking(track)
+        .with_reexpansion(settings.reexpand)
+        .with_early_goal_test(early_goal_test)
+        .with_cost_bound(settings.cost_bound);
+        let astar = match settings.memory_limit_kb {
+            Some(limit) => astar::AStar::with_memory_limit(limit),
+            None => astar::AStar::new(),
+        }
+        .with_solution_tracking(track)
+        .with_reexpansion(settings.reexpand)
+        .with_cost_bound(settings.cost_bound)
+        .with_tie_breaking(settings.tie_breaking)
+        .with_lazy_duplicate_detection(settings.lazy_duplicates);
+
         match algo_name {
-            "BFS" => bfs::BFS.search(problem),
-            "DFS" => dfs::DFS::with_max_depth(max_depth).search(problem),
-            "ID" => iterative_deepening::IterativeDeepening::new(max_depth).search(problem),
-            "A*-Manhattan" | "A*" => astar::AStar.search(problem),
-            "IDA*-Manhattan" | "IDA*" => idastar::IDAStar::new(max_depth * 2).search(problem),
+            "BFS" => Self::run_search(&bfs, problem, shared, settings.profile),
+            "DFS" => Self::run_search(
+                &dfs::DFS::with_max_depth(max_depth)
+                    .with_solution_tracking(track)
+                    .with_reexpansion(settings.reexpand)
+                    .with_early_goal_test(early_goal_test)
+                    .with_cost_bound(settings.cost_bound)
+                    .with_successor_ordering(settings.order_successors),
+                problem,
+                shared,
+                settings.profile,
+            ),
+            "ID" => Self::run_search(
+                &iterative_deepening::IterativeDeepening::new(max_depth)
+                    .with_solution_tracking(track)
+                    .with_cost_bound(settings.cost_bound)
+                    .with_successor_ordering(settings.order_successors),
+                problem,
+                shared,
+                settings.profile,
+            ),
+            "DLS" => Self::run_search(
+                &depth_limited::DepthLimited::new(settings.depth_limit.unwrap_or(max_depth))
+                    .with_solution_tracking(track)
+                    .with_cost_bound(settings.cost_bound)
+                    .with_successor_ordering(settings.order_successors),
+                problem,
+                shared,
+                settings.profile,
+            ),
+            "Frontier" => Self::run_search(
+                &frontier::FrontierSearch::new(max_depth).with_solution_tracking(track),
+                problem,
+                shared,
+                settings.profile,
+            ),
+            "Bellman-Ford" => Self::run_search(
+                &bellman_ford::BellmanFord::new().with_solution_tracking(track),
+                problem,
+                shared,
+                settings.profile,
+            ),
+            "Yen" => Self::run_search(
+                &yen::Yen::new(settings.k).with_solution_tracking(track),
+                problem,
+                shared,
+                settings.profile,
+            ),
+            "UCS" => Self::run_search(
+                &uniform_cost::UniformCost::new()
+                    .with_solution_tracking(track)
+                    .with_cost_bound(settings.cost_bound),
+                problem,
+                shared,
+                settings.profile,
+            ),
+            _ if informed == "A*" => Self::run_search(&astar, problem, shared, settings.profile),
+            _ if informed == "Greedy" => Self::run_search(
+                &best_first::Greedy::new()
+                    .with_memory_limit_kb(settings.memory_limit_kb)
+                    .with_solution_tracking(track)
+                    .with_cost_bound(settings.cost_bound),
+                problem,
+                shared,
+                settings.profile,
+            ),
+            _ if informed == "BFHS" => Self::run_search(
+                &bfhs::BreadthFirstHeuristicSearch::new(max_depth).with_solution_tracking(track),
+                problem,
+                shared,
+                settings.profile,
+            ),
+            _ if informed == "IDA*" => Self::run_search(
+                &idastar::IDAStar::new(max_depth * 2)
+                    .with_cost_bound(settings.cost_bound)
+                    .with_successor_ordering(settings.order_successors),
+                problem,
+                shared,
+                settings.profile,
+            ),
+            _ if informed == "Perimeter" => Self::run_perimeter(problem, settings, shared),
+            _ if informed == "BiA*" => Self::run_bidirectional(problem, settings, shared),
+            _ if informed == "AWA*" => Self::run_search(
+                &anytime::AnytimeWeightedAStar::new(settings.weight)
+                    .with_cost_bound(settings.cost_bound),
+                problem,
+                shared,
+                settings.profile,
+            ),
+            _ if informed == "SMA*" => Self::run_search(
+                &sma::SMAStar::new(settings.memory_budget).with_cost_bound(settings.cost_bound),
+                problem,
+                shared,
+                settings.profile,
+            ),
+            _ if informed == "LRTA*" => Self::run_search(
+                &lrta::LRTAStar::new(settings.lookahead, settings.step_budget)
+                    .with_max_steps(max_depth * 100),
+                problem,
+                shared,
+                settings.profile,
+            ),
+            _ if informed == "EES" => Self::run_search(
+                &ees::ExplicitEstimationSearch::new(settings.bound).with_solution_tracking(track),
+                problem,
+                shared,
+                settings.profile,
+            ),
+            _ if informed == "EHC" => Self::run_search(
+                &ehc::EnforcedHillClimbing::new().with_solution_tracking(track),
+                problem,
+                shared,
+                settings.profile,
+            ),
+            _ if informed == "GA" => Self::run_search(
+                &genetic::GeneticAlgorithm::new(
+                    settings.population,
+                    settings.generations,
+                    max_depth,
+                )
+                .with_seed(settings.seed)
+                .with_solution_tracking(track),
+                problem,
+                shared,
+                settings.profile,
+            ),
             _ => SearchResult {
                 solution: None,
                 metrics: crate::benchmarking::Metrics::default(),
-                status: 2,
+                status: SearchStatus::Error(format!("Algorithme inconnu: {}", algo_name)),
+                profile: None,
+                improvements: Vec::new(),
             },
         }
     }
 
-    fn execute_algorithm_with_shared<P: Problem>(
-        algo_name: &str,
+    fn run_perimeter<P: BackwardProblem>(
         problem: &P,
-        shared: SharedMetrics,
-        max_depth: usize,
-    ) -> SearchResult {
-        match algo_name {
-            "BFS" => bfs::BFS.search_with_shared_metrics(problem, shared),
-            "DFS" => {
-                dfs::DFS::with_max_depth(max_depth).search_with_shared_metrics(problem, shared)
-            }
-            "ID" => iterative_deepening::IterativeDeepening::new(max_depth)
-                .search_with_shared_metrics(problem, shared),
-            "A*-Manhattan" | "A*" => astar::AStar.search_with_shared_metrics(problem, shared),
-            "IDA*-Manhattan" | "IDA*" => {
-                idastar::IDAStar::new(max_depth * 2).search_with_shared_metrics(problem, shared)
-            }
-            _ => SearchResult {
+        settings: AlgorithmSettings,
+        shared: Option<SharedMetrics>,
+    ) -> SearchResult<P::Action> {
+        if problem.goal_states().is_empty() {
+            return SearchResult {
+                solution: None,
+                metrics: crate::benchmarking::Metrics::default(),
+                status: SearchStatus::Error(
+                    "Périmètre: le problème n'énumère pas ses états but".to_string(),
+                ),
+                profile: None,
+                improvements: Vec::new(),
+            };
+        }
+        let perimeter = Perimeter::build(problem, settings.perimeter_radius);
+        let mut recorder = Self::recorder(shared, settings.profile);
+        recorder.set_precompute_ms(perimeter.build_ms());
+        let result = idastar::IDAStar::new(settings.max_depth * 2)
+            .with_cost_bound(settings.cost_bound)
+            .search_with_recorder(&PerimeterProblem::new(problem, &perimeter), recorder);
+        perimeter.complete(problem, result)
+    }
+
+    fn run_bidirectional<P: BackwardProblem>(
+        problem: &P,
+        settings: AlgorithmSettings,
+        shared: Option<SharedMetrics>,
+    ) -> SearchResult<P::Action> {
+        if problem.goal_states().is_empty() {
+            return SearchResult {
                 solution: None,
                 metrics: crate::benchmarking::Metrics::default(),
-                status: 2,
+                status: SearchStatus::Error(
+                    "BiA*: le problème n'énumère pas ses états but".to_string(),
+                ),
+                profile: None,
+                improvements: Vec::new(),
+            };
+        }
+        bidirectional::BidirectionalAStar::new()
+            .with_cost_bound(settings.cost_bound)
+            .search_with_recorder(problem, Self::recorder(shared, settings.profile))
+    }
+
+    fn recorder(shared: Option<SharedMetrics>, profile: bool) -> MetricsRecorder {
+        let recorder = match shared {
+            Some(shared) => MetricsRecorder::with_shared(shared),
+            None => MetricsRecorder::new(),
+        };
+        if profile {
+            recorder.with_profile()
+        } else {
+            recorder
+        }
+    }
+
+    fn run_search<A: SearchAlgorithm, P: Problem>(
+        algorithm: &A,
+        problem: &P,
+        shared: Option<SharedMetrics>,
+        profile: bool,
+    ) -> SearchResult<P::Action> {
+        algorithm.search_with_recorder(problem, Self::recorder(shared, profile))
+    }
+
+    fn reseed_duplicates<F: ProblemFactory>(
+        config: &BenchmarkConfig,
+        generated: &mut [(u64, F::Instance)],
+    ) -> usize {
+        let mut seen = HashSet::new();
+        let mut duplicates = 0;
+
+        for (seed, problem) in generated.iter_mut() {
+            let mut attempts = 0;
+            while !seen.insert(F::serialize(problem)) {
+                if attempts == MAX_RESEEDS {
+                    duplicates += 1;
+                    break;
+                }
+                *seed = seeding::reseed(*seed);
+                *problem = F::generate(config, *seed);
+                attempts += 1;
+            }
+        }
+
+        duplicates
+    }
+
+    fn task_context<F: ProblemFactory>(&self, total: usize) -> TaskContext {
+        TaskContext {
+            problem_name: F::label(&self.config),
+            total,
+            unit_costs: F::unit_costs(&self.config),
+            admissible: F::admissible_heuristic(&self.config),
+            settings: AlgorithmSettings {
+                max_depth: F::max_depth(&self.config),
+                memory_limit_kb: (self.config.memory_limit_mb > 0)
+                    .then_some(self.config.memory_limit_mb * 1024),
+                track_solutions: self.config.track_solutions,
+                reexpand: self.config.reexpand,
+                cost_bound: self.config.cost_bound,
+                depth_limit: self.config.depth_limit,
+                perimeter_radius: self.config.perimeter_radius,
+                memory_budget: self.config.memory_budget,
+                weight: self.config.weight,
+                bound: self.config.bound,
+                lookahead: self.config.lookahead,
+                step_budget: self.config.step_budget,
+                k: self.config.k,
+                population: self.config.population,
+                generations: self.config.generations,
+                seed: self.config.seed,
+                tie_breaking: self.config.tie_breaking,
+                lazy_duplicates: self.config.lazy_duplicates,
+                order_successors: self.config.order_successors,
+                profile: self.config.profile,
             },
         }
     }
 
-    fn execute_benchmarks<P, F, G>(
+    pub(crate) fn outcome(result: &BenchmarkResult) -> String {
+        let ipc = result
+            .properties
+            .get("ipc_ms")
+            .and_then(|ms| ms.as_f64())
+            .map(|ms| format!("\t IPC {:.2}ms", ms))
+            .unwrap_or_default();
+        let peak = result
+            .properties
+            .get("peak_rss_kb")
+            .and_then(|kb| kb.as_u64())
+            .map(|kb| format!("\t pic RSS {} Ko", kb))
+            .unwrap_or_default();
+        let precompute = Some(result.metrics.precompute_ms)
+            .filter(|&ms| ms > 0.0)
+            .map(|ms| format!("\t précalcul {:.2}ms", ms))
+            .unwrap_or_default();
+        let paths = Some(&result.metrics)
+            .filter(|metrics| metrics.paths_found > 0)
+            .map(|metrics| {
+                format!(
+                    "\t {} chemins ({} candidats générés)",
+                    metrics.paths_found, metrics.paths_generated
+                )
+            })
+            .unwrap_or_default();
+        let escapes = Some(result.metrics.plateau_escapes)
+            .filter(|&count| count > 0)
+            .map(|count| format!("\t {} sorties de plateau", count))
+            .unwrap_or_default();
+        let duplicates = Some(result.metrics.duplicates)
+            .filter(|&count| count > 0)
+            .map(|count| format!("\t {} doublons écartés", count))
+            .unwrap_or_default();
+        let underconsistent = Some(result.metrics.underconsistent)
+            .filter(|&count| count > 0)
+            .map(|count| format!("\t {} expansions sous-cohérentes", count))
+            .unwrap_or_default();
+        let generations = Some(result.metrics.generations)
+            .filter(|&count| count > 0)
+            .map(|count| format!("\t {} générations", count))
+            .unwrap_or_default();
+        let signed = result
+            .metrics
+            .signed_cost
+            .map(|cost| format!("\t coût signé {}", cost))
+            .unwrap_or_default();
+        let steps = Some(&result.metrics)
+            .filter(|metrics| metrics.steps > 0)
+            .map(|metrics| {
+                format!(
+                    "\t {} pas (planification max {:.3}ms, moy. {:.3}ms)",
+                    metrics.steps, metrics.max_step_ms, metrics.mean_step_ms
+                )
+            })
+            .unwrap_or_default();
+        match &result.error {
+            None => format!(
+                "✓ {}{}{}{}{}{}{}{}{}{}{}",
+                result.metrics.summary(),
+                signed,
+                precompute,
+                steps,
+                paths,
+                escapes,
+                duplicates,
+                underconsistent,
+                generations,
+                ipc,
+                peak
+            ),
+            Some(err) if result.properties.contains_key("incumbent_cost") => format!(
+                "✗ {} (meilleure solution: coût {} après {:.2}ms, {}v/{}g)",
+                err,
+                result.metrics.solution_cost,
+                result.properties["incumbent_ms"]
+                    .as_f64()
+                    .unwrap_or_default(),
+                result.metrics.nodes_visited,
+                result.metrics.nodes_generated
+            ),
+            Some(err) if result.metrics.nodes_visited > 0 => format!(
+                "✗ {} (partiel: {}v/{}g)",
+                err, result.metrics.nodes_visited, result.metrics.nodes_generated
+            ),
+            Some(err) => format!("✗ {}", err),
+        }
+    }
+
+    fn run_task<P>(
         &self,
-        algorithm_names: Vec<&str>,
-        problem_generator: F,
-        problem_name_fn: Arc<dyn Fn(usize) -> String + Send + Sync>,
-        initial_state_formatter: G,
-        max_depth: usize,
-    ) -> Result<Vec<BenchmarkResult>, Box<dyn std::error::Error>>
+        context: &TaskContext,
+        index: usize,
+        instance_id: usize,
+        instance: &PreparedInstance<P>,
+        algo_name: &str,
+    ) -> (BenchmarkResult, f64)
     where
-        P: Problem + Clone + Send + Sync + 'static,
+        P: BackwardProblem + Send + Sync + 'static,
         P::State: Send,
-        F: Fn(usize) -> P + Send + Sync + Clone,
-        G: Fn(&P) -> String + Send + Sync,
+        P::Action: Send,
     {
-        let all_tasks: Vec<_> = algorithm_names
-            .iter()
-            .flat_map(|algo_name| {
-                let gen = problem_generator.clone();
-                (0..self.config.iterations).map(move |instance_id| {
-                    let problem = gen(instance_id);
-                    (instance_id, problem, *algo_name)
-                })
-            })
-            .collect();
+        let problem = &instance.problem;
+        let total = context.total;
+        let task_start = Instant::now();
+        let live =
+            (self.progress.is_observed() || self.config.timeout_secs > 0).then(SharedMetrics::new);
+        if let Some(metrics) = live.as_ref().filter(|_| self.progress.is_observed()) {
+            self.progress.send(ProgressEvent::TaskStarted {
+                task: index,
+                algorithm: algo_name.to_string(),
+                instance: instance_id,
+                thread: rayon::current_thread_index(),
+                metrics: metrics.clone(),
+            });
+        }
+        if self.progress.is_console() {
+            println!(
+                "  Instance {}\t {}/{}\t Démarrage...",
+                algo_name,
+                instance_id + 1,
+                total
+            );
+        }
 
-        println!(
-            "\nExécution de {} tâches en parallèle sur {} threads...\n",
-            all_tasks.len(),
-            self.config.threads
+        let mut properties = instance.properties.clone();
+        let (base_name, settings) = sweep::resolve(algo_name, context.settings, &mut properties);
+        let trace = self.config.trace.then(SharedTrace::default);
+        let (mut result, external) = self.execute_with_timeout(
+            problem,
+            base_name,
+            Duration::from_secs(self.config.timeout_secs),
+            settings,
+            live.clone(),
+            trace.clone(),
         );
+        if let Some(trace) = trace {
+            let path = Path::new(&Self::trace_dir(&self.config.output_file)).join(format!(
+                "{}-{}-{}.trace",
+                file_stem(&context.problem_name),
+                file_stem(algo_name),
+                instance_id + 1
+            ));
+            let header = format!(
+                "{} sur {}, instance {} (graine {}): {}",
+                algo_name,
+                context.problem_name,
+                instance_id + 1,
+                instance.seed,
+                result.status
+            );
+            let trace = std::mem::take(&mut *trace.lock().unwrap());
+            if let Err(e) = writer::write_trace(&path, &header, problem.as_ref(), &trace) {
+                self.progress.log(format!(
+                    "  Erreur d'écriture de la trace {}: {}",
+                    path.display(),
+                    e
+                ));
+            }
+        }
 
-        let timeout_duration = Duration::from_secs(self.config.timeout_secs);
-        let problem_name = problem_name_fn;
-
-        let results: Vec<BenchmarkResult> = all_tasks
-            .par_iter()
-            .filter_map(|(instance_id, problem, algo_name)| {
-                println!(
-                    "  Instance {}\t {}/{}\t Démarrage...",
-                    algo_name,
-                    instance_id + 1,
-                    self.config.iterations
-                );
+        let incumbent = live
+            .and_then(|live| live.incumbent())
+            .filter(|_| result.status == SearchStatus::Timeout && result.solution.is_some());
+        if let Some(incumbent) = &incumbent {
+            properties.insert("incumbent_cost".to_string(), incumbent.cost.into());
+            properties.insert("incumbent_ms".to_string(), incumbent.time_ms.into());
+        }
+        if let Some(actions) = result.solution.as_ref().filter(|_| {
+            (result.status.is_solved() || incumbent.is_some()) && self.config.track_solutions
+        }) {
+            if let Err(e) =
+                validation::validate_solution(problem.as_ref(), actions, &result.metrics)
+            {
+                result.status = SearchStatus::Error(format!("Solution invalide: {}", e));
+            }
+        }
 
-                let (result, error_msg) =
-                    self.execute_with_timeout(problem, algo_name, timeout_duration, max_depth);
-
-                let status = if result.status == 0 { "✓" } else { "✗" };
-                let summary = if result.status == 0 {
-                    result.metrics.summary()
-                } else if let Some(ref err) = error_msg {
-                    if result.metrics.nodes_visited > 0 {
-                        format!(
-                            "{} (partiel: {}v/{}g)",
-                            err, result.metrics.nodes_visited, result.metrics.nodes_generated
-                        )
-                    } else {
-                        err.clone()
-                    }
-                } else {
-                    "Pas de solution trouvée".to_string()
-                };
+        let mut final_error = match &result.status {
+            SearchStatus::Solved => None,
+            SearchStatus::Timeout => Some(format!(
+                "Timeout après {} secondes",
+                self.config.timeout_secs
+            )),
+            status => Some(status.to_string()),
+        };
 
-                println!(
-                    "  Instance {}\t {}/{}\t {} {}",
-                    algo_name,
-                    instance_id + 1,
-                    self.config.iterations,
-                    status,
-                    summary
+        let solved = result.status.is_solved();
+        let optimal = instance.optimal_cost.and_then(|reference| match reference {
+            Some(cost) if solved => Some(result.metrics.solution_cost == cost),
+            None if solved => Some(false),
+            _ => None,
+        });
+        let guarantee = match consistency::suboptimality_bound(
+            base_name,
+            context.unit_costs,
+            context.admissible,
+        ) {
+            Some(_) if base_name.starts_with("AWA*") && !solved => Some(settings.weight),
+            None if base_name.starts_with("EES") && context.admissible => Some(settings.bound),
+            bound => bound,
+        };
+        if let Some(bound) = guarantee {
+            properties.insert("suboptimality_bound".to_string(), bound.into());
+        }
+        if let Some((bound, cost)) =
+            guarantee
+                .zip(instance.optimal_cost.flatten())
+                .filter(|&(bound, cost)| {
+                    solved && result.metrics.solution_cost as f64 > bound * cost as f64
+                })
+        {
+            result.status = SearchStatus::Error(format!(
+                "Borne de sous-optimalité violée: coût {} > {} × optimal {}",
+                result.metrics.solution_cost, bound, cost
+            ));
+            final_error = Some(result.status.to_string());
+        }
+        if let Some(cost) = instance.optimal_cost.flatten().filter(|_| solved) {
+            properties.insert(
+                "optimality_gap".to_string(),
+                consistency::optimality_gap(result.metrics.solution_cost, cost).into(),
+            );
+        }
+
+        if base_name.starts_with("A*") {
+            properties.insert(
+                "tie_breaking".to_string(),
+                settings.tie_breaking.to_string().into(),
+            );
+            properties.insert(
+                "lazy_duplicates".to_string(),
+                settings.lazy_duplicates.into(),
+            );
+        }
+        if matches!(
+            base_name.split('-').next(),
+            Some("DFS" | "ID" | "DLS" | "IDA*")
+        ) {
+            properties.insert(
+                "order_successors".to_string(),
+                settings.order_successors.into(),
+            );
+        }
+        if let Some(bound) = settings.cost_bound {
+            let met = solved && result.metrics.solution_cost <= bound;
+            properties.insert("cost_bound".to_string(), bound.into());
+            properties.insert("bound_met".to_string(), met.into());
+            if met {
+                properties.insert("bound_time_ms".to_string(), result.metrics.time_ms.into());
+            }
+        }
+        if result.metrics.precompute_ms > 0.0 {
+            properties.insert(
+                "precompute_ms".to_string(),
+                result.metrics.precompute_ms.into(),
+            );
+        }
+        if let Some(external) = external {
+            properties.insert("ipc_calls".to_string(), external.calls.into());
+            properties.insert("ipc_ms".to_string(), external.time_ms.into());
+            if result.metrics.time_ms > 0.0 {
+                properties.insert(
+                    "ipc_share".to_string(),
+                    (external.time_ms / result.metrics.time_ms).min(1.0).into(),
                 );
+            }
+        }
 
-                let (final_status, final_error) = if result.status == 0 {
-                    (0, None)
-                } else if error_msg
-                    .as_ref()
-                    .map(|e| e.contains("Timeout"))
-                    .unwrap_or(false)
-                {
-                    (1, error_msg)
-                } else {
-                    (
-                        2,
-                        error_msg.or_else(|| Some("Pas de solution trouvée".to_string())),
-                    )
-                };
+        let result = BenchmarkResult {
+            algorithm: algo_name.to_string(),
+            problem: context.problem_name.clone(),
+            problem_size: self.config.size,
+            instance_id,
+            seed: instance.seed,
+            status: result.status,
+            metrics: result.metrics,
+            timestamp: chrono::Local::now().to_rfc3339(),
+            hasher: HASHER_NAME.to_string(),
+            initial_state: Some(instance.description.clone()),
+            solution: result
+                .solution
+                .filter(|_| self.config.track_solutions)
+                .map(|actions| actions.iter().map(|a| a.to_string()).collect()),
+            error: final_error,
+            optimal,
+            cost_optimal: (!context.unit_costs).then_some(guarantee == Some(1.0)),
+            properties,
+            profile: result.profile,
+            improvements: result.improvements,
+            host: None,
+        };
 
-                Some(BenchmarkResult {
+        if self.progress.is_console() {
+            println!(
+                "  Instance {}\t {}/{}\t {}",
+                algo_name,
+                instance_id + 1,
+                total,
+                Self::outcome(&result)
+            );
+        }
+        if optimal == Some(false) {
+            self.progress.log(format!(
+                "  Instance {}\t {}/{}\t ⚠ solution non optimale (coût {}, référence {})",
+                algo_name,
+                instance_id + 1,
+                total,
+                result.metrics.solution_cost,
+                instance
+                    .optimal_cost
+                    .flatten()
+                    .map_or("aucune".to_string(), |cost| cost.to_string())
+            ));
+        }
+
+        let time_ms = elapsed_ms(task_start);
+        if self.progress.is_observed() {
+            self.progress.send(ProgressEvent::TaskFinished {
+                task: index,
+                result: Box::new(result.clone()),
+                time_ms,
+            });
+        }
+        (result, time_ms)
+    }
+
+    fn run_isolated<P: Problem>(
+        &self,
+        context: &TaskContext,
+        index: usize,
+        instance_id: usize,
+        instance: &PreparedInstance<P>,
+        algo_name: &str,
+    ) -> (BenchmarkResult, f64) {
+        let task = RemoteTask {
+            task: index,
+            problem: context.problem_name.clone(),
+            algorithm: algo_name.to_string(),
+            instance_id,
+            seed: instance.seed,
+            total: context.total,
+            verified: instance.optimal_cost.is_some(),
+            optimal_cost: instance.optimal_cost.flatten(),
+        };
+        let task_start = Instant::now();
+        let result = match isolation::spawn(&self.config, &task) {
+            Ok((result, _)) => result,
+            Err(failure) => {
+                let mut properties = instance.properties.clone();
+                if let Some(peak) = failure.peak_rss_kb {
+                    properties.insert("peak_rss_kb".to_string(), peak.into());
+                }
+                let error = match &failure.status {
+                    SearchStatus::Timeout => format!(
+                        "Timeout après {} secondes (processus isolé tué)",
+                        self.config.timeout_secs
+                    ),
+                    status => status.to_string(),
+                };
+                BenchmarkResult {
                     algorithm: algo_name.to_string(),
-                    problem: problem_name(self.config.size),
+                    problem: context.problem_name.clone(),
                     problem_size: self.config.size,
-                    instance_id: *instance_id,
-                    status: final_status,
-                    metrics: result.metrics,
+                    instance_id,
+                    seed: instance.seed,
+                    status: failure.status,
+                    metrics: crate::benchmarking::Metrics::default(),
                     timestamp: chrono::Local::now().to_rfc3339(),
-                    initial_state: Some(initial_state_formatter(problem)),
-                    error: final_error,
-                })
-            })
-            .collect();
+                    hasher: HASHER_NAME.to_string(),
+                    initial_state: Some(instance.description.clone()),
+                    solution: None,
+                    error: Some(error),
+                    optimal: None,
+                    cost_optimal: None,
+                    properties,
+                    profile: None,
+                    improvements: Vec::new(),
+                    host: None,
+                }
+            }
+        };
+        let time_ms = elapsed_ms(task_start);
+
+        if self.progress.is_console() {
+            println!(
+                "  Instance {}\t {}/{}\t {}",
+                algo_name,
+                instance_id + 1,
+                context.total,
+                Self::outcome(&result)
+            );
+        }
+        if self.progress.is_observed() {
+            self.progress.send(ProgressEvent::TaskFinished {
+                task: index,
+                result: Box::new(result.clone()),
+                time_ms,
+            });
+        }
+        (result, time_ms)
+    }
 
-        Ok(results)
+    fn skipped_result<P>(
+        &self,
+        context: &TaskContext,
+        index: usize,
+        instance_id: usize,
+        instance: &PreparedInstance<P>,
+        algo_name: &str,
+        reason: SkipReason,
+    ) -> (BenchmarkResult, f64) {
+        let mut properties = instance.properties.clone();
+        properties.insert("skipped".to_string(), reason.code().into());
+        let result = BenchmarkResult {
+            algorithm: algo_name.to_string(),
+            problem: context.problem_name.clone(),
+            problem_size: self.config.size,
+            instance_id,
+            seed: instance.seed,
+            status: SearchStatus::Skipped,
+            metrics: crate::benchmarking::Metrics::default(),
+            timestamp: chrono::Local::now().to_rfc3339(),
+            hasher: HASHER_NAME.to_string(),
+            initial_state: Some(instance.description.clone()),
+            solution: None,
+            error: Some(format!("{}: {}", SearchStatus::Skipped, reason.label())),
+            optimal: None,
+            cost_optimal: None,
+            properties,
+            profile: None,
+            improvements: Vec::new(),
+            host: None,
+        };
+        if self.progress.is_console() {
+            println!(
+                "  Instance {}\t {}/{}\t {}",
+                algo_name,
+                instance_id + 1,
+                context.total,
+                Self::outcome(&result)
+            );
+        }
+        if self.progress.is_observed() {
+            self.progress.send(ProgressEvent::TaskFinished {
+                task: index,
+                result: Box::new(result.clone()),
+                time_ms: 0.0,
+            });
+        }
+        (result, 0.0)
     }
 
-    pub fn run(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let mut all_results = Vec::new();
+    pub fn run_remote_task<F: ProblemFactory>(
+        &self,
+        task: &RemoteTask,
+    ) -> Option<(BenchmarkResult, f64)>
+    where
+        <F::Instance as Problem>::State: Send,
+        <F::Instance as Problem>::Action: Send,
+    {
+        if F::label(&self.config) != task.problem {
+            return None;
+        }
+        let problem = F::generate(&self.config, task.seed);
+        let instance = PreparedInstance {
+            seed: task.seed,
+            description: F::describe(&problem),
+            properties: F::properties(&problem),
+            optimal_cost: task.verified.then_some(task.optimal_cost),
+            problem: Arc::new(problem),
+        };
+        let variant = self.heuristic_variant::<F>(&instance, &task.algorithm);
+        let context = self.task_context::<F>(task.total);
+        Some(self.run_task(
+            &context,
+            task.task,
+            task.instance_id,
+            variant.as_ref().unwrap_or(&instance),
+            &task.algorithm,
+        ))
+    }
 
-        match self.config.problem.as_str() {
-            "taquin" | "all" => {
-                println!(
-                    "Benchmarking Taquin {}x{}",
-                    self.config.size, self.config.size
-                );
-                let taquin_results = self.benchmark_taquin()?;
-                all_results.extend(taquin_results);
+    pub fn solve_instance<F: ProblemFactory>(&self, instance_id: usize) -> Vec<BenchmarkResult>
+    where
+        <F::Instance as Problem>::State: Send,
+        <F::Instance as Problem>::Action: Send,
+    {
+        let label = F::label(&self.config);
+        let seed = seeding::instance_seed(self.config.seed, &label, self.config.size, instance_id);
+        let problem = F::generate(&self.config, seed);
+        let instance = PreparedInstance {
+            seed,
+            description: F::describe(&problem),
+            properties: F::properties(&problem),
+            optimal_cost: None,
+            problem: Arc::new(problem),
+        };
+        let context = self.task_context::<F>(instance_id + 1);
+        let algorithm_names =
+            Self::algorithm_names(&self.config, &F::heuristic_labels(&self.config));
+        sweep::expand(&self.config, algorithm_names)
+            .iter()
+            .enumerate()
+            .map(|(task, algo_name)| {
+                let variant = self.heuristic_variant::<F>(&instance, algo_name);
+                let instance = variant.as_ref().unwrap_or(&instance);
+                self.run_task(&context, task, instance_id, instance, algo_name)
+                    .0
+            })
+            .collect()
+    }
+
+    fn execute_benchmarks<F: ProblemFactory>(
+        &self,
+        algorithm_names: Vec<String>,
+    ) -> Result<ProblemRun, Box<dyn std::error::Error>>
+    where
+        <F::Instance as Problem>::State: Send,
+        <F::Instance as Problem>::Action: Send,
+    {
+        let problem_name = F::label(&self.config);
+        let unit_costs = F::unit_costs(&self.config);
+        let admissible = F::admissible_heuristic(&self.config);
+        if !unit_costs
+            && algorithm_names
+                .iter()
+                .any(|name| name.starts_with("BFS") || name.starts_with("DFS"))
+        {
+            self.progress.log(
+                "  Note: coûts non unitaires, BFS et DFS minimisent le nombre d'actions et non le coût (cost_optimal: false)"
+                    .to_string(),
+            );
+        }
+        let preparation_start = Instant::now();
+        let (generated, known_costs, duplicates) = match &self.config.instances {
+            Some(source) => {
+                let (generated, known_costs): (Vec<_>, Vec<_>) =
+                    F::load_instances(&self.config, source)?
+                        .into_iter()
+                        .map(|(problem, known_cost)| ((0, problem), known_cost))
+                        .unzip();
+                (generated, known_costs, 0)
             }
-            "shortest-path" => {
-                println!("Benchmarking Plus Court Chemin (Grille)");
-                let path_results = self.benchmark_shortest_path()?;
-                all_results.extend(path_results);
+            None => {
+                let mut generated: Vec<(u64, F::Instance)> = (0..self.config.iterations)
+                    .into_par_iter()
+                    .map(|instance_id| {
+                        let seed = seeding::instance_seed(
+                            self.config.seed,
+                            &problem_name,
+                            self.config.size,
+                            instance_id,
+                        );
+                        (seed, F::generate(&self.config, seed))
+                    })
+                    .collect();
+                let duplicates = Self::reseed_duplicates::<F>(&self.config, &mut generated);
+                let known_costs = vec![None; generated.len()];
+                (generated, known_costs, duplicates)
             }
-            "shortest-path-random" => {
-                println!("Benchmarking Plus Court Chemin (Graphe Aléatoire)");
-                let path_results = self.benchmark_shortest_path_random()?;
-                all_results.extend(path_results);
+        };
+        let instances: Vec<PreparedInstance<F::Instance>> = generated
+            .into_par_iter()
+            .zip(known_costs)
+            .map(|((seed, problem), known_cost)| PreparedInstance {
+                seed,
+                description: F::describe(&problem),
+                properties: F::properties(&problem),
+                optimal_cost: match known_cost {
+                    Some(cost) => Some(Some(cost)),
+                    None => self
+                        .config
+                        .verify_optimal
+                        .then(|| reference::optimal_cost(&problem, unit_costs)),
+                },
+                problem: Arc::new(problem),
+            })
+            .collect();
+        let total = instances.len();
+        let variants: Vec<(Heuristic, Vec<PreparedInstance<F::Instance>>)> = self
+            .config
+            .heuristics
+            .iter()
+            .filter_map(|&heuristic| {
+                instances
+                    .iter()
+                    .map(|instance| {
+                        F::with_heuristic(&instance.problem, heuristic)
+                            .map(|problem| instance.with_problem(problem))
+                    })
+                    .collect::<Option<Vec<_>>>()
+                    .map(|prepared| (heuristic, prepared))
+            })
+            .collect();
+        let all_tasks: Vec<_> = algorithm_names
+            .iter()
+            .flat_map(|algo_name| {
+                variants
+                    .iter()
+                    .find(|(heuristic, _)| Self::has_heuristic(algo_name, heuristic.label()))
+                    .map_or(&instances, |(_, prepared)| prepared)
+                    .iter()
+                    .enumerate()
+                    .map(move |(instance_id, instance)| (instance_id, instance, algo_name.as_str()))
+            })
+            .collect();
+        let preparation = PreparationReport {
+            problem: problem_name.clone(),
+            instances: instances.len(),
+            duplicates,
+            time_ms: elapsed_ms(preparation_start),
+        };
+
+        self.progress.log(format!(
+            "Préparation: {} instances générées en {:.2} ms",
+            preparation.instances, preparation.time_ms
+        ));
+        if duplicates > 0 {
+            self.progress.log(format!(
+                "Note: {} instances en double (trop peu d'instances distinctes pour cette taille)",
+                duplicates
+            ));
+        }
+
+        self.progress.log(match &self.coordinator {
+            Some(coordinator) => format!(
+                "\nExécution de {} tâches réparties sur les workers de {}...\n",
+                all_tasks.len(),
+                coordinator.address()
+            ),
+            None if self.config.isolate => format!(
+                "\nExécution de {} tâches dans des processus isolés, {} à la fois...\n",
+                all_tasks.len(),
+                self.config.threads
+            ),
+            None => format!(
+                "\nExécution de {} tâches en parallèle sur {} threads...\n",
+                all_tasks.len(),
+                self.config.threads
+            ),
+        });
+
+        let use_history = algorithm_names.iter().all(|algo_name| {
+            self.history
+                .contains_key(&(algo_name.clone(), problem_name.clone()))
+        });
+        let expected_cost = |algo_name: &str| {
+            if use_history {
+                self.history[&(algo_name.to_string(), problem_name.clone())]
+            } else {
+                Self::static_cost(algo_name)
             }
-            _ => {
-                return Err(format!("Problème inconnu: {}", self.config.problem).into());
+        };
+        let mut order: Vec<usize> = (0..all_tasks.len()).collect();
+        order.sort_by(|&a, &b| {
+            expected_cost(all_tasks[b].2).total_cmp(&expected_cost(all_tasks[a].2))
+        });
+
+        let threads = match &self.coordinator {
+            Some(coordinator) => coordinator.threads(),
+            None => self.config.threads,
+        }
+        .max(1);
+        let deadline = self.budget.as_ref().map(Budget::problem_deadline);
+        let estimate = |algo_name: &str| {
+            self.budget
+                .as_ref()
+                .and_then(|budget| budget.observed_ms(&problem_name, algo_name))
+                .or_else(|| {
+                    self.history
+                        .get(&(algo_name.to_string(), problem_name.clone()))
+                        .copied()
+                })
+        };
+        let mut skips = vec![None; all_tasks.len()];
+        if let (Some(budget), Some(deadline)) = (&self.budget, deadline) {
+            let tasks: Vec<(usize, &str)> = all_tasks
+                .iter()
+                .map(|(instance_id, _, algo_name)| (*instance_id, *algo_name))
+                .collect();
+            let allotted = deadline.saturating_duration_since(Instant::now());
+            match budget.plan(deadline, &tasks, threads, estimate) {
+                Some(plan) => {
+                    self.progress.log(format!(
+                        "Budget: {:.1} s alloués à ce problème, {:.1} s estimés{}",
+                        allotted.as_secs_f64(),
+                        plan.estimated_ms / 1000.0,
+                        if plan.iterations < total || !plan.dropped.is_empty() {
+                            format!(
+                                " après réduction à {} instance(s){}",
+                                plan.iterations,
+                                if plan.dropped.is_empty() {
+                                    String::new()
+                                } else {
+                                    format!(", sans {}", plan.dropped.join(", "))
+                                }
+                            )
+                        } else {
+                            String::new()
+                        }
+                    ));
+                    skips = plan.skips;
+                }
+                None => self.progress.log(format!(
+                    "Budget: {:.1} s alloués à ce problème, durées inconnues : tâches écartées en cours d'exécution si le temps manque",
+                    allotted.as_secs_f64()
+                )),
             }
         }
 
-        self.save_results(&all_results)?;
+        self.progress.send(ProgressEvent::ProblemStarted {
+            problem: problem_name.clone(),
+            tasks: all_tasks.len(),
+        });
+        let run_start = Instant::now();
+        let context = self.task_context::<F>(total);
+        let mut timed_results: Vec<(usize, BenchmarkResult, f64)> = match &self.coordinator {
+            Some(coordinator) => {
+                if self.config.instances.is_some() {
+                    return Err("--instances n'est pas disponible en mode distribué".into());
+                }
+                let (skipped, order): (Vec<usize>, Vec<usize>) =
+                    order.into_iter().partition(|&index| skips[index].is_some());
+                let skipped: Vec<(usize, BenchmarkResult, f64)> = skipped
+                    .into_iter()
+                    .map(|index| {
+                        let (instance_id, instance, algo_name) = &all_tasks[index];
+                        let reason = skips[index].expect("tâche écartée");
+                        let (result, time_ms) = self.skipped_result(
+                            &context,
+                            index,
+                            *instance_id,
+                            instance,
+                            algo_name,
+                            reason,
+                        );
+                        (index, result, time_ms)
+                    })
+                    .collect();
+                let tasks = order
+                    .iter()
+                    .map(|&index| {
+                        let (instance_id, instance, algo_name) = &all_tasks[index];
+                        RemoteTask {
+                            task: index,
+                            problem: problem_name.clone(),
+                            algorithm: algo_name.to_string(),
+                            instance_id: *instance_id,
+                            seed: instance.seed,
+                            total,
+                            verified: instance.optimal_cost.is_some(),
+                            optimal_cost: instance.optimal_cost.flatten(),
+                        }
+                    })
+                    .collect();
+                coordinator
+                    .execute(tasks, |task, result, time_ms| {
+                        if self.progress.is_console() {
+                            println!(
+                                "  Instance {}\t {}/{}\t {} [{}]",
+                                task.algorithm,
+                                task.instance_id + 1,
+                                total,
+                                Self::outcome(result),
+                                result.host.as_deref().unwrap_or("?")
+                            );
+                        }
+                        if self.progress.is_observed() {
+                            self.progress.send(ProgressEvent::TaskFinished {
+                                task: task.task,
+                                result: Box::new(result.clone()),
+                                time_ms,
+                            });
+                        }
+                    })?
+                    .into_iter()
+                    .chain(skipped)
+                    .collect()
+            }
+            None => order
+                .into_iter()
+                .par_bridge()
+                .map(|index| {
+                    let (instance_id, instance, algo_name) = &all_tasks[index];
+                    let skip = skips[index].or_else(|| {
+                        let budget = self.budget.as_ref()?;
+                        budget.check(deadline?, estimate(algo_name))
+                    });
+                    if let Some(reason) = skip {
+                        let (result, time_ms) = self.skipped_result(
+                            &context,
+                            index,
+                            *instance_id,
+                            instance,
+                            algo_name,
+                            reason,
+                        );
+                        return (index, result, time_ms);
+                    }
+                    let (result, time_ms) = if self.config.isolate {
+                        self.run_isolated(&context, index, *instance_id, instance, algo_name)
+                    } else {
+                        self.run_task(&context, index, *instance_id, instance, algo_name)
+                    };
+                    if let Some(budget) = &self.budget {
+                        budget.record(&problem_name, algo_name, time_ms);
+                    }
+                    (index, result, time_ms)
+                })
+                .collect(),
+        };
 
-        self.print_summary(&all_results);
+        let wall_ms = elapsed_ms(run_start);
+        let busy_ms: f64 = timed_results.iter().map(|(_, _, task_ms)| task_ms).sum();
+        let threads = threads as f64;
+        let scheduling = SchedulingReport {
+            problem: problem_name.clone(),
+            ordering: if use_history { "history" } else { "static" }.to_string(),
+            wall_ms,
+            busy_ms,
+            efficiency: if wall_ms > 0.0 {
+                (busy_ms / (wall_ms * threads)).min(1.0)
+            } else {
+                1.0
+            },
+        };
 
-        Ok(())
-    }
+        self.progress.log(format!(
+            "\nOrdonnancement ({}): {:.2} ms de calcul en {:.2} ms, efficacité {:.0}%",
+            if use_history {
+                "historique"
+            } else {
+                "statique"
+            },
+            scheduling.busy_ms,
+            scheduling.wall_ms,
+            scheduling.efficiency * 100.0
+        ));
 
-    fn benchmark_taquin(&self) -> Result<Vec<BenchmarkResult>, Box<dyn std::error::Error>> {
-        let algorithm_names = self.get_algorithm_names(true)?;
-        let size = self.config.size;
-        let max_depth = size * size * 10;
+        timed_results.sort_by_key(|(index, _, _)| *index);
+        let results: Vec<BenchmarkResult> = timed_results
+            .into_iter()
+            .map(|(_, result, _)| result)
+            .collect();
 
-        let problem_generator = move |_instance_id: usize| {
-            let mut problem = Taquin::new(size, taquin::HeuristicType::Manhattan);
-            problem.generate_random(size * size * 10);
-            problem
-        };
+        if self.config.check_consistency {
+            let is_optimal = |algo_name: &str| {
+                consistency::is_optimal_algorithm(algo_name, unit_costs, admissible)
+            };
+            if let Some(disagreement) = consistency::find_disagreement(&results, is_optimal) {
+                return Err(format!(
+                    "Incohérence entre algorithmes optimaux sur {} (instance {}, graine {}): {}\nInstance pour reproduction:\n{}",
+                    problem_name,
+                    disagreement.instance_id + 1,
+                    instances[disagreement.instance_id].seed,
+                    disagreement.summary(),
+                    F::serialize(&instances[disagreement.instance_id].problem)
+                )
+                .into());
+            }
+            let checked: Vec<&String> = algorithm_names
+                .iter()
+                .filter(|algo_name| is_optimal(algo_name))
+                .collect();
+            if checked.len() < 2 {
+                self.progress.log(
+                    "Cohérence: moins de deux algorithmes optimaux, rien à comparer".to_string(),
+                );
+            } else {
+                self.progress.log(format!(
+                    "Cohérence: coûts identiques pour {} sur {} instances",
+                    checked
+                        .iter()
+                        .map(|s| s.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    instances.len()
+                ));
+            }
+        }
 
-        self.execute_benchmarks(
-            algorithm_names,
-            problem_generator,
-            Arc::new(move |size| format!("Taquin-{}x{}", size, size)),
-            |p: &Taquin| p.initial_state_string(),
-            max_depth,
-        )
+        Ok(ProblemRun {
+            results,
+            preparation,
+            scheduling,
+        })
     }
 
-    fn benchmark_shortest_path(&self) -> Result<Vec<BenchmarkResult>, Box<dyn std::error::Error>> {
-        let algorithm_names = self.get_algorithm_names(false)?;
-        let size = self.config.size;
-        let max_depth = (size * size).min(500);
+    pub fn run(self) -> Result<(), Box<dyn std::error::Error>> {
+        let entries: Vec<&ProblemEntry> = match self.config.problem.as_str() {
+            "all" => registry::PROBLEMS
+                .iter()
+                .filter(|entry| match (entry.validate)(&self.config) {
+                    Ok(()) => true,
+                    Err(e) => {
+                        self.progress.log(format!("{} ignoré: {}", entry.title, e));
+                        false
+                    }
+                })
+                .collect(),
+            name => match registry::find(name) {
+                Some(entry) => {
+                    (entry.validate)(&self.config)?;
+                    vec![entry]
+                }
+                None => {
+                    return Err(format!(
+                        "Problème inconnu: {} (disponibles: {})",
+                        self.config.problem,
+                        registry::names().join(", ")
+                    )
+                    .into());
+                }
+            },
+        };
 
-        if size > 20 {
-            println!(
-                "  Note: ID peut être lent sur grandes grilles (profondeur max: {})\n",
-                max_depth
-            );
+        let run_start = Instant::now();
+        let timestamp = chrono::Local::now().to_rfc3339();
+        let mut phases = PhaseTimings::default();
+        let mut writer = ResultWriter::create(&self.config.output_file)?;
+        let mut all_results = Vec::new();
+        let mut preparation = Vec::new();
+        let mut scheduling = Vec::new();
+        if let Some(budget) = &self.budget {
+            budget.set_problems(entries.len());
+        }
+
+        for entry in entries {
+            self.progress.log(format!("Benchmarking {}", entry.title));
+            let run = if scenario::enabled(&self.config) {
+                scenario::benchmark(&self.config, &self.progress)?
+            } else if all_pairs::enabled(&self.config) {
+                all_pairs::benchmark(&self.config, &self.progress)?
+            } else {
+                (entry.run)(&self)?
+            };
+            phases.generation_ms += run.preparation.time_ms;
+            phases.search_ms += run.scheduling.wall_ms;
+
+            let serialization_start = Instant::now();
+            writer.write_batch(&run.preparation.problem, &run.results)?;
+            phases.serialization_ms += elapsed_ms(serialization_start);
+
+            all_results.extend(run.results);
+            preparation.push(run.preparation);
+            scheduling.push(run.scheduling);
+            if let Some(budget) = &self.budget {
+                budget.finish_problem();
+            }
         }
 
-        let problem_generator = move |_instance_id: usize| ShortestPath::generate_grid(size, size);
+        let serialization_start = Instant::now();
+        let results_index = writer.finish()?;
+        phases.serialization_ms += elapsed_ms(serialization_start);
 
-        self.execute_benchmarks(
-            algorithm_names,
-            problem_generator,
-            Arc::new(move |size| format!("ShortestPath-{}x{}", size, size)),
-            |p: &ShortestPath| p.initial_state_string(),
-            max_depth,
-        )
+        let aggregation_start = Instant::now();
+        self.print_summary(&all_results);
+        let stability = stability::analyze(&self.config, &all_results);
+        for line in stability::lines(&self.config, &stability) {
+            self.progress.log(line);
+        }
+        self.print_heuristic_comparison(&all_results);
+        phases.aggregation_ms = elapsed_ms(aggregation_start);
+
+        phases.finish(elapsed_ms(run_start));
+        for line in phases.lines() {
+            self.progress.log(line);
+        }
+
+        let metadata = RunMetadata {
+            timestamp,
+            algorithm: self.config.algorithm.to_string(),
+            problem: self.config.problem.clone(),
+            problem_size: self.config.size,
+            iterations: self.config.iterations,
+            threads: self.config.threads,
+            timeout_secs: self.config.timeout_secs,
+            memory_limit_mb: self.config.memory_limit_mb,
+            hasher: HASHER_NAME.to_string(),
+            seed: self.config.seed,
+            reexpand: self.config.reexpand,
+            cost_bound: self.config.cost_bound,
+            preparation,
+            scheduling,
+            results_index,
+            stability,
+            phases,
+            hosts: self
+                .coordinator
+                .as_ref()
+                .map_or_else(Vec::new, |coordinator| coordinator.hosts()),
+        };
+        self.save_metadata(&metadata)?;
+        match runs::register(&self.config.output_file, &metadata) {
+            Ok(run) => self.progress.log(format!(
+                "\nExécution {} enregistrée dans {}",
+                run.id,
+                runs::index_path(runs::directory(&run.results)).display()
+            )),
+            Err(e) => self
+                .progress
+                .log(format!("\nIndex des exécutions non mis à jour: {}", e)),
+        }
+
+        Ok(())
     }
 
-    fn benchmark_shortest_path_random(
+    pub fn benchmark_problem<F: ProblemFactory>(
         &self,
-    ) -> Result<Vec<BenchmarkResult>, Box<dyn std::error::Error>> {
-        let algorithm_names = self.get_algorithm_names(false)?;
-        let size = self.config.size;
-        let nodes = size;
-        let edges = nodes * 3;
-        let max_depth = nodes;
-
-        println!(
-            "  Configuration: {} nœuds, ~{} arêtes par graphe\n",
-            nodes, edges
+    ) -> Result<ProblemRun, Box<dyn std::error::Error>>
+    where
+        <F::Instance as Problem>::State: Send,
+        <F::Instance as Problem>::Action: Send,
+    {
+        let algorithm_names = sweep::expand(
+            &self.config,
+            Self::algorithm_names(&self.config, &F::heuristic_labels(&self.config)),
         );
+        let notes = F::notes(&self.config);
+        for note in &notes {
+            self.progress.log(format!("  {}", note));
+        }
+        if !notes.is_empty() {
+            self.progress.log(String::new());
+        }
 
-        let problem_generator = move |_instance_id: usize| {
-            let timestamp = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_nanos() as u64;
-            ShortestPath::generate_random_with_seed(nodes, edges, 0, nodes - 1, Some(timestamp))
-        };
+        self.execute_benchmarks::<F>(algorithm_names)
+    }
 
-        self.execute_benchmarks(
-            algorithm_names,
-            problem_generator,
-            Arc::new(move |size| format!("ShortestPath-Random-{}", size)),
-            |p: &ShortestPath| p.initial_state_string(),
-            max_depth,
-        )
+    pub fn trace_dir(output_file: &str) -> String {
+        Path::new(output_file)
+            .with_extension("traces")
+            .to_string_lossy()
+            .into_owned()
     }
 
-    fn save_results(&self, results: &[BenchmarkResult]) -> Result<(), Box<dyn std::error::Error>> {
-        let json = serde_json::to_string_pretty(results)?;
-        let mut file = File::create(&self.config.output_file)?;
+    pub fn metadata_path(output_file: &str) -> String {
+        Path::new(output_file)
+            .with_extension("meta.json")
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    fn save_metadata(&self, metadata: &RunMetadata) -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::to_string_pretty(metadata)?;
+        let mut file = File::create(Self::metadata_path(&self.config.output_file))?;
         file.write_all(json.as_bytes())?;
         Ok(())
     }
 
     fn print_summary(&self, results: &[BenchmarkResult]) {
-        println!("\nRésumé:");
+        self.progress.log("\nRésumé:".to_string());
 
         let mut grouped: std::collections::HashMap<(String, String), Vec<&BenchmarkResult>> =
             std::collections::HashMap::new();
 
         for result in results {
             let key = (result.algorithm.clone(), result.problem.clone());
-            grouped.entry(key).or_insert_with(Vec::new).push(result);
+            grouped.entry(key).or_default().push(result);
         }
 
         for ((algorithm, problem), group) in grouped {
             let group_vec: Vec<BenchmarkResult> = group.iter().map(|r| (*r).clone()).collect();
             let aggregated = AggregatedResults::from_results(&group_vec);
 
-            println!("\n{} sur {}", algorithm, problem);
-            println!(
+            self.progress
+                .log(format!("\n{} sur {}", algorithm, problem));
+            self.progress.log(format!(
                 "  Succès: {}/{}",
                 aggregated.successful_instances, aggregated.total_instances
-            );
-            println!("  Temps moyen: {:.2} ms", aggregated.avg_time_ms);
-            println!("  Mémoire moyenne: {:.0} Ko", aggregated.avg_memory_kb);
-            println!(
+            ));
+            if aggregated.limited_instances > 0 {
+                self.progress.log(format!(
+                    "  Limite de profondeur atteinte: {}/{}",
+                    aggregated.limited_instances, aggregated.total_instances
+                ));
+            }
+            if aggregated.skipped_instances > 0 {
+                self.progress.log(format!(
+                    "  Ignorées (budget): {}",
+                    aggregated.skipped_instances
+                ));
+            }
+            self.progress
+                .log(format!("  Temps moyen: {:.2} ms", aggregated.avg_time_ms));
+            self.progress.log(format!(
+                "  Mémoire moyenne: {:.0} Ko",
+                aggregated.avg_memory_kb
+            ));
+            self.progress.log(format!(
                 "  Nœuds visités (moy.): {:.0}",
                 aggregated.avg_nodes_visited
-            );
-            println!(
+            ));
+            self.progress.log(format!(
                 "  Longueur solution (moy.): {:.1}",
                 aggregated.avg_solution_length
+            ));
+            self.progress.log(format!(
+                "  Coût solution (moy.): {:.1}{}",
+                aggregated.avg_solution_cost,
+                if aggregated.cost_optimal == Some(false) {
+                    " (coût optimal non garanti)"
+                } else {
+                    ""
+                }
+            ));
+            self.progress
+                .log(format!("  EBF moyen: {:.2}", aggregated.avg_ebf));
+            if aggregated.checked_instances > 0 {
+                self.progress.log(format!(
+                    "  Solutions optimales: {}/{}",
+                    aggregated.optimal_instances, aggregated.checked_instances
+                ));
+            }
+            if let (Some(avg_gap), Some(max_gap)) = (aggregated.avg_gap, aggregated.max_gap) {
+                self.progress.log(format!(
+                    "  Écart à l'optimum (coût/optimal): moyen {:.3}, max {:.3}, garantie {}",
+                    avg_gap,
+                    max_gap,
+                    aggregated
+                        .suboptimality_bound
+                        .map_or("aucune".to_string(), |bound| format!("≤ {}", bound))
+                ));
+            }
+            if aggregated.total_evictions > 0 {
+                self.progress.log(format!(
+                    "  Évictions (total): {}",
+                    aggregated.total_evictions
+                ));
+            }
+            if aggregated.total_reexpansions > 0 {
+                self.progress.log(format!(
+                    "  Ré-expansions (total): {}",
+                    aggregated.total_reexpansions
+                ));
+            }
+        }
+    }
+
+    fn print_heuristic_comparison(&self, results: &[BenchmarkResult]) {
+        let labels: Vec<String> = self
+            .config
+            .heuristics
+            .iter()
+            .map(|h| h.label().to_string())
+            .collect();
+        if labels.len() < 2 {
+            return;
+        }
+
+        let mut grouped: BTreeMap<(String, String), Vec<Vec<&BenchmarkResult>>> = BTreeMap::new();
+        for result in results {
+            let Some(heuristic) = Self::heuristic_of(&result.algorithm, &labels) else {
+                continue;
+            };
+            let (name, _) = sweep::split(&result.algorithm);
+            let base = format!(
+                "{}{}",
+                &name[..name.len() - heuristic.len() - 1],
+                &result.algorithm[name.len()..]
+            );
+            let index = labels.iter().position(|h| h == heuristic).unwrap_or(0);
+            grouped
+                .entry((base, result.problem.clone()))
+                .or_insert_with(|| vec![Vec::new(); labels.len()])[index]
+                .push(result);
+        }
+
+        self.progress
+            .log("\nComparaison des heuristiques:".to_string());
+        for ((algorithm, problem), by_heuristic) in grouped {
+            let solved: Vec<HashMap<usize, &BenchmarkResult>> = by_heuristic
+                .iter()
+                .map(|group| {
+                    group
+                        .iter()
+                        .filter(|r| r.status.is_solved())
+                        .map(|r| (r.instance_id, *r))
+                        .collect()
+                })
+                .collect();
+            let common: Vec<usize> = solved[0]
+                .keys()
+                .copied()
+                .filter(|id| solved.iter().all(|s| s.contains_key(id)))
+                .collect();
+            let mean = |values: Vec<f64>| values.iter().sum::<f64>() / values.len().max(1) as f64;
+            let nodes: Vec<f64> = solved
+                .iter()
+                .map(|s| {
+                    mean(
+                        common
+                            .iter()
+                            .map(|id| s[id].metrics.nodes_visited as f64)
+                            .collect(),
+                    )
+                })
+                .collect();
+            let fewest = nodes.iter().copied().fold(f64::INFINITY, f64::min).max(1.0);
+
+            self.progress.log(format!(
+                "\n{} sur {} ({} instances résolues par toutes les heuristiques)",
+                algorithm,
+                problem,
+                common.len()
+            ));
+            for (index, label) in labels.iter().enumerate() {
+                let time_ms = mean(
+                    common
+                        .iter()
+                        .map(|id| solved[index][id].metrics.time_ms)
+                        .collect(),
+                );
+                let best = common
+                    .iter()
+                    .filter(|id| {
+                        let visited = solved[index][*id].metrics.nodes_visited;
+                        solved
+                            .iter()
+                            .all(|s| visited <= s[*id].metrics.nodes_visited)
+                    })
+                    .count();
+                self.progress.log(format!(
+                    "  {:<16} succès {}/{}\t nœuds (moy.) {:.0} (x{:.2})\t temps (moy.) {:.2} ms\t meilleure sur {}/{}",
+                    label,
+                    solved[index].len(),
+                    by_heuristic[index].len(),
+                    nodes[index],
+                    nodes[index] / fewest,
+                    time_ms,
+                    best,
+                    common.len()
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::problems::shortest_path::RandomGraphFactory;
+    use crate::problems::taquin::TaquinFactory;
+
+    #[test]
+    fn test_algorithms_share_instances() {
+        let runner = BenchmarkRunner::new(BenchmarkConfig {
+            problem: "shortest-path-random".to_string(),
+            size: 20,
+            iterations: 4,
+            output_file: "target/shared-instances-test.json".to_string(),
+            timeout_secs: 0,
+            seed: 7,
+            ..BenchmarkConfig::default()
+        });
+        let run = runner.benchmark_problem::<RandomGraphFactory>().unwrap();
+
+        let mut by_instance: HashMap<usize, Vec<&BenchmarkResult>> = HashMap::new();
+        for result in &run.results {
+            by_instance
+                .entry(result.instance_id)
+                .or_default()
+                .push(result);
+        }
+        assert_eq!(by_instance.len(), 4);
+        for results in by_instance.values() {
+            assert_eq!(results.len(), 6);
+            assert!(results.iter().all(|r| r.seed == results[0].seed));
+            assert!(results
+                .iter()
+                .all(|r| r.initial_state == results[0].initial_state));
+        }
+    }
+
+    #[test]
+    fn test_heuristics_are_a_dimension() {
+        let config = BenchmarkConfig {
+            algorithm: AlgorithmChoice::Astar,
+            problem: "taquin".to_string(),
+            iterations: 3,
+            output_file: "target/heuristics-test.json".to_string(),
+            timeout_secs: 0,
+            seed: 3,
+            heuristics: vec![
+                Heuristic::None,
+                Heuristic::Manhattan,
+                Heuristic::LinearConflict,
+            ],
+            ..BenchmarkConfig::default()
+        };
+        let labels = TaquinFactory::heuristic_labels(&config);
+        let both = BenchmarkConfig {
+            direction: Direction::Both,
+            ..config.clone()
+        };
+        assert_eq!(
+            BenchmarkRunner::algorithm_names(&both, &labels),
+            vec![
+                "A*-None",
+                "A*-Manhattan",
+                "A*-LinearConflict",
+                "A*-backward"
+            ]
+        );
+
+        let run = BenchmarkRunner::new(config)
+            .benchmark_problem::<TaquinFactory>()
+            .unwrap();
+        assert_eq!(run.results.len(), 9);
+        for instance_id in 0..3 {
+            let nodes = |algorithm: &str| {
+                let result = run
+                    .results
+                    .iter()
+                    .find(|r| r.instance_id == instance_id && r.algorithm == algorithm)
+                    .unwrap();
+                assert!(result.status.is_solved());
+                (result.metrics.nodes_visited, result.metrics.solution_cost)
+            };
+            let (none, manhattan, conflict) = (
+                nodes("A*-None"),
+                nodes("A*-Manhattan"),
+                nodes("A*-LinearConflict"),
             );
-            println!("  EBF moyen: {:.2}", aggregated.avg_ebf);
+            assert!(none.1 == manhattan.1 && manhattan.1 == conflict.1);
+            assert!(conflict.0 <= manhattan.0 && manhattan.0 <= none.0);
         }
     }
 }
@@ -0,0 +1,237 @@
+use super::metrics::RunMetadata;
+use super::runner::BenchmarkRunner;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const INDEX_FILE: &str = "index.json";
+pub const DEFAULT_DIR: &str = "results";
+const LATEST: &str = "latest";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunEntry {
+    pub id: String,
+    pub timestamp: String,
+    pub results: String,
+    pub metadata: String,
+    pub algorithm: String,
+    pub problem: String,
+    pub problem_size: usize,
+    pub iterations: usize,
+    pub seed: u64,
+    pub count: usize,
+}
+
+impl RunEntry {
+    fn files(&self) -> Vec<PathBuf> {
+        vec![
+            PathBuf::from(&self.results),
+            PathBuf::from(&self.metadata),
+            PathBuf::from(BenchmarkRunner::trace_dir(&self.results)),
+        ]
+    }
+}
+
+pub fn index_path(dir: &Path) -> PathBuf {
+    dir.join(INDEX_FILE)
+}
+
+pub fn directory(output_file: &str) -> &Path {
+    Path::new(output_file)
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."))
+}
+
+pub fn load(dir: &Path) -> Result<Vec<RunEntry>, String> {
+    let path = index_path(dir);
+    match fs::read_to_string(&path) {
+        Ok(json) => serde_json::from_str(&json).map_err(|e| format!("{}: {}", path.display(), e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(format!("{}: {}", path.display(), e)),
+    }
+}
+
+fn save(dir: &Path, runs: &[RunEntry]) -> Result<(), String> {
+    let path = index_path(dir);
+    let staging = path.with_extension("json.tmp");
+    let json = serde_json::to_string_pretty(runs).map_err(|e| e.to_string())?;
+    fs::write(&staging, json)
+        .and_then(|_| fs::rename(&staging, &path))
+        .map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn run_id(timestamp: &str, runs: &[RunEntry]) -> String {
+    let base = chrono::DateTime::parse_from_rfc3339(timestamp)
+        .map(|time| time.format("%Y%m%d-%H%M%S").to_string())
+        .unwrap_or_else(|_| "run".to_string());
+    let taken = |id: &str| runs.iter().any(|run| run.id == id);
+    (1..)
+        .map(|n| match n {
+            1 => base.clone(),
+            n => format!("{}-{}", base, n),
+        })
+        .find(|id| !taken(id))
+        .expect("identifiant libre")
+}
+
+pub fn register(output_file: &str, metadata: &RunMetadata) -> Result<RunEntry, String> {
+    let dir = directory(output_file);
+    let mut runs = load(dir)?;
+    runs.retain(|run| run.results != output_file);
+    let entry = RunEntry {
+        id: run_id(&metadata.timestamp, &runs),
+        timestamp: metadata.timestamp.clone(),
+        results: output_file.to_string(),
+        metadata: BenchmarkRunner::metadata_path(output_file),
+        algorithm: metadata.algorithm.clone(),
+        problem: metadata.problem.clone(),
+        problem_size: metadata.problem_size,
+        iterations: metadata.iterations,
+        seed: metadata.seed,
+        count: metadata.results_index.iter().map(|entry| entry.count).sum(),
+    };
+    runs.push(entry.clone());
+    save(dir, &runs)?;
+    Ok(entry)
+}
+
+fn find<'a>(runs: &'a [RunEntry], id: &str) -> Option<&'a RunEntry> {
+    let back = match id.strip_prefix(LATEST) {
+        Some("") => 0,
+        Some(offset) => offset.strip_prefix('~')?.parse().ok()?,
+        None => return runs.iter().find(|run| run.id == id),
+    };
+    runs.iter().rev().nth(back)
+}
+
+pub fn resolve(spec: &str, dir: &Path) -> Result<String, String> {
+    if Path::new(spec).exists() {
+        return Ok(spec.to_string());
+    }
+    let runs = load(dir)?;
+    find(&runs, spec)
+        .map(|run| run.results.clone())
+        .ok_or_else(|| {
+            format!(
+                "{}: ni fichier ni exécution connue de {} (voir `runs list`)",
+                spec,
+                index_path(dir).display()
+            )
+        })
+}
+
+pub fn list(dir: &Path) -> Result<(), String> {
+    let runs = load(dir)?;
+    println!("Exécutions ({}):", index_path(dir).display());
+    if runs.is_empty() {
+        println!("  aucune");
+    }
+    for run in &runs {
+        let missing = if Path::new(&run.results).exists() {
+            ""
+        } else {
+            " (fichier absent)"
+        };
+        println!(
+            "  {:<18} {} taille {}, {}, {} résultats\t {}{}",
+            run.id, run.problem, run.problem_size, run.algorithm, run.count, run.results, missing
+        );
+    }
+    Ok(())
+}
+
+pub fn prune(dir: &Path, keep: usize, dry_run: bool) -> Result<Vec<RunEntry>, String> {
+    let mut runs = load(dir)?;
+    let removed: Vec<RunEntry> = runs.drain(..runs.len().saturating_sub(keep)).collect();
+    if dry_run {
+        return Ok(removed);
+    }
+    for run in &removed {
+        for path in run.files() {
+            let outcome = if path.is_dir() {
+                fs::remove_dir_all(&path)
+            } else {
+                fs::remove_file(&path)
+            };
+            match outcome {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                    return Err(format!("{}: {}", path.display(), e))
+                }
+                _ => {}
+            }
+        }
+    }
+    save(dir, &runs)?;
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarking::ResultIndexEntry;
+
+    fn metadata(timestamp: &str) -> RunMetadata {
+        RunMetadata {
+            timestamp: timestamp.to_string(),
+            algorithm: "all".to_string(),
+            problem: "taquin".to_string(),
+            problem_size: 3,
+            iterations: 2,
+            threads: 1,
+            timeout_secs: 0,
+            memory_limit_mb: 0,
+            hasher: String::new(),
+            seed: 7,
+            reexpand: false,
+            cost_bound: None,
+            preparation: Vec::new(),
+            scheduling: Vec::new(),
+            results_index: vec![ResultIndexEntry {
+                problem: "Taquin".to_string(),
+                first: 0,
+                count: 10,
+            }],
+            stability: Vec::new(),
+            phases: Default::default(),
+            hosts: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_index_registers_resolves_and_prunes() {
+        let dir = Path::new("target/runs-index-test");
+        let _ = fs::remove_dir_all(dir);
+        fs::create_dir_all(dir).unwrap();
+        let file = |name: &str| dir.join(name).to_string_lossy().into_owned();
+        let timestamp = "2026-03-01T10:00:00+01:00";
+
+        for name in ["a.json", "b.json", "a.json", "c.json"] {
+            fs::write(file(name), "[]").unwrap();
+            fs::write(BenchmarkRunner::metadata_path(&file(name)), "{}").unwrap();
+            register(&file(name), &metadata(timestamp)).unwrap();
+        }
+        let runs = load(dir).unwrap();
+        let ids: Vec<&str> = runs.iter().map(|run| run.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            ["20260301-100000-2", "20260301-100000", "20260301-100000-3"]
+        );
+        assert_eq!(runs[1].count, 10);
+
+        assert_eq!(resolve("20260301-100000", dir).unwrap(), file("a.json"));
+        assert_eq!(resolve("latest", dir).unwrap(), file("c.json"));
+        assert_eq!(resolve("latest~2", dir).unwrap(), file("b.json"));
+        assert_eq!(resolve(&file("b.json"), dir).unwrap(), file("b.json"));
+        assert!(resolve("latest~3", dir).is_err());
+
+        assert_eq!(prune(dir, 1, true).unwrap().len(), 2);
+        assert!(Path::new(&file("b.json")).exists());
+        let removed = prune(dir, 1, false).unwrap();
+        assert_eq!(removed.len(), 2);
+        assert!(!Path::new(&file("b.json")).exists());
+        assert!(!Path::new(&BenchmarkRunner::metadata_path(&file("a.json"))).exists());
+        assert_eq!(load(dir).unwrap().len(), 1);
+        assert!(Path::new(&file("c.json")).exists());
+    }
+}
@@ -0,0 +1,644 @@
+use super::metrics::{BenchmarkResult, InstanceProperties, PreparationReport, SchedulingReport};
+use super::progress::Progress;
+use super::runner::{BenchmarkConfig, BenchmarkRunner, ProblemRun};
+use crate::algorithms::astar::AStar;
+use crate::algorithms::dstar_lite::DStarLite;
+use crate::algorithms::lpastar::LpaStar;
+use crate::algorithms::{validation, SearchAlgorithm, SearchResult, SearchStatus};
+use crate::problems::registry::ProblemFactory;
+use crate::problems::shortest_path::{CostMapFactory, Edge, GraphFileFactory, GridFactory};
+use crate::problems::ShortestPath;
+use crate::utils::hashing::HASHER_NAME;
+use crate::utils::seeding;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+
+pub const PROBLEMS: &[&str] = &["shortest-path", "shortest-path-file", "shortest-path-map"];
+const ALGORITHMS: [&str; 3] = ["A*", "LPA*", "D* Lite"];
+const OBSTACLE_DENSITY: f64 = 0.2;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChangeKind {
+    Add(usize),
+    Remove,
+    Cost(usize),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EdgeChange {
+    pub kind: ChangeKind,
+    pub from: String,
+    pub to: String,
+    pub both: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct Scenario {
+    pub episodes: Vec<Vec<EdgeChange>>,
+}
+
+fn parse_change(tokens: &[&str]) -> Result<EdgeChange, String> {
+    let (both, tokens) = match tokens.split_last() {
+        Some((&"both", rest)) => (true, rest),
+        _ => (false, tokens),
+    };
+    let cost = |token: &str| {
+        token
+            .parse::<usize>()
+            .map_err(|_| format!("coût invalide: {}", token))
+    };
+    let kind = match tokens {
+        ["add", _, _, value] => ChangeKind::Add(cost(value)?),
+        ["remove", _, _] => ChangeKind::Remove,
+        ["cost", _, _, value] => ChangeKind::Cost(cost(value)?),
+        _ => return Err("attendu: add A B COÛT | remove A B | cost A B COÛT [both]".to_string()),
+    };
+    Ok(EdgeChange {
+        kind,
+        from: tokens[1].to_string(),
+        to: tokens[2].to_string(),
+        both,
+    })
+}
+
+pub fn parse(contents: &str) -> Result<Scenario, String> {
+    let mut scenario = Scenario::default();
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        if tokens == ["episode"] {
+            scenario.episodes.push(Vec::new());
+            continue;
+        }
+        let change = parse_change(&tokens).map_err(|e| format!("ligne {}: {}", index + 1, e))?;
+        match scenario.episodes.last_mut() {
+            Some(episode) => episode.push(change),
+            None => {
+                return Err(format!(
+                    "ligne {}: modification avant le premier épisode",
+                    index + 1
+                ))
+            }
+        }
+    }
+    if scenario.episodes.is_empty() {
+        return Err("aucun épisode".to_string());
+    }
+    Ok(scenario)
+}
+
+pub fn load(path: &str) -> Result<Scenario, String> {
+    let contents = fs::read_to_string(path).map_err(|e| format!("Scénario {}: {}", path, e))?;
+    parse(&contents).map_err(|e| format!("Scénario {}: {}", path, e))
+}
+
+fn node(problem: &ShortestPath, label: &str) -> Result<usize, String> {
+    problem
+        .find_node(label)
+        .ok_or_else(|| format!("nœud inconnu: {}", label))
+}
+
+pub fn apply(
+    problem: &mut ShortestPath,
+    change: &EdgeChange,
+) -> Result<Vec<(usize, usize)>, String> {
+    let (from, to) = (node(problem, &change.from)?, node(problem, &change.to)?);
+    let mut edges = vec![(from, to)];
+    if change.both {
+        edges.push((to, from));
+    }
+    for &(from, to) in &edges {
+        let applied = match change.kind {
+            ChangeKind::Add(cost) => {
+                problem.add_edge(from, to, cost);
+                true
+            }
+            ChangeKind::Remove => problem.remove_edge(from, to),
+            ChangeKind::Cost(cost) => problem.set_edge_cost(from, to, cost),
+        };
+        if !applied {
+            return Err(format!("arête absente: {} -> {}", from, to));
+        }
+    }
+    Ok(edges)
+}
+
+fn grid_neighbors(problem: &ShortestPath, cell: usize) -> Vec<usize> {
+    let Some((width, height)) = problem.grid_dimensions() else {
+        return Vec::new();
+    };
+    let (row, col) = (cell / width, cell % width);
+    [
+        (col + 1 < width).then(|| cell + 1),
+        (row + 1 < height).then(|| cell + width),
+        (col > 0).then(|| cell - 1),
+        (row > 0).then(|| cell - width),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+pub fn obstacle_changes<R: Rng>(
+    problem: &ShortestPath,
+    blocked: &mut HashSet<usize>,
+    toggles: usize,
+    rng: &mut R,
+) -> Vec<EdgeChange> {
+    let Some((width, height)) = problem.grid_dimensions() else {
+        return Vec::new();
+    };
+    let mut changes = Vec::new();
+    for _ in 0..toggles {
+        let cell = rng.gen_range(0..width * height);
+        if cell == problem.start() || cell == problem.goal() {
+            continue;
+        }
+        let kind = if blocked.insert(cell) {
+            ChangeKind::Remove
+        } else {
+            blocked.remove(&cell);
+            ChangeKind::Add(1)
+        };
+        for neighbor in grid_neighbors(problem, cell) {
+            if !blocked.contains(&neighbor) {
+                changes.push(EdgeChange {
+                    kind: kind.clone(),
+                    from: cell.to_string(),
+                    to: neighbor.to_string(),
+                    both: true,
+                });
+            }
+        }
+    }
+    changes
+}
+
+pub fn enabled(config: &BenchmarkConfig) -> bool {
+    config.scenario.is_some() || config.dynamic_obstacles > 0
+}
+
+pub fn validate(config: &BenchmarkConfig) -> Result<(), String> {
+    let (option, problems) = match &config.scenario {
+        Some(_) if config.dynamic_obstacles > 0 => {
+            return Err(
+                "--dynamic-obstacles génère ses propres épisodes (--scenario non supporté)"
+                    .to_string(),
+            )
+        }
+        Some(_) => ("--scenario", PROBLEMS),
+        None if config.dynamic_obstacles > 0 => ("--dynamic-obstacles", &PROBLEMS[..1]),
+        None => return Ok(()),
+    };
+    if !problems.contains(&config.problem.as_str()) {
+        return Err(format!(
+            "{} ne s'applique qu'à {} (problème: {})",
+            option,
+            problems.join(", "),
+            config.problem
+        ));
+    }
+    if config.sources > 1 {
+        return Err(format!("{} suppose un seul départ", option));
+    }
+    if config.instances.is_some() {
+        return Err(format!(
+            "{} génère ses instances (--instances non supporté)",
+            option
+        ));
+    }
+    match &config.scenario {
+        Some(path) => load(path).map(|_| ()),
+        None => Ok(()),
+    }
+}
+
+fn to_result(
+    config: &BenchmarkConfig,
+    problem: &ShortestPath,
+    label: &str,
+    instance_id: usize,
+    seed: u64,
+    algorithm: &str,
+    mut result: SearchResult<Edge>,
+) -> BenchmarkResult {
+    if let Some(actions) = result.solution.as_ref() {
+        if let Err(e) = validation::validate_solution(problem, actions, &result.metrics) {
+            result.status = SearchStatus::Error(format!("Solution invalide: {}", e));
+        }
+    }
+    BenchmarkResult {
+        algorithm: algorithm.to_string(),
+        problem: label.to_string(),
+        problem_size: config.size,
+        instance_id,
+        seed,
+        error: (!result.status.is_solved()).then(|| result.status.to_string()),
+        status: result.status,
+        metrics: result.metrics,
+        timestamp: chrono::Local::now().to_rfc3339(),
+        hasher: HASHER_NAME.to_string(),
+        initial_state: Some(problem.initial_state_string()),
+        solution: result
+            .solution
+            .filter(|_| config.track_solutions)
+            .map(|actions| actions.iter().map(|a| a.to_string()).collect()),
+        optimal: None,
+        cost_optimal: None,
+        properties: Default::default(),
+        profile: None,
+        improvements: Vec::new(),
+        host: None,
+    }
+}
+
+type NextEpisode<'a> = dyn FnMut(usize, &mut ShortestPath, &[Edge]) -> Result<(usize, Vec<(usize, usize)>), String>
+    + 'a;
+
+struct Instance<'a> {
+    label: &'a str,
+    instance_id: usize,
+    seed: u64,
+    properties: InstanceProperties,
+}
+
+fn replan_instance(
+    config: &BenchmarkConfig,
+    progress: &Progress,
+    instance: &Instance,
+    mut problem: ShortestPath,
+    episodes: usize,
+    fixed_start: bool,
+    next_episode: &mut NextEpisode,
+) -> Result<Vec<BenchmarkResult>, String> {
+    let mut lpastar = fixed_start.then(LpaStar::new);
+    let mut dstar = DStarLite::new();
+    let algorithms: Vec<&str> = ALGORITHMS
+        .into_iter()
+        .filter(|&algorithm| fixed_start || algorithm != "LPA*")
+        .collect();
+    let mut totals = vec![(0usize, 0.0f64); algorithms.len()];
+    let mut path = Vec::new();
+    let mut results = Vec::new();
+
+    for episode in 0..=episodes {
+        let (changes, edges) = match episode {
+            0 => (0, Vec::new()),
+            episode => next_episode(episode, &mut problem, &path)?,
+        };
+        for &(from, to) in &edges {
+            if let Some(lpastar) = &mut lpastar {
+                lpastar.edge_changed(to);
+            }
+            dstar.edge_changed(from);
+        }
+
+        let mut searches = vec![AStar::new().search(&problem)];
+        if let Some(lpastar) = &mut lpastar {
+            searches.push(lpastar.search(&problem));
+        }
+        let planned = dstar.search(&problem);
+        path = planned.solution.clone().unwrap_or_default();
+        searches.push(planned);
+
+        let mut costs = Vec::new();
+        for ((algorithm, result), total) in algorithms.iter().zip(searches).zip(&mut totals) {
+            total.0 += result.metrics.nodes_visited;
+            total.1 += result.metrics.time_ms;
+            let mut result = to_result(
+                config,
+                &problem,
+                instance.label,
+                instance.instance_id,
+                instance.seed,
+                algorithm,
+                result,
+            );
+            result.properties = instance.properties.clone();
+            result
+                .properties
+                .insert("episode".to_string(), episode.into());
+            result
+                .properties
+                .insert("changes".to_string(), changes.into());
+            if !fixed_start {
+                result
+                    .properties
+                    .insert("agent".to_string(), problem.start().into());
+            }
+            result
+                .properties
+                .insert("cumulative_nodes".to_string(), total.0.into());
+            result
+                .properties
+                .insert("cumulative_ms".to_string(), total.1.into());
+            if progress.is_console() {
+                println!(
+                    "  Instance {}\t {}/{}\t épisode {}\t {}",
+                    algorithm,
+                    instance.instance_id + 1,
+                    config.iterations,
+                    episode,
+                    BenchmarkRunner::outcome(&result)
+                );
+            }
+            costs.push(
+                result
+                    .status
+                    .is_solved()
+                    .then_some(result.metrics.solution_cost),
+            );
+            results.push(result);
+        }
+        if costs.iter().any(|cost| *cost != costs[0]) {
+            let costs: Vec<String> = algorithms
+                .iter()
+                .zip(&costs)
+                .map(|(algorithm, cost)| format!("{} {:?}", algorithm, cost))
+                .collect();
+            progress.log(format!(
+                "  Instance {}/{}\t épisode {}\t ⚠ coûts différents ({})",
+                instance.instance_id + 1,
+                config.iterations,
+                episode,
+                costs.join(", ")
+            ));
+        }
+    }
+    let totals: Vec<String> = algorithms
+        .iter()
+        .zip(&totals)
+        .map(|(algorithm, (nodes, ms))| format!("{} {} nœuds ({:.2}ms)", algorithm, nodes, ms))
+        .collect();
+    progress.log(format!(
+        "  Instance {}/{}\t total: {}",
+        instance.instance_id + 1,
+        config.iterations,
+        totals.join(", ")
+    ));
+    Ok(results)
+}
+
+pub(super) fn problem_run(
+    config: &BenchmarkConfig,
+    label: String,
+    ordering: &str,
+    start: Instant,
+    generation_ms: f64,
+    results: Vec<BenchmarkResult>,
+) -> ProblemRun {
+    let wall_ms = start.elapsed().as_micros() as f64 / 1000.0;
+    ProblemRun {
+        results,
+        preparation: PreparationReport {
+            problem: label.clone(),
+            instances: config.iterations,
+            duplicates: 0,
+            time_ms: generation_ms,
+        },
+        scheduling: SchedulingReport {
+            problem: label,
+            ordering: ordering.to_string(),
+            wall_ms,
+            busy_ms: wall_ms - generation_ms,
+            efficiency: 1.0,
+        },
+    }
+}
+
+fn replan<F: ProblemFactory<Instance = ShortestPath>>(
+    config: &BenchmarkConfig,
+    progress: &Progress,
+    scenario: &Scenario,
+    name: &str,
+) -> Result<ProblemRun, String> {
+    let base_label = F::label(config);
+    let label = format!("{}-{}", base_label, name);
+    let start = Instant::now();
+    let mut generation_ms = 0.0;
+    let mut results = Vec::new();
+
+    for instance_id in 0..config.iterations {
+        let generation_start = Instant::now();
+        let seed = seeding::instance_seed(config.seed, &base_label, config.size, instance_id);
+        let problem = F::generate(config, seed);
+        let mut properties = F::properties(&problem);
+        properties.insert("scenario".to_string(), name.into());
+        generation_ms += generation_start.elapsed().as_micros() as f64 / 1000.0;
+
+        let instance = Instance {
+            label: &label,
+            instance_id,
+            seed,
+            properties,
+        };
+        results.extend(replan_instance(
+            config,
+            progress,
+            &instance,
+            problem,
+            scenario.episodes.len(),
+            true,
+            &mut |episode, problem, _| {
+                let changes = &scenario.episodes[episode - 1];
+                let mut edges = Vec::new();
+                for change in changes {
+                    edges.extend(
+                        apply(problem, change)
+                            .map_err(|e| format!("Épisode {}: {}", episode, e))?,
+                    );
+                }
+                Ok((changes.len(), edges))
+            },
+        )?);
+    }
+
+    Ok(problem_run(
+        config,
+        label,
+        "scenario",
+        start,
+        generation_ms,
+        results,
+    ))
+}
+
+fn dynamic_obstacles(config: &BenchmarkConfig, progress: &Progress) -> Result<ProblemRun, String> {
+    let base_label = GridFactory::label(config);
+    let label = format!("{}-obstacles", base_label);
+    let start = Instant::now();
+    let mut generation_ms = 0.0;
+    let mut results = Vec::new();
+    let episodes = config.dynamic_obstacles;
+
+    for instance_id in 0..config.iterations {
+        let generation_start = Instant::now();
+        let seed = seeding::instance_seed(config.seed, &base_label, config.size, instance_id);
+        let mut problem = GridFactory::generate(config, seed);
+        let mut rng = StdRng::seed_from_u64(seeding::reseed(seed));
+        let mut blocked = HashSet::new();
+        let initial = (config.size * config.size) as f64 * OBSTACLE_DENSITY;
+        for change in obstacle_changes(&problem, &mut blocked, initial as usize, &mut rng) {
+            apply(&mut problem, &change)?;
+        }
+        let mut properties = GridFactory::properties(&problem);
+        properties.insert("scenario".to_string(), "obstacles".into());
+        properties.insert("obstacles".to_string(), blocked.len().into());
+        generation_ms += generation_start.elapsed().as_micros() as f64 / 1000.0;
+
+        let instance = Instance {
+            label: &label,
+            instance_id,
+            seed,
+            properties,
+        };
+        results.extend(replan_instance(
+            config,
+            progress,
+            &instance,
+            problem,
+            episodes,
+            false,
+            &mut |episode, problem, path| {
+                if !path.is_empty() {
+                    let steps = path.len().div_ceil(episodes - episode + 2);
+                    problem.set_start(path[steps - 1].to);
+                }
+                let changes = obstacle_changes(problem, &mut blocked, config.size, &mut rng);
+                let mut edges = Vec::new();
+                for change in &changes {
+                    edges.extend(apply(problem, change)?);
+                }
+                Ok((changes.len(), edges))
+            },
+        )?);
+    }
+
+    Ok(problem_run(
+        config,
+        label,
+        "scenario",
+        start,
+        generation_ms,
+        results,
+    ))
+}
+
+pub fn benchmark(config: &BenchmarkConfig, progress: &Progress) -> Result<ProblemRun, String> {
+    if config.dynamic_obstacles > 0 {
+        progress.log(format!(
+            "Obstacles dynamiques: {} épisodes, {} cases basculées par épisode, l'agent avance sur son chemin ; A* repart de zéro, D* Lite réutilise ses valeurs g",
+            config.dynamic_obstacles, config.size
+        ));
+        return dynamic_obstacles(config, progress);
+    }
+    let path = config.scenario.as_deref().ok_or("--scenario est requis")?;
+    let scenario = load(path)?;
+    let name = Path::new(path)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    progress.log(format!(
+        "Scénario {}: {} épisodes de replanification, A* repart de zéro, LPA* et D* Lite réutilisent leurs valeurs g",
+        name,
+        scenario.episodes.len()
+    ));
+    match config.problem.as_str() {
+        "shortest-path" => replan::<GridFactory>(config, progress, &scenario, &name),
+        "shortest-path-file" => replan::<GraphFileFactory>(config, progress, &scenario, &name),
+        "shortest-path-map" => replan::<CostMapFactory>(config, progress, &scenario, &name),
+        problem => Err(format!("--scenario non supporté pour {}", problem)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lpastar_matches_astar_across_episodes() {
+        let scenario = parse(
+            "# fermeture puis réouverture\nepisode\nremove 1 2 both\ncost 5 6 9 both\nepisode\nadd 1 2 1 both\n\nepisode\n",
+        )
+        .unwrap();
+        assert_eq!(scenario.episodes.len(), 3);
+        assert_eq!(scenario.episodes[2], Vec::new());
+        assert!(parse("remove 1 2").is_err());
+        assert!(parse("episode\nmove 1 2").is_err());
+
+        let mut problem = ShortestPath::generate_grid_between(5, 5, 0, 24);
+        let mut incremental = LpaStar::new();
+        let mut dstar = DStarLite::new();
+        let first = incremental.search(&problem);
+        assert_eq!(first.metrics.solution_cost, 8);
+        assert_eq!(dstar.search(&problem).metrics.solution_cost, 8);
+        let mut underconsistent = 0;
+        for changes in &scenario.episodes {
+            for change in changes {
+                for (from, to) in apply(&mut problem, change).unwrap() {
+                    incremental.edge_changed(to);
+                    dstar.edge_changed(from);
+                }
+            }
+            let reference = AStar::new().search(&problem);
+            for result in [incremental.search(&problem), dstar.search(&problem)] {
+                assert_eq!(
+                    result.metrics.solution_cost,
+                    reference.metrics.solution_cost
+                );
+                let actions = result.solution.unwrap();
+                assert!(validation::validate_solution(&problem, &actions, &result.metrics).is_ok());
+                assert!(result.metrics.nodes_visited < first.metrics.nodes_visited);
+                assert_eq!(result.metrics.reexpansions, 0);
+                underconsistent += result.metrics.underconsistent;
+            }
+        }
+        assert!(underconsistent > 0);
+        assert!(apply(&mut problem, &parse_change(&["remove", "0", "24"]).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_dstar_lite_follows_the_agent_through_moving_obstacles() {
+        let mut problem = ShortestPath::generate_grid_between(12, 12, 0, 143);
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut blocked = HashSet::new();
+        for change in obstacle_changes(&problem, &mut blocked, 25, &mut rng) {
+            apply(&mut problem, &change).unwrap();
+        }
+        assert!(!blocked.is_empty());
+
+        let mut dstar = DStarLite::new();
+        let mut path = dstar.search(&problem).solution.unwrap_or_default();
+        let mut repairs = 0;
+        for _ in 0..6 {
+            if let Some(edge) = path.first() {
+                problem.set_start(edge.to);
+            }
+            for change in obstacle_changes(&problem, &mut blocked, 12, &mut rng) {
+                for (from, _) in apply(&mut problem, &change).unwrap() {
+                    dstar.edge_changed(from);
+                }
+            }
+            let reference = AStar::new().search(&problem);
+            let result = dstar.search(&problem);
+            assert_eq!(result.status, reference.status);
+            assert_eq!(
+                result.metrics.solution_cost,
+                reference.metrics.solution_cost
+            );
+            if let Some(actions) = &result.solution {
+                assert!(validation::validate_solution(&problem, actions, &result.metrics).is_ok());
+            }
+            repairs += result.metrics.nodes_visited;
+            path = result.solution.unwrap_or_default();
+        }
+        assert!(repairs > 0);
+    }
+}
@@ -0,0 +1,306 @@
+use super::metrics::{AggregatedResults, BenchmarkResult};
+use super::runs;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+const INDEX_HTML: &str = include_str!("server.html");
+
+pub struct ServeConfig {
+    pub address: String,
+    pub sources: Vec<String>,
+}
+
+#[derive(Default)]
+pub(crate) struct Filters {
+    file: Option<String>,
+    algorithm: Option<String>,
+    problem: Option<String>,
+    size: Option<usize>,
+}
+
+impl Filters {
+    pub(crate) fn from_query(query: &str) -> Self {
+        let params = parse_query(query);
+        let text = |key: &str| params.get(key).filter(|v| !v.is_empty()).cloned();
+        Filters {
+            file: text("file"),
+            algorithm: text("algorithm"),
+            problem: text("problem"),
+            size: text("size").and_then(|size| size.parse().ok()),
+        }
+    }
+
+    pub(crate) fn matches(&self, file: &str, result: &BenchmarkResult) -> bool {
+        self.file.as_deref().is_none_or(|f| f == file)
+            && self
+                .algorithm
+                .as_deref()
+                .is_none_or(|a| a == result.algorithm)
+            && self.problem.as_deref().is_none_or(|p| p == result.problem)
+            && self.size.is_none_or(|s| s == result.problem_size)
+    }
+}
+
+pub(crate) struct LoadedFile {
+    pub(crate) name: String,
+    pub(crate) results: Result<Vec<BenchmarkResult>, String>,
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let hex = |i: usize| bytes.get(i).and_then(|&b| (b as char).to_digit(16));
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match (bytes[i], hex(i + 1), hex(i + 2)) {
+            (b'%', Some(high), Some(low)) => {
+                decoded.push((high * 16 + low) as u8);
+                i += 2;
+            }
+            (b'+', _, _) => decoded.push(b' '),
+            (byte, _, _) => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn parse_query(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(key), percent_decode(value))
+        })
+        .collect()
+}
+
+fn result_files(sources: &[String]) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for source in sources {
+        let path = Path::new(source);
+        if !path.is_dir() {
+            files.push(path.to_path_buf());
+            continue;
+        }
+        let mut entries: Vec<PathBuf> = fs::read_dir(path)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                let name = path.to_string_lossy();
+                name.ends_with(".json")
+                    && !name.ends_with(".meta.json")
+                    && path
+                        .file_name()
+                        .is_some_and(|name| name != runs::INDEX_FILE)
+            })
+            .collect();
+        entries.sort();
+        files.extend(entries);
+    }
+    files
+}
+
+fn load(sources: &[String]) -> Vec<LoadedFile> {
+    result_files(sources)
+        .into_iter()
+        .map(|path| LoadedFile {
+            name: path.display().to_string(),
+            results: fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|contents| serde_json::from_str(&contents).map_err(|e| e.to_string())),
+        })
+        .collect()
+}
+
+fn facets(files: &[LoadedFile]) -> Value {
+    let mut algorithms = BTreeSet::new();
+    let mut problems = BTreeSet::new();
+    let mut sizes = BTreeSet::new();
+    let mut errors = Vec::new();
+
+    for file in files {
+        match &file.results {
+            Ok(results) => {
+                for result in results {
+                    algorithms.insert(result.algorithm.as_str());
+                    problems.insert(result.problem.as_str());
+                    sizes.insert(result.problem_size);
+                }
+            }
+            Err(e) => errors.push(json!({ "file": file.name, "error": e })),
+        }
+    }
+
+    json!({
+        "files": files.iter().map(|file| &file.name).collect::<Vec<_>>(),
+        "algorithms": algorithms,
+        "problems": problems,
+        "sizes": sizes,
+        "errors": errors,
+    })
+}
+
+pub(crate) fn summary(files: &[LoadedFile], filters: &Filters) -> Value {
+    let mut groups: BTreeMap<(&str, &str, usize, &str), Vec<BenchmarkResult>> = BTreeMap::new();
+    for file in files {
+        for result in file.results.iter().flatten() {
+            if filters.matches(&file.name, result) {
+                groups
+                    .entry((
+                        &result.problem,
+                        &file.name,
+                        result.problem_size,
+                        &result.algorithm,
+                    ))
+                    .or_default()
+                    .push(result.clone());
+            }
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|((_, file, _, _), results)| {
+            let mut row =
+                serde_json::to_value(AggregatedResults::from_results(&results)).unwrap_or_default();
+            row["file"] = json!(file);
+            row
+        })
+        .collect()
+}
+
+fn results(files: &[LoadedFile], filters: &Filters) -> Value {
+    files
+        .iter()
+        .flat_map(|file| {
+            file.results
+                .iter()
+                .flatten()
+                .filter(|result| filters.matches(&file.name, result))
+                .map(|result| {
+                    json!({
+                        "file": file.name,
+                        "algorithm": result.algorithm,
+                        "problem": result.problem,
+                        "problem_size": result.problem_size,
+                        "instance_id": result.instance_id,
+                        "status": result.status,
+                        "metrics": result.metrics,
+                        "profile": result.profile,
+                        "improvements": result.improvements,
+                    })
+                })
+        })
+        .collect()
+}
+
+fn header(value: &str) -> Header {
+    Header::from_bytes("Content-Type", value).expect("en-tête valide")
+}
+
+fn respond(request: Request, config: &ServeConfig) -> std::io::Result<()> {
+    let url = request.url().to_string();
+    let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+    let json_response = |value: Value| {
+        Response::from_string(value.to_string())
+            .with_header(header("application/json; charset=utf-8"))
+    };
+
+    let response = match (request.method(), path) {
+        (Method::Get, "/") => {
+            Response::from_string(INDEX_HTML).with_header(header("text/html; charset=utf-8"))
+        }
+        (Method::Get, "/api/facets") => json_response(facets(&load(&config.sources))),
+        (Method::Get, "/api/summary") => {
+            json_response(summary(&load(&config.sources), &Filters::from_query(query)))
+        }
+        (Method::Get, "/api/results") => {
+            json_response(results(&load(&config.sources), &Filters::from_query(query)))
+        }
+        _ => Response::from_string("Introuvable").with_status_code(404),
+    };
+    request.respond(response)
+}
+
+pub fn run(config: &ServeConfig) -> Result<(), Box<dyn Error>> {
+    let server = Server::http(&config.address)
+        .map_err(|e| format!("Impossible d'écouter sur {}: {}", config.address, e))?;
+
+    println!("Tableau de bord: http://{}", server.server_addr());
+    for file in result_files(&config.sources) {
+        println!("  {}", file.display());
+    }
+    println!("Ctrl-C pour arrêter");
+
+    for request in server.incoming_requests() {
+        if let Err(e) = respond(request, config) {
+            eprintln!("Erreur de réponse: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::SearchStatus;
+    use crate::benchmarking::Metrics;
+
+    fn result(algorithm: &str, size: usize, time_ms: f64) -> BenchmarkResult {
+        BenchmarkResult {
+            algorithm: algorithm.to_string(),
+            problem: format!("Taquin-{}x{}", size, size),
+            problem_size: size,
+            instance_id: 0,
+            seed: 0,
+            status: SearchStatus::Solved,
+            metrics: Metrics {
+                time_ms,
+                ..Metrics::default()
+            },
+            timestamp: String::new(),
+            hasher: String::new(),
+            initial_state: None,
+            solution: None,
+            error: None,
+            optimal: None,
+            cost_optimal: None,
+            properties: Default::default(),
+            profile: None,
+            improvements: Vec::new(),
+            host: None,
+        }
+    }
+
+    #[test]
+    fn test_summary_groups_filtered_results() {
+        let files = vec![LoadedFile {
+            name: "a.json".to_string(),
+            results: Ok(vec![
+                result("A*-Manhattan", 3, 1.0),
+                result("A*-Manhattan", 3, 3.0),
+                result("BFS", 3, 8.0),
+                result("A*-Manhattan", 4, 50.0),
+            ]),
+        }];
+
+        let filters = Filters::from_query("algorithm=A%2A-Manhattan&size=3&problem=");
+        let rows = summary(&files, &filters);
+        assert_eq!(rows.as_array().map(Vec::len), Some(1));
+        assert_eq!(rows[0]["avg_time_ms"], 2.0);
+        assert_eq!(rows[0]["file"], "a.json");
+
+        let all = summary(&files, &Filters::default());
+        assert_eq!(all.as_array().map(Vec::len), Some(3));
+        assert_eq!(percent_decode("Taquin+3%C3%973%"), "Taquin 3×3%");
+    }
+}
@@ -0,0 +1,188 @@
+use super::metrics::BenchmarkResult;
+use super::runner::BenchmarkConfig;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const MIN_SAMPLES: usize = 3;
+const RECOMMENDED_ITERATIONS: usize = 30;
+const SHORT_TASK_MS: f64 = 1.0;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StabilityReport {
+    pub problem: String,
+    pub algorithm: String,
+    pub samples: usize,
+    pub time_cv: f64,
+    pub noisy: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recommendations: Vec<String>,
+}
+
+fn coefficient_of_variation(values: &[f64]) -> Option<f64> {
+    if values.len() < MIN_SAMPLES {
+        return None;
+    }
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    if mean <= 0.0 {
+        return None;
+    }
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    Some(variance.sqrt() / mean)
+}
+
+fn time_per_node(result: &BenchmarkResult) -> Option<f64> {
+    (result.status.is_solved() && result.metrics.nodes_visited > 0 && result.metrics.time_ms > 0.0)
+        .then(|| result.metrics.time_ms / result.metrics.nodes_visited as f64)
+}
+
+pub fn time_cv<'a>(results: impl IntoIterator<Item = &'a BenchmarkResult>) -> Option<f64> {
+    let samples: Vec<f64> = results.into_iter().filter_map(time_per_node).collect();
+    coefficient_of_variation(&samples)
+}
+
+fn recommendations(config: &BenchmarkConfig, samples: usize, avg_time_ms: f64) -> Vec<String> {
+    let mut advice = Vec::new();
+    if samples < RECOMMENDED_ITERATIONS {
+        advice.push(format!(
+            "augmenter --iterations ({} → {} ou plus)",
+            config.iterations, RECOMMENDED_ITERATIONS
+        ));
+    }
+    if config.threads != 1 && !config.isolate {
+        advice.push("--threads 1 ou --isolate pour limiter la contention".to_string());
+    }
+    if avg_time_ms < SHORT_TASK_MS {
+        advice.push("tâches trop courtes pour l'horloge : augmenter --size".to_string());
+    }
+    advice
+}
+
+pub fn analyze(config: &BenchmarkConfig, results: &[BenchmarkResult]) -> Vec<StabilityReport> {
+    let mut grouped: BTreeMap<(&str, &str), Vec<&BenchmarkResult>> = BTreeMap::new();
+    for result in results {
+        grouped
+            .entry((&result.problem, &result.algorithm))
+            .or_default()
+            .push(result);
+    }
+
+    let threshold = config.stability_threshold / 100.0;
+    grouped
+        .into_iter()
+        .filter_map(|((problem, algorithm), group)| {
+            let solved: Vec<&BenchmarkResult> = group
+                .into_iter()
+                .filter(|r| time_per_node(r).is_some())
+                .collect();
+            let time_cv = time_cv(solved.iter().copied())?;
+            let noisy = time_cv > threshold;
+            let avg_time_ms =
+                solved.iter().map(|r| r.metrics.time_ms).sum::<f64>() / solved.len() as f64;
+            Some(StabilityReport {
+                problem: problem.to_string(),
+                algorithm: algorithm.to_string(),
+                samples: solved.len(),
+                time_cv,
+                noisy,
+                recommendations: if noisy {
+                    recommendations(config, solved.len(), avg_time_ms)
+                } else {
+                    Vec::new()
+                },
+            })
+        })
+        .collect()
+}
+
+pub fn lines(config: &BenchmarkConfig, reports: &[StabilityReport]) -> Vec<String> {
+    if reports.is_empty() {
+        return Vec::new();
+    }
+    let mut lines = vec![format!(
+        "\nStabilité des mesures (CV du temps par nœud, seuil {}%):",
+        config.stability_threshold
+    )];
+    let noisy: Vec<&StabilityReport> = reports.iter().filter(|r| r.noisy).collect();
+    for report in &noisy {
+        lines.push(format!(
+            "  ⚠ {} sur {}: CV {:.1}% sur {} instances → {}",
+            report.algorithm,
+            report.problem,
+            report.time_cv * 100.0,
+            report.samples,
+            report.recommendations.join(" ; ")
+        ));
+    }
+    let worst = reports.iter().map(|r| r.time_cv).fold(0.0, f64::max);
+    lines.push(format!(
+        "  {}/{} configuration(s) stable(s) (CV max {:.1}%)",
+        reports.len() - noisy.len(),
+        reports.len(),
+        worst * 100.0
+    ));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::SearchStatus;
+    use crate::benchmarking::Metrics;
+
+    fn result(algorithm: &str, time_ms: f64, nodes_visited: usize) -> BenchmarkResult {
+        BenchmarkResult {
+            algorithm: algorithm.to_string(),
+            problem: "Taquin-3x3".to_string(),
+            problem_size: 3,
+            instance_id: 0,
+            seed: 0,
+            status: SearchStatus::Solved,
+            metrics: Metrics {
+                time_ms,
+                nodes_visited,
+                ..Metrics::default()
+            },
+            timestamp: String::new(),
+            hasher: String::new(),
+            initial_state: None,
+            solution: None,
+            error: None,
+            optimal: None,
+            cost_optimal: None,
+            properties: Default::default(),
+            profile: None,
+            improvements: Vec::new(),
+            host: None,
+        }
+    }
+
+    #[test]
+    fn test_flags_noisy_groups_only() {
+        let config = BenchmarkConfig {
+            iterations: 4,
+            threads: 4,
+            ..BenchmarkConfig::default()
+        };
+        let results = vec![
+            result("A*", 10.0, 1000),
+            result("A*", 20.0, 2000),
+            result("A*", 5.0, 500),
+            result("A*", 40.5, 4000),
+            result("BFS", 1.0, 1000),
+            result("BFS", 3.0, 1000),
+            result("BFS", 1.0, 1000),
+            result("DFS", 1.0, 10),
+        ];
+        let reports = analyze(&config, &results);
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].algorithm, "A*");
+        assert!(!reports[0].noisy && reports[0].time_cv < 0.01);
+        assert!(reports[1].noisy);
+        assert_eq!(reports[1].recommendations.len(), 2);
+        assert!(lines(&config, &reports)
+            .last()
+            .unwrap()
+            .contains("1/2 configuration(s)"));
+    }
+}
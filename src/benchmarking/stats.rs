@@ -0,0 +1,392 @@
+use super::import::{self, ImportOptions};
+use super::metrics::BenchmarkResult;
+use super::options::{ImportFormat, StatsGroup, StatsMetric, StatusFilter};
+use super::runs;
+use crate::algorithms::SearchStatus;
+use chrono::NaiveDate;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+pub struct StatsConfig {
+    pub sources: Vec<String>,
+    pub runs_dir: String,
+    pub algorithms: Vec<String>,
+    pub problems: Vec<String>,
+    pub size: Option<usize>,
+    pub statuses: Vec<StatusFilter>,
+    pub since: Option<NaiveDate>,
+    pub until: Option<NaiveDate>,
+    pub group_by: Vec<StatsGroup>,
+    pub metric: StatsMetric,
+    pub percentiles: Vec<f64>,
+    pub json: bool,
+}
+
+pub struct GroupStats {
+    pub key: Vec<String>,
+    pub count: usize,
+    pub solved: usize,
+    pub mean: f64,
+    pub min: f64,
+    pub max: f64,
+    pub percentiles: Vec<f64>,
+}
+
+pub fn parse_date(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| format!("date attendue au format AAAA-MM-JJ (reçu: {})", value))
+}
+
+pub fn parse_percentile(value: &str) -> Result<f64, String> {
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|p| (0.0..=100.0).contains(p))
+        .ok_or_else(|| format!("percentile entre 0 et 100 attendu (reçu: {})", value))
+}
+
+impl StatusFilter {
+    fn matches(self, status: &SearchStatus) -> bool {
+        matches!(
+            (self, status),
+            (StatusFilter::Solved, SearchStatus::Solved)
+                | (StatusFilter::Timeout, SearchStatus::Timeout)
+                | (StatusFilter::NoSolution, SearchStatus::NoSolution)
+                | (StatusFilter::DepthLimit, SearchStatus::DepthLimitReached)
+                | (StatusFilter::MemoryExceeded, SearchStatus::MemoryExceeded)
+                | (StatusFilter::NegativeCycle, SearchStatus::NegativeCycle)
+                | (StatusFilter::Skipped, SearchStatus::Skipped)
+                | (StatusFilter::Error, SearchStatus::Error(_))
+        )
+    }
+}
+
+impl StatsMetric {
+    fn unit(self) -> &'static str {
+        match self {
+            StatsMetric::Time => "ms",
+            StatsMetric::Memory => "Ko",
+            _ => "",
+        }
+    }
+
+    fn value(self, result: &BenchmarkResult) -> f64 {
+        let metrics = &result.metrics;
+        match self {
+            StatsMetric::Time => metrics.time_ms,
+            StatsMetric::Nodes => metrics.nodes_visited as f64,
+            StatsMetric::Generated => metrics.nodes_generated as f64,
+            StatsMetric::Memory => metrics.memory_kb as f64,
+            StatsMetric::Length => metrics.solution_length as f64,
+            StatsMetric::Cost => metrics.solution_cost as f64,
+        }
+    }
+}
+
+fn date(result: &BenchmarkResult) -> Option<NaiveDate> {
+    chrono::DateTime::parse_from_rfc3339(&result.timestamp)
+        .ok()
+        .map(|time| time.date_naive())
+}
+
+fn status_name(status: &SearchStatus) -> &'static str {
+    match status {
+        SearchStatus::Solved => "solved",
+        SearchStatus::Timeout => "timeout",
+        SearchStatus::NoSolution => "no-solution",
+        SearchStatus::DepthLimitReached => "depth-limit",
+        SearchStatus::MemoryExceeded => "memory-exceeded",
+        SearchStatus::NegativeCycle => "negative-cycle",
+        SearchStatus::Skipped => "skipped",
+        SearchStatus::Error(_) => "error",
+    }
+}
+
+fn group_value(group: StatsGroup, result: &BenchmarkResult) -> String {
+    match group {
+        StatsGroup::Algorithm => result.algorithm.clone(),
+        StatsGroup::Problem => result.problem.clone(),
+        StatsGroup::Size => result.problem_size.to_string(),
+        StatsGroup::Status => status_name(&result.status).to_string(),
+        StatsGroup::Host => result.host.clone().unwrap_or_else(|| "local".to_string()),
+        StatsGroup::Date => date(result).map_or("?".to_string(), |date| date.to_string()),
+    }
+}
+
+fn keep(config: &StatsConfig, result: &BenchmarkResult) -> bool {
+    let listed = |names: &[String], name: &str| {
+        names.is_empty()
+            || names.iter().any(|n| {
+                name.strip_prefix(n.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with(['-', '[']))
+            })
+    };
+    listed(&config.algorithms, &result.algorithm)
+        && listed(&config.problems, &result.problem)
+        && config.size.is_none_or(|size| size == result.problem_size)
+        && if config.statuses.is_empty() {
+            result.status != SearchStatus::Skipped
+        } else {
+            config.statuses.iter().any(|s| s.matches(&result.status))
+        }
+        && (config.since.is_none() && config.until.is_none()
+            || date(result).is_some_and(|date| {
+                config.since.is_none_or(|since| date >= since)
+                    && config.until.is_none_or(|until| date <= until)
+            }))
+}
+
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    let (low, high) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[low] + (sorted[high] - sorted[low]) * (rank - low as f64)
+}
+
+pub fn compute(config: &StatsConfig, results: &[BenchmarkResult]) -> Vec<GroupStats> {
+    let mut groups: BTreeMap<Vec<String>, Vec<&BenchmarkResult>> = BTreeMap::new();
+    for result in results.iter().filter(|r| keep(config, r)) {
+        let key = config
+            .group_by
+            .iter()
+            .map(|&group| group_value(group, result))
+            .collect();
+        groups.entry(key).or_default().push(result);
+    }
+
+    groups
+        .into_iter()
+        .map(|(key, group)| {
+            let mut values: Vec<f64> = group.iter().map(|r| config.metric.value(r)).collect();
+            values.sort_by(f64::total_cmp);
+            GroupStats {
+                key,
+                count: group.len(),
+                solved: group.iter().filter(|r| r.status.is_solved()).count(),
+                mean: values.iter().sum::<f64>() / values.len() as f64,
+                min: values[0],
+                max: values[values.len() - 1],
+                percentiles: config
+                    .percentiles
+                    .iter()
+                    .map(|&p| percentile(&values, p))
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+fn percentile_label(p: f64) -> String {
+    format!("p{}", p)
+}
+
+pub fn render_text(config: &StatsConfig, stats: &[GroupStats], total: usize) -> String {
+    let unit = match config.metric.unit() {
+        "" => String::new(),
+        unit => format!(", {}", unit),
+    };
+    let mut header: Vec<String> = config.group_by.iter().map(|g| g.to_string()).collect();
+    header.extend(["n", "succès", "moyenne", "min"].map(String::from));
+    header.extend(config.percentiles.iter().map(|&p| percentile_label(p)));
+    header.push("max".to_string());
+
+    let mut rows = vec![header];
+    for group in stats {
+        let mut row = group.key.clone();
+        row.push(group.count.to_string());
+        row.push(format!(
+            "{:.0}%",
+            group.solved as f64 * 100.0 / group.count as f64
+        ));
+        for value in [group.mean, group.min]
+            .into_iter()
+            .chain(group.percentiles.iter().copied())
+            .chain([group.max])
+        {
+            row.push(format!("{:.2}", value));
+        }
+        rows.push(row);
+    }
+
+    let widths: Vec<usize> = (0..rows[0].len())
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let mut out = format!(
+        "Statistiques ({}{}) : {} résultat(s) retenu(s) sur {}, {} groupe(s)\n",
+        config.metric,
+        unit,
+        stats.iter().map(|g| g.count).sum::<usize>(),
+        total,
+        stats.len()
+    );
+    for row in rows {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(column, (cell, &width))| {
+                if column < config.group_by.len() {
+                    format!("{:<width$}", cell)
+                } else {
+                    format!("{:>width$}", cell)
+                }
+            })
+            .collect();
+        out.push_str(&format!("  {}\n", cells.join("  ").trim_end()));
+    }
+    out
+}
+
+pub fn render_json(config: &StatsConfig, stats: &[GroupStats]) -> Value {
+    Value::Array(
+        stats
+            .iter()
+            .map(|group| {
+                let key: serde_json::Map<String, Value> = config
+                    .group_by
+                    .iter()
+                    .zip(&group.key)
+                    .map(|(g, value)| (g.to_string(), value.clone().into()))
+                    .collect();
+                let percentiles: serde_json::Map<String, Value> = config
+                    .percentiles
+                    .iter()
+                    .zip(&group.percentiles)
+                    .map(|(&p, &value)| (percentile_label(p), value.into()))
+                    .collect();
+                json!({
+                    "group": key,
+                    "metric": config.metric.to_string(),
+                    "count": group.count,
+                    "solved": group.solved,
+                    "mean": group.mean,
+                    "min": group.min,
+                    "max": group.max,
+                    "percentiles": percentiles,
+                })
+            })
+            .collect(),
+    )
+}
+
+fn load(spec: &str, runs_dir: &str) -> Result<Vec<BenchmarkResult>, String> {
+    if [".db", ".sqlite", ".sqlite3"]
+        .iter()
+        .any(|extension| spec.ends_with(extension))
+    {
+        return Err(format!(
+            "{}: les résultats sont écrits en JSON, aucune base SQLite à lire",
+            spec
+        ));
+    }
+    let path = runs::resolve(spec, Path::new(runs_dir))?;
+    import::load(&path, ImportFormat::Auto, &ImportOptions::default())
+}
+
+pub fn run(config: &StatsConfig) -> Result<(), String> {
+    let mut results = Vec::new();
+    for source in &config.sources {
+        results.extend(load(source, &config.runs_dir)?);
+    }
+    let stats = compute(config, &results);
+    if config.json {
+        let json = serde_json::to_string_pretty(&render_json(config, &stats))
+            .map_err(|e| e.to_string())?;
+        println!("{}", json);
+    } else {
+        print!("{}", render_text(config, &stats, results.len()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarking::Metrics;
+
+    fn result(algorithm: &str, size: usize, time_ms: f64, status: SearchStatus) -> BenchmarkResult {
+        BenchmarkResult {
+            algorithm: algorithm.to_string(),
+            problem: "Taquin".to_string(),
+            problem_size: size,
+            instance_id: 0,
+            seed: 0,
+            status,
+            metrics: Metrics {
+                time_ms,
+                ..Metrics::default()
+            },
+            timestamp: format!("2026-0{}-15T12:00:00+00:00", size),
+            hasher: String::new(),
+            initial_state: None,
+            solution: None,
+            error: None,
+            optimal: None,
+            cost_optimal: None,
+            properties: Default::default(),
+            profile: None,
+            improvements: Vec::new(),
+            host: None,
+        }
+    }
+
+    #[test]
+    fn test_filters_groups_and_percentiles() {
+        let results: Vec<BenchmarkResult> = [1.0, 2.0, 3.0, 4.0, 5.0]
+            .into_iter()
+            .map(|ms| result("A*", 3, ms, SearchStatus::Solved))
+            .chain([
+                result("A*", 4, 100.0, SearchStatus::Solved),
+                result("BFS", 3, 60.0, SearchStatus::Timeout),
+            ])
+            .collect();
+        let mut config = StatsConfig {
+            sources: Vec::new(),
+            runs_dir: runs::DEFAULT_DIR.to_string(),
+            algorithms: Vec::new(),
+            problems: Vec::new(),
+            size: Some(3),
+            statuses: Vec::new(),
+            since: None,
+            until: None,
+            group_by: vec![StatsGroup::Algorithm],
+            metric: StatsMetric::Time,
+            percentiles: vec![50.0, 90.0],
+            json: false,
+        };
+
+        let stats = compute(&config, &results);
+        assert_eq!(stats.len(), 2);
+        assert_eq!(
+            (stats[0].key.clone(), stats[0].count),
+            (vec!["A*".to_string()], 5)
+        );
+        assert_eq!(stats[0].mean, 3.0);
+        assert_eq!(stats[0].percentiles[0], 3.0);
+        assert!((stats[0].percentiles[1] - 4.6).abs() < 1e-9);
+        assert_eq!((stats[1].solved, stats[1].max), (0, 60.0));
+
+        config.algorithms = vec!["A".to_string(), "BFS".to_string()];
+        assert_eq!(compute(&config, &results).len(), 1);
+        config.algorithms.clear();
+        config.size = None;
+        config.statuses = vec![StatusFilter::Solved];
+        config.since = Some(parse_date("2026-04-01").unwrap());
+        config.group_by = vec![StatsGroup::Date, StatsGroup::Status];
+        let stats = compute(&config, &results);
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].key, vec!["2026-04-15", "solved"]);
+        assert!(
+            render_text(&config, &stats, results.len()).contains("1 résultat(s) retenu(s) sur 7")
+        );
+        assert_eq!(render_json(&config, &stats)[0]["percentiles"]["p90"], 100.0);
+
+        assert!(parse_date("15/04/2026").is_err());
+        assert!(parse_percentile("101").is_err());
+    }
+}
@@ -0,0 +1,227 @@
+use super::metrics::SharedMetrics;
+use super::progress::ProgressEvent;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use tiny_http::{Method, Response, Server};
+
+const METRICS_INTERVAL: Duration = Duration::from_millis(500);
+const SSE_HEADERS: &str = "HTTP/1.1 200 OK\r\n\
+    Content-Type: text/event-stream; charset=utf-8\r\n\
+    Cache-Control: no-cache\r\n\
+    Connection: close\r\n\
+    Access-Control-Allow-Origin: *\r\n\r\n";
+
+type Client = Box<dyn Write + Send>;
+
+#[derive(Default)]
+struct Clients {
+    writers: Vec<Client>,
+    history: Vec<String>,
+    closed: bool,
+}
+
+impl Clients {
+    fn broadcast(&mut self, message: String, replay: bool) {
+        self.writers.retain_mut(|writer| {
+            writer
+                .write_all(message.as_bytes())
+                .and_then(|_| writer.flush())
+                .is_ok()
+        });
+        if replay {
+            self.history.push(message);
+        }
+    }
+}
+
+pub struct StreamHandle {
+    server: Arc<Server>,
+    broadcaster: JoinHandle<()>,
+    listener: JoinHandle<()>,
+}
+
+impl StreamHandle {
+    pub fn finish(self) {
+        let _ = self.broadcaster.join();
+        self.server.unblock();
+        let _ = self.listener.join();
+    }
+}
+
+fn message(event: &str, data: &Value) -> String {
+    format!("event: {}\ndata: {}\n\n", event, data)
+}
+
+fn event_message(event: &ProgressEvent) -> String {
+    match event {
+        ProgressEvent::ProblemStarted { problem, tasks } => {
+            message("problem", &json!({ "problem": problem, "tasks": tasks }))
+        }
+        ProgressEvent::TaskStarted {
+            task,
+            algorithm,
+            instance,
+            thread,
+            ..
+        } => message(
+            "task_started",
+            &json!({
+                "task": task,
+                "algorithm": algorithm,
+                "instance": instance,
+                "thread": thread,
+            }),
+        ),
+        ProgressEvent::TaskFinished {
+            task,
+            result,
+            time_ms,
+        } => message(
+            "task_finished",
+            &json!({ "task": task, "time_ms": time_ms, "result": result }),
+        ),
+        ProgressEvent::Log(line) => message("log", &json!({ "line": line })),
+    }
+}
+
+fn metrics_message(task: usize, metrics: &SharedMetrics) -> String {
+    message(
+        "metrics",
+        &json!({
+            "task": task,
+            "elapsed_ms": metrics.elapsed().as_secs_f64() * 1000.0,
+            "metrics": metrics.get(),
+        }),
+    )
+}
+
+fn broadcast(events: Receiver<ProgressEvent>, clients: Arc<Mutex<Clients>>) {
+    let mut running: BTreeMap<usize, SharedMetrics> = BTreeMap::new();
+    loop {
+        match events.recv_timeout(METRICS_INTERVAL) {
+            Ok(event) => {
+                match &event {
+                    ProgressEvent::ProblemStarted { .. } => running.clear(),
+                    ProgressEvent::TaskStarted { task, metrics, .. } => {
+                        running.insert(*task, metrics.clone());
+                    }
+                    ProgressEvent::TaskFinished { task, .. } => {
+                        running.remove(task);
+                    }
+                    ProgressEvent::Log(_) => {}
+                }
+                clients
+                    .lock()
+                    .unwrap()
+                    .broadcast(event_message(&event), true);
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                let mut clients = clients.lock().unwrap();
+                for (task, metrics) in &running {
+                    clients.broadcast(metrics_message(*task, metrics), false);
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    let mut clients = clients.lock().unwrap();
+    clients.broadcast(message("done", &json!({})), true);
+    clients.writers.clear();
+    clients.closed = true;
+}
+
+fn listen(server: Arc<Server>, clients: Arc<Mutex<Clients>>) {
+    for request in server.incoming_requests() {
+        if (request.method(), request.url()) != (&Method::Get, "/events") {
+            let _ = request.respond(Response::from_string("Introuvable").with_status_code(404));
+            continue;
+        }
+
+        let mut writer = request.into_writer();
+        let mut clients = clients.lock().unwrap();
+        let replayed = writer
+            .write_all(SSE_HEADERS.as_bytes())
+            .and_then(|_| {
+                clients
+                    .history
+                    .iter()
+                    .try_for_each(|message| writer.write_all(message.as_bytes()))
+            })
+            .and_then(|_| writer.flush());
+        if replayed.is_ok() && !clients.closed {
+            clients.writers.push(writer);
+        }
+    }
+}
+
+pub fn serve(address: &str, events: Receiver<ProgressEvent>) -> Result<StreamHandle, String> {
+    let server = Arc::new(
+        Server::http(address)
+            .map_err(|e| format!("Impossible d'écouter sur {}: {}", address, e))?,
+    );
+    println!(
+        "Suivi en direct (SSE): http://{}/events",
+        server.server_addr()
+    );
+
+    let clients = Arc::new(Mutex::new(Clients::default()));
+    let broadcaster = {
+        let clients = Arc::clone(&clients);
+        thread::spawn(move || broadcast(events, clients))
+    };
+    let listener = {
+        let server = Arc::clone(&server);
+        thread::spawn(move || listen(server, clients))
+    };
+
+    Ok(StreamHandle {
+        server,
+        broadcaster,
+        listener,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc::channel;
+
+    #[test]
+    fn test_events_are_replayed_and_closed() {
+        let (sender, receiver) = channel();
+        let clients = Arc::new(Mutex::new(Clients::default()));
+        let broadcaster = {
+            let clients = Arc::clone(&clients);
+            thread::spawn(move || broadcast(receiver, clients))
+        };
+
+        sender
+            .send(ProgressEvent::ProblemStarted {
+                problem: "Taquin-3x3".to_string(),
+                tasks: 2,
+            })
+            .unwrap();
+        sender
+            .send(ProgressEvent::Log("Résumé".to_string()))
+            .unwrap();
+        drop(sender);
+        broadcaster.join().unwrap();
+
+        let clients = clients.lock().unwrap();
+        assert!(clients.closed);
+        assert_eq!(
+            clients.history,
+            vec![
+                "event: problem\ndata: {\"problem\":\"Taquin-3x3\",\"tasks\":2}\n\n".to_string(),
+                "event: log\ndata: {\"line\":\"Résumé\"}\n\n".to_string(),
+                "event: done\ndata: {}\n\n".to_string(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,270 @@
+use super::metrics::InstanceProperties;
+use super::options::SweepParameter;
+use super::runner::{AlgorithmSettings, BenchmarkConfig};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+impl SweepParameter {
+    fn applies_to(self, algorithm: &str) -> bool {
+        match self {
+            SweepParameter::MaxDepth => {
+                matches!(
+                    algorithm,
+                    "DFS" | "ID" | "Frontier" | "BFHS" | "IDA*" | "Perimeter" | "GA"
+                )
+            }
+            SweepParameter::DepthLimit => algorithm == "DLS",
+            SweepParameter::MemoryLimit => matches!(algorithm, "BFS" | "A*" | "Greedy"),
+            SweepParameter::PerimeterRadius => algorithm == "Perimeter",
+            SweepParameter::MemoryBudget => algorithm == "SMA*",
+            SweepParameter::Lookahead | SweepParameter::StepBudget => algorithm == "LRTA*",
+            SweepParameter::K => algorithm == "Yen",
+            SweepParameter::Population | SweepParameter::Generations => algorithm == "GA",
+            SweepParameter::CostBound => true,
+        }
+    }
+
+    fn property(self) -> &'static str {
+        match self {
+            SweepParameter::MaxDepth => "max_depth",
+            SweepParameter::DepthLimit => "depth_limit",
+            SweepParameter::MemoryLimit => "memory_limit_mb",
+            SweepParameter::PerimeterRadius => "perimeter_radius",
+            SweepParameter::MemoryBudget => "memory_budget",
+            SweepParameter::Lookahead => "lookahead",
+            SweepParameter::StepBudget => "step_budget",
+            SweepParameter::K => "k",
+            SweepParameter::Population => "population",
+            SweepParameter::Generations => "generations",
+            SweepParameter::CostBound => "cost_bound",
+        }
+    }
+
+    fn apply(self, settings: &mut AlgorithmSettings, value: usize) {
+        match self {
+            SweepParameter::MaxDepth => settings.max_depth = value,
+            SweepParameter::DepthLimit => settings.depth_limit = Some(value),
+            SweepParameter::MemoryLimit => {
+                settings.memory_limit_kb = (value > 0).then_some(value * 1024)
+            }
+            SweepParameter::PerimeterRadius => settings.perimeter_radius = value,
+            SweepParameter::MemoryBudget => settings.memory_budget = value,
+            SweepParameter::Lookahead => settings.lookahead = value,
+            SweepParameter::StepBudget => settings.step_budget = value,
+            SweepParameter::K => settings.k = value,
+            SweepParameter::Population => settings.population = value,
+            SweepParameter::Generations => settings.generations = value,
+            SweepParameter::CostBound => settings.cost_bound = Some(value),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Sweep {
+    pub parameter: SweepParameter,
+    pub values: Vec<usize>,
+}
+
+pub fn parse(spec: &str) -> Result<Sweep, String> {
+    let (name, values) = spec
+        .split_once('=')
+        .ok_or_else(|| format!("attendu PARAMÈTRE=V1,V2,… (reçu: {})", spec))?;
+    let parameter = SweepParameter::from_str(name.trim(), true).map_err(|_| {
+        let names: Vec<String> = SweepParameter::value_variants()
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+        format!(
+            "paramètre inconnu: {} (disponibles: {})",
+            name,
+            names.join(", ")
+        )
+    })?;
+    let values = values
+        .split(',')
+        .map(|value| {
+            value
+                .trim()
+                .parse::<usize>()
+                .map_err(|_| format!("valeur invalide pour {}: {}", parameter, value))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Sweep { parameter, values })
+}
+
+fn base_algorithm(algo_name: &str) -> &str {
+    algo_name.split('-').next().unwrap_or(algo_name)
+}
+
+pub fn validate(config: &BenchmarkConfig) -> Result<(), String> {
+    for (i, sweep) in config.sweeps.iter().enumerate() {
+        if sweep.values.is_empty() {
+            return Err(format!("--sweep {}: aucune valeur", sweep.parameter));
+        }
+        if config.sweeps[..i]
+            .iter()
+            .any(|other| other.parameter == sweep.parameter)
+        {
+            return Err(format!("--sweep {} apparaît deux fois", sweep.parameter));
+        }
+    }
+    if !config.sweeps.is_empty() && config.scenario.is_some() {
+        return Err("--sweep n'est pas disponible avec --scenario".to_string());
+    }
+    if let Some(sweep) = config.sweeps.iter().find(|sweep| {
+        !super::BenchmarkRunner::algorithm_names(config, &[])
+            .iter()
+            .any(|name| sweep.parameter.applies_to(base_algorithm(name)))
+    }) {
+        return Err(format!(
+            "--sweep {} ne concerne aucun algorithme sélectionné (algorithme: {})",
+            sweep.parameter, config.algorithm
+        ));
+    }
+    Ok(())
+}
+
+pub fn expand(config: &BenchmarkConfig, algorithm_names: Vec<String>) -> Vec<String> {
+    if config.sweeps.is_empty() {
+        return algorithm_names;
+    }
+    let mut expanded = Vec::new();
+    for name in algorithm_names {
+        let mut combinations = vec![Vec::new()];
+        for sweep in &config.sweeps {
+            if !sweep.parameter.applies_to(base_algorithm(&name)) {
+                continue;
+            }
+            combinations = combinations
+                .iter()
+                .flat_map(|combination: &Vec<String>| {
+                    sweep.values.iter().map(move |value| {
+                        let mut next = combination.clone();
+                        next.push(format!("{}={}", sweep.parameter, value));
+                        next
+                    })
+                })
+                .collect();
+        }
+        for combination in combinations {
+            expanded.push(match combination.as_slice() {
+                [] => name.clone(),
+                parameters => format!("{}[{}]", name, parameters.join(",")),
+            });
+        }
+    }
+    expanded
+}
+
+pub fn split(algo_name: &str) -> (&str, Vec<(SweepParameter, usize)>) {
+    let Some((name, parameters)) = algo_name
+        .strip_suffix(']')
+        .and_then(|rest| rest.split_once('['))
+    else {
+        return (algo_name, Vec::new());
+    };
+    let parameters = parameters
+        .split(',')
+        .filter_map(|assignment| {
+            let (parameter, value) = assignment.split_once('=')?;
+            Some((
+                SweepParameter::from_str(parameter, false).ok()?,
+                value.parse().ok()?,
+            ))
+        })
+        .collect();
+    (name, parameters)
+}
+
+pub(crate) fn resolve<'a>(
+    algo_name: &'a str,
+    mut settings: AlgorithmSettings,
+    properties: &mut InstanceProperties,
+) -> (&'a str, AlgorithmSettings) {
+    let (name, parameters) = split(algo_name);
+    for (parameter, value) in parameters {
+        parameter.apply(&mut settings, value);
+        properties.insert(parameter.property().to_string(), value.into());
+    }
+    (name, settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarking::{AlgorithmChoice, BenchmarkRunner, TieBreaking};
+
+    #[test]
+    fn test_sweep_expands_applicable_algorithms() {
+        let config = BenchmarkConfig {
+            sweeps: vec![
+                parse("max-depth=10,20").unwrap(),
+                parse("cost-bound=30").unwrap(),
+            ],
+            ..BenchmarkConfig::default()
+        };
+        assert!(parse("weight=1,2").is_err());
+        assert!(parse("max-depth=10,x").is_err());
+        assert!(validate(&config).is_ok());
+
+        let names = expand(&config, vec!["BFS".to_string(), "DFS".to_string()]);
+        assert_eq!(
+            names,
+            vec![
+                "BFS[cost-bound=30]",
+                "DFS[max-depth=10,cost-bound=30]",
+                "DFS[max-depth=20,cost-bound=30]",
+            ]
+        );
+
+        let settings = AlgorithmSettings {
+            max_depth: 100,
+            memory_limit_kb: None,
+            track_solutions: true,
+            reexpand: false,
+            cost_bound: None,
+            depth_limit: None,
+            perimeter_radius: 10,
+            memory_budget: 100_000,
+            weight: 2.0,
+            bound: 1.5,
+            lookahead: 3,
+            step_budget: 1000,
+            k: 5,
+            population: 100,
+            generations: 200,
+            seed: 0,
+            tie_breaking: TieBreaking::HighG,
+            lazy_duplicates: false,
+            order_successors: false,
+            profile: false,
+        };
+        let mut properties = InstanceProperties::new();
+        let (name, resolved) = resolve(&names[2], settings, &mut properties);
+        assert_eq!(name, "DFS");
+        assert_eq!((resolved.max_depth, resolved.cost_bound), (20, Some(30)));
+        assert_eq!(properties["max_depth"], 20);
+        assert_eq!(split("A*-Manhattan"), ("A*-Manhattan", Vec::new()));
+
+        let astar = BenchmarkConfig {
+            algorithm: AlgorithmChoice::Astar,
+            sweeps: vec![parse("perimeter-radius=2,4").unwrap()],
+            ..BenchmarkConfig::default()
+        };
+        assert!(validate(&astar).is_err());
+        let perimeter = BenchmarkConfig {
+            algorithm: AlgorithmChoice::Perimeter,
+            ..astar
+        };
+        assert_eq!(
+            expand(
+                &perimeter,
+                BenchmarkRunner::algorithm_names(&perimeter, &["Manhattan".to_string()])
+            ),
+            vec![
+                "Perimeter-Manhattan[perimeter-radius=2]",
+                "Perimeter-Manhattan[perimeter-radius=4]"
+            ]
+        );
+    }
+}
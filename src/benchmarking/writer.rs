@@ -0,0 +1,160 @@
+use super::metrics::{BenchmarkResult, ResultIndexEntry};
+use crate::algorithms::trace::SearchTrace;
+use crate::algorithms::Problem;
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+pub struct ResultWriter {
+    out: BufWriter<File>,
+    written: usize,
+    index: Vec<ResultIndexEntry>,
+}
+
+impl ResultWriter {
+    pub fn create(path: &str) -> Result<Self, Box<dyn Error>> {
+        let mut out = BufWriter::new(File::create(path)?);
+        out.write_all(b"[")?;
+        Ok(ResultWriter {
+            out,
+            written: 0,
+            index: Vec::new(),
+        })
+    }
+
+    pub fn write_batch(
+        &mut self,
+        problem: &str,
+        results: &[BenchmarkResult],
+    ) -> Result<(), Box<dyn Error>> {
+        let first = self.written;
+        for result in results {
+            let separator: &[u8] = if self.written == 0 { b"\n" } else { b",\n" };
+            self.out.write_all(separator)?;
+            serde_json::to_writer_pretty(&mut self.out, result)?;
+            self.written += 1;
+        }
+        self.out.flush()?;
+
+        self.index.push(ResultIndexEntry {
+            problem: problem.to_string(),
+            first,
+            count: results.len(),
+        });
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> Result<Vec<ResultIndexEntry>, Box<dyn Error>> {
+        self.out.write_all(b"\n]\n")?;
+        self.out.flush()?;
+        Ok(self.index)
+    }
+}
+
+pub fn write_trace<P: Problem>(
+    path: &Path,
+    header: &str,
+    problem: &P,
+    trace: &SearchTrace<P::State>,
+) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut out = BufWriter::new(File::create(path)?);
+    writeln!(out, "# {}", header)?;
+    writeln!(
+        out,
+        "# {} expansions{}",
+        trace.expansions.len(),
+        if trace.truncated { " (tronqué)" } else { "" }
+    )?;
+    for state in &trace.expansions {
+        writeln!(out, "{}", problem.encode_state(state))?;
+    }
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::SearchStatus;
+    use crate::benchmarking::Metrics;
+
+    fn result(instance_id: usize) -> BenchmarkResult {
+        BenchmarkResult {
+            algorithm: "BFS".to_string(),
+            problem: "Taquin-3x3".to_string(),
+            problem_size: 3,
+            instance_id,
+            seed: 0,
+            status: SearchStatus::Solved,
+            metrics: Metrics::default(),
+            timestamp: String::new(),
+            hasher: String::new(),
+            initial_state: None,
+            solution: None,
+            error: None,
+            optimal: None,
+            cost_optimal: None,
+            properties: Default::default(),
+            profile: None,
+            improvements: Vec::new(),
+            host: None,
+        }
+    }
+
+    #[test]
+    fn test_batches_form_a_json_array() {
+        let path = std::env::temp_dir().join(format!("writer-{}.json", std::process::id()));
+        let path = path.to_string_lossy().into_owned();
+
+        let mut writer = ResultWriter::create(&path).unwrap();
+        writer
+            .write_batch("Taquin-3x3", &[result(0), result(1)])
+            .unwrap();
+        writer.write_batch("Vide", &[]).unwrap();
+        writer.write_batch("Taquin-3x3", &[result(2)]).unwrap();
+        let index = writer.finish().unwrap();
+
+        let parsed: Vec<BenchmarkResult> =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed[2].instance_id, 2);
+        assert_eq!((index[2].first, index[2].count), (2, 1));
+    }
+
+    #[test]
+    fn test_trace_lists_encoded_expansions() {
+        use crate::algorithms::bfs::BFS;
+        use crate::algorithms::trace::Traced;
+        use crate::algorithms::SearchAlgorithm;
+        use crate::problems::taquin::HeuristicType;
+        use crate::problems::Taquin;
+
+        let taquin =
+            Taquin::from_state(3, vec![1, 2, 5, 3, 4, 0, 6, 7, 8], HeuristicType::Manhattan);
+        let traced = Traced::new(&taquin)
+            .with_edges(false)
+            .with_max_expansions(2);
+        BFS::new().search(&traced);
+        let trace = traced.take_trace();
+        assert!(trace.truncated && trace.edges.is_empty());
+
+        let path = std::env::temp_dir()
+            .join(format!("traces-{}", std::process::id()))
+            .join("bfs.trace");
+        write_trace(&path, "BFS", &taquin, &trace).unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(
+            lines[..3],
+            ["# BFS", "# 2 expansions (tronqué)", "125340678"]
+        );
+        assert_eq!(lines.len(), 4);
+    }
+}
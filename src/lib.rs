@@ -0,0 +1,4 @@
+pub mod algorithms;
+pub mod benchmarking;
+pub mod problems;
+pub mod utils;
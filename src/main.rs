@@ -1,21 +1,50 @@
-use clap::Parser;
+use chrono::NaiveDate;
+use clap::builder::PossibleValuesParser;
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, Subcommand};
 use std::fs;
+use std::path::Path;
+use std::sync::Arc;
 use std::time::Instant;
 
-mod algorithms;
-mod benchmarking;
-mod problems;
-mod utils;
-
-use benchmarking::{BenchmarkConfig, BenchmarkRunner};
+#[cfg(feature = "web")]
+use benchmarking_rust::benchmarking::api::{self, ApiConfig};
+use benchmarking_rust::benchmarking::budget;
+use benchmarking_rust::benchmarking::compare::{self, CompareConfig};
+#[cfg(feature = "tui")]
+use benchmarking_rust::benchmarking::dashboard;
+use benchmarking_rust::benchmarking::debugger::DebugConfig;
+use benchmarking_rust::benchmarking::distributed::{self, Coordinator, WorkerConfig};
+use benchmarking_rust::benchmarking::dot::{self, DotConfig};
+use benchmarking_rust::benchmarking::fuzz::{self, FuzzConfig};
+#[cfg(feature = "grpc")]
+use benchmarking_rust::benchmarking::grpc::{self, GrpcConfig};
+use benchmarking_rust::benchmarking::heuristic_check::{self, HeuristicCheckConfig};
+use benchmarking_rust::benchmarking::import::{self, ImportConfig, ImportOptions};
+use benchmarking_rust::benchmarking::isolation;
+use benchmarking_rust::benchmarking::render::{self, RenderConfig};
+use benchmarking_rust::benchmarking::runs;
+#[cfg(feature = "web")]
+use benchmarking_rust::benchmarking::server::{self, ServeConfig};
+use benchmarking_rust::benchmarking::stats::{self, StatsConfig};
+#[cfg(feature = "web")]
+use benchmarking_rust::benchmarking::stream;
+use benchmarking_rust::benchmarking::sweep::{self, Sweep};
+use benchmarking_rust::benchmarking::{
+    AlgorithmChoice, BenchmarkConfig, BenchmarkRunner, CompareFormat, Connectivity, Direction,
+    GoalTest, HeatmapMode, Heuristic, ImportFormat, MazeGenerator, MoveCost, ShuffleMode,
+    StatsGroup, StatsMetric, StatusFilter, TieBreaking,
+};
+use benchmarking_rust::problems::registry;
+use benchmarking_rust::utils::hashing::HASHER_NAME;
 
 #[derive(Parser, Debug)]
-#[command(author, version, about = "Benchmarking d'algorithmes de recherche", long_about = None)]
+#[command(name = "benchmark", author, version, about = "Benchmarking d'algorithmes de recherche", long_about = None)]
 struct Args {
-    #[arg(short, long, default_value = "all")]
-    algorithm: String,
+    #[arg(short, long, value_enum, default_value_t = AlgorithmChoice::All)]
+    algorithm: AlgorithmChoice,
 
-    #[arg(short, long, default_value = "all")]
+    #[arg(short, long, default_value = "all", value_parser = problem_names())]
     problem: String,
 
     #[arg(short, long, default_value = "3")]
@@ -32,17 +61,895 @@ struct Args {
 
     #[arg(long, default_value = "60")]
     timeout: u64,
+
+    #[arg(long, default_value = "0")]
+    memory_limit: usize,
+
+    #[arg(long)]
+    no_solutions: bool,
+
+    #[arg(long, value_enum, default_value_t = ShuffleMode::Walk)]
+    shuffle: ShuffleMode,
+
+    #[arg(long, value_enum, default_value_t = MoveCost::Unit)]
+    move_cost: MoveCost,
+
+    #[arg(long, value_enum, value_delimiter = ',', value_name = "H1,H2")]
+    heuristics: Vec<Heuristic>,
+
+    #[arg(long, value_name = "5-5-5|1,2,3/4,5,6")]
+    pdb_partition: Option<String>,
+
+    #[arg(long, default_value = "results/pdb")]
+    pdb_dir: String,
+
+    #[arg(long)]
+    verify_optimal: bool,
+
+    #[arg(long)]
+    check_consistency: bool,
+
+    #[arg(long)]
+    seed: Option<u64>,
+
+    #[arg(long)]
+    instances: Option<String>,
+
+    #[arg(long, value_enum, default_value_t = Connectivity::None)]
+    connectivity: Connectivity,
+
+    #[arg(long, value_enum, default_value_t = MazeGenerator::Backtracker)]
+    maze_generator: MazeGenerator,
+
+    #[arg(long, value_name = "FRACTION", default_value = "0")]
+    negative_edges: f64,
+
+    #[arg(long, value_name = "FICHIER")]
+    graph: Option<String>,
+
+    #[arg(long, value_name = "FICHIER")]
+    map: Option<String>,
+
+    #[arg(long, value_name = "FICHIER")]
+    scenario: Option<String>,
+
+    #[arg(long, value_name = "ÉPISODES", default_value_t = 0)]
+    dynamic_obstacles: usize,
+
+    #[arg(long)]
+    all_pairs: bool,
+
+    #[arg(long, value_name = "K", default_value_t = 4)]
+    colors: usize,
+
+    #[arg(long, value_name = "NŒUD[,NŒUD...]")]
+    source: Option<String>,
+
+    #[arg(long, value_name = "N", default_value_t = 1)]
+    sources: usize,
+
+    #[arg(long, value_name = "NŒUD")]
+    target: Option<String>,
+
+    #[arg(long, value_name = "BUT")]
+    goal: Option<String>,
+
+    #[arg(long)]
+    undirected: bool,
+
+    #[arg(long, value_name = "FICHIER")]
+    problem_script: Option<String>,
+
+    #[arg(long, value_name = "COMMANDE", conflicts_with = "problem_script")]
+    problem_command: Option<String>,
+
+    #[arg(long)]
+    reexpand: bool,
+
+    #[arg(long, value_enum, default_value_t = GoalTest::Expansion)]
+    goal_test: GoalTest,
+
+    #[arg(long, value_enum, default_value_t = Direction::Forward)]
+    direction: Direction,
+
+    #[arg(long, value_name = "C")]
+    cost_bound: Option<usize>,
+
+    #[arg(long, value_name = "N")]
+    depth_limit: Option<usize>,
+
+    #[arg(long, value_name = "R", default_value = "10")]
+    perimeter_radius: usize,
+
+    #[arg(long, value_name = "NŒUDS", default_value = "100000")]
+    memory_budget: usize,
+
+    #[arg(long, value_name = "W", default_value = "2")]
+    weight: f64,
+
+    #[arg(long, value_name = "W", default_value = "1.5")]
+    bound: f64,
+
+    #[arg(long, value_name = "PROFONDEUR", default_value = "3")]
+    lookahead: usize,
+
+    #[arg(long, value_name = "EXPANSIONS", default_value = "1000")]
+    step_budget: usize,
+
+    #[arg(long, value_name = "K", default_value = "5")]
+    k: usize,
+
+    #[arg(long, value_name = "INDIVIDUS", default_value = "100")]
+    population: usize,
+
+    #[arg(long, value_name = "N", default_value = "200")]
+    generations: usize,
+
+    #[arg(long, value_enum, default_value_t = TieBreaking::HighG)]
+    tie_breaking: TieBreaking,
+
+    #[arg(long)]
+    lazy_duplicates: bool,
+
+    #[arg(long)]
+    order_successors: bool,
+
+    #[arg(long, value_name = "PARAMÈTRE=V1,V2", value_parser = sweep::parse)]
+    sweep: Vec<Sweep>,
+
+    #[arg(long)]
+    profile: bool,
+
+    #[arg(long)]
+    trace: bool,
+
+    #[arg(long, requires = "trace")]
+    trace_limit: Option<usize>,
+
+    #[arg(long, value_name = "ADRESSE")]
+    coordinator: Option<String>,
+
+    #[arg(long, conflicts_with = "coordinator")]
+    isolate: bool,
+
+    #[arg(long, default_value = "20", value_name = "POURCENT")]
+    stability_threshold: f64,
+
+    #[arg(long, value_name = "DURÉE", value_parser = budget::parse)]
+    budget: Option<u64>,
+
+    #[cfg(feature = "tui")]
+    #[arg(long)]
+    tui: bool,
+
+    #[cfg(feature = "web")]
+    #[arg(long, value_name = "ADRESSE")]
+    stream: Option<String>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Vérifie les invariants sur des petites instances aléatoires
+    Fuzz {
+        #[arg(short, long, default_value = "all", value_parser = problem_names())]
+        problem: String,
+
+        #[arg(short, long, default_value = "100")]
+        cases: usize,
+
+        #[arg(long)]
+        seed: Option<u64>,
+
+        #[arg(long, default_value = "3")]
+        max_size: usize,
+
+        #[arg(long, default_value = "1")]
+        samples: usize,
+    },
+    /// Compare l'heuristique aux distances réelles (recherche arrière depuis le but)
+    Heuristic {
+        #[arg(short, long, default_value = "all", value_parser = problem_names())]
+        problem: String,
+
+        #[arg(short, long, default_value = "3")]
+        size: usize,
+
+        #[arg(short, long, default_value = "5")]
+        instances: usize,
+
+        #[arg(long, default_value = "1000")]
+        samples: usize,
+
+        #[arg(long, default_value = "200000")]
+        max_states: usize,
+
+        #[arg(long, value_enum, default_value_t = ShuffleMode::Walk)]
+        shuffle: ShuffleMode,
+
+        #[arg(long, value_enum, default_value_t = MoveCost::Unit)]
+        move_cost: MoveCost,
+
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// Exporte l'arbre de recherche exploré au format DOT (Graphviz), avec g/h/f et la solution
+    Dot {
+        #[arg(short, long, default_value = "all", value_parser = problem_names())]
+        problem: String,
+
+        #[arg(short, long, value_enum, default_value_t = AlgorithmChoice::All)]
+        algorithm: AlgorithmChoice,
+
+        #[arg(short, long, default_value = "3")]
+        size: usize,
+
+        #[arg(long, default_value = "1")]
+        instance: usize,
+
+        #[arg(long, default_value = "200")]
+        max_nodes: usize,
+
+        #[arg(short, long, default_value = "results/dot")]
+        output: String,
+
+        #[arg(long, value_enum, default_value_t = ShuffleMode::Walk)]
+        shuffle: ShuffleMode,
+
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// Exécute une recherche pas à pas (expansions, frontière, g/h/f, points d'arrêt) sur une instance
+    Debug {
+        #[arg(short, long, default_value = "taquin", value_parser = PossibleValuesParser::new(registry::names()))]
+        problem: String,
+
+        #[arg(short, long, value_enum, default_value_t = AlgorithmChoice::Astar)]
+        algorithm: AlgorithmChoice,
+
+        #[arg(short, long, default_value = "3")]
+        size: usize,
+
+        #[arg(long, default_value = "1")]
+        instance: usize,
+
+        #[arg(long, value_enum, default_value_t = ShuffleMode::Walk)]
+        shuffle: ShuffleMode,
+
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// Compare deux fichiers de résultats ou exécutions indexées (régressions et améliorations par problème et algorithme)
+    Compare {
+        baseline: String,
+
+        candidate: String,
+
+        #[arg(long, default_value = runs::DEFAULT_DIR)]
+        runs_dir: String,
+
+        #[arg(long, value_enum, default_value_t = CompareFormat::Text)]
+        format: CompareFormat,
+
+        #[arg(long, default_value = "5")]
+        threshold: f64,
+
+        #[arg(long)]
+        fail_on_regression: bool,
+    },
+    /// Convertit des mesures hyperfine ou Criterion (JSON) en fichier de résultats comparable
+    Import {
+        #[arg(required = true)]
+        sources: Vec<String>,
+
+        #[arg(long, value_enum, default_value_t = ImportFormat::Auto)]
+        format: ImportFormat,
+
+        #[arg(short, long, default_value = "externe")]
+        problem: String,
+
+        #[arg(short, long, default_value = "0")]
+        size: usize,
+
+        #[arg(long)]
+        label: Option<String>,
+
+        #[arg(short, long, default_value = "results/imported_results.json")]
+        output: String,
+    },
+    /// Recalcule des statistiques sur des fichiers de résultats existants (filtres, regroupements, percentiles)
+    Stats {
+        #[arg(required = true)]
+        sources: Vec<String>,
+
+        #[arg(short, long, value_delimiter = ',')]
+        algorithm: Vec<String>,
+
+        #[arg(short, long, value_delimiter = ',')]
+        problem: Vec<String>,
+
+        #[arg(short, long)]
+        size: Option<usize>,
+
+        #[arg(long, value_enum, value_delimiter = ',')]
+        status: Vec<StatusFilter>,
+
+        #[arg(long, value_name = "AAAA-MM-JJ", value_parser = stats::parse_date)]
+        since: Option<NaiveDate>,
+
+        #[arg(long, value_name = "AAAA-MM-JJ", value_parser = stats::parse_date)]
+        until: Option<NaiveDate>,
+
+        #[arg(
+            long,
+            value_enum,
+            value_delimiter = ',',
+            default_value = "problem,algorithm"
+        )]
+        group_by: Vec<StatsGroup>,
+
+        #[arg(long, value_enum, default_value_t = StatsMetric::Time)]
+        metric: StatsMetric,
+
+        #[arg(long, value_delimiter = ',', default_value = "50,90,99", value_parser = stats::parse_percentile)]
+        percentiles: Vec<f64>,
+
+        #[arg(long)]
+        json: bool,
+
+        #[arg(long, default_value = runs::DEFAULT_DIR)]
+        runs_dir: String,
+    },
+    /// Liste ou supprime les exécutions enregistrées dans l'index (results/index.json)
+    Runs {
+        #[command(subcommand)]
+        action: RunsAction,
+    },
+    /// Exécute une tâche reçue sur l'entrée standard (processus lancé par --isolate)
+    #[command(name = isolation::CHILD_COMMAND, hide = true)]
+    IsolatedTask,
+    /// Exécute les tâches réparties par un coordinateur (benchmark --coordinator)
+    Worker {
+        #[arg(long, value_name = "HÔTE:PORT")]
+        connect: String,
+
+        #[arg(short = 't', long, default_value = "0")]
+        threads: usize,
+
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// Sert un tableau de bord web local (tableaux et graphiques filtrables) sur des fichiers de résultats
+    #[cfg(feature = "web")]
+    Serve {
+        #[arg(default_value = "results")]
+        sources: Vec<String>,
+
+        #[arg(long, default_value = "127.0.0.1:8080")]
+        address: String,
+    },
+    /// Sert une API REST : soumission de configurations, suivi des tâches, résultats et résumés en JSON
+    #[cfg(feature = "web")]
+    Server {
+        #[arg(long, default_value = "127.0.0.1:8081")]
+        address: String,
+
+        #[arg(short, long, default_value = "results/api")]
+        output: String,
+
+        #[arg(short = 't', long, default_value = "0")]
+        max_threads: usize,
+    },
+    /// Sert le service gRPC benchmark.Benchmarking (Solve, Benchmark en flux) défini dans proto/benchmark.proto
+    #[cfg(feature = "grpc")]
+    Grpc {
+        #[arg(long, default_value = "127.0.0.1:50051")]
+        address: String,
+
+        #[arg(short, long, default_value = "results/grpc")]
+        output: String,
+
+        #[arg(short = 't', long, default_value = "0")]
+        max_threads: usize,
+    },
+    /// Affiche une grille avec le chemin trouvé, les cellules développées et la frontière
+    Render {
+        #[arg(short, long, value_enum, default_value_t = AlgorithmChoice::All)]
+        algorithm: AlgorithmChoice,
+
+        #[arg(short, long, default_value = "10")]
+        size: usize,
+
+        #[arg(long, default_value = "1")]
+        instance: usize,
+
+        #[arg(long, value_enum, default_value_t = GoalTest::Expansion)]
+        goal_test: GoalTest,
+
+        #[arg(long, value_enum)]
+        heatmap: Option<HeatmapMode>,
+
+        #[arg(short, long, default_value = "results/heatmaps")]
+        output: String,
+
+        #[arg(long)]
+        seed: Option<u64>,
+
+        #[arg(long, value_name = "FICHIER")]
+        map: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum RunsAction {
+    /// Affiche les exécutions indexées, de la plus ancienne à la plus récente
+    List {
+        #[arg(long, default_value = runs::DEFAULT_DIR)]
+        dir: String,
+    },
+    /// Supprime les exécutions les plus anciennes (résultats, métadonnées, traces)
+    Prune {
+        #[arg(long)]
+        keep: usize,
+
+        #[arg(long, default_value = runs::DEFAULT_DIR)]
+        dir: String,
+
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+fn run_runs(action: RunsAction) -> Result<(), String> {
+    match action {
+        RunsAction::List { dir } => runs::list(Path::new(&dir)),
+        RunsAction::Prune { keep, dir, dry_run } => {
+            let removed = runs::prune(Path::new(&dir), keep, dry_run)?;
+            for run in &removed {
+                println!("  - {} ({})", run.id, run.results);
+            }
+            println!(
+                "{} exécution(s) {}",
+                removed.len(),
+                if dry_run {
+                    "à supprimer (--dry-run)"
+                } else {
+                    "supprimée(s)"
+                }
+            );
+            Ok(())
+        }
+    }
+}
+
+fn problem_names() -> PossibleValuesParser {
+    PossibleValuesParser::new(std::iter::once("all").chain(registry::names()))
+}
+
+fn run_fuzz(problem: &str, config: FuzzConfig) {
+    println!("Fuzzing:");
+    println!("  Problème: {}", problem);
+    println!("  Cas par problème: {}", config.cases);
+    println!("  Taille max: {}", config.max_size);
+    println!("  Graine: {}", config.seed);
+    println!();
+
+    match fuzz::run(problem, &config) {
+        Ok(()) => println!("\nFuzzing terminé: aucun invariant violé"),
+        Err(e) => {
+            eprintln!("\nÉchec du fuzzing: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run_heuristic_check(config: &BenchmarkConfig, check: &HeuristicCheckConfig) {
+    println!("Vérification des heuristiques:");
+    println!("  Problème: {}", config.problem);
+    println!("  Taille: {}", config.size);
+    println!("  Instances: {}", check.instances);
+    println!("  États échantillonnés par instance: {}", check.samples);
+    println!("  Graine: {}", config.seed);
+    println!();
+
+    if let Err(e) = heuristic_check::run(config, check) {
+        eprintln!("\nErreur: {}", e);
+        std::process::exit(1);
+    }
 }
 
 fn main() {
     let args = Args::parse();
 
+    match args.command {
+        Some(Command::Fuzz {
+            problem,
+            cases,
+            seed,
+            max_size,
+            samples,
+        }) => {
+            let config = FuzzConfig {
+                cases,
+                seed: seed.unwrap_or_else(rand::random),
+                max_size,
+                samples,
+            };
+            run_fuzz(&problem, config);
+            return;
+        }
+        Some(Command::Heuristic {
+            problem,
+            size,
+            instances,
+            samples,
+            max_states,
+            shuffle,
+            move_cost,
+            seed,
+        }) => {
+            let config = BenchmarkConfig {
+                problem,
+                size,
+                shuffle,
+                move_cost,
+                seed: seed.unwrap_or_else(rand::random),
+                ..BenchmarkConfig::default()
+            };
+            let check = HeuristicCheckConfig {
+                instances,
+                samples,
+                max_states,
+            };
+            run_heuristic_check(&config, &check);
+            return;
+        }
+        Some(Command::Dot {
+            problem,
+            algorithm,
+            size,
+            instance,
+            max_nodes,
+            output,
+            shuffle,
+            seed,
+        }) => {
+            let config = BenchmarkConfig {
+                algorithm,
+                problem,
+                size,
+                shuffle,
+                seed: seed.unwrap_or_else(rand::random),
+                ..BenchmarkConfig::default()
+            };
+            let export = DotConfig {
+                instance: instance.saturating_sub(1),
+                max_nodes,
+                output_dir: output,
+            };
+            println!("Export DOT (graine {}):", config.seed);
+            if let Err(e) = dot::run(&config, &export) {
+                eprintln!("\nErreur: {}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some(Command::Debug {
+            problem,
+            algorithm,
+            size,
+            instance,
+            shuffle,
+            seed,
+        }) => {
+            let config = BenchmarkConfig {
+                algorithm,
+                problem,
+                size,
+                shuffle,
+                seed: seed.unwrap_or_else(rand::random),
+                ..BenchmarkConfig::default()
+            };
+            let entry = registry::find(&config.problem).expect("problème validé par clap");
+            let debug = DebugConfig {
+                instance: instance.saturating_sub(1),
+            };
+            if let Err(e) = (entry.validate)(&config).and_then(|_| (entry.debug)(&config, &debug)) {
+                eprintln!("Erreur: {}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some(Command::Compare {
+            baseline,
+            candidate,
+            format,
+            runs_dir,
+            threshold,
+            fail_on_regression,
+        }) => {
+            let config = CompareConfig {
+                baseline,
+                candidate,
+                format,
+                threshold,
+                runs_dir,
+            };
+            match compare::run(&config) {
+                Ok(regressions) if fail_on_regression && regressions > 0 => std::process::exit(2),
+                Ok(_) => {}
+                Err(e) => {
+                    eprintln!("Erreur: {}", e);
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+        Some(Command::Stats {
+            sources,
+            algorithm,
+            problem,
+            size,
+            status,
+            since,
+            until,
+            group_by,
+            metric,
+            percentiles,
+            json,
+            runs_dir,
+        }) => {
+            let config = StatsConfig {
+                sources,
+                runs_dir,
+                algorithms: algorithm,
+                problems: problem,
+                size,
+                statuses: status,
+                since,
+                until,
+                group_by,
+                metric,
+                percentiles,
+                json,
+            };
+            if let Err(e) = stats::run(&config) {
+                eprintln!("Erreur: {}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some(Command::Runs { action }) => {
+            if let Err(e) = run_runs(action) {
+                eprintln!("Erreur: {}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some(Command::Import {
+            sources,
+            format,
+            problem,
+            size,
+            label,
+            output,
+        }) => {
+            let config = ImportConfig {
+                sources,
+                format,
+                options: ImportOptions {
+                    problem,
+                    size,
+                    label,
+                },
+                output,
+            };
+            match import::run(&config) {
+                Ok(count) => println!("{} résultats importés dans {}", count, config.output),
+                Err(e) => {
+                    eprintln!("Erreur: {}", e);
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+        Some(Command::IsolatedTask) => {
+            if let Err(e) = isolation::run_child() {
+                eprintln!("Erreur: {}", e);
+                std::process::exit(1);
+            }
+            std::process::exit(0);
+        }
+        Some(Command::Worker {
+            connect,
+            threads,
+            name,
+        }) => {
+            let worker = WorkerConfig {
+                address: connect,
+                threads: if threads == 0 {
+                    num_cpus::get()
+                } else {
+                    threads
+                },
+                name,
+            };
+            match distributed::run_worker(&worker) {
+                Ok(tasks) => println!("\nWorker terminé: {} tâches exécutées", tasks),
+                Err(e) => {
+                    eprintln!("Erreur: {}", e);
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+        #[cfg(feature = "web")]
+        Some(Command::Serve { sources, address }) => {
+            if let Err(e) = server::run(&ServeConfig { address, sources }) {
+                eprintln!("Erreur: {}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        #[cfg(feature = "web")]
+        Some(Command::Server {
+            address,
+            output,
+            max_threads,
+        }) => {
+            let config = ApiConfig {
+                address,
+                output_dir: output,
+                max_threads: if max_threads == 0 {
+                    num_cpus::get()
+                } else {
+                    max_threads
+                },
+            };
+            if let Err(e) = api::run(&config) {
+                eprintln!("Erreur: {}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        #[cfg(feature = "grpc")]
+        Some(Command::Grpc {
+            address,
+            output,
+            max_threads,
+        }) => {
+            let config = GrpcConfig {
+                address,
+                output_dir: output,
+                max_threads: if max_threads == 0 {
+                    num_cpus::get()
+                } else {
+                    max_threads
+                },
+            };
+            if let Err(e) = grpc::run(&config) {
+                eprintln!("Erreur: {}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some(Command::Render {
+            algorithm,
+            size,
+            instance,
+            goal_test,
+            heatmap,
+            output,
+            seed,
+            map,
+        }) => {
+            let config = BenchmarkConfig {
+                algorithm,
+                problem: if map.is_some() {
+                    "shortest-path-map"
+                } else {
+                    "shortest-path"
+                }
+                .to_string(),
+                map,
+                size,
+                goal_test,
+                seed: seed.unwrap_or_else(rand::random),
+                ..BenchmarkConfig::default()
+            };
+            let render = RenderConfig {
+                instance: instance.saturating_sub(1),
+                heatmap,
+                output_dir: output,
+            };
+            if let Err(e) = render::run(&config, &render) {
+                eprintln!("Erreur: {}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        None => {}
+    }
+
     let num_threads = if args.threads == 0 {
         num_cpus::get()
     } else {
         args.threads
     };
 
+    let seed = args.seed.unwrap_or_else(rand::random);
+
+    let config = BenchmarkConfig {
+        algorithm: args.algorithm,
+        problem: match (&args.problem_script, &args.problem_command) {
+            (Some(_), _) if args.problem == "all" => "script".to_string(),
+            (_, Some(_)) if args.problem == "all" => "subprocess".to_string(),
+            _ => args.problem.clone(),
+        },
+        size: args.size,
+        iterations: args.iterations,
+        output_file: args.output.clone(),
+        threads: num_threads,
+        isolate: args.isolate,
+        timeout_secs: args.timeout,
+        memory_limit_mb: args.memory_limit,
+        track_solutions: !args.no_solutions,
+        shuffle: args.shuffle,
+        move_cost: args.move_cost,
+        heuristics: args.heuristics.clone(),
+        pdb_partition: args.pdb_partition.clone(),
+        pdb_dir: args.pdb_dir.clone(),
+        verify_optimal: args.verify_optimal,
+        check_consistency: args.check_consistency,
+        seed,
+        instances: args.instances.clone(),
+        connectivity: args.connectivity,
+        maze_generator: args.maze_generator,
+        negative_edges: args.negative_edges,
+        graph: args.graph.clone(),
+        map: args.map.clone(),
+        scenario: args.scenario.clone(),
+        dynamic_obstacles: args.dynamic_obstacles,
+        all_pairs: args.all_pairs,
+        colors: args.colors,
+        source: args.source.clone(),
+        sources: args.sources,
+        target: args.target.clone(),
+        goal: args.goal.clone(),
+        undirected: args.undirected,
+        problem_script: args.problem_script.clone(),
+        problem_command: args.problem_command.clone(),
+        reexpand: args.reexpand,
+        goal_test: args.goal_test,
+        direction: args.direction,
+        cost_bound: args.cost_bound,
+        depth_limit: args.depth_limit,
+        sweeps: args.sweep.clone(),
+        perimeter_radius: args.perimeter_radius,
+        memory_budget: args.memory_budget,
+        weight: args.weight,
+        bound: args.bound,
+        lookahead: args.lookahead,
+        step_budget: args.step_budget,
+        k: args.k,
+        population: args.population,
+        generations: args.generations,
+        tie_breaking: args.tie_breaking,
+        lazy_duplicates: args.lazy_duplicates,
+        order_successors: args.order_successors,
+        profile: args.profile,
+        trace: args.trace,
+        trace_limit: args.trace_limit,
+        stability_threshold: args.stability_threshold,
+        budget_secs: args.budget,
+    };
+
+    if let Err(e) = config.validate() {
+        Args::command().error(ErrorKind::ArgumentConflict, e).exit();
+    }
+
     rayon::ThreadPoolBuilder::new()
         .num_threads(num_threads)
         .build_global()
@@ -50,34 +957,268 @@ fn main() {
 
     println!("Détails du benchmark:");
     println!("  Algorithme: {}", args.algorithm);
-    println!("  Problème: {}", args.problem);
+    println!("  Problème: {}", config.problem);
+    if let Some(script) = &args.problem_script {
+        println!("  Script: {}", script);
+    }
+    if let Some(command) = &args.problem_command {
+        println!("  Commande: {}", command);
+    }
     println!("  Taille: {}", args.size);
-    println!("  Itérations: {}", args.iterations);
-    println!("  Threads: {}", num_threads);
+    if let Some(graph) = args
+        .graph
+        .as_ref()
+        .filter(|_| config.problem == "map-coloring")
+    {
+        println!("  Graphe: {}", graph);
+    } else if let Some(graph) = &args.graph {
+        println!(
+            "  Graphe: {} ({} -> {})",
+            graph,
+            args.source.as_deref().unwrap_or("aléatoire"),
+            args.target.as_deref().unwrap_or("aléatoire")
+        );
+    }
+    if args.colors != 4 {
+        println!("  Couleurs: {}", args.colors);
+    }
+    if let Some(map) = &args.map {
+        println!("  Carte: {}", map);
+    }
+    if let Some(scenario) = &args.scenario {
+        println!(
+            "  Scénario de replanification: {} (A* répété contre LPA* et D* Lite)",
+            scenario
+        );
+    }
+    if args.dynamic_obstacles > 0 {
+        println!(
+            "  Obstacles dynamiques: {} épisodes (A* répété contre D* Lite)",
+            args.dynamic_obstacles
+        );
+    }
+    if args.all_pairs {
+        println!("  Toutes paires: Floyd-Warshall contre Dijkstra depuis chaque source");
+    }
+    if args.move_cost != MoveCost::Unit {
+        println!("  Coût des mouvements du taquin: {}", args.move_cost);
+    }
+    if let Some(goal) = &args.goal {
+        println!("  But du taquin: {}", goal);
+    }
+    if !args.heuristics.is_empty() {
+        let heuristics: Vec<String> = args.heuristics.iter().map(|h| h.to_string()).collect();
+        println!("  Heuristiques comparées: {}", heuristics.join(", "));
+    }
+    if args.heuristics.contains(&Heuristic::PatternDb) {
+        println!(
+            "  Bases de motifs: {} (cache: {})",
+            args.pdb_partition
+                .as_deref()
+                .unwrap_or("partition par défaut"),
+            args.pdb_dir
+        );
+    }
+    if args.sources > 1 {
+        println!("  Départs: {} (recherche multi-source)", args.sources);
+    }
+    match &args.instances {
+        Some(source) => println!("  Jeu d'instances: {}", source),
+        None => println!("  Itérations: {}", args.iterations),
+    }
+    match &args.coordinator {
+        Some(address) => println!("  Coordinateur: {} (threads des workers)", address),
+        None if args.isolate => println!(
+            "  Isolation: un processus par tâche, {} à la fois",
+            num_threads
+        ),
+        None => println!("  Threads: {}", num_threads),
+    }
     println!("  Timeout: {}sec", args.timeout);
+    if args.memory_limit > 0 {
+        println!("  Limite mémoire: {} Mo", args.memory_limit);
+    }
+    if args.no_solutions {
+        println!("  Solutions: non reconstruites");
+    }
+    if args.verify_optimal {
+        println!("  Vérification d'optimalité: oui");
+    }
+    if args.check_consistency {
+        println!("  Cohérence des algorithmes optimaux: oui");
+    }
+    if args.goal_test != GoalTest::Expansion {
+        println!("  Test de but (BFS, DFS): {}", args.goal_test);
+    }
+    if args.direction != Direction::Forward {
+        println!("  Sens de recherche: {}", args.direction);
+    }
+    if args.reexpand {
+        println!("  Ré-expansion sur chemin moins coûteux: oui");
+    }
+    if let Some(bound) = args.cost_bound {
+        println!(
+            "  Borne de coût: {} (arrêt à la première solution ≤ {})",
+            bound, bound
+        );
+    }
+    if let Some(limit) = args.depth_limit {
+        println!(
+            "  Limite de profondeur DLS: {} (coupure distinguée de l'épuisement)",
+            limit
+        );
+    }
+    if args.algorithm == AlgorithmChoice::Perimeter {
+        println!(
+            "  Rayon du périmètre: {} (précalcul arrière depuis le but)",
+            args.perimeter_radius
+        );
+    }
+    if args.algorithm == AlgorithmChoice::Awastar {
+        println!(
+            "  Poids AWA*: {} (première solution au plus {} fois l'optimum, puis améliorée jusqu'au timeout)",
+            args.weight, args.weight
+        );
+    }
+    if args.algorithm == AlgorithmChoice::Ees {
+        println!(
+            "  Borne EES: {} (solution au plus {} fois l'optimum, vérifiée si un optimum de référence est connu)",
+            args.bound, args.bound
+        );
+    }
+    if args.algorithm == AlgorithmChoice::Sma {
+        println!(
+            "  Budget mémoire SMA*: {} nœuds (les pires feuilles sont oubliées au-delà)",
+            args.memory_budget
+        );
+    }
+    if args.negative_edges > 0.0 {
+        println!(
+            "  Arêtes négatives: {:.0}% des arêtes (cycles négatifs détectés par Bellman-Ford)",
+            args.negative_edges * 100.0
+        );
+    }
+    if args.algorithm == AlgorithmChoice::Yen {
+        println!(
+            "  Chemins énumérés (Yen): les {} meilleurs chemins sans cycle",
+            args.k
+        );
+    }
+    if args.algorithm == AlgorithmChoice::Lrta {
+        println!(
+            "  Anticipation LRTA*: profondeur {}, au plus {} expansions par pas",
+            args.lookahead, args.step_budget
+        );
+    }
+    if args.algorithm == AlgorithmChoice::Ga {
+        println!(
+            "  Algorithme génétique: {} individus, au plus {} générations",
+            args.population, args.generations
+        );
+    }
+    if args.tie_breaking != TieBreaking::HighG {
+        println!(
+            "  Départage A* à f égal: {} (enregistré dans les propriétés des résultats)",
+            args.tie_breaking
+        );
+    }
+    if args.lazy_duplicates {
+        println!(
+            "  Détection paresseuse des doublons A*: liste fermée vérifiée à l'expansion seulement"
+        );
+    }
+    if args.order_successors {
+        println!("  Successeurs ordonnés par heuristique croissante (DFS, ID, DLS, IDA*)");
+    }
+    for sweep in &args.sweep {
+        let values: Vec<String> = sweep.values.iter().map(|v| v.to_string()).collect();
+        println!("  Balayage {}: {}", sweep.parameter, values.join(", "));
+    }
+    if args.stability_threshold != 20.0 {
+        println!("  Seuil de stabilité (CV): {}%", args.stability_threshold);
+    }
+    if let Some(secs) = args.budget {
+        println!(
+            "  Budget: {} (itérations réduites ou algorithmes lents écartés si nécessaire)",
+            budget::format(secs)
+        );
+    }
+    if args.profile {
+        println!("  Profil de recherche (borne f, frontière): oui");
+    }
+    if args.trace {
+        let limit = args.trace_limit.map_or("sans limite".to_string(), |limit| {
+            format!("{} expansions max", limit)
+        });
+        println!(
+            "  Traces d'expansion: {} ({})",
+            BenchmarkRunner::trace_dir(&args.output),
+            limit
+        );
+    }
+    println!("  Graine: {}", seed);
+    println!("  Hachage: {}", HASHER_NAME);
     println!();
 
     fs::create_dir_all("results").expect("Impossible de créer le dossier results");
 
-    let config = BenchmarkConfig {
-        algorithm: args.algorithm.clone(),
-        problem: args.problem.clone(),
-        size: args.size,
-        iterations: args.iterations,
-        output_file: args.output.clone(),
-        threads: num_threads,
-        timeout_secs: args.timeout,
-    };
-
     let start = Instant::now();
-    let runner = BenchmarkRunner::new(config);
+    let mut runner = BenchmarkRunner::new(config);
+    let coordinator = args.coordinator.as_ref().map(|address| {
+        match Coordinator::bind(address, runner.config().clone()) {
+            Ok(coordinator) => {
+                println!(
+                    "En attente de workers: benchmark worker --connect {}\n",
+                    coordinator.address()
+                );
+                Arc::new(coordinator)
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        }
+    });
+    if let Some(coordinator) = &coordinator {
+        runner.set_coordinator(Arc::clone(coordinator));
+    }
+    #[cfg(feature = "web")]
+    let stream = match &args.stream {
+        Some(address) => match stream::serve(address, runner.progress_mut().subscribe()) {
+            Ok(handle) => Some(handle),
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        },
+        None => None,
+    };
+    #[cfg(feature = "tui")]
+    let outcome = if args.tui {
+        dashboard::run(runner)
+    } else {
+        runner.run()
+    };
+    #[cfg(not(feature = "tui"))]
+    let outcome = runner.run();
+    if let Some(coordinator) = coordinator {
+        coordinator.shutdown();
+    }
+    #[cfg(feature = "web")]
+    if let Some(stream) = stream {
+        stream.finish();
+    }
 
-    match runner.run() {
+    match outcome {
         Ok(_) => {
             let duration = start.elapsed();
             println!("\nBenchmarks terminés");
             println!("  Temps total: {:.2?}", duration);
             println!("  Résultats sauvegardés dans {}", args.output);
+            println!(
+                "  Métadonnées sauvegardées dans {}",
+                BenchmarkRunner::metadata_path(&args.output)
+            );
         }
         Err(e) => {
             eprintln!("\nErreur lors du benchmark: {}", e);
@@ -0,0 +1,653 @@
+use super::registry::ProblemFactory;
+use crate::algorithms::{ActionList, BackwardProblem, Problem, SuccessorList, Successors};
+use crate::benchmarking::{BenchmarkConfig, Direction, InstanceProperties};
+use crate::utils::graph_formats::{self, GraphData};
+use crate::utils::instance_sets::{self, KnownInstance};
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::mem::size_of;
+use std::path::Path;
+use std::sync::Arc;
+
+pub type Assignment = Vec<Option<u16>>;
+
+#[derive(Clone, Debug)]
+pub enum Constraint {
+    NotEqual(usize, usize),
+    AllDifferent(Vec<usize>),
+}
+
+impl Constraint {
+    fn variables(&self) -> Vec<usize> {
+        match self {
+            Constraint::NotEqual(a, b) => vec![*a, *b],
+            Constraint::AllDifferent(variables) => variables.clone(),
+        }
+    }
+
+    fn allows(&self, assignment: &[Option<u16>], variable: usize, value: u16) -> bool {
+        match self {
+            Constraint::NotEqual(a, b) => {
+                let other = if *a == variable { *b } else { *a };
+                assignment[other] != Some(value)
+            }
+            Constraint::AllDifferent(variables) => variables
+                .iter()
+                .all(|&other| other == variable || assignment[other] != Some(value)),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct CspModel {
+    name: String,
+    variables: Vec<Arc<str>>,
+    domains: Vec<Vec<u16>>,
+    constraints: Vec<Constraint>,
+    watchers: Vec<Vec<usize>>,
+    value_names: Vec<String>,
+}
+
+impl CspModel {
+    pub fn new(name: &str, value_names: Vec<String>) -> Self {
+        CspModel {
+            name: name.to_string(),
+            value_names,
+            ..CspModel::default()
+        }
+    }
+
+    pub fn add_variable(&mut self, name: &str, domain: Vec<u16>) -> usize {
+        self.variables.push(name.into());
+        self.domains.push(domain);
+        self.watchers.push(Vec::new());
+        self.variables.len() - 1
+    }
+
+    pub fn add_constraint(&mut self, constraint: Constraint) {
+        let id = self.constraints.len();
+        for variable in constraint.variables() {
+            self.watchers[variable].push(id);
+        }
+        self.constraints.push(constraint);
+    }
+
+    pub fn len(&self) -> usize {
+        self.variables.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.variables.is_empty()
+    }
+
+    pub fn constraint_count(&self) -> usize {
+        self.constraints.len()
+    }
+
+    fn value_name(&self, value: u16) -> String {
+        self.value_names
+            .get(value as usize)
+            .cloned()
+            .unwrap_or_else(|| value.to_string())
+    }
+
+    fn consistent(&self, assignment: &[Option<u16>], variable: usize, value: u16) -> bool {
+        self.watchers[variable]
+            .iter()
+            .all(|&id| self.constraints[id].allows(assignment, variable, value))
+    }
+
+    fn candidates(&self, assignment: &[Option<u16>], variable: usize) -> Vec<u16> {
+        self.domains[variable]
+            .iter()
+            .copied()
+            .filter(|&value| self.consistent(assignment, variable, value))
+            .collect()
+    }
+
+    fn most_constrained(&self, assignment: &[Option<u16>]) -> Option<(usize, Vec<u16>)> {
+        let mut best: Option<(usize, Vec<u16>)> = None;
+        for variable in (0..self.len()).filter(|&variable| assignment[variable].is_none()) {
+            let values = self.candidates(assignment, variable);
+            if best
+                .as_ref()
+                .is_none_or(|(_, best_values)| values.len() < best_values.len())
+            {
+                let dead_end = values.is_empty();
+                best = Some((variable, values));
+                if dead_end {
+                    break;
+                }
+            }
+        }
+        best
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Assign {
+    variable: Arc<str>,
+    index: usize,
+    value: u16,
+}
+
+impl fmt::Display for Assign {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", self.variable, self.value)
+    }
+}
+
+#[derive(Clone)]
+pub struct CspProblem {
+    model: Arc<CspModel>,
+    initial: Assignment,
+}
+
+impl CspProblem {
+    pub fn new(model: Arc<CspModel>, initial: Assignment) -> Result<Self, String> {
+        if initial.len() != model.len() {
+            return Err(format!(
+                "{} valeurs pour {} variables",
+                initial.len(),
+                model.len()
+            ));
+        }
+        for (variable, value) in initial.iter().enumerate() {
+            if let Some(value) = *value {
+                if !model.domains[variable].contains(&value) {
+                    return Err(format!(
+                        "{}={}: hors du domaine",
+                        model.variables[variable], value
+                    ));
+                }
+                if !model.consistent(&initial, variable, value) {
+                    return Err(format!(
+                        "{}={}: contrainte violée",
+                        model.variables[variable], value
+                    ));
+                }
+            }
+        }
+        Ok(CspProblem { model, initial })
+    }
+
+    pub fn model(&self) -> &CspModel {
+        &self.model
+    }
+
+    pub fn unassigned(&self, state: &Assignment) -> usize {
+        state.iter().filter(|value| value.is_none()).count()
+    }
+
+    pub fn assignment_string(&self, state: &Assignment) -> String {
+        let values: Vec<String> = state
+            .iter()
+            .map(|value| value.map_or(".".to_string(), |value| self.model.value_name(value)))
+            .collect();
+        if values.iter().all(|value| value.len() == 1) {
+            values.concat()
+        } else {
+            self.model
+                .variables
+                .iter()
+                .zip(values)
+                .map(|(variable, value)| format!("{}={}", variable, value))
+                .collect::<Vec<_>>()
+                .join(" ")
+        }
+    }
+}
+
+impl Problem for CspProblem {
+    type State = Assignment;
+    type Action = Assign;
+
+    fn initial_state(&self) -> Self::State {
+        self.initial.clone()
+    }
+
+    fn is_goal(&self, state: &Self::State) -> bool {
+        state.iter().all(Option::is_some)
+    }
+
+    fn actions(&self, state: &Self::State) -> ActionList<Assign> {
+        match self.model.most_constrained(state) {
+            Some((index, values)) => values
+                .into_iter()
+                .map(|value| Assign {
+                    variable: Arc::clone(&self.model.variables[index]),
+                    index,
+                    value,
+                })
+                .collect(),
+            None => ActionList::new(),
+        }
+    }
+
+    fn apply(&self, state: &Self::State, action: &Assign) -> (Self::State, usize) {
+        let mut next = state.clone();
+        next[action.index] = Some(action.value);
+        (next, 1)
+    }
+
+    fn successors_into(&self, state: &Self::State, buffer: &mut Successors<Self>) {
+        buffer.clear();
+        for action in self.actions(state) {
+            let (next, cost) = self.apply(state, &action);
+            buffer.push((action, next, cost));
+        }
+    }
+
+    fn heuristic(&self, state: &Self::State) -> usize {
+        self.unassigned(state)
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "{}: {} variables, {} contraintes, {} à assigner",
+            self.model.name,
+            self.model.len(),
+            self.model.constraint_count(),
+            self.unassigned(&self.initial)
+        )
+    }
+
+    fn state_heap_bytes(&self, state: &Self::State) -> usize {
+        state.capacity() * size_of::<Option<u16>>()
+    }
+
+    fn encode_state(&self, state: &Self::State) -> String {
+        self.assignment_string(state)
+    }
+}
+
+impl BackwardProblem for CspProblem {
+    fn goal_states(&self) -> Vec<Assignment> {
+        Vec::new()
+    }
+
+    fn predecessors(&self, _state: &Assignment) -> SuccessorList<Assignment> {
+        SuccessorList::new()
+    }
+}
+
+fn forward_only(config: &BenchmarkConfig, label: &str) -> Result<(), String> {
+    if config.direction != Direction::Forward {
+        return Err(format!(
+            "{}: recherche arrière impossible (solutions inconnues à l'avance)",
+            label
+        ));
+    }
+    Ok(())
+}
+
+const SUDOKU_SIDE: usize = 9;
+
+fn sudoku_model(side: usize) -> CspModel {
+    let block = (side as f64).sqrt() as usize;
+    let names = (0..=side).map(|value| value.to_string()).collect();
+    let mut model = CspModel::new(&format!("Sudoku {}x{}", side, side), names);
+    for row in 0..side {
+        for col in 0..side {
+            model.add_variable(
+                &format!("r{}c{}", row + 1, col + 1),
+                (1..=side as u16).collect(),
+            );
+        }
+    }
+    for line in 0..side {
+        model.add_constraint(Constraint::AllDifferent(
+            (0..side).map(|col| line * side + col).collect(),
+        ));
+        model.add_constraint(Constraint::AllDifferent(
+            (0..side).map(|row| row * side + line).collect(),
+        ));
+        let (top, left) = (line / block * block, line % block * block);
+        model.add_constraint(Constraint::AllDifferent(
+            (0..side)
+                .map(|cell| (top + cell / block) * side + left + cell % block)
+                .collect(),
+        ));
+    }
+    model
+}
+
+pub fn parse_sudoku(line: &str) -> Result<Assignment, String> {
+    let cells: Vec<char> = line.chars().filter(|c| !c.is_whitespace()).collect();
+    let side = (cells.len() as f64).sqrt() as usize;
+    let block = (side as f64).sqrt() as usize;
+    if side * side != cells.len() || block * block != side || side > 9 {
+        return Err(format!(
+            "{} cases: 16 (4x4) ou 81 (9x9) attendues",
+            cells.len()
+        ));
+    }
+    cells
+        .into_iter()
+        .map(|cell| match cell {
+            '.' | '0' | '_' => Ok(None),
+            digit => match digit.to_digit(10) {
+                Some(value) if value as usize <= side => Ok(Some(value as u16)),
+                _ => Err(format!("case invalide '{}'", digit)),
+            },
+        })
+        .collect()
+}
+
+pub fn sudoku(assignment: Assignment) -> Result<CspProblem, String> {
+    let side = (assignment.len() as f64).sqrt() as usize;
+    CspProblem::new(Arc::new(sudoku_model(side)), assignment)
+}
+
+fn shuffled_lines<R: Rng>(block: usize, rng: &mut R) -> Vec<usize> {
+    let mut bands: Vec<usize> = (0..block).collect();
+    bands.shuffle(rng);
+    let mut lines = Vec::with_capacity(block * block);
+    for band in bands {
+        let mut inner: Vec<usize> = (0..block).collect();
+        inner.shuffle(rng);
+        lines.extend(inner.into_iter().map(|line| band * block + line));
+    }
+    lines
+}
+
+fn solved_sudoku<R: Rng>(rng: &mut R) -> Vec<u16> {
+    let block = 3;
+    let mut digits: Vec<u16> = (1..=SUDOKU_SIDE as u16).collect();
+    digits.shuffle(rng);
+    let (rows, cols) = (shuffled_lines(block, rng), shuffled_lines(block, rng));
+    let mut grid = Vec::with_capacity(SUDOKU_SIDE * SUDOKU_SIDE);
+    for &row in &rows {
+        for &col in &cols {
+            grid.push(digits[(block * (row % block) + row / block + col) % SUDOKU_SIDE]);
+        }
+    }
+    grid
+}
+
+pub struct SudokuFactory;
+
+impl ProblemFactory for SudokuFactory {
+    type Instance = CspProblem;
+
+    fn label(config: &BenchmarkConfig) -> String {
+        format!("Sudoku-{}", config.size)
+    }
+
+    fn max_depth(_config: &BenchmarkConfig) -> usize {
+        SUDOKU_SIDE * SUDOKU_SIDE
+    }
+
+    fn generate(config: &BenchmarkConfig, seed: u64) -> CspProblem {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+        let mut grid: Assignment = solved_sudoku(&mut rng).into_iter().map(Some).collect();
+        let mut cells: Vec<usize> = (0..grid.len()).collect();
+        cells.shuffle(&mut rng);
+        for &cell in cells.iter().take(config.size) {
+            grid[cell] = None;
+        }
+        sudoku(grid).expect("grille résolue cohérente")
+    }
+
+    fn load_instances(
+        _config: &BenchmarkConfig,
+        source: &str,
+    ) -> Result<Vec<KnownInstance<CspProblem>>, String> {
+        instance_sets::read(source)?
+            .lines()
+            .enumerate()
+            .map(|(number, line)| (number, line.split('#').next().unwrap_or_default().trim()))
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(number, line)| {
+                parse_sudoku(line)
+                    .and_then(sudoku)
+                    .map(|problem| (problem, None))
+                    .map_err(|e| format!("{}, ligne {}: {}", source, number + 1, e))
+            })
+            .collect()
+    }
+
+    fn validate(config: &BenchmarkConfig) -> Result<(), String> {
+        if config.size > SUDOKU_SIDE * SUDOKU_SIDE {
+            return Err(format!(
+                "--size {}: au plus {} cases vides",
+                config.size,
+                SUDOKU_SIDE * SUDOKU_SIDE
+            ));
+        }
+        forward_only(config, "Sudoku")
+    }
+
+    fn properties(instance: &CspProblem) -> InstanceProperties {
+        InstanceProperties::from([
+            (
+                "blanks".to_string(),
+                instance.unassigned(&instance.initial).into(),
+            ),
+            (
+                "side".to_string(),
+                ((instance.model.len() as f64).sqrt() as usize).into(),
+            ),
+        ])
+    }
+
+    fn unit_costs(_config: &BenchmarkConfig) -> bool {
+        true
+    }
+
+    fn describe(instance: &CspProblem) -> String {
+        instance.assignment_string(&instance.initial)
+    }
+
+    fn notes(config: &BenchmarkConfig) -> Vec<String> {
+        vec![format!(
+            "Sudoku: {} cases vides, variable la plus contrainte en premier, heuristique = cases restantes",
+            config.size
+        )]
+    }
+}
+
+const COLOR_NAMES: &[&str] = &["rouge", "vert", "bleu", "jaune", "violet", "orange"];
+const RANDOM_MAP_NEIGHBORS: usize = 3;
+
+fn color_names(colors: usize) -> Vec<String> {
+    (0..colors)
+        .map(|color| match COLOR_NAMES.get(color) {
+            Some(name) => name.to_string(),
+            None => format!("c{}", color + 1),
+        })
+        .collect()
+}
+
+pub fn coloring(
+    regions: &[String],
+    borders: &BTreeSet<(usize, usize)>,
+    colors: usize,
+) -> CspProblem {
+    let mut model = CspModel::new(
+        &format!("Coloration en {} couleurs", colors),
+        color_names(colors),
+    );
+    for region in regions {
+        model.add_variable(region, (0..colors as u16).collect());
+    }
+    for &(a, b) in borders {
+        model.add_constraint(Constraint::NotEqual(a, b));
+    }
+    CspProblem::new(Arc::new(model), vec![None; regions.len()]).expect("assignation vide")
+}
+
+fn graph_borders(graph: &GraphData) -> BTreeSet<(usize, usize)> {
+    graph
+        .edges
+        .iter()
+        .filter(|&&(from, to, _)| from != to)
+        .map(|&(from, to, _)| (from.min(to), from.max(to)))
+        .collect()
+}
+
+fn random_borders<R: Rng>(regions: usize, rng: &mut R) -> BTreeSet<(usize, usize)> {
+    let points: Vec<(f64, f64)> = (0..regions).map(|_| (rng.gen(), rng.gen())).collect();
+    let mut borders = BTreeSet::new();
+    for (a, &(x, y)) in points.iter().enumerate() {
+        let mut others: Vec<(f64, usize)> = points
+            .iter()
+            .enumerate()
+            .filter(|&(b, _)| b != a)
+            .map(|(b, &(u, v))| ((u - x).powi(2) + (v - y).powi(2), b))
+            .collect();
+        others.sort_by(|p, q| p.0.total_cmp(&q.0));
+        for &(_, b) in others.iter().take(RANDOM_MAP_NEIGHBORS) {
+            borders.insert((a.min(b), a.max(b)));
+        }
+    }
+    borders
+}
+
+pub struct ColoringFactory;
+
+impl ColoringFactory {
+    fn graph(config: &BenchmarkConfig) -> Result<Option<Arc<GraphData>>, String> {
+        config
+            .graph
+            .as_deref()
+            .map(|path| graph_formats::load(path, true))
+            .transpose()
+    }
+}
+
+impl ProblemFactory for ColoringFactory {
+    type Instance = CspProblem;
+
+    fn label(config: &BenchmarkConfig) -> String {
+        let name = match config
+            .graph
+            .as_deref()
+            .and_then(|path| Path::new(path).file_stem())
+        {
+            Some(stem) => stem.to_string_lossy().into_owned(),
+            None => config.size.to_string(),
+        };
+        format!("MapColoring-{}-{}c", name, config.colors)
+    }
+
+    fn max_depth(config: &BenchmarkConfig) -> usize {
+        match Self::graph(config) {
+            Ok(Some(graph)) => graph.labels.len(),
+            _ => config.size,
+        }
+    }
+
+    fn generate(config: &BenchmarkConfig, seed: u64) -> CspProblem {
+        match Self::graph(config).expect("graphe vérifié par validate") {
+            Some(graph) => coloring(&graph.labels, &graph_borders(&graph), config.colors),
+            None => {
+                let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+                let regions: Vec<String> = (0..config.size)
+                    .map(|region| format!("R{}", region))
+                    .collect();
+                let borders = random_borders(config.size, &mut rng);
+                coloring(&regions, &borders, config.colors)
+            }
+        }
+    }
+
+    fn validate(config: &BenchmarkConfig) -> Result<(), String> {
+        if config.colors == 0 || config.colors > u16::MAX as usize {
+            return Err(format!("--colors {}: au moins 1 couleur", config.colors));
+        }
+        let regions = match Self::graph(config)? {
+            Some(graph) => graph.labels.len(),
+            None => config.size,
+        };
+        if regions == 0 {
+            return Err("Coloration: aucune région".to_string());
+        }
+        forward_only(config, "Coloration")
+    }
+
+    fn properties(instance: &CspProblem) -> InstanceProperties {
+        InstanceProperties::from([
+            ("regions".to_string(), instance.model.len().into()),
+            (
+                "borders".to_string(),
+                instance.model.constraint_count().into(),
+            ),
+            (
+                "colors".to_string(),
+                instance.model.value_names.len().into(),
+            ),
+        ])
+    }
+
+    fn unit_costs(_config: &BenchmarkConfig) -> bool {
+        true
+    }
+
+    fn describe(instance: &CspProblem) -> String {
+        format!(
+            "{} régions, {} frontières, {} couleurs",
+            instance.model.len(),
+            instance.model.constraint_count(),
+            instance.model.value_names.len()
+        )
+    }
+
+    fn notes(config: &BenchmarkConfig) -> Vec<String> {
+        match Self::graph(config) {
+            Ok(Some(_)) => vec![
+                "Coloration d'un graphe importé: toutes les instances sont identiques".to_string(),
+            ],
+            _ => vec![format!(
+                "Carte aléatoire: {} régions reliées chacune à leurs {} plus proches voisines",
+                config.size, RANDOM_MAP_NEIGHBORS
+            )],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::dfs::DFS;
+    use crate::algorithms::validation::validate_solution;
+    use crate::algorithms::SearchAlgorithm;
+
+    #[test]
+    fn test_csp_solves_sudoku_and_coloring() {
+        let puzzle = parse_sudoku(
+            "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79",
+        )
+        .unwrap();
+        let problem = sudoku(puzzle).unwrap();
+        assert_eq!(problem.unassigned(&problem.initial_state()), 51);
+        let result = DFS::new().search(&problem);
+        assert!(result.status.is_solved());
+        let solution = result.solution.unwrap();
+        assert_eq!(solution.len(), 51);
+        assert!(validate_solution(&problem, &solution, &result.metrics).is_ok());
+        assert!(parse_sudoku("55..").and_then(sudoku).is_err());
+        assert!(parse_sudoku("123").is_err());
+
+        let config = BenchmarkConfig {
+            size: 40,
+            ..BenchmarkConfig::default()
+        };
+        let generated = SudokuFactory::generate(&config, 7);
+        assert_eq!(generated.unassigned(&generated.initial_state()), 40);
+        assert!(DFS::new().search(&generated).status.is_solved());
+
+        let regions: Vec<String> = ["a", "b", "c", "d"].map(String::from).to_vec();
+        let borders = BTreeSet::from([(0, 1), (1, 2), (0, 2), (2, 3)]);
+        assert!(DFS::new()
+            .search(&coloring(&regions, &borders, 3))
+            .status
+            .is_solved());
+        assert!(!DFS::new()
+            .search(&coloring(&regions, &borders, 2))
+            .status
+            .is_solved());
+    }
+}
@@ -1,5 +1,15 @@
+pub mod csp;
+pub mod packed_taquin;
+pub mod pattern_db;
+pub mod pocket_cube;
+pub mod registry;
+#[cfg(feature = "script")]
+pub mod script;
 pub mod shortest_path;
+pub mod subprocess;
 pub mod taquin;
 
+pub use packed_taquin::PackedTaquin;
+pub use pocket_cube::PocketCube;
 pub use shortest_path::ShortestPath;
 pub use taquin::Taquin;
@@ -0,0 +1,440 @@
+use super::pattern_db::PatternDatabase;
+use super::registry::ProblemFactory;
+use super::taquin::{
+    goal_properties, linear_conflicts, pairwise_tile_cost, tile_weight, HeuristicType, Move,
+    Taquin, TaquinFactory,
+};
+use crate::algorithms::{
+    ActionList, BackwardProblem, HeuristicSuccessors, Problem, SuccessorList, Successors,
+};
+use crate::benchmarking::{BenchmarkConfig, Heuristic, InstanceProperties, MoveCost};
+use crate::utils::instance_sets::KnownInstance;
+use std::sync::Arc;
+
+const NIBBLE_LOW_BITS: u64 = 0x1111_1111_1111_1111;
+
+#[derive(Clone)]
+pub struct PackedTaquin {
+    size: usize,
+    initial_state: u64,
+    goal_state: u64,
+    goal_positions: [u8; 16],
+    cell_mask: u64,
+    heuristic_type: HeuristicType,
+    move_cost: MoveCost,
+    weights: [u16; 16],
+    distances: [[u16; 16]; 16],
+    pattern_db: Option<Arc<PatternDatabase>>,
+}
+
+impl PackedTaquin {
+    pub fn from_tiles(size: usize, tiles: &[u8], heuristic: HeuristicType) -> Self {
+        let goal: Vec<u8> = (0..(size * size) as u8).collect();
+        Self::from_tiles_with_goal(size, tiles, &goal, heuristic, MoveCost::Unit)
+    }
+
+    pub fn from_tiles_with_goal(
+        size: usize,
+        tiles: &[u8],
+        goal: &[u8],
+        heuristic: HeuristicType,
+        move_cost: MoveCost,
+    ) -> Self {
+        assert!(
+            size <= 4,
+            "La représentation compacte est limitée aux plateaux 4x4"
+        );
+        assert_eq!(tiles.len(), size * size);
+        assert_eq!(goal.len(), size * size);
+
+        let cells = size * size;
+        let mut goal_positions = [0u8; 16];
+        for (pos, &tile) in goal.iter().enumerate() {
+            goal_positions[tile as usize] = pos as u8;
+        }
+        let cell_mask = if cells == 16 {
+            u64::MAX
+        } else {
+            (1u64 << (4 * cells)) - 1
+        };
+
+        let mut weights = [0u16; 16];
+        let mut distances = [[0u16; 16]; 16];
+        for (tile, row) in distances.iter_mut().enumerate().take(cells) {
+            weights[tile] = tile_weight(move_cost, tile as u8) as u16;
+            let target = goal_positions[tile] as usize;
+            for (pos, distance) in row.iter_mut().enumerate().take(cells) {
+                *distance = ((pos / size).abs_diff(target / size)
+                    + (pos % size).abs_diff(target % size)) as u16
+                    * weights[tile];
+            }
+        }
+
+        PackedTaquin {
+            size,
+            initial_state: Self::pack(tiles),
+            goal_state: Self::pack(goal),
+            goal_positions,
+            cell_mask,
+            heuristic_type: heuristic,
+            move_cost,
+            weights,
+            distances,
+            pattern_db: None,
+        }
+    }
+
+    pub fn from_taquin(taquin: &Taquin, heuristic: HeuristicType) -> Self {
+        PackedTaquin {
+            pattern_db: taquin.pattern_db().cloned(),
+            ..Self::from_tiles_with_goal(
+                taquin.size(),
+                &taquin.initial_state(),
+                taquin.goal_state(),
+                heuristic,
+                taquin.move_cost(),
+            )
+        }
+    }
+
+    pub fn with_heuristic(mut self, heuristic: HeuristicType) -> Self {
+        self.heuristic_type = heuristic;
+        self
+    }
+
+    pub fn pack(tiles: &[u8]) -> u64 {
+        tiles
+            .iter()
+            .enumerate()
+            .fold(0u64, |acc, (i, &tile)| acc | (tile as u64) << (4 * i))
+    }
+
+    pub fn unpack(&self, state: u64) -> Vec<u8> {
+        (0..self.size * self.size)
+            .map(|i| Self::tile_at(state, i))
+            .collect()
+    }
+
+    fn tile_at(state: u64, pos: usize) -> u8 {
+        ((state >> (4 * pos)) & 0xF) as u8
+    }
+
+    fn find_blank(&self, state: u64) -> usize {
+        let folded = state | (state >> 1) | (state >> 2) | (state >> 3);
+        let zero_nibbles = !folded & NIBBLE_LOW_BITS & self.cell_mask;
+        zero_nibbles.trailing_zeros() as usize / 4
+    }
+
+    fn target(&self, blank: usize, direction: Move) -> Option<usize> {
+        let row = blank / self.size;
+        let col = blank % self.size;
+        match direction {
+            Move::Up if row > 0 => Some(blank - self.size),
+            Move::Down if row < self.size - 1 => Some(blank + self.size),
+            Move::Left if col > 0 => Some(blank - 1),
+            Move::Right if col < self.size - 1 => Some(blank + 1),
+            _ => None,
+        }
+    }
+
+    fn slide(state: u64, blank: usize, target: usize) -> (u64, u8) {
+        let tile = Self::tile_at(state, target);
+        let cleared = state & !(0xFu64 << (4 * target));
+        (cleared | (tile as u64) << (4 * blank), tile)
+    }
+
+    pub fn apply_with_heuristic(&self, state: u64, h: usize, direction: Move) -> (u64, usize) {
+        let blank = self.find_blank(state);
+        let target = self
+            .target(blank, direction)
+            .expect("Mouvement impossible depuis cette position");
+        let (next, tile) = Self::slide(state, blank, target);
+        let before = self.distances[tile as usize][target] as usize;
+        let after = self.distances[tile as usize][blank] as usize;
+        (next, h + after - before)
+    }
+
+    fn tile_cost(&self, tile: u8, pos: usize) -> usize {
+        match self.heuristic_type {
+            HeuristicType::Manhattan | HeuristicType::LinearConflict => {
+                self.distances[tile as usize][pos] as usize
+            }
+            HeuristicType::Hamming => {
+                usize::from(self.goal_positions[tile as usize] as usize != pos)
+                    * self.weights[tile as usize] as usize
+            }
+            HeuristicType::PatternDB | HeuristicType::None => 0,
+        }
+    }
+
+    fn manhattan_distance(&self, state: u64) -> usize {
+        (0..self.size * self.size)
+            .map(|pos| {
+                let tile = Self::tile_at(state, pos);
+                if tile == 0 {
+                    0
+                } else {
+                    self.distances[tile as usize][pos] as usize
+                }
+            })
+            .sum()
+    }
+
+    fn hamming_distance(&self, state: u64) -> usize {
+        (0..self.size * self.size)
+            .map(|pos| Self::tile_at(state, pos))
+            .enumerate()
+            .filter(|&(pos, tile)| tile != 0 && self.goal_positions[tile as usize] as usize != pos)
+            .map(|(_, tile)| self.weights[tile as usize] as usize)
+            .sum()
+    }
+
+    fn linear_conflict(&self, state: u64) -> usize {
+        self.manhattan_distance(state)
+            + linear_conflicts(
+                self.size,
+                &self.unpack(state),
+                |tile| self.goal_positions[tile as usize] as usize,
+                |tile| self.weights[tile as usize] as usize,
+            )
+    }
+
+    fn pattern_db_distance(&self, state: u64) -> usize {
+        self.pattern_db.as_ref().map_or(0, |pattern_db| {
+            pattern_db.evaluate((0..self.size * self.size).map(|pos| Self::tile_at(state, pos)))
+        })
+    }
+
+    pub fn initial_state_string(&self) -> String {
+        let mut result = String::new();
+        for (i, val) in self.unpack(self.initial_state).into_iter().enumerate() {
+            if i > 0 && i % self.size == 0 {
+                result.push('\n');
+            }
+            result.push_str(&format!("{:3}", val));
+        }
+        result
+    }
+}
+
+impl Problem for PackedTaquin {
+    type State = u64;
+    type Action = Move;
+
+    fn initial_state(&self) -> u64 {
+        self.initial_state
+    }
+
+    fn is_goal(&self, state: &u64) -> bool {
+        *state == self.goal_state
+    }
+
+    fn actions(&self, state: &u64) -> ActionList<Move> {
+        let blank = self.find_blank(*state);
+        Move::ALL
+            .into_iter()
+            .filter(|&direction| self.target(blank, direction).is_some())
+            .collect()
+    }
+
+    fn apply(&self, state: &u64, action: &Move) -> (u64, usize) {
+        let blank = self.find_blank(*state);
+        let target = self
+            .target(blank, *action)
+            .expect("Mouvement impossible depuis cette position");
+        let (next, tile) = Self::slide(*state, blank, target);
+        (next, self.weights[tile as usize] as usize)
+    }
+
+    fn successors_into(&self, state: &u64, buffer: &mut Successors<Self>) {
+        buffer.clear();
+        let blank = self.find_blank(*state);
+        for direction in Move::ALL {
+            if let Some(target) = self.target(blank, direction) {
+                let (next, tile) = Self::slide(*state, blank, target);
+                buffer.push((direction, next, self.weights[tile as usize] as usize));
+            }
+        }
+    }
+
+    fn successors_with_heuristic_into(
+        &self,
+        state: &u64,
+        heuristic: usize,
+        buffer: &mut HeuristicSuccessors<Self>,
+    ) {
+        buffer.clear();
+        let blank = self.find_blank(*state);
+        for direction in Move::ALL {
+            if let Some(target) = self.target(blank, direction) {
+                let (next, tile) = Self::slide(*state, blank, target);
+                let h = match self.heuristic_type {
+                    HeuristicType::LinearConflict => self.linear_conflict(next),
+                    HeuristicType::PatternDB => self.pattern_db_distance(next),
+                    _ => heuristic + self.tile_cost(tile, blank) - self.tile_cost(tile, target),
+                };
+                buffer.push((direction, next, self.weights[tile as usize] as usize, h));
+            }
+        }
+    }
+
+    fn heuristic(&self, state: &u64) -> usize {
+        match self.heuristic_type {
+            HeuristicType::Manhattan => self.manhattan_distance(*state),
+            HeuristicType::Hamming => self.hamming_distance(*state),
+            HeuristicType::LinearConflict => self.linear_conflict(*state),
+            HeuristicType::PatternDB => self.pattern_db_distance(*state),
+            HeuristicType::None => 0,
+        }
+    }
+
+    fn encode_state(&self, state: &u64) -> String {
+        (0..self.size * self.size)
+            .filter_map(|pos| char::from_digit(Self::tile_at(*state, pos) as u32, 16))
+            .collect()
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "Taquin compact {}x{} - Heuristique: {:?} - Coût des mouvements: {}",
+            self.size, self.size, self.heuristic_type, self.move_cost
+        )
+    }
+}
+
+impl BackwardProblem for PackedTaquin {
+    fn goal_states(&self) -> Vec<u64> {
+        vec![self.goal_state]
+    }
+
+    fn predecessors(&self, state: &u64) -> SuccessorList<u64> {
+        self.successors(state)
+    }
+
+    fn heuristic_between(&self, from: &u64, to: &u64) -> usize {
+        if self.is_goal(to) {
+            return self.heuristic(from);
+        }
+        pairwise_tile_cost(
+            self.size,
+            self.heuristic_type,
+            &self.unpack(*from),
+            &self.unpack(*to),
+            |tile| self.weights[tile as usize] as usize,
+        )
+    }
+}
+
+pub struct PackedTaquinFactory;
+
+impl ProblemFactory for PackedTaquinFactory {
+    type Instance = PackedTaquin;
+
+    fn label(config: &BenchmarkConfig) -> String {
+        TaquinFactory::label(config).replacen("Taquin", "Taquin-Packed", 1)
+    }
+
+    fn max_depth(config: &BenchmarkConfig) -> usize {
+        TaquinFactory::max_depth(config)
+    }
+
+    fn validate(config: &BenchmarkConfig) -> Result<(), String> {
+        if config.size > 4 {
+            return Err(format!(
+                "La représentation compacte ne supporte pas le taquin {}x{} (4x4 maximum)",
+                config.size, config.size
+            ));
+        }
+        TaquinFactory::validate(config)
+    }
+
+    fn generate(config: &BenchmarkConfig, seed: u64) -> PackedTaquin {
+        let taquin = TaquinFactory::generate(config, seed);
+        PackedTaquin::from_taquin(&taquin, HeuristicType::Manhattan)
+    }
+
+    fn describe(instance: &PackedTaquin) -> String {
+        instance.initial_state_string()
+    }
+
+    fn properties(instance: &PackedTaquin) -> InstanceProperties {
+        let goal = instance.unpack(instance.goal_state);
+        let mut properties = goal_properties(&goal);
+        if let Some(pattern_db) = &instance.pattern_db {
+            properties.insert("pdb".to_string(), pattern_db.label().into());
+        }
+        properties
+    }
+
+    fn load_instances(
+        config: &BenchmarkConfig,
+        source: &str,
+    ) -> Result<Vec<KnownInstance<PackedTaquin>>, String> {
+        Ok(TaquinFactory::load_instances(config, source)?
+            .into_iter()
+            .map(|(taquin, known_cost)| {
+                (
+                    PackedTaquin::from_taquin(&taquin, HeuristicType::Manhattan),
+                    known_cost,
+                )
+            })
+            .collect())
+    }
+
+    fn unit_costs(config: &BenchmarkConfig) -> bool {
+        TaquinFactory::unit_costs(config)
+    }
+
+    fn heuristic_labels(config: &BenchmarkConfig) -> Vec<String> {
+        TaquinFactory::heuristic_labels(config)
+    }
+
+    fn with_heuristic(instance: &PackedTaquin, heuristic: Heuristic) -> Option<PackedTaquin> {
+        (heuristic != Heuristic::PatternDb || instance.pattern_db.is_some())
+            .then(|| instance.clone().with_heuristic(heuristic.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pack_roundtrip() {
+        let tiles = vec![1, 2, 3, 4, 0, 5, 6, 7, 8];
+        let packed = PackedTaquin::from_tiles(3, &tiles, HeuristicType::Manhattan);
+        assert_eq!(packed.unpack(packed.initial_state()), tiles);
+        assert_eq!(packed.find_blank(packed.initial_state()), 4);
+    }
+
+    #[test]
+    fn test_matches_vec_representation() {
+        let tiles = vec![5, 1, 2, 3, 4, 0, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+        let packed = PackedTaquin::from_tiles(4, &tiles, HeuristicType::Manhattan);
+        let reference = Taquin::from_state(4, tiles.clone(), HeuristicType::Manhattan);
+
+        let state = packed.initial_state();
+        assert_eq!(packed.heuristic(&state), reference.heuristic(&tiles));
+        assert_eq!(packed.actions(&state), reference.actions(&tiles));
+
+        let h = packed.heuristic(&state);
+        for action in packed.actions(&state) {
+            let (next, _) = packed.apply(&state, &action);
+            let (expected, _) = reference.apply(&tiles, &action);
+            assert_eq!(packed.unpack(next), expected);
+
+            let (incremental, new_h) = packed.apply_with_heuristic(state, h, action);
+            assert_eq!(incremental, next);
+            assert_eq!(new_h, packed.heuristic(&next));
+        }
+
+        let weighted = reference.clone().with_move_cost(MoveCost::Square);
+        let packed = PackedTaquin::from_taquin(&weighted, HeuristicType::Manhattan);
+        assert_eq!(packed.heuristic(&state), weighted.heuristic(&tiles));
+        for action in packed.actions(&state) {
+            let (next, cost) = packed.apply(&state, &action);
+            let (expected, expected_cost) = weighted.apply(&tiles, &action);
+            assert_eq!((packed.unpack(next), cost), (expected, expected_cost));
+        }
+    }
+}
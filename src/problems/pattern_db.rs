@@ -0,0 +1,374 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock};
+
+const MAGIC: &[u8; 4] = b"PDB1";
+const MAX_STATES: usize = 1 << 28;
+const MAX_PATTERN: usize = 5;
+const UNSEEN: u8 = u8::MAX;
+
+pub type Partition = Vec<Vec<u8>>;
+
+struct Pattern {
+    tiles: Vec<u8>,
+    weights: Vec<usize>,
+    table: Arc<Vec<u8>>,
+}
+
+pub struct PatternDatabase {
+    patterns: Vec<Pattern>,
+    label: String,
+}
+
+fn split(sizes: &[usize]) -> Partition {
+    let mut next = 1;
+    sizes
+        .iter()
+        .map(|&count| {
+            let group = (next..next + count).map(|tile| tile as u8).collect();
+            next += count;
+            group
+        })
+        .collect()
+}
+
+pub fn default_partition(size: usize) -> Partition {
+    let tiles = size * size - 1;
+    let groups = tiles.div_ceil(MAX_PATTERN);
+    let sizes: Vec<usize> = (0..groups)
+        .map(|i| tiles / groups + usize::from(i < tiles % groups))
+        .collect();
+    split(&sizes)
+}
+
+fn max_pattern(cells: usize) -> usize {
+    (1..)
+        .find(|&k| {
+            cells
+                .checked_pow(k as u32 + 2)
+                .is_none_or(|states| states > MAX_STATES)
+        })
+        .expect("taille de motif bornée")
+}
+
+pub fn parse_partition(size: usize, spec: &str) -> Result<Partition, String> {
+    let cells = size * size;
+    let partition: Partition = if spec.contains(['/', ',']) {
+        spec.split('/')
+            .map(|group| {
+                group
+                    .split(',')
+                    .map(|tile| {
+                        tile.trim()
+                            .parse::<u8>()
+                            .map_err(|_| format!("--pdb-partition: tuile invalide '{}'", tile))
+                    })
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?
+    } else {
+        let sizes =
+            spec.split('-')
+                .map(|count| {
+                    count.trim().parse::<usize>().map_err(|_| {
+                        format!("--pdb-partition: taille de groupe invalide '{}'", count)
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+        let covered: usize = sizes.iter().sum();
+        if covered != cells - 1 {
+            return Err(format!(
+                "--pdb-partition: les groupes {} couvrent {} tuiles, le taquin {}x{} en a {}",
+                spec,
+                covered,
+                size,
+                size,
+                cells - 1
+            ));
+        }
+        split(&sizes)
+    };
+    check(size, &partition)?;
+    Ok(partition)
+}
+
+fn check(size: usize, partition: &Partition) -> Result<(), String> {
+    let cells = size * size;
+    let limit = max_pattern(cells);
+    let mut seen = vec![false; cells];
+    for group in partition {
+        if group.is_empty() {
+            return Err("--pdb-partition: groupe vide".to_string());
+        }
+        if group.len() > limit {
+            return Err(format!(
+                "--pdb-partition: motif de {} tuiles trop grand pour le taquin {}x{} ({} maximum)",
+                group.len(),
+                size,
+                size,
+                limit
+            ));
+        }
+        for &tile in group {
+            if tile == 0 || tile as usize >= cells {
+                return Err(format!(
+                    "--pdb-partition: tuile {} hors du taquin {}x{} (1 à {})",
+                    tile,
+                    size,
+                    size,
+                    cells - 1
+                ));
+            }
+            if std::mem::replace(&mut seen[tile as usize], true) {
+                return Err(format!(
+                    "--pdb-partition: la tuile {} apparaît dans deux motifs",
+                    tile
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn partition_label(partition: &Partition) -> String {
+    let sizes: Vec<usize> = partition.iter().map(Vec::len).collect();
+    if split(&sizes) == *partition {
+        let sizes: Vec<String> = sizes.iter().map(|count| count.to_string()).collect();
+        return sizes.join("-");
+    }
+    partition
+        .iter()
+        .map(|group| {
+            let tiles: Vec<String> = group.iter().map(|tile| tile.to_string()).collect();
+            tiles.join(",")
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn neighbours(size: usize, cell: usize) -> impl Iterator<Item = usize> {
+    let (row, col) = (cell / size, cell % size);
+    [
+        (row > 0).then(|| cell - size),
+        (row + 1 < size).then(|| cell + size),
+        (col > 0).then(|| cell - 1),
+        (col + 1 < size).then(|| cell + 1),
+    ]
+    .into_iter()
+    .flatten()
+}
+
+fn seen(visited: &[u64], state: usize) -> bool {
+    visited[state / 64] & (1u64 << (state % 64)) != 0
+}
+
+fn mark(visited: &mut [u64], state: usize) -> bool {
+    let fresh = !seen(visited, state);
+    visited[state / 64] |= 1u64 << (state % 64);
+    fresh
+}
+
+fn build(size: usize, tiles: &[u8], goal_positions: &[usize]) -> Vec<u8> {
+    let cells = size * size;
+    let weights: Vec<usize> = (0..tiles.len()).map(|i| cells.pow(i as u32)).collect();
+    let patterns = cells.pow(tiles.len() as u32);
+    let mut table = vec![UNSEEN; patterns];
+    let mut visited = vec![0u64; (patterns * cells).div_ceil(64)];
+    let goal: usize = tiles
+        .iter()
+        .zip(&weights)
+        .map(|(&tile, weight)| goal_positions[tile as usize] * weight)
+        .sum();
+    let mut current = vec![goal * cells + goal_positions[0]];
+    let mut occupant = vec![None; cells];
+    let mut level = 0u8;
+
+    while !current.is_empty() {
+        let mut next = Vec::new();
+        let mut stack: Vec<usize> = current
+            .into_iter()
+            .filter(|&state| mark(&mut visited, state))
+            .collect();
+        while let Some(state) = stack.pop() {
+            let (pattern, blank) = (state / cells, state % cells);
+            if table[pattern] == UNSEEN {
+                table[pattern] = level;
+            }
+            occupant.fill(None);
+            for (i, weight) in weights.iter().enumerate() {
+                occupant[pattern / weight % cells] = Some(i);
+            }
+            for cell in neighbours(size, blank) {
+                match occupant[cell] {
+                    None => {
+                        let neighbour = pattern * cells + cell;
+                        if mark(&mut visited, neighbour) {
+                            stack.push(neighbour);
+                        }
+                    }
+                    Some(i) => {
+                        let moved =
+                            (pattern + blank * weights[i] - cell * weights[i]) * cells + cell;
+                        if !seen(&visited, moved) {
+                            next.push(moved);
+                        }
+                    }
+                }
+            }
+        }
+        current = next;
+        level += 1;
+    }
+    table
+}
+
+type TableCache = Mutex<HashMap<String, Arc<Vec<u8>>>>;
+
+fn table(
+    size: usize,
+    tiles: &[u8],
+    goal_positions: &[usize],
+    dir: &str,
+) -> Result<Arc<Vec<u8>>, String> {
+    static CACHE: OnceLock<TableCache> = OnceLock::new();
+    let join = |values: &mut dyn Iterator<Item = usize>| {
+        values
+            .map(|value| value.to_string())
+            .collect::<Vec<_>>()
+            .join("-")
+    };
+    let path = Path::new(dir).join(format!(
+        "taquin-{}x{}-{}-{}-{}.pdb",
+        size,
+        size,
+        join(&mut tiles.iter().map(|&tile| tile as usize)),
+        join(&mut tiles.iter().map(|&tile| goal_positions[tile as usize])),
+        goal_positions[0]
+    ));
+    let key = path.to_string_lossy().into_owned();
+    let mut cache = CACHE.get_or_init(Default::default).lock().unwrap();
+    if let Some(table) = cache.get(&key) {
+        return Ok(Arc::clone(table));
+    }
+
+    let entries = (size * size).pow(tiles.len() as u32);
+    let table = match fs::read(&path) {
+        Ok(bytes) if bytes.len() == MAGIC.len() + entries && bytes.starts_with(MAGIC) => {
+            bytes[MAGIC.len()..].to_vec()
+        }
+        _ => {
+            let table = build(size, tiles, goal_positions);
+            fs::create_dir_all(dir)
+                .and_then(|_| fs::write(&path, [MAGIC.as_slice(), &table].concat()))
+                .map_err(|e| format!("Cache PDB {}: {}", path.display(), e))?;
+            table
+        }
+    };
+    let table = Arc::new(table);
+    cache.insert(key, Arc::clone(&table));
+    Ok(table)
+}
+
+impl PatternDatabase {
+    pub fn load(
+        size: usize,
+        goal: &[u8],
+        partition: &Partition,
+        dir: &str,
+    ) -> Result<Self, String> {
+        check(size, partition)?;
+        let cells = size * size;
+        let mut goal_positions = vec![0; cells];
+        for (pos, &tile) in goal.iter().enumerate() {
+            goal_positions[tile as usize] = pos;
+        }
+        let patterns = partition
+            .iter()
+            .map(|tiles| {
+                Ok(Pattern {
+                    tiles: tiles.clone(),
+                    weights: (0..tiles.len()).map(|i| cells.pow(i as u32)).collect(),
+                    table: table(size, tiles, &goal_positions, dir)?,
+                })
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(PatternDatabase {
+            patterns,
+            label: partition_label(partition),
+        })
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn evaluate(&self, tiles: impl IntoIterator<Item = u8>) -> usize {
+        let mut positions = [0usize; 256];
+        for (pos, tile) in tiles.into_iter().enumerate() {
+            positions[tile as usize] = pos;
+        }
+        self.patterns
+            .iter()
+            .map(|pattern| {
+                let index: usize = pattern
+                    .tiles
+                    .iter()
+                    .zip(&pattern.weights)
+                    .map(|(&tile, weight)| positions[tile as usize] * weight)
+                    .sum();
+                pattern.table[index] as usize
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::astar::AStar;
+    use crate::algorithms::{Problem, SearchAlgorithm};
+    use crate::problems::taquin::HeuristicType;
+    use crate::problems::Taquin;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_pattern_database_dominates_manhattan_and_stays_admissible() {
+        assert_eq!(default_partition(4), split(&[5, 5, 5]));
+        assert_eq!(default_partition(3), split(&[4, 4]));
+        assert_eq!(parse_partition(4, "6-6-3").unwrap(), split(&[6, 6, 3]));
+        assert_eq!(
+            partition_label(&parse_partition(3, "1,3/2").unwrap()),
+            "1,3/2"
+        );
+        assert!(parse_partition(4, "5-5").is_err());
+        assert!(parse_partition(4, "7-7-1").is_err());
+        assert!(parse_partition(3, "1,2/2,3").is_err());
+        assert!(parse_partition(3, "9").is_err());
+
+        let goal: Vec<u8> = (0..9).collect();
+        let dir = "target/pdb-test";
+        let pattern_db =
+            Arc::new(PatternDatabase::load(3, &goal, &default_partition(3), dir).unwrap());
+        assert_eq!(pattern_db.label(), "4-4");
+        assert_eq!(pattern_db.evaluate(goal.iter().copied()), 0);
+
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..5 {
+            let mut manhattan = Taquin::new(3, HeuristicType::Manhattan);
+            manhattan.generate_uniform(&mut rng);
+            let patterns = manhattan
+                .clone()
+                .with_heuristic(HeuristicType::PatternDB)
+                .with_pattern_db(Arc::clone(&pattern_db));
+            let start = manhattan.initial_state();
+            let optimal = AStar::new().search(&manhattan);
+            let result = AStar::new().search(&patterns);
+            assert!(patterns.heuristic(&start) >= manhattan.heuristic(&start));
+            assert!(patterns.heuristic(&start) <= optimal.metrics.solution_cost);
+            assert_eq!(result.metrics.solution_cost, optimal.metrics.solution_cost);
+            assert!(result.metrics.nodes_visited <= optimal.metrics.nodes_visited);
+        }
+    }
+}
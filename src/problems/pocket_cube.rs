@@ -0,0 +1,340 @@
+use super::registry::ProblemFactory;
+use crate::algorithms::{ActionList, BackwardProblem, Problem, SuccessorList, Successors};
+use crate::benchmarking::{BenchmarkConfig, InstanceProperties};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::fmt;
+
+const CORNERS: usize = 8;
+const CORNER_BITS: usize = 5;
+const TWISTED_PER_TURN: usize = 4;
+const MAX_DISTANCE: usize = 14;
+const CORNER_NAMES: [&str; CORNERS] = ["URF", "UFL", "ULB", "UBR", "DFR", "DLF", "DBL", "DRB"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Face {
+    U,
+    R,
+    F,
+}
+
+impl Face {
+    pub const ALL: [Face; 3] = [Face::U, Face::R, Face::F];
+
+    fn permutation(self) -> [u8; CORNERS] {
+        match self {
+            Face::U => [3, 0, 1, 2, 4, 5, 6, 7],
+            Face::R => [4, 1, 2, 0, 7, 5, 6, 3],
+            Face::F => [1, 5, 2, 3, 0, 4, 6, 7],
+        }
+    }
+
+    fn orientation(self) -> [u8; CORNERS] {
+        match self {
+            Face::U => [0; CORNERS],
+            Face::R => [2, 0, 0, 1, 1, 0, 0, 2],
+            Face::F => [1, 2, 0, 0, 2, 1, 0, 0],
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Turn {
+    pub face: Face,
+    pub quarters: u8,
+}
+
+impl Turn {
+    pub const ALL: [Turn; 9] = [
+        Turn::new(Face::U, 1),
+        Turn::new(Face::U, 2),
+        Turn::new(Face::U, 3),
+        Turn::new(Face::R, 1),
+        Turn::new(Face::R, 2),
+        Turn::new(Face::R, 3),
+        Turn::new(Face::F, 1),
+        Turn::new(Face::F, 2),
+        Turn::new(Face::F, 3),
+    ];
+
+    pub const fn new(face: Face, quarters: u8) -> Self {
+        Turn { face, quarters }
+    }
+}
+
+impl fmt::Display for Turn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let suffix = match self.quarters {
+            1 => "",
+            2 => "2",
+            _ => "'",
+        };
+        write!(f, "{:?}{}", self.face, suffix)
+    }
+}
+
+#[derive(Clone)]
+pub struct PocketCube {
+    initial_state: u64,
+    scramble: Vec<Turn>,
+}
+
+impl PocketCube {
+    pub fn solved() -> Self {
+        PocketCube {
+            initial_state: Self::solved_state(),
+            scramble: Vec::new(),
+        }
+    }
+
+    pub fn from_scramble(scramble: Vec<Turn>) -> Self {
+        let initial_state = scramble
+            .iter()
+            .fold(Self::solved_state(), |state, &turn| Self::turn(state, turn));
+        PocketCube {
+            initial_state,
+            scramble,
+        }
+    }
+
+    pub fn random_scramble<R: Rng>(depth: usize, rng: &mut R) -> Vec<Turn> {
+        let mut scramble: Vec<Turn> = Vec::with_capacity(depth);
+        while scramble.len() < depth {
+            let turn = Turn::ALL[rng.gen_range(0..Turn::ALL.len())];
+            if scramble.last().is_none_or(|last| last.face != turn.face) {
+                scramble.push(turn);
+            }
+        }
+        scramble
+    }
+
+    pub fn scramble(&self) -> &[Turn] {
+        &self.scramble
+    }
+
+    fn solved_state() -> u64 {
+        Self::pack(&[0, 1, 2, 3, 4, 5, 6, 7], &[0; CORNERS])
+    }
+
+    pub fn pack(permutation: &[u8; CORNERS], orientation: &[u8; CORNERS]) -> u64 {
+        (0..CORNERS).fold(0u64, |acc, i| {
+            acc | ((permutation[i] | orientation[i] << 3) as u64) << (CORNER_BITS * i)
+        })
+    }
+
+    pub fn unpack(state: u64) -> ([u8; CORNERS], [u8; CORNERS]) {
+        let mut permutation = [0u8; CORNERS];
+        let mut orientation = [0u8; CORNERS];
+        for i in 0..CORNERS {
+            let corner = (state >> (CORNER_BITS * i)) as u8 & 0x1F;
+            permutation[i] = corner & 0x7;
+            orientation[i] = corner >> 3;
+        }
+        (permutation, orientation)
+    }
+
+    fn quarter_turn(state: u64, face: Face) -> u64 {
+        let (permutation, orientation) = Self::unpack(state);
+        let (moved, twist) = (face.permutation(), face.orientation());
+        let mut next_permutation = [0u8; CORNERS];
+        let mut next_orientation = [0u8; CORNERS];
+        for i in 0..CORNERS {
+            let from = moved[i] as usize;
+            next_permutation[i] = permutation[from];
+            next_orientation[i] = (orientation[from] + twist[i]) % 3;
+        }
+        Self::pack(&next_permutation, &next_orientation)
+    }
+
+    pub fn turn(state: u64, turn: Turn) -> u64 {
+        (0..turn.quarters).fold(state, |state, _| Self::quarter_turn(state, turn.face))
+    }
+
+    pub fn twisted_corners(state: u64) -> usize {
+        let (_, orientation) = Self::unpack(state);
+        orientation.iter().filter(|&&twist| twist != 0).count()
+    }
+
+    pub fn state_string(state: u64) -> String {
+        let (permutation, orientation) = Self::unpack(state);
+        CORNER_NAMES
+            .iter()
+            .zip(permutation.iter().zip(&orientation))
+            .map(|(position, (&corner, &twist))| {
+                format!("{}:{}+{}", position, CORNER_NAMES[corner as usize], twist)
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    pub fn scramble_string(&self) -> String {
+        let turns: Vec<String> = self.scramble.iter().map(Turn::to_string).collect();
+        turns.join(" ")
+    }
+}
+
+impl Problem for PocketCube {
+    type State = u64;
+    type Action = Turn;
+
+    fn initial_state(&self) -> u64 {
+        self.initial_state
+    }
+
+    fn is_goal(&self, state: &u64) -> bool {
+        *state == Self::solved_state()
+    }
+
+    fn actions(&self, _state: &u64) -> ActionList<Turn> {
+        Turn::ALL.into_iter().collect()
+    }
+
+    fn apply(&self, state: &u64, action: &Turn) -> (u64, usize) {
+        (Self::turn(*state, *action), 1)
+    }
+
+    fn successors_into(&self, state: &u64, buffer: &mut Successors<Self>) {
+        buffer.clear();
+        for face in Face::ALL {
+            let mut next = *state;
+            for quarters in 1..=3 {
+                next = Self::quarter_turn(next, face);
+                buffer.push((Turn::new(face, quarters), next, 1));
+            }
+        }
+    }
+
+    fn heuristic(&self, state: &u64) -> usize {
+        Self::twisted_corners(*state).div_ceil(TWISTED_PER_TURN)
+    }
+
+    fn encode_state(&self, state: &u64) -> String {
+        let (permutation, orientation) = Self::unpack(*state);
+        permutation
+            .iter()
+            .chain(&orientation)
+            .map(|digit| digit.to_string())
+            .collect()
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "Cube 2x2 - Mélange de {} mouvements - Heuristique: orientation des coins",
+            self.scramble.len()
+        )
+    }
+}
+
+impl BackwardProblem for PocketCube {
+    fn goal_states(&self) -> Vec<u64> {
+        vec![Self::solved_state()]
+    }
+
+    fn predecessors(&self, state: &u64) -> SuccessorList<u64> {
+        self.successors(state)
+    }
+}
+
+pub struct PocketCubeFactory;
+
+impl ProblemFactory for PocketCubeFactory {
+    type Instance = PocketCube;
+
+    fn label(config: &BenchmarkConfig) -> String {
+        format!("PocketCube-{}", config.size)
+    }
+
+    fn max_depth(_config: &BenchmarkConfig) -> usize {
+        MAX_DISTANCE
+    }
+
+    fn validate(config: &BenchmarkConfig) -> Result<(), String> {
+        if config.size == 0 {
+            return Err(
+                "--size 0: le mélange du cube doit compter au moins un mouvement".to_string(),
+            );
+        }
+        Ok(())
+    }
+
+    fn generate(config: &BenchmarkConfig, seed: u64) -> PocketCube {
+        let mut rng = StdRng::seed_from_u64(seed);
+        PocketCube::from_scramble(PocketCube::random_scramble(config.size, &mut rng))
+    }
+
+    fn describe(instance: &PocketCube) -> String {
+        format!(
+            "Mélange: {}\n{}",
+            instance.scramble_string(),
+            PocketCube::state_string(instance.initial_state)
+        )
+    }
+
+    fn properties(instance: &PocketCube) -> InstanceProperties {
+        InstanceProperties::from([
+            ("scramble".to_string(), instance.scramble_string().into()),
+            (
+                "twisted_corners".to_string(),
+                PocketCube::twisted_corners(instance.initial_state).into(),
+            ),
+        ])
+    }
+
+    fn unit_costs(_config: &BenchmarkConfig) -> bool {
+        true
+    }
+
+    fn notes(config: &BenchmarkConfig) -> Vec<String> {
+        vec![format!(
+            "Cube 2x2: mélange de {} mouvements (U, R, F en quarts et demi-tours), coin DBL fixe, heuristique = coins mal orientés / 4",
+            config.size
+        )]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::bfs::BFS;
+    use crate::algorithms::idastar::IDAStar;
+    use crate::algorithms::{validation, SearchAlgorithm};
+
+    #[test]
+    fn test_turns_form_a_group_and_heuristic_is_admissible() {
+        let solved = PocketCube::solved().initial_state();
+        for turn in Turn::ALL {
+            let once = PocketCube::turn(solved, turn);
+            assert_ne!(once, solved);
+            assert_eq!(
+                PocketCube::turn(once, Turn::new(turn.face, 4 - turn.quarters)),
+                solved
+            );
+        }
+        let sexy = [
+            Turn::new(Face::R, 1),
+            Turn::new(Face::U, 1),
+            Turn::new(Face::R, 3),
+            Turn::new(Face::U, 3),
+        ];
+        let mut state = solved;
+        for _ in 0..6 {
+            state = sexy
+                .iter()
+                .fold(state, |state, &turn| PocketCube::turn(state, turn));
+        }
+        assert_eq!(state, solved);
+
+        let mut rng = StdRng::seed_from_u64(11);
+        for depth in [2, 4, 6] {
+            let cube = PocketCube::from_scramble(PocketCube::random_scramble(depth, &mut rng));
+            let bfs = BFS::new().search(&cube);
+            let result = IDAStar::new(2 * MAX_DISTANCE).search(&cube);
+            assert!(result.status.is_solved());
+            assert_eq!(result.metrics.solution_cost, bfs.metrics.solution_cost);
+            assert!(result.metrics.solution_cost <= depth);
+            assert!(cube.heuristic(&cube.initial_state()) <= result.metrics.solution_cost);
+            let actions = result.solution.unwrap();
+            validation::validate_solution(&cube, &actions, &result.metrics).unwrap();
+        }
+    }
+}
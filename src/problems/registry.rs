@@ -0,0 +1,384 @@
+#[cfg(feature = "script")]
+use super::script;
+use super::{csp, packed_taquin, pocket_cube, shortest_path, subprocess, taquin};
+use crate::algorithms::BackwardProblem;
+use crate::benchmarking::debugger::{self, DebugConfig};
+use crate::benchmarking::distributed::RemoteTask;
+use crate::benchmarking::dot::{self, DotConfig};
+use crate::benchmarking::fuzz::{self, FuzzConfig};
+use crate::benchmarking::heuristic_check::{self, HeuristicCheckConfig, HeuristicReport};
+use crate::benchmarking::{
+    BenchmarkConfig, BenchmarkResult, BenchmarkRunner, Heuristic, InstanceProperties, ProblemRun,
+};
+use crate::utils::instance_sets::KnownInstance;
+use std::error::Error;
+
+pub struct ParamSpec {
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+pub trait ProblemFactory {
+    type Instance: BackwardProblem + Send + Sync + 'static;
+
+    fn label(config: &BenchmarkConfig) -> String;
+    fn max_depth(config: &BenchmarkConfig) -> usize;
+    fn generate(config: &BenchmarkConfig, seed: u64) -> Self::Instance;
+    fn describe(instance: &Self::Instance) -> String;
+
+    fn serialize(instance: &Self::Instance) -> String {
+        Self::describe(instance)
+    }
+
+    fn properties(instance: &Self::Instance) -> InstanceProperties {
+        let _ = instance;
+        InstanceProperties::new()
+    }
+
+    fn load_instances(
+        config: &BenchmarkConfig,
+        source: &str,
+    ) -> Result<Vec<KnownInstance<Self::Instance>>, String> {
+        let _ = source;
+        Err(format!(
+            "{}: les jeux d'instances ne sont pas supportés",
+            Self::label(config)
+        ))
+    }
+
+    fn validate(config: &BenchmarkConfig) -> Result<(), String> {
+        let _ = config;
+        Ok(())
+    }
+
+    fn heuristic_labels(config: &BenchmarkConfig) -> Vec<String> {
+        let _ = config;
+        Vec::new()
+    }
+
+    fn with_heuristic(instance: &Self::Instance, heuristic: Heuristic) -> Option<Self::Instance> {
+        let _ = (instance, heuristic);
+        None
+    }
+
+    fn unit_costs(config: &BenchmarkConfig) -> bool {
+        let _ = config;
+        false
+    }
+
+    fn admissible_heuristic(config: &BenchmarkConfig) -> bool {
+        let _ = config;
+        true
+    }
+
+    fn notes(config: &BenchmarkConfig) -> Vec<String> {
+        let _ = config;
+        Vec::new()
+    }
+}
+
+pub type BenchmarkFn = fn(&BenchmarkRunner) -> Result<ProblemRun, Box<dyn Error>>;
+pub type RemoteTaskFn = fn(&BenchmarkRunner, &RemoteTask) -> Option<(BenchmarkResult, f64)>;
+pub type SolveFn = fn(&BenchmarkRunner, usize) -> Vec<BenchmarkResult>;
+pub type ValidateFn = fn(&BenchmarkConfig) -> Result<(), String>;
+pub type FuzzFn = fn(&FuzzConfig) -> Result<usize, String>;
+pub type HeuristicCheckFn = fn(&BenchmarkConfig, &HeuristicCheckConfig) -> HeuristicReport;
+pub type DotFn = fn(&BenchmarkConfig, &DotConfig) -> Result<Vec<String>, String>;
+pub type DebugFn = fn(&BenchmarkConfig, &DebugConfig) -> Result<(), String>;
+
+pub struct ProblemEntry {
+    pub name: &'static str,
+    pub title: &'static str,
+    pub params: &'static [ParamSpec],
+    pub validate: ValidateFn,
+    pub run: BenchmarkFn,
+    pub run_task: RemoteTaskFn,
+    pub solve: SolveFn,
+    pub fuzz: FuzzFn,
+    pub check_heuristic: HeuristicCheckFn,
+    pub export_dot: DotFn,
+    pub debug: DebugFn,
+}
+
+const SHUFFLE_PARAM: ParamSpec = ParamSpec {
+    name: "shuffle",
+    description: "walk (marche aléatoire depuis le but) ou uniform (tirage uniforme parmi les états solubles)",
+};
+
+pub static PROBLEMS: &[ProblemEntry] = &[
+    ProblemEntry {
+        name: "taquin",
+        title: "Taquin",
+        params: &[
+            ParamSpec {
+                name: "size",
+                description: "Côté du plateau (3 pour le 8-puzzle, 4 pour le 15-puzzle)",
+            },
+            SHUFFLE_PARAM,
+        ],
+        validate: taquin::TaquinFactory::validate,
+        run: BenchmarkRunner::benchmark_problem::<taquin::TaquinFactory>,
+        run_task: BenchmarkRunner::run_remote_task::<taquin::TaquinFactory>,
+        solve: BenchmarkRunner::solve_instance::<taquin::TaquinFactory>,
+        fuzz: fuzz::fuzz_problem::<taquin::TaquinFactory>,
+        check_heuristic: heuristic_check::check_problem::<taquin::TaquinFactory>,
+        export_dot: dot::export_problem::<taquin::TaquinFactory>,
+        debug: debugger::debug_problem::<taquin::TaquinFactory>,
+    },
+    ProblemEntry {
+        name: "taquin-packed",
+        title: "Taquin (représentation compacte u64)",
+        params: &[
+            ParamSpec {
+                name: "size",
+                description: "Côté du plateau (4 au maximum)",
+            },
+            SHUFFLE_PARAM,
+        ],
+        validate: packed_taquin::PackedTaquinFactory::validate,
+        run: BenchmarkRunner::benchmark_problem::<packed_taquin::PackedTaquinFactory>,
+        run_task: BenchmarkRunner::run_remote_task::<packed_taquin::PackedTaquinFactory>,
+        solve: BenchmarkRunner::solve_instance::<packed_taquin::PackedTaquinFactory>,
+        fuzz: fuzz::fuzz_problem::<packed_taquin::PackedTaquinFactory>,
+        check_heuristic: heuristic_check::check_problem::<packed_taquin::PackedTaquinFactory>,
+        export_dot: dot::export_problem::<packed_taquin::PackedTaquinFactory>,
+        debug: debugger::debug_problem::<packed_taquin::PackedTaquinFactory>,
+    },
+    ProblemEntry {
+        name: "pocket-cube",
+        title: "Cube 2x2 (Pocket Cube)",
+        params: &[ParamSpec {
+            name: "size",
+            description: "Nombre de mouvements du mélange aléatoire depuis le cube résolu",
+        }],
+        validate: pocket_cube::PocketCubeFactory::validate,
+        run: BenchmarkRunner::benchmark_problem::<pocket_cube::PocketCubeFactory>,
+        run_task: BenchmarkRunner::run_remote_task::<pocket_cube::PocketCubeFactory>,
+        solve: BenchmarkRunner::solve_instance::<pocket_cube::PocketCubeFactory>,
+        fuzz: fuzz::fuzz_problem::<pocket_cube::PocketCubeFactory>,
+        check_heuristic: heuristic_check::check_problem::<pocket_cube::PocketCubeFactory>,
+        export_dot: dot::export_problem::<pocket_cube::PocketCubeFactory>,
+        debug: debugger::debug_problem::<pocket_cube::PocketCubeFactory>,
+    },
+    ProblemEntry {
+        name: "shortest-path",
+        title: "Plus Court Chemin (Grille)",
+        params: &[ParamSpec {
+            name: "size",
+            description: "Côté de la grille",
+        }],
+        validate: shortest_path::GridFactory::validate,
+        run: BenchmarkRunner::benchmark_problem::<shortest_path::GridFactory>,
+        run_task: BenchmarkRunner::run_remote_task::<shortest_path::GridFactory>,
+        solve: BenchmarkRunner::solve_instance::<shortest_path::GridFactory>,
+        fuzz: fuzz::fuzz_problem::<shortest_path::GridFactory>,
+        check_heuristic: heuristic_check::check_problem::<shortest_path::GridFactory>,
+        export_dot: dot::export_problem::<shortest_path::GridFactory>,
+        debug: debugger::debug_problem::<shortest_path::GridFactory>,
+    },
+    ProblemEntry {
+        name: "maze",
+        title: "Labyrinthe Parfait",
+        params: &[
+            ParamSpec {
+                name: "size",
+                description: "Côté du labyrinthe en cellules (grille de (2n+1)² cases murs compris)",
+            },
+            ParamSpec {
+                name: "maze_generator",
+                description: "backtracker (parcours en profondeur, longs couloirs) ou prim (Prim aléatoire, nombreuses impasses courtes)",
+            },
+        ],
+        validate: shortest_path::MazeFactory::validate,
+        run: BenchmarkRunner::benchmark_problem::<shortest_path::MazeFactory>,
+        run_task: BenchmarkRunner::run_remote_task::<shortest_path::MazeFactory>,
+        solve: BenchmarkRunner::solve_instance::<shortest_path::MazeFactory>,
+        fuzz: fuzz::fuzz_problem::<shortest_path::MazeFactory>,
+        check_heuristic: heuristic_check::check_problem::<shortest_path::MazeFactory>,
+        export_dot: dot::export_problem::<shortest_path::MazeFactory>,
+        debug: debugger::debug_problem::<shortest_path::MazeFactory>,
+    },
+    ProblemEntry {
+        name: "shortest-path-random",
+        title: "Plus Court Chemin (Graphe Aléatoire)",
+        params: &[
+            ParamSpec {
+                name: "size",
+                description: "Nombre de nœuds du graphe (3 arêtes par nœud)",
+            },
+            ParamSpec {
+                name: "connectivity",
+                description: "none (graphe brut), path (chemin départ→but construit d'abord) ou resample (re-tirage jusqu'à ce que le but soit atteignable)",
+            },
+        ],
+        validate: shortest_path::RandomGraphFactory::validate,
+        run: BenchmarkRunner::benchmark_problem::<shortest_path::RandomGraphFactory>,
+        run_task: BenchmarkRunner::run_remote_task::<shortest_path::RandomGraphFactory>,
+        solve: BenchmarkRunner::solve_instance::<shortest_path::RandomGraphFactory>,
+        fuzz: fuzz::fuzz_problem::<shortest_path::RandomGraphFactory>,
+        check_heuristic: heuristic_check::check_problem::<shortest_path::RandomGraphFactory>,
+        export_dot: dot::export_problem::<shortest_path::RandomGraphFactory>,
+        debug: debugger::debug_problem::<shortest_path::RandomGraphFactory>,
+    },
+    ProblemEntry {
+        name: "shortest-path-file",
+        title: "Plus Court Chemin (Graphe Importé)",
+        params: &[
+            ParamSpec {
+                name: "graph",
+                description: "Fichier du graphe: GraphML (.graphml), adjacence JSON (.json) ou liste d'arêtes source,cible[,poids] (autres extensions)",
+            },
+            ParamSpec {
+                name: "source",
+                description: "Nœud de départ (tiré au hasard par instance si absent)",
+            },
+            ParamSpec {
+                name: "target",
+                description: "Nœud but (tiré parmi les nœuds atteignables si absent)",
+            },
+            ParamSpec {
+                name: "undirected",
+                description: "Ajoute l'arête inverse de chaque arête (CSV et JSON ; GraphML suit edgedefault)",
+            },
+        ],
+        validate: shortest_path::GraphFileFactory::validate,
+        run: BenchmarkRunner::benchmark_problem::<shortest_path::GraphFileFactory>,
+        run_task: BenchmarkRunner::run_remote_task::<shortest_path::GraphFileFactory>,
+        solve: BenchmarkRunner::solve_instance::<shortest_path::GraphFileFactory>,
+        fuzz: fuzz::fuzz_problem::<shortest_path::GraphFileFactory>,
+        check_heuristic: heuristic_check::check_problem::<shortest_path::GraphFileFactory>,
+        export_dot: dot::export_problem::<shortest_path::GraphFileFactory>,
+        debug: debugger::debug_problem::<shortest_path::GraphFileFactory>,
+    },
+    ProblemEntry {
+        name: "shortest-path-map",
+        title: "Plus Court Chemin (Carte de Coûts)",
+        params: &[ParamSpec {
+            name: "map",
+            description: "Carte ASCII (. ou espace = 1, chiffres 1-9 = coût, # @ T X 0 = mur, S/G = départ/but) ou PNG en niveaux de gris (noir ou transparent = mur, du blanc = 1 au gris foncé = 8)",
+        }],
+        validate: shortest_path::CostMapFactory::validate,
+        run: BenchmarkRunner::benchmark_problem::<shortest_path::CostMapFactory>,
+        run_task: BenchmarkRunner::run_remote_task::<shortest_path::CostMapFactory>,
+        solve: BenchmarkRunner::solve_instance::<shortest_path::CostMapFactory>,
+        fuzz: fuzz::fuzz_problem::<shortest_path::CostMapFactory>,
+        check_heuristic: heuristic_check::check_problem::<shortest_path::CostMapFactory>,
+        export_dot: dot::export_problem::<shortest_path::CostMapFactory>,
+        debug: debugger::debug_problem::<shortest_path::CostMapFactory>,
+    },
+    ProblemEntry {
+        name: "sudoku",
+        title: "Sudoku (CSP)",
+        params: &[ParamSpec {
+            name: "size",
+            description: "Nombre de cases vides d'une grille 9x9 résolue tirée au hasard (--instances: une grille de 16 ou 81 cases par ligne, . ou 0 pour une case vide)",
+        }],
+        validate: csp::SudokuFactory::validate,
+        run: BenchmarkRunner::benchmark_problem::<csp::SudokuFactory>,
+        run_task: BenchmarkRunner::run_remote_task::<csp::SudokuFactory>,
+        solve: BenchmarkRunner::solve_instance::<csp::SudokuFactory>,
+        fuzz: fuzz::fuzz_problem::<csp::SudokuFactory>,
+        check_heuristic: heuristic_check::check_problem::<csp::SudokuFactory>,
+        export_dot: dot::export_problem::<csp::SudokuFactory>,
+        debug: debugger::debug_problem::<csp::SudokuFactory>,
+    },
+    ProblemEntry {
+        name: "map-coloring",
+        title: "Coloration de Carte (CSP)",
+        params: &[
+            ParamSpec {
+                name: "size",
+                description: "Nombre de régions de la carte aléatoire (sans --graph)",
+            },
+            ParamSpec {
+                name: "colors",
+                description: "Nombre de couleurs disponibles",
+            },
+            ParamSpec {
+                name: "graph",
+                description: "Frontières entre régions lues depuis un graphe (mêmes formats que shortest-path-file, arêtes non orientées)",
+            },
+        ],
+        validate: csp::ColoringFactory::validate,
+        run: BenchmarkRunner::benchmark_problem::<csp::ColoringFactory>,
+        run_task: BenchmarkRunner::run_remote_task::<csp::ColoringFactory>,
+        solve: BenchmarkRunner::solve_instance::<csp::ColoringFactory>,
+        fuzz: fuzz::fuzz_problem::<csp::ColoringFactory>,
+        check_heuristic: heuristic_check::check_problem::<csp::ColoringFactory>,
+        export_dot: dot::export_problem::<csp::ColoringFactory>,
+        debug: debugger::debug_problem::<csp::ColoringFactory>,
+    },
+    ProblemEntry {
+        name: "subprocess",
+        title: "Problème Externe (Processus)",
+        params: &[
+            ParamSpec {
+                name: "problem_command",
+                description: "Commande d'un programme lisant des requêtes JSON {op, size, seed, state} sur stdin (init, is_goal, successors, heuristic) et répondant une ligne JSON par requête",
+            },
+            ParamSpec {
+                name: "size",
+                description: "Transmis au programme avec chaque requête",
+            },
+        ],
+        validate: subprocess::SubprocessFactory::validate,
+        run: BenchmarkRunner::benchmark_problem::<subprocess::SubprocessFactory>,
+        run_task: BenchmarkRunner::run_remote_task::<subprocess::SubprocessFactory>,
+        solve: BenchmarkRunner::solve_instance::<subprocess::SubprocessFactory>,
+        fuzz: fuzz::fuzz_problem::<subprocess::SubprocessFactory>,
+        check_heuristic: heuristic_check::check_problem::<subprocess::SubprocessFactory>,
+        export_dot: dot::export_problem::<subprocess::SubprocessFactory>,
+        debug: debugger::debug_problem::<subprocess::SubprocessFactory>,
+    },
+    #[cfg(feature = "script")]
+    ProblemEntry {
+        name: "script",
+        title: "Problème Scripté (Rhai)",
+        params: &[
+            ParamSpec {
+                name: "problem_script",
+                description: "Script Rhai définissant initial_state() ou initial_state(size, seed), is_goal(state), successors(state) et, en option, heuristic(state), max_depth(size), goal_states() et predecessors(state)",
+            },
+            ParamSpec {
+                name: "size",
+                description: "Transmis à initial_state(size, seed) et max_depth(size)",
+            },
+        ],
+        validate: script::ScriptFactory::validate,
+        run: BenchmarkRunner::benchmark_problem::<script::ScriptFactory>,
+        run_task: BenchmarkRunner::run_remote_task::<script::ScriptFactory>,
+        solve: BenchmarkRunner::solve_instance::<script::ScriptFactory>,
+        fuzz: fuzz::fuzz_problem::<script::ScriptFactory>,
+        check_heuristic: heuristic_check::check_problem::<script::ScriptFactory>,
+        export_dot: dot::export_problem::<script::ScriptFactory>,
+        debug: debugger::debug_problem::<script::ScriptFactory>,
+    },
+];
+
+pub fn find(name: &str) -> Option<&'static ProblemEntry> {
+    PROBLEMS.iter().find(|entry| entry.name == name)
+}
+
+pub fn names() -> Vec<&'static str> {
+    PROBLEMS.iter().map(|entry| entry.name).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_names_are_unique() {
+        let mut names = names();
+        let total = names.len();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), total);
+    }
+
+    #[test]
+    fn test_find() {
+        assert!(find("taquin").is_some());
+        assert!(find("inconnu").is_none());
+    }
+}
@@ -0,0 +1,410 @@
+use super::registry::ProblemFactory;
+use crate::algorithms::{ActionList, BackwardProblem, Problem, SuccessorList, Successors};
+use crate::benchmarking::{BenchmarkConfig, Direction};
+use rhai::{CallFnOptions, Dynamic, Engine, FuncArgs, Scope, AST};
+use std::any::{type_name, Any};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock};
+
+const REQUIRED: &[(&str, usize)] = &[("is_goal", 1), ("successors", 1)];
+const DEFAULT_MAX_DEPTH: usize = 50;
+
+pub struct ScriptProgram {
+    engine: Engine,
+    ast: AST,
+    name: String,
+}
+
+impl ScriptProgram {
+    pub fn compile(name: &str, source: &str) -> Result<Self, String> {
+        let engine = Engine::new();
+        let ast = engine.compile(source).map_err(|e| e.to_string())?;
+        let program = ScriptProgram {
+            engine,
+            ast,
+            name: name.to_string(),
+        };
+        if !program.declares("initial_state", 0) && !program.declares("initial_state", 2) {
+            return Err(
+                "fonction initial_state() ou initial_state(size, seed) absente".to_string(),
+            );
+        }
+        for &(function, arity) in REQUIRED {
+            if !program.declares(function, arity) {
+                return Err(format!(
+                    "fonction {} à {} paramètre(s) absente",
+                    function, arity
+                ));
+            }
+        }
+        Ok(program)
+    }
+
+    fn declares(&self, function: &str, arity: usize) -> bool {
+        self.ast
+            .iter_functions()
+            .any(|f| f.name == function && f.params.len() == arity)
+    }
+
+    fn call<T: Any + Clone>(&self, function: &str, args: impl FuncArgs) -> Result<T, String> {
+        let value: Dynamic = self
+            .engine
+            .call_fn_with_options(
+                CallFnOptions::new().eval_ast(false),
+                &mut Scope::new(),
+                &self.ast,
+                function,
+                args,
+            )
+            .map_err(|e| format!("{}: {}()", e, function))?;
+        let found = value.type_name();
+        value.try_cast().ok_or_else(|| {
+            format!(
+                "{}() renvoie {} au lieu de {}",
+                function,
+                found,
+                type_name::<T>().rsplit("::").next().unwrap_or_default()
+            )
+        })
+    }
+
+    fn eval<T: Any + Clone>(&self, function: &str, args: impl FuncArgs) -> T {
+        self.call(function, args)
+            .unwrap_or_else(|e| panic!("Script {}: {}", self.name, e))
+    }
+}
+
+#[derive(Clone)]
+pub struct ScriptState {
+    key: String,
+    value: Dynamic,
+}
+
+impl ScriptState {
+    fn new(value: Dynamic) -> Self {
+        ScriptState {
+            key: format!("{:?}", value),
+            value,
+        }
+    }
+}
+
+impl PartialEq for ScriptState {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl Eq for ScriptState {}
+
+impl Hash for ScriptState {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key.hash(state);
+    }
+}
+
+impl fmt::Debug for ScriptState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.key)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScriptAction {
+    label: String,
+    next: ScriptState,
+    cost: usize,
+}
+
+impl fmt::Display for ScriptAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.label)
+    }
+}
+
+fn to_cost(value: Dynamic) -> Result<usize, String> {
+    match value.as_int() {
+        Ok(cost) if cost >= 0 => Ok(cost as usize),
+        _ => Err(format!("coût invalide: {} (entier positif attendu)", value)),
+    }
+}
+
+fn to_action(index: usize, entry: Dynamic) -> Result<ScriptAction, String> {
+    let (label, next, cost) = if entry.is_map() {
+        let mut map = entry.cast::<rhai::Map>();
+        let next = map.remove("state").ok_or("successeur sans champ state")?;
+        let label = map.remove("action").map(|action| action.to_string());
+        let cost = map.remove("cost").map(to_cost).transpose()?;
+        (label, next, cost)
+    } else if entry.is_array() {
+        let mut parts = entry.cast::<rhai::Array>().into_iter();
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(label), Some(next), cost) => (
+                Some(label.to_string()),
+                next,
+                cost.map(to_cost).transpose()?,
+            ),
+            _ => return Err("successeur [action, état, coût?] attendu".to_string()),
+        }
+    } else {
+        return Err(format!("successeur invalide: {}", entry));
+    };
+    Ok(ScriptAction {
+        label: label.unwrap_or_else(|| index.to_string()),
+        next: ScriptState::new(next),
+        cost: cost.unwrap_or(1),
+    })
+}
+
+#[derive(Clone)]
+pub struct ScriptProblem {
+    program: Arc<ScriptProgram>,
+    initial: ScriptState,
+    heuristic: bool,
+}
+
+impl ScriptProblem {
+    pub fn new(program: Arc<ScriptProgram>, size: usize, seed: u64) -> Result<Self, String> {
+        let initial = if program.declares("initial_state", 2) {
+            program.call("initial_state", (size as rhai::INT, seed as rhai::INT))?
+        } else {
+            program.call("initial_state", ())?
+        };
+        Ok(ScriptProblem {
+            heuristic: program.declares("heuristic", 1),
+            initial: ScriptState::new(initial),
+            program,
+        })
+    }
+
+    fn try_successors(&self, state: &ScriptState) -> Result<Vec<ScriptAction>, String> {
+        let entries: rhai::Array = self.program.call("successors", (state.value.clone(),))?;
+        entries
+            .into_iter()
+            .enumerate()
+            .map(|(index, entry)| to_action(index, entry))
+            .collect()
+    }
+
+    fn script_successors(&self, state: &ScriptState) -> Vec<ScriptAction> {
+        self.try_successors(state)
+            .unwrap_or_else(|e| panic!("Script {}: {}", self.program.name, e))
+    }
+
+    fn check(&self) -> Result<(), String> {
+        self.program
+            .call::<bool>("is_goal", (self.initial.value.clone(),))?;
+        self.try_successors(&self.initial)?;
+        Ok(())
+    }
+}
+
+impl Problem for ScriptProblem {
+    type State = ScriptState;
+    type Action = ScriptAction;
+
+    fn initial_state(&self) -> Self::State {
+        self.initial.clone()
+    }
+
+    fn is_goal(&self, state: &Self::State) -> bool {
+        self.program.eval("is_goal", (state.value.clone(),))
+    }
+
+    fn actions(&self, state: &Self::State) -> ActionList<ScriptAction> {
+        self.script_successors(state).into_iter().collect()
+    }
+
+    fn apply(&self, _state: &Self::State, action: &ScriptAction) -> (Self::State, usize) {
+        (action.next.clone(), action.cost)
+    }
+
+    fn successors_into(&self, state: &Self::State, buffer: &mut Successors<Self>) {
+        buffer.clear();
+        for action in self.script_successors(state) {
+            let (next, cost) = (action.next.clone(), action.cost);
+            buffer.push((action, next, cost));
+        }
+    }
+
+    fn heuristic(&self, state: &Self::State) -> usize {
+        if !self.heuristic {
+            return 0;
+        }
+        let value: rhai::INT = self.program.eval("heuristic", (state.value.clone(),));
+        value.max(0) as usize
+    }
+
+    fn description(&self) -> String {
+        format!("Problème scripté {}", self.program.name)
+    }
+}
+
+impl BackwardProblem for ScriptProblem {
+    fn goal_states(&self) -> Vec<ScriptState> {
+        if !self.program.declares("goal_states", 0) {
+            return Vec::new();
+        }
+        let goals: rhai::Array = self.program.eval("goal_states", ());
+        goals.into_iter().map(ScriptState::new).collect()
+    }
+
+    fn predecessors(&self, state: &ScriptState) -> SuccessorList<ScriptState> {
+        if !self.program.declares("predecessors", 1) {
+            return SuccessorList::new();
+        }
+        let entries: rhai::Array = self.program.eval("predecessors", (state.value.clone(),));
+        entries
+            .into_iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                let action = to_action(index, entry)
+                    .unwrap_or_else(|e| panic!("Script {}: {}", self.program.name, e));
+                (action.next, action.cost)
+            })
+            .collect()
+    }
+}
+
+type ProgramCache = Mutex<HashMap<String, Arc<ScriptProgram>>>;
+
+pub fn load(path: &str) -> Result<Arc<ScriptProgram>, String> {
+    static CACHE: OnceLock<ProgramCache> = OnceLock::new();
+    if let Some(program) = CACHE
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap()
+        .get(path)
+    {
+        return Ok(Arc::clone(program));
+    }
+
+    let source = fs::read_to_string(path).map_err(|e| format!("Script {}: {}", path, e))?;
+    let program = Arc::new(
+        ScriptProgram::compile(path, &source).map_err(|e| format!("Script {}: {}", path, e))?,
+    );
+    CACHE
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap()
+        .insert(path.to_string(), Arc::clone(&program));
+    Ok(program)
+}
+
+pub struct ScriptFactory;
+
+impl ScriptFactory {
+    fn program(config: &BenchmarkConfig) -> Result<Arc<ScriptProgram>, String> {
+        let path = config
+            .problem_script
+            .as_deref()
+            .ok_or("--problem-script est requis (fichier Rhai)")?;
+        load(path)
+    }
+}
+
+impl ProblemFactory for ScriptFactory {
+    type Instance = ScriptProblem;
+
+    fn label(config: &BenchmarkConfig) -> String {
+        let name = config
+            .problem_script
+            .as_deref()
+            .and_then(|path| Path::new(path).file_stem())
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        format!("Script-{}-{}", name, config.size)
+    }
+
+    fn max_depth(config: &BenchmarkConfig) -> usize {
+        Self::program(config)
+            .ok()
+            .filter(|program| program.declares("max_depth", 1))
+            .and_then(|program| {
+                program
+                    .call::<rhai::INT>("max_depth", (config.size as rhai::INT,))
+                    .ok()
+            })
+            .map_or(DEFAULT_MAX_DEPTH, |depth| depth.max(0) as usize)
+    }
+
+    fn generate(config: &BenchmarkConfig, seed: u64) -> ScriptProblem {
+        let program = Self::program(config).expect("script vérifié par validate");
+        ScriptProblem::new(program, config.size, seed)
+            .unwrap_or_else(|e| panic!("Script {}: {}", Self::label(config), e))
+    }
+
+    fn validate(config: &BenchmarkConfig) -> Result<(), String> {
+        let program = Self::program(config)?;
+        if config.direction != Direction::Forward
+            && !(program.declares("goal_states", 0) && program.declares("predecessors", 1))
+        {
+            return Err(format!(
+                "Script {}: la recherche arrière nécessite goal_states() et predecessors(state)",
+                program.name
+            ));
+        }
+        ScriptProblem::new(Arc::clone(&program), config.size, config.seed)
+            .and_then(|problem| problem.check())
+            .map_err(|e| format!("Script {}: {}", program.name, e))
+    }
+
+    fn admissible_heuristic(config: &BenchmarkConfig) -> bool {
+        Self::program(config).is_ok_and(|program| !program.declares("heuristic", 1))
+    }
+
+    fn describe(instance: &ScriptProblem) -> String {
+        instance.initial.key.clone()
+    }
+
+    fn notes(config: &BenchmarkConfig) -> Vec<String> {
+        vec![format!(
+            "Problème interprété (Rhai): chaque test de but et chaque expansion appellent le script, profondeur max {}",
+            Self::max_depth(config)
+        )]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::astar::AStar;
+    use crate::algorithms::SearchAlgorithm;
+
+    const JUGS: &str = r#"
+        fn initial_state() { [0, 0] }
+        fn is_goal(state) { state[0] == 2 || state[1] == 2 }
+        fn successors(state) {
+            let a = state[0];
+            let b = state[1];
+            let pour_ab = min(a, 3 - b);
+            let pour_ba = min(b, 4 - a);
+            [
+                ["remplir A", [4, b]],
+                ["remplir B", [a, 3]],
+                ["vider A", [0, b]],
+                ["vider B", [a, 0]],
+                #{ action: "A -> B", state: [a - pour_ab, b + pour_ab], cost: 1 },
+                #{ action: "B -> A", state: [a + pour_ba, b - pour_ba] },
+            ]
+        }
+    "#;
+
+    #[test]
+    fn test_scripted_water_jugs() {
+        let program = Arc::new(ScriptProgram::compile("jugs", JUGS).unwrap());
+        let problem = ScriptProblem::new(program, 0, 0).unwrap();
+        problem.check().unwrap();
+        assert_eq!(problem.successors(&problem.initial_state()).len(), 6);
+
+        let result = AStar::new().search(&problem);
+        assert!(result.status.is_solved());
+        assert_eq!(result.solution.map(|actions| actions.len()), Some(4));
+
+        assert!(ScriptProgram::compile("vide", "fn is_goal(s) { true }").is_err());
+    }
+}
@@ -1,50 +1,371 @@
-use crate::algorithms::Problem;
+use super::registry::ProblemFactory;
+use crate::algorithms::{ActionList, BackwardProblem, Problem, SuccessorList, Successors};
+use crate::benchmarking::{BenchmarkConfig, Connectivity, InstanceProperties};
+use crate::utils::cost_maps::{self, CostMap};
+use crate::utils::graph_formats::{self, GraphData};
+use crate::utils::mazes;
+use crate::utils::seeding;
 use rand::Rng;
-use std::collections::HashMap;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Edge {
+    pub id: usize,
+    pub from: usize,
+    pub to: usize,
+}
+
+impl fmt::Display for Edge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "e{}:{}->{}", self.id, self.from, self.to)
+    }
+}
+
+struct Landmarks {
+    from: Vec<Vec<Option<usize>>>,
+    to: Vec<Vec<Option<usize>>>,
+}
+
+impl Landmarks {
+    fn lower_bound(&self, from: usize, to: usize) -> usize {
+        let distance = |table: &Vec<Option<usize>>, node: usize| table.get(node).copied().flatten();
+        let ahead = self
+            .from
+            .iter()
+            .filter_map(|table| Some(distance(table, to)?.saturating_sub(distance(table, from)?)));
+        let behind = self
+            .to
+            .iter()
+            .filter_map(|table| Some(distance(table, from)?.saturating_sub(distance(table, to)?)));
+        ahead.chain(behind).max().unwrap_or(0)
+    }
+}
 
 #[derive(Clone)]
 pub struct ShortestPath {
-    graph: HashMap<usize, Vec<(usize, usize)>>,
+    graph: HashMap<usize, Vec<usize>>,
+    reverse_graph: HashMap<usize, Vec<usize>>,
+    edges: Vec<(usize, usize, usize)>,
     start: usize,
+    extra_starts: Vec<usize>,
     goal: usize,
     heuristic_values: HashMap<usize, usize>,
+    negative_weights: HashMap<usize, i64>,
     seed: Option<u64>,
+    dimensions: Option<(usize, usize)>,
+    min_step_cost: usize,
+    source_graph: Option<Arc<GraphData>>,
+    landmarks: Option<Arc<Landmarks>>,
 }
 
 impl ShortestPath {
+    fn node_label(&self, node: usize) -> String {
+        match &self.source_graph {
+            Some(graph) => graph.labels[node].clone(),
+            None => node.to_string(),
+        }
+    }
+
+    fn starts_label(&self) -> String {
+        self.starts()
+            .map(|node| self.node_label(node))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
     pub fn initial_state_string(&self) -> String {
-        if let Some(seed) = self.seed {
-            format!("Seed: {}", seed)
-        } else {
-            format!("Start: {} -> Goal: {}", self.start, self.goal)
+        match self.seed {
+            Some(seed) if self.source_graph.is_none() => format!("Seed: {}", seed),
+            _ => format!(
+                "Start: {} -> Goal: {}",
+                self.starts_label(),
+                self.node_label(self.goal)
+            ),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut heuristic: Vec<(usize, usize)> = self
+            .heuristic_values
+            .iter()
+            .map(|(&n, &h)| (n, h))
+            .collect();
+        heuristic.sort_unstable();
+        let mut live: Vec<usize> = self.graph.values().flatten().copied().collect();
+        live.sort_unstable();
+        let edges: Vec<(usize, usize, usize)> = live.iter().map(|&id| self.edges[id]).collect();
+        let mut json = serde_json::json!({
+            "start": self.start,
+            "goal": self.goal,
+            "edges": edges,
+            "heuristic": heuristic,
+        });
+        if !self.extra_starts.is_empty() {
+            json["extra_starts"] = self.extra_starts.clone().into();
+        }
+        let negative: Vec<(usize, i64)> = live
+            .iter()
+            .enumerate()
+            .filter_map(|(index, id)| self.negative_weights.get(id).map(|&w| (index, w)))
+            .collect();
+        if !negative.is_empty() {
+            json["negative_weights"] = serde_json::json!(negative);
+        }
+        json.to_string()
+    }
+
+    pub fn reachable_from_start(&self) -> HashSet<usize> {
+        let mut reached: HashSet<usize> = self.starts().collect();
+        let mut stack: Vec<usize> = reached.iter().copied().collect();
+        while let Some(node) = stack.pop() {
+            for &id in self.graph.get(&node).into_iter().flatten() {
+                let to = self.edges[id].1;
+                if reached.insert(to) {
+                    stack.push(to);
+                }
+            }
+        }
+        reached
+    }
+
+    pub fn grid_dimensions(&self) -> Option<(usize, usize)> {
+        self.dimensions
+    }
+
+    pub fn is_blocked(&self, node: usize) -> bool {
+        !self.graph.contains_key(&node) && !self.reverse_graph.contains_key(&node)
+    }
+
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    pub fn starts(&self) -> impl Iterator<Item = usize> + '_ {
+        std::iter::once(self.start).chain(self.extra_starts.iter().copied())
+    }
+
+    pub fn set_start(&mut self, node: usize) {
+        self.start = node;
+    }
+
+    pub fn add_start(&mut self, node: usize) {
+        if node != self.start && !self.extra_starts.contains(&node) {
+            self.extra_starts.push(node);
+        }
+    }
+
+    pub fn goal(&self) -> usize {
+        self.goal
+    }
+
+    pub fn render_grid(
+        &self,
+        path: &[usize],
+        expanded: &HashSet<usize>,
+        generated: &HashSet<usize>,
+    ) -> Option<String> {
+        let (width, height) = self.dimensions?;
+        let path: HashSet<usize> = path.iter().copied().collect();
+        let starts: HashSet<usize> = self.starts().collect();
+        let mut rendered = String::new();
+
+        for row in 0..height {
+            let line: Vec<&str> = (0..width)
+                .map(|col| {
+                    let cell = row * width + col;
+                    if starts.contains(&cell) {
+                        "S"
+                    } else if cell == self.goal {
+                        "G"
+                    } else if path.contains(&cell) {
+                        "*"
+                    } else if expanded.contains(&cell) {
+                        "o"
+                    } else if generated.contains(&cell) {
+                        "+"
+                    } else if self.is_blocked(cell) {
+                        "#"
+                    } else {
+                        "."
+                    }
+                })
+                .collect();
+            rendered.push_str(&line.join(" "));
+            rendered.push('\n');
         }
+
+        Some(rendered)
     }
 
     pub fn new(start: usize, goal: usize) -> Self {
         ShortestPath {
             graph: HashMap::new(),
+            reverse_graph: HashMap::new(),
+            edges: Vec::new(),
             start,
+            extra_starts: Vec::new(),
             goal,
             heuristic_values: HashMap::new(),
+            negative_weights: HashMap::new(),
             seed: None,
+            dimensions: None,
+            min_step_cost: 0,
+            source_graph: None,
+            landmarks: None,
+        }
+    }
+
+    pub fn from_graph(graph: Arc<GraphData>, start: usize, goal: usize) -> Self {
+        let mut problem = ShortestPath::new(start, goal);
+        for &(from, to, cost) in &graph.edges {
+            problem.add_edge(from, to, cost);
         }
+        problem.source_graph = Some(graph);
+        problem
     }
 
     pub fn add_edge(&mut self, from: usize, to: usize, cost: usize) {
-        self.graph
-            .entry(from)
-            .or_insert_with(Vec::new)
-            .push((to, cost));
+        let id = self.edges.len();
+        self.landmarks = None;
+        self.edges.push((from, to, cost));
+        self.graph.entry(from).or_default().push(id);
+        self.reverse_graph.entry(to).or_default().push(id);
+    }
+
+    pub fn remove_edge(&mut self, from: usize, to: usize) -> bool {
+        let edges = &self.edges;
+        let Some(outgoing) = self.graph.get_mut(&from) else {
+            return false;
+        };
+        let before = outgoing.len();
+        outgoing.retain(|&id| edges[id].1 != to);
+        if outgoing.len() == before {
+            return false;
+        }
+        if let Some(incoming) = self.reverse_graph.get_mut(&to) {
+            incoming.retain(|&id| edges[id].0 != from);
+        }
+        true
+    }
+
+    pub fn set_edge_cost(&mut self, from: usize, to: usize, cost: usize) -> bool {
+        self.landmarks = None;
+        let mut found = false;
+        for &id in self.graph.get(&from).into_iter().flatten() {
+            if self.edges[id].1 == to {
+                self.edges[id].2 = cost;
+                self.negative_weights.remove(&id);
+                found = true;
+            }
+        }
+        found
+    }
+
+    pub fn add_signed_edge(&mut self, from: usize, to: usize, weight: i64) {
+        if weight < 0 {
+            self.negative_weights.insert(self.edges.len(), weight);
+        }
+        self.add_edge(from, to, weight.max(0) as usize);
+    }
+
+    pub fn negative_edges(&self) -> usize {
+        self.negative_weights.len()
+    }
+
+    pub fn negate_random_edges<R: Rng>(&mut self, fraction: f64, rng: &mut R) {
+        self.landmarks = None;
+        for id in 0..self.edges.len() {
+            if rng.gen_bool(fraction) {
+                self.negative_weights
+                    .insert(id, -(self.edges[id].2.max(1) as i64));
+                self.edges[id].2 = 0;
+            }
+        }
+    }
+
+    pub fn find_node(&self, label: &str) -> Option<usize> {
+        if let (Some((width, height)), Some((row, col))) = (self.dimensions, label.split_once(','))
+        {
+            let (row, col): (usize, usize) = (row.trim().parse().ok()?, col.trim().parse().ok()?);
+            return (row < height && col < width).then_some(row * width + col);
+        }
+        match &self.source_graph {
+            Some(graph) => graph.find(label),
+            None => label.parse().ok(),
+        }
     }
 
     pub fn set_heuristic(&mut self, node: usize, value: usize) {
         self.heuristic_values.insert(node, value);
     }
 
+    fn distances_from(&self, source: usize, reverse: bool) -> Vec<Option<usize>> {
+        let adjacency = if reverse {
+            &self.reverse_graph
+        } else {
+            &self.graph
+        };
+        let nodes = self
+            .edges
+            .iter()
+            .flat_map(|&(from, to, _)| [from, to])
+            .chain(self.starts())
+            .chain([self.goal, source])
+            .max()
+            .map_or(0, |node| node + 1);
+        let mut distances = vec![None; nodes];
+        let mut frontier = BinaryHeap::from([Reverse((0, source))]);
+        while let Some(Reverse((distance, node))) = frontier.pop() {
+            if distances[node].is_some() {
+                continue;
+            }
+            distances[node] = Some(distance);
+            for &id in adjacency.get(&node).into_iter().flatten() {
+                let (from, to, cost) = self.edges[id];
+                let next = if reverse { from } else { to };
+                if distances[next].is_none() {
+                    frontier.push(Reverse((distance + cost, next)));
+                }
+            }
+        }
+        distances
+    }
+
+    pub fn with_landmarks(mut self, count: usize) -> Self {
+        let mut closest = self.distances_from(self.start, false);
+        let (mut from, mut to) = (Vec::new(), Vec::new());
+        for _ in 0..count {
+            let Some(landmark) = (0..closest.len())
+                .filter(|&node| closest[node].is_some_and(|distance| distance > 0))
+                .max_by_key(|&node| closest[node])
+            else {
+                break;
+            };
+            let ahead = self.distances_from(landmark, false);
+            for (nearest, distance) in closest.iter_mut().zip(&ahead) {
+                if let (Some(nearest), Some(distance)) = (nearest.as_mut(), distance) {
+                    *nearest = (*nearest).min(*distance);
+                }
+            }
+            from.push(ahead);
+            to.push(self.distances_from(landmark, true));
+        }
+        self.landmarks = (!from.is_empty()).then(|| Arc::new(Landmarks { from, to }));
+        self
+    }
+
     pub fn generate_grid(width: usize, height: usize) -> Self {
-        let start = 0;
-        let goal = width * height - 1;
+        Self::generate_grid_between(width, height, 0, width * height - 1)
+    }
+
+    pub fn generate_grid_between(width: usize, height: usize, start: usize, goal: usize) -> Self {
         let mut graph = ShortestPath::new(start, goal);
+        graph.dimensions = Some((width, height));
+        graph.min_step_cost = 1;
 
         for row in 0..height {
             for col in 0..width {
@@ -76,6 +397,34 @@ impl ShortestPath {
         graph
     }
 
+    pub fn from_cost_map(map: &CostMap, start: usize, goal: usize) -> Self {
+        let (width, height) = (map.width, map.height);
+        let mut graph = ShortestPath::new(start, goal);
+        graph.dimensions = Some((width, height));
+        let min_cost = map.min_cost();
+        graph.min_step_cost = min_cost;
+
+        for current in map.open_cells() {
+            let (row, col) = (current / width, current % width);
+            let neighbors = [
+                (col + 1 < width).then(|| current + 1),
+                (row + 1 < height).then(|| current + width),
+                (col > 0).then(|| current - 1),
+                (row > 0).then(|| current - width),
+            ];
+            for next in neighbors.into_iter().flatten() {
+                if let Some(cost) = map.cost(next) {
+                    graph.add_edge(current, next, cost);
+                }
+            }
+
+            let h = row.abs_diff(goal / width) + col.abs_diff(goal % width);
+            graph.set_heuristic(current, h * min_cost);
+        }
+
+        graph
+    }
+
     pub fn generate_random(nodes: usize, edges: usize, start: usize, goal: usize) -> Self {
         Self::generate_random_with_seed(nodes, edges, start, goal, None)
     }
@@ -87,6 +436,28 @@ impl ShortestPath {
         goal: usize,
         seed: Option<u64>,
     ) -> Self {
+        Self::generate_random_graph(nodes, edges, start, goal, seed, false)
+    }
+
+    pub fn generate_connected_with_seed(
+        nodes: usize,
+        edges: usize,
+        start: usize,
+        goal: usize,
+        seed: u64,
+    ) -> Self {
+        Self::generate_random_graph(nodes, edges, start, goal, Some(seed), true)
+    }
+
+    fn generate_random_graph(
+        nodes: usize,
+        edges: usize,
+        start: usize,
+        goal: usize,
+        seed: Option<u64>,
+        with_path: bool,
+    ) -> Self {
+        use rand::seq::SliceRandom;
         use rand::SeedableRng;
 
         let mut graph = ShortestPath::new(start, goal);
@@ -98,7 +469,22 @@ impl ShortestPath {
             Box::new(rand::thread_rng())
         };
 
-        for _ in 0..edges {
+        let mut path_edges = 0;
+        if with_path && start != goal {
+            let mut intermediates: Vec<usize> =
+                (0..nodes).filter(|&n| n != start && n != goal).collect();
+            intermediates.shuffle(&mut rng);
+            intermediates.truncate(rng.gen_range(0..=intermediates.len()));
+
+            let mut from = start;
+            for to in intermediates.into_iter().chain(std::iter::once(goal)) {
+                graph.add_edge(from, to, rng.gen_range(1..10));
+                from = to;
+                path_edges += 1;
+            }
+        }
+
+        for _ in path_edges..edges {
             let from = rng.gen_range(0..nodes);
             let to = rng.gen_range(0..nodes);
             let cost = rng.gen_range(1..10);
@@ -123,33 +509,681 @@ impl ShortestPath {
 
 impl Problem for ShortestPath {
     type State = usize;
+    type Action = Edge;
 
     fn initial_state(&self) -> Self::State {
         self.start
     }
 
+    fn initial_states(&self) -> Vec<Self::State> {
+        self.starts().collect()
+    }
+
     fn is_goal(&self, state: &Self::State) -> bool {
         *state == self.goal
     }
 
-    fn successors(&self, state: &Self::State) -> Vec<(Self::State, usize)> {
-        self.graph.get(state).cloned().unwrap_or_default()
+    fn actions(&self, state: &Self::State) -> ActionList<Edge> {
+        self.graph
+            .get(state)
+            .map(|ids| {
+                ids.iter()
+                    .map(|&id| Edge {
+                        id,
+                        from: *state,
+                        to: self.edges[id].1,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn apply(&self, _state: &Self::State, action: &Edge) -> (Self::State, usize) {
+        let (_, to, cost) = self.edges[action.id];
+        (to, cost)
+    }
+
+    fn successors_into(&self, state: &Self::State, buffer: &mut Successors<Self>) {
+        buffer.clear();
+        if let Some(ids) = self.graph.get(state) {
+            for &id in ids {
+                let (from, to, cost) = self.edges[id];
+                buffer.push((Edge { id, from, to }, to, cost));
+            }
+        }
     }
 
     fn heuristic(&self, state: &Self::State) -> usize {
         *self.heuristic_values.get(state).unwrap_or(&0)
     }
 
+    fn signed_cost(&self, _state: &Self::State, action: &Edge) -> i64 {
+        match self.negative_weights.get(&action.id) {
+            Some(&weight) => weight,
+            None => self.edges[action.id].2 as i64,
+        }
+    }
+
     fn description(&self) -> String {
         format!(
             "Plus court chemin: {} nœuds, de {} à {}",
             self.graph.len(),
-            self.start,
-            self.goal
+            self.starts_label(),
+            self.node_label(self.goal)
         )
     }
 }
 
+impl BackwardProblem for ShortestPath {
+    fn goal_states(&self) -> Vec<usize> {
+        vec![self.goal]
+    }
+
+    fn predecessors(&self, state: &usize) -> SuccessorList<usize> {
+        self.reverse_graph
+            .get(state)
+            .into_iter()
+            .flatten()
+            .map(|&id| {
+                let (from, _, cost) = self.edges[id];
+                (from, cost)
+            })
+            .collect()
+    }
+
+    fn heuristic_between(&self, from: &usize, to: &usize) -> usize {
+        match self.dimensions {
+            Some((width, _)) => {
+                let rows = (from / width).abs_diff(to / width);
+                let cols = (from % width).abs_diff(to % width);
+                (rows + cols) * self.min_step_cost
+            }
+            None => match &self.landmarks {
+                Some(landmarks) => landmarks.lower_bound(*from, *to),
+                None if *to == self.goal => self.heuristic(from),
+                None => 0,
+            },
+        }
+    }
+}
+
+pub struct GridFactory;
+
+impl ProblemFactory for GridFactory {
+    type Instance = ShortestPath;
+
+    fn label(config: &BenchmarkConfig) -> String {
+        format!(
+            "ShortestPath-{}x{}{}",
+            config.size,
+            config.size,
+            sources_suffix(config)
+        )
+    }
+
+    fn max_depth(config: &BenchmarkConfig) -> usize {
+        (config.size * config.size).min(500)
+    }
+
+    fn generate(config: &BenchmarkConfig, seed: u64) -> ShortestPath {
+        use rand::SeedableRng;
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+        let cells = config.size * config.size;
+        let start = rng.gen_range(0..cells);
+        let goal = if cells > 1 {
+            (start + rng.gen_range(1..cells)) % cells
+        } else {
+            start
+        };
+        let mut grid = ShortestPath::generate_grid_between(config.size, config.size, start, goal);
+        add_random_starts(&mut grid, config.sources, 0..cells, &mut rng);
+        grid
+    }
+
+    fn validate(config: &BenchmarkConfig) -> Result<(), String> {
+        if config.sources >= config.size * config.size {
+            return Err(format!(
+                "--sources {}: au plus {} départs sur une grille {}x{}",
+                config.sources,
+                (config.size * config.size).saturating_sub(1),
+                config.size,
+                config.size
+            ));
+        }
+        Ok(())
+    }
+
+    fn properties(instance: &ShortestPath) -> InstanceProperties {
+        sources_properties(instance)
+    }
+
+    fn unit_costs(_config: &BenchmarkConfig) -> bool {
+        true
+    }
+
+    fn describe(instance: &ShortestPath) -> String {
+        instance.initial_state_string()
+    }
+
+    fn serialize(instance: &ShortestPath) -> String {
+        instance.to_json()
+    }
+
+    fn notes(config: &BenchmarkConfig) -> Vec<String> {
+        if config.size > 20 {
+            vec![format!(
+                "Note: ID peut être lent sur grandes grilles (profondeur max: {})",
+                Self::max_depth(config)
+            )]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+pub struct MazeFactory;
+
+impl MazeFactory {
+    fn open_cells(config: &BenchmarkConfig) -> usize {
+        2 * config.size * config.size - 1
+    }
+}
+
+impl ProblemFactory for MazeFactory {
+    type Instance = ShortestPath;
+
+    fn label(config: &BenchmarkConfig) -> String {
+        format!(
+            "Maze-{}x{}-{}{}",
+            config.size,
+            config.size,
+            config.maze_generator,
+            sources_suffix(config)
+        )
+    }
+
+    fn max_depth(config: &BenchmarkConfig) -> usize {
+        Self::open_cells(config)
+    }
+
+    fn generate(config: &BenchmarkConfig, seed: u64) -> ShortestPath {
+        use rand::SeedableRng;
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+        let map = mazes::generate(config.size, config.maze_generator, &mut rng);
+        let (start, goal) = (map.start.unwrap_or(0), map.goal.unwrap_or(0));
+        let mut maze = ShortestPath::from_cost_map(&map, start, goal);
+        maze.seed = Some(seed);
+        add_random_starts(&mut maze, config.sources, map.open_cells(), &mut rng);
+        maze
+    }
+
+    fn validate(config: &BenchmarkConfig) -> Result<(), String> {
+        if config.size < 2 {
+            return Err(format!(
+                "Taille de labyrinthe invalide: {} (au moins 2 cellules de côté)",
+                config.size
+            ));
+        }
+        if config.sources >= Self::open_cells(config) {
+            return Err(format!(
+                "--sources {}: au plus {} départs dans un labyrinthe {}x{}",
+                config.sources,
+                Self::open_cells(config) - 1,
+                config.size,
+                config.size
+            ));
+        }
+        Ok(())
+    }
+
+    fn properties(instance: &ShortestPath) -> InstanceProperties {
+        let dead_ends = instance
+            .graph
+            .values()
+            .filter(|edges| edges.len() == 1)
+            .count();
+        let mut properties =
+            InstanceProperties::from([("dead_ends".to_string(), dead_ends.into())]);
+        properties.extend(sources_properties(instance));
+        properties
+    }
+
+    fn unit_costs(_config: &BenchmarkConfig) -> bool {
+        true
+    }
+
+    fn describe(instance: &ShortestPath) -> String {
+        instance
+            .render_grid(&[], &HashSet::new(), &HashSet::new())
+            .unwrap_or_else(|| instance.initial_state_string())
+    }
+
+    fn serialize(instance: &ShortestPath) -> String {
+        instance.to_json()
+    }
+
+    fn notes(config: &BenchmarkConfig) -> Vec<String> {
+        vec![format!(
+            "Labyrinthe parfait {}x{} ({}): chemin unique entre deux cases, heuristique Manhattan",
+            config.size, config.size, config.maze_generator
+        )]
+    }
+}
+
+const MAX_RESAMPLES: usize = 64;
+const LANDMARKS: usize = 4;
+
+fn sources_suffix(config: &BenchmarkConfig) -> String {
+    if config.sources > 1 {
+        format!("-{}sources", config.sources)
+    } else {
+        String::new()
+    }
+}
+
+fn add_random_starts<R: Rng>(
+    problem: &mut ShortestPath,
+    sources: usize,
+    nodes: impl IntoIterator<Item = usize>,
+    rng: &mut R,
+) {
+    let mut nodes: Vec<usize> = nodes
+        .into_iter()
+        .filter(|&node| node != problem.goal && node != problem.start)
+        .collect();
+    while problem.starts().count() < sources && !nodes.is_empty() {
+        let node = nodes.swap_remove(rng.gen_range(0..nodes.len()));
+        problem.add_start(node);
+    }
+}
+
+fn sources_properties(instance: &ShortestPath) -> InstanceProperties {
+    if instance.extra_starts.is_empty() {
+        return InstanceProperties::new();
+    }
+    InstanceProperties::from([
+        (
+            "sources".to_string(),
+            (instance.extra_starts.len() + 1).into(),
+        ),
+        ("source".to_string(), instance.starts_label().into()),
+    ])
+}
+
+pub struct RandomGraphFactory;
+
+impl ProblemFactory for RandomGraphFactory {
+    type Instance = ShortestPath;
+
+    fn label(config: &BenchmarkConfig) -> String {
+        format!("ShortestPath-Random-{}", config.size)
+    }
+
+    fn max_depth(config: &BenchmarkConfig) -> usize {
+        config.size
+    }
+
+    fn generate(config: &BenchmarkConfig, seed: u64) -> ShortestPath {
+        use rand::SeedableRng;
+
+        let nodes = config.size;
+        let edges = nodes * 3;
+        let mut graph = match config.connectivity {
+            Connectivity::Path => {
+                ShortestPath::generate_connected_with_seed(nodes, edges, 0, nodes - 1, seed)
+            }
+            Connectivity::Resample => {
+                let mut seed = seed;
+                let mut graph =
+                    ShortestPath::generate_random_with_seed(nodes, edges, 0, nodes - 1, Some(seed));
+                for _ in 0..MAX_RESAMPLES {
+                    if graph.reachable_from_start().contains(&graph.goal) {
+                        break;
+                    }
+                    seed = seeding::reseed(seed);
+                    graph = ShortestPath::generate_random_with_seed(
+                        nodes,
+                        edges,
+                        0,
+                        nodes - 1,
+                        Some(seed),
+                    );
+                }
+                graph
+            }
+            Connectivity::None => {
+                ShortestPath::generate_random_with_seed(nodes, edges, 0, nodes - 1, Some(seed))
+            }
+        };
+        if config.negative_edges > 0.0 {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(seeding::reseed(seed));
+            graph.negate_random_edges(config.negative_edges, &mut rng);
+        }
+        graph.with_landmarks(LANDMARKS)
+    }
+
+    fn validate(config: &BenchmarkConfig) -> Result<(), String> {
+        if config.size < 2 {
+            return Err(format!(
+                "--size {}: le graphe aléatoire doit compter au moins 2 nœuds",
+                config.size
+            ));
+        }
+        if !(0.0..=1.0).contains(&config.negative_edges) {
+            return Err(format!(
+                "--negative-edges attend une fraction entre 0 et 1 (reçu: {})",
+                config.negative_edges
+            ));
+        }
+        Ok(())
+    }
+
+    fn properties(instance: &ShortestPath) -> InstanceProperties {
+        let reached = instance.reachable_from_start();
+        let mut properties = InstanceProperties::from([
+            (
+                "goal_reachable".to_string(),
+                reached.contains(&instance.goal).into(),
+            ),
+            ("reachable_nodes".to_string(), reached.len().into()),
+        ]);
+        if instance.negative_edges() > 0 {
+            properties.insert(
+                "negative_edges".to_string(),
+                instance.negative_edges().into(),
+            );
+        }
+        properties
+    }
+
+    fn admissible_heuristic(_config: &BenchmarkConfig) -> bool {
+        false
+    }
+
+    fn describe(instance: &ShortestPath) -> String {
+        instance.initial_state_string()
+    }
+
+    fn serialize(instance: &ShortestPath) -> String {
+        instance.to_json()
+    }
+
+    fn notes(config: &BenchmarkConfig) -> Vec<String> {
+        vec![format!(
+            "Configuration: {} nœuds, ~{} arêtes par graphe",
+            config.size,
+            config.size * 3
+        )]
+    }
+}
+
+pub struct GraphFileFactory;
+
+impl GraphFileFactory {
+    fn graph(config: &BenchmarkConfig) -> Result<Arc<GraphData>, String> {
+        let path = config
+            .graph
+            .as_deref()
+            .ok_or("--graph est requis (GraphML, liste d'arêtes CSV ou adjacence JSON)")?;
+        graph_formats::load(path, config.undirected)
+    }
+
+    fn source_labels(config: &BenchmarkConfig) -> Vec<&str> {
+        config
+            .source
+            .as_deref()
+            .into_iter()
+            .flat_map(|sources| sources.split(','))
+            .map(str::trim)
+            .filter(|label| !label.is_empty())
+            .collect()
+    }
+
+    fn endpoint(graph: &GraphData, label: Option<&str>, flag: &str) -> Result<(), String> {
+        match label {
+            Some(label) if graph.find(label).is_none() => {
+                Err(format!("{} {}: nœud absent du graphe", flag, label))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl ProblemFactory for GraphFileFactory {
+    type Instance = ShortestPath;
+
+    fn label(config: &BenchmarkConfig) -> String {
+        let name = config
+            .graph
+            .as_deref()
+            .and_then(|path| Path::new(path).file_stem())
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        format!("ShortestPath-File-{}{}", name, sources_suffix(config))
+    }
+
+    fn max_depth(config: &BenchmarkConfig) -> usize {
+        Self::graph(config).map_or(0, |graph| graph.labels.len())
+    }
+
+    fn generate(config: &BenchmarkConfig, seed: u64) -> ShortestPath {
+        use rand::SeedableRng;
+
+        let graph = Self::graph(config).expect("graphe vérifié par validate");
+        let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+        let sources: Vec<usize> = Self::source_labels(config)
+            .into_iter()
+            .filter_map(|label| graph.find(label))
+            .collect();
+        let start = match sources.first() {
+            Some(&start) => start,
+            None => rng.gen_range(0..graph.labels.len()),
+        };
+        let mut problem = ShortestPath::from_graph(Arc::clone(&graph), start, start);
+        for &source in sources.iter().skip(1) {
+            problem.add_start(source);
+        }
+        let target = config.target.as_deref().and_then(|label| graph.find(label));
+        if let Some(goal) = target {
+            problem.goal = goal;
+        }
+        add_random_starts(
+            &mut problem,
+            config.sources,
+            0..graph.labels.len(),
+            &mut rng,
+        );
+        problem.goal = match target {
+            Some(goal) => goal,
+            None => {
+                let starts: HashSet<usize> = problem.starts().collect();
+                let mut reached: Vec<usize> = problem
+                    .reachable_from_start()
+                    .into_iter()
+                    .filter(|node| !starts.contains(node))
+                    .collect();
+                reached.sort_unstable();
+                if reached.is_empty() {
+                    start
+                } else {
+                    reached[rng.gen_range(0..reached.len())]
+                }
+            }
+        };
+        problem
+    }
+
+    fn validate(config: &BenchmarkConfig) -> Result<(), String> {
+        let graph = Self::graph(config)?;
+        for label in Self::source_labels(config) {
+            Self::endpoint(&graph, Some(label), "--source")?;
+        }
+        if config.sources >= graph.labels.len() {
+            return Err(format!(
+                "--sources {}: le graphe n'a que {} nœuds",
+                config.sources,
+                graph.labels.len()
+            ));
+        }
+        Self::endpoint(&graph, config.target.as_deref(), "--target")
+    }
+
+    fn properties(instance: &ShortestPath) -> InstanceProperties {
+        let mut properties = InstanceProperties::from([(
+            "goal_reachable".to_string(),
+            instance
+                .reachable_from_start()
+                .contains(&instance.goal)
+                .into(),
+        )]);
+        properties.extend(sources_properties(instance));
+        if let Some(graph) = &instance.source_graph {
+            properties
+                .entry("source".to_string())
+                .or_insert_with(|| graph.labels[instance.start].clone().into());
+            properties.insert(
+                "target".to_string(),
+                graph.labels[instance.goal].clone().into(),
+            );
+        }
+        properties
+    }
+
+    fn unit_costs(config: &BenchmarkConfig) -> bool {
+        Self::graph(config).is_ok_and(|graph| graph.edges.iter().all(|&(_, _, cost)| cost == 1))
+    }
+
+    fn describe(instance: &ShortestPath) -> String {
+        instance.initial_state_string()
+    }
+
+    fn serialize(instance: &ShortestPath) -> String {
+        instance.to_json()
+    }
+
+    fn notes(config: &BenchmarkConfig) -> Vec<String> {
+        match Self::graph(config) {
+            Ok(graph) => vec![format!(
+                "Graphe: {} nœuds, {} arêtes (heuristique nulle: A* se comporte comme Dijkstra)",
+                graph.labels.len(),
+                graph.edges.len()
+            )],
+            Err(_) => Vec::new(),
+        }
+    }
+}
+
+pub struct CostMapFactory;
+
+impl CostMapFactory {
+    fn map(config: &BenchmarkConfig) -> Result<Arc<CostMap>, String> {
+        let path = config
+            .map
+            .as_deref()
+            .ok_or("--map est requis (carte ASCII ou PNG en niveaux de gris)")?;
+        cost_maps::load(path)
+    }
+}
+
+impl ProblemFactory for CostMapFactory {
+    type Instance = ShortestPath;
+
+    fn label(config: &BenchmarkConfig) -> String {
+        let name = config
+            .map
+            .as_deref()
+            .and_then(|path| Path::new(path).file_stem())
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        format!("ShortestPath-Map-{}{}", name, sources_suffix(config))
+    }
+
+    fn max_depth(config: &BenchmarkConfig) -> usize {
+        Self::map(config).map_or(0, |map| map.open_cells().count())
+    }
+
+    fn generate(config: &BenchmarkConfig, seed: u64) -> ShortestPath {
+        use rand::SeedableRng;
+
+        let map = Self::map(config).expect("carte vérifiée par validate");
+        let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+        let open: Vec<usize> = map.open_cells().collect();
+        let start = map
+            .start
+            .unwrap_or_else(|| open[rng.gen_range(0..open.len())]);
+        let mut problem = ShortestPath::from_cost_map(&map, start, map.goal.unwrap_or(start));
+        add_random_starts(&mut problem, config.sources, open, &mut rng);
+        if map.goal.is_none() {
+            let starts: HashSet<usize> = problem.starts().collect();
+            let mut reached: Vec<usize> = problem
+                .reachable_from_start()
+                .into_iter()
+                .filter(|node| !starts.contains(node))
+                .collect();
+            reached.sort_unstable();
+            if !reached.is_empty() {
+                let goal = reached[rng.gen_range(0..reached.len())];
+                let extra_starts = std::mem::take(&mut problem.extra_starts);
+                problem = ShortestPath::from_cost_map(&map, start, goal);
+                problem.extra_starts = extra_starts;
+            }
+        }
+        problem
+    }
+
+    fn validate(config: &BenchmarkConfig) -> Result<(), String> {
+        let map = Self::map(config)?;
+        let open = map.open_cells().count();
+        if config.sources >= open {
+            return Err(format!(
+                "--sources {}: la carte n'a que {} cases praticables",
+                config.sources, open
+            ));
+        }
+        Ok(())
+    }
+
+    fn properties(instance: &ShortestPath) -> InstanceProperties {
+        let mut properties = InstanceProperties::from([(
+            "goal_reachable".to_string(),
+            instance
+                .reachable_from_start()
+                .contains(&instance.goal)
+                .into(),
+        )]);
+        properties.extend(sources_properties(instance));
+        properties
+    }
+
+    fn unit_costs(config: &BenchmarkConfig) -> bool {
+        Self::map(config).is_ok_and(|map| map.max_cost() == 1)
+    }
+
+    fn describe(instance: &ShortestPath) -> String {
+        instance.initial_state_string()
+    }
+
+    fn serialize(instance: &ShortestPath) -> String {
+        instance.to_json()
+    }
+
+    fn notes(config: &BenchmarkConfig) -> Vec<String> {
+        match Self::map(config) {
+            Ok(map) => vec![format!(
+                "Carte: {}x{}, {} cases praticables, coûts {} à {} (heuristique: Manhattan × coût minimal)",
+                map.width,
+                map.height,
+                map.open_cells().count(),
+                map.min_cost(),
+                map.max_cost()
+            )],
+            Err(_) => Vec::new(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -161,10 +1195,208 @@ mod tests {
         assert_eq!(graph.goal, 8);
     }
 
+    #[test]
+    fn test_render_grid_overlays() {
+        let graph = ShortestPath::generate_grid(3, 3);
+        let expanded = HashSet::from([0, 1, 4]);
+        let generated = HashSet::from([1, 2, 3, 4, 5, 7]);
+        let rendered = graph.render_grid(&[1, 4, 7, 8], &expanded, &generated);
+
+        assert_eq!(rendered.as_deref(), Some("S * +\n+ * +\n. * G\n"));
+        assert_eq!(
+            ShortestPath::new(0, 1).render_grid(&[], &expanded, &generated),
+            None
+        );
+    }
+
     #[test]
     fn test_successors() {
         let graph = ShortestPath::generate_grid(3, 3);
         let successors = graph.successors(&4);
         assert_eq!(successors.len(), 4);
     }
+
+    #[test]
+    fn test_edge_actions() {
+        let mut graph = ShortestPath::new(0, 2);
+        graph.add_edge(0, 1, 4);
+        graph.add_edge(0, 2, 9);
+
+        let actions = graph.actions(&0);
+        assert_eq!(actions.len(), 2);
+        assert_eq!(graph.apply(&0, &actions[1]), (2, 9));
+        assert_eq!(actions[1].to_string(), "e1:0->2");
+    }
+
+    #[test]
+    fn test_cost_map_avoids_expensive_terrain() {
+        use crate::algorithms::{astar::AStar, bfs::BFS, SearchAlgorithm};
+
+        let map = cost_maps::parse_ascii("S9G\n...\n").unwrap();
+        let problem = ShortestPath::from_cost_map(&map, 0, 2);
+        assert!(!problem.is_blocked(5) && problem.heuristic(&0) == 2);
+        let result = AStar::new().search(&problem);
+        assert_eq!(result.metrics.solution_cost, 4);
+        assert_eq!(result.metrics.solution_length, 4);
+        assert_eq!(BFS::new().search(&problem).metrics.solution_cost, 10);
+
+        let map = cost_maps::parse_ascii("S#G\n3#.\n").unwrap();
+        let problem = ShortestPath::from_cost_map(&map, 0, 2);
+        assert!(problem.is_blocked(1));
+        assert!(!problem.reachable_from_start().contains(&2));
+    }
+
+    #[test]
+    fn test_graph_file_endpoints() {
+        let path = std::env::temp_dir().join("benchmark_graph_file_test.csv");
+        std::fs::write(&path, "a,b,2\nb,c,3\nc,d,1\n").unwrap();
+        let mut config = BenchmarkConfig {
+            problem: "shortest-path-file".to_string(),
+            graph: Some(path.to_string_lossy().into_owned()),
+            source: Some("b".to_string()),
+            ..BenchmarkConfig::default()
+        };
+        assert!(GraphFileFactory::validate(&config).is_ok());
+        for seed in 0..10 {
+            let problem = GraphFileFactory::generate(&config, seed);
+            assert_eq!(problem.start, 1);
+            assert!(matches!(problem.goal, 2 | 3));
+        }
+
+        config.target = Some("a".to_string());
+        let problem = GraphFileFactory::generate(&config, 0);
+        assert_eq!(problem.initial_state_string(), "Start: b -> Goal: a");
+        assert!(!problem.reachable_from_start().contains(&problem.goal));
+        config.target = Some("z".to_string());
+        assert!(GraphFileFactory::validate(&config).is_err());
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_multi_source_search_starts_from_nearest() {
+        use crate::algorithms::validation::validate_solution;
+        use crate::algorithms::{
+            astar::AStar, bfs::BFS, dfs::DFS, idastar::IDAStar,
+            iterative_deepening::IterativeDeepening, reference, SearchAlgorithm,
+        };
+
+        let mut graph = ShortestPath::new(0, 3);
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(1, 2, 1);
+        graph.add_edge(2, 3, 1);
+        graph.add_edge(5, 4, 1);
+        graph.add_edge(4, 3, 1);
+        graph.add_start(5);
+        graph.add_start(5);
+        assert_eq!(graph.initial_states(), vec![0, 5]);
+        assert_eq!(graph.initial_state_string(), "Start: 0,5 -> Goal: 3");
+        assert_eq!(reference::optimal_cost(&graph, false), Some(2));
+
+        let results = [
+            BFS::new().search(&graph),
+            BFS::with_memory_limit(1024).search(&graph),
+            AStar::new().search(&graph),
+            AStar::with_memory_limit(1024).search(&graph),
+            IDAStar::new(10).search(&graph),
+            IterativeDeepening::new(10).search(&graph),
+        ];
+        for result in results {
+            let solution = result.solution.unwrap();
+            assert_eq!(solution.first().map(|edge| edge.from), Some(5));
+            assert_eq!(result.metrics.solution_cost, 2);
+            assert!(validate_solution(&graph, &solution, &result.metrics).is_ok());
+        }
+        let dfs = DFS::new().search(&graph);
+        assert!(validate_solution(&graph, &dfs.solution.unwrap(), &dfs.metrics).is_ok());
+    }
+
+    fn reload(json: &str) -> ShortestPath {
+        #[derive(serde::Deserialize)]
+        struct Serialized {
+            start: usize,
+            goal: usize,
+            edges: Vec<(usize, usize, usize)>,
+            heuristic: Vec<(usize, usize)>,
+            #[serde(default)]
+            extra_starts: Vec<usize>,
+            #[serde(default)]
+            negative_weights: Vec<(usize, i64)>,
+        }
+
+        let data: Serialized = serde_json::from_str(json).unwrap();
+        let mut graph = ShortestPath::new(data.start, data.goal);
+        for (from, to, cost) in data.edges {
+            graph.add_edge(from, to, cost);
+        }
+        for (node, value) in data.heuristic {
+            graph.set_heuristic(node, value);
+        }
+        for node in data.extra_starts {
+            graph.add_start(node);
+        }
+        graph.negative_weights.extend(data.negative_weights);
+        graph
+    }
+
+    #[test]
+    fn test_removed_edges_do_not_survive_json_round_trip() {
+        let mut graph = ShortestPath::new(0, 3);
+        graph.add_edge(0, 1, 1);
+        graph.add_signed_edge(1, 3, -2);
+        graph.add_edge(0, 2, 4);
+        graph.add_signed_edge(2, 3, -1);
+        graph.add_start(2);
+        graph.set_heuristic(2, 1);
+        assert!(graph.remove_edge(0, 1));
+
+        let reloaded = reload(&graph.to_json());
+        assert_eq!(reloaded.to_json(), graph.to_json());
+        assert_eq!(reloaded.edges.len(), 3);
+        assert!(!reloaded.successors(&0).iter().any(|&(to, _)| to == 1));
+        assert_eq!(reloaded.negative_edges(), 2);
+        assert_eq!(reloaded.initial_states(), vec![0, 2]);
+        assert_eq!(reloaded.heuristic(&2), 1);
+    }
+
+    #[test]
+    fn test_landmark_bounds_are_admissible_between_any_nodes() {
+        use crate::algorithms::backward::Backward;
+        use crate::algorithms::bidirectional::BidirectionalAStar;
+        use crate::algorithms::uniform_cost::UniformCost;
+        use crate::algorithms::{astar::AStar, SearchAlgorithm};
+        use crate::benchmarking::MetricsRecorder;
+
+        for seed in 0..10 {
+            let graph = ShortestPath::generate_connected_with_seed(40, 120, 0, 39, seed);
+            let graph = graph.with_landmarks(4);
+            for from in 0..40 {
+                let exact = graph.distances_from(from, false);
+                for (to, distance) in exact.iter().enumerate() {
+                    if let Some(distance) = distance {
+                        assert!(graph.heuristic_between(&from, &to) <= *distance);
+                    }
+                }
+            }
+
+            let reference = UniformCost::new().search(&graph);
+            let bidirectional =
+                BidirectionalAStar::new().search_with_recorder(&graph, MetricsRecorder::new());
+            let backward = Backward::new(&graph);
+            let regression = backward.into_forward(AStar::new().search(&backward));
+            for result in [bidirectional, regression] {
+                assert_eq!(
+                    result.metrics.solution_cost,
+                    reference.metrics.solution_cost
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_connected_graph_reaches_goal() {
+        for seed in 0..20 {
+            let graph = ShortestPath::generate_connected_with_seed(30, 30, 0, 29, seed);
+            assert!(graph.reachable_from_start().contains(&29));
+        }
+    }
 }
@@ -0,0 +1,395 @@
+use super::registry::ProblemFactory;
+use crate::algorithms::{
+    ActionList, BackwardProblem, ExternalCalls, Problem, SuccessorList, Successors,
+};
+use crate::benchmarking::{BenchmarkConfig, Direction};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+const DEFAULT_MAX_DEPTH: usize = 50;
+
+struct Connection {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl Connection {
+    fn spawn(command: &str) -> Result<Self, String> {
+        let mut parts = command.split_whitespace();
+        let program = parts.next().ok_or("commande vide")?;
+        let mut child = Command::new(program)
+            .args(parts)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|e| format!("lancement de {}: {}", command, e))?;
+        let stdin = child.stdin.take().ok_or("stdin indisponible")?;
+        let stdout = child.stdout.take().ok_or("stdout indisponible")?;
+        Ok(Connection {
+            child,
+            stdin,
+            stdout: BufReader::new(stdout),
+        })
+    }
+
+    fn exchange(&mut self, request: &str) -> Result<Value, String> {
+        writeln!(self.stdin, "{}", request)
+            .and_then(|_| self.stdin.flush())
+            .map_err(|e| format!("écriture: {}", e))?;
+        let mut line = String::new();
+        match self.stdout.read_line(&mut line) {
+            Ok(0) => Err("le processus s'est arrêté".to_string()),
+            Ok(_) => serde_json::from_str(&line).map_err(|e| format!("réponse invalide: {}", e)),
+            Err(e) => Err(format!("lecture: {}", e)),
+        }
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+type ConnectionPool = Mutex<HashMap<String, Vec<Connection>>>;
+
+thread_local! {
+    static CALLS: Cell<(usize, Duration)> = const { Cell::new((0, Duration::ZERO)) };
+}
+
+fn pool() -> &'static ConnectionPool {
+    static POOL: OnceLock<ConnectionPool> = OnceLock::new();
+    POOL.get_or_init(Default::default)
+}
+
+fn request(command: &str, message: Value) -> Result<Value, String> {
+    let idle = pool()
+        .lock()
+        .unwrap()
+        .get_mut(command)
+        .and_then(|connections| connections.pop());
+    let mut connection = match idle {
+        Some(connection) => connection,
+        None => Connection::spawn(command)?,
+    };
+
+    let start = Instant::now();
+    let response = connection.exchange(&message.to_string());
+    CALLS.with(|calls| {
+        let (count, elapsed) = calls.get();
+        calls.set((count + 1, elapsed + start.elapsed()));
+    });
+    if response.is_ok() {
+        pool()
+            .lock()
+            .unwrap()
+            .entry(command.to_string())
+            .or_default()
+            .push(connection);
+    }
+
+    let mut response = response?;
+    match response.get("error") {
+        Some(error) => Err(format!("{}: {}", message["op"], error)),
+        None => Ok(response.take()),
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct ExternalState(String);
+
+impl ExternalState {
+    fn new(value: &Value) -> Self {
+        ExternalState(value.to_string())
+    }
+
+    fn value(&self) -> Value {
+        serde_json::from_str(&self.0).expect("état sérialisé par serde_json")
+    }
+}
+
+impl fmt::Debug for ExternalState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExternalAction {
+    label: String,
+    next: ExternalState,
+    cost: usize,
+}
+
+impl fmt::Display for ExternalAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.label)
+    }
+}
+
+#[derive(Deserialize)]
+struct InitResponse {
+    state: Value,
+    #[serde(default)]
+    heuristic: bool,
+    #[serde(default)]
+    admissible: bool,
+    max_depth: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct Successor {
+    action: Option<Value>,
+    state: Value,
+    #[serde(default = "unit_cost")]
+    cost: usize,
+}
+
+fn unit_cost() -> usize {
+    1
+}
+
+#[derive(Clone)]
+pub struct SubprocessProblem {
+    command: Arc<str>,
+    size: usize,
+    seed: u64,
+    initial: ExternalState,
+    heuristic: bool,
+    admissible: bool,
+    max_depth: Option<usize>,
+}
+
+impl SubprocessProblem {
+    pub fn new(command: &str, size: usize, seed: u64) -> Result<Self, String> {
+        let response = request(command, json!({ "op": "init", "size": size, "seed": seed }))?;
+        let init: InitResponse = serde_json::from_value(response)
+            .map_err(|e| format!("réponse à init invalide: {}", e))?;
+        Ok(SubprocessProblem {
+            command: command.into(),
+            size,
+            seed,
+            initial: ExternalState::new(&init.state),
+            heuristic: init.heuristic,
+            admissible: !init.heuristic || init.admissible,
+            max_depth: init.max_depth,
+        })
+    }
+
+    fn call(&self, op: &str, state: &ExternalState) -> Result<Value, String> {
+        request(
+            &self.command,
+            json!({ "op": op, "size": self.size, "seed": self.seed, "state": state.value() }),
+        )
+    }
+
+    fn field<T: for<'de> Deserialize<'de>>(
+        &self,
+        op: &str,
+        field: &str,
+        state: &ExternalState,
+    ) -> Result<T, String> {
+        let mut response = self.call(op, state)?;
+        serde_json::from_value(response[field].take())
+            .map_err(|e| format!("réponse à {} invalide ({}): {}", op, field, e))
+    }
+
+    fn try_successors(&self, state: &ExternalState) -> Result<Vec<ExternalAction>, String> {
+        let successors: Vec<Successor> = self.field("successors", "successors", state)?;
+        Ok(successors
+            .into_iter()
+            .enumerate()
+            .map(|(index, successor)| ExternalAction {
+                label: match successor.action {
+                    Some(Value::String(label)) => label,
+                    Some(action) => action.to_string(),
+                    None => index.to_string(),
+                },
+                next: ExternalState::new(&successor.state),
+                cost: successor.cost,
+            })
+            .collect())
+    }
+
+    fn expect<T>(&self, outcome: Result<T, String>) -> T {
+        outcome.unwrap_or_else(|e| panic!("Processus {}: {}", self.command, e))
+    }
+
+    fn check(&self) -> Result<(), String> {
+        self.field::<bool>("is_goal", "goal", &self.initial)?;
+        self.try_successors(&self.initial)?;
+        if self.heuristic {
+            self.field::<usize>("heuristic", "h", &self.initial)?;
+        }
+        Ok(())
+    }
+}
+
+impl Problem for SubprocessProblem {
+    type State = ExternalState;
+    type Action = ExternalAction;
+
+    fn initial_state(&self) -> Self::State {
+        self.initial.clone()
+    }
+
+    fn is_goal(&self, state: &Self::State) -> bool {
+        self.expect(self.field("is_goal", "goal", state))
+    }
+
+    fn actions(&self, state: &Self::State) -> ActionList<ExternalAction> {
+        self.expect(self.try_successors(state))
+            .into_iter()
+            .collect()
+    }
+
+    fn apply(&self, _state: &Self::State, action: &ExternalAction) -> (Self::State, usize) {
+        (action.next.clone(), action.cost)
+    }
+
+    fn successors_into(&self, state: &Self::State, buffer: &mut Successors<Self>) {
+        buffer.clear();
+        for action in self.expect(self.try_successors(state)) {
+            let (next, cost) = (action.next.clone(), action.cost);
+            buffer.push((action, next, cost));
+        }
+    }
+
+    fn heuristic(&self, state: &Self::State) -> usize {
+        if self.heuristic {
+            self.expect(self.field("heuristic", "h", state))
+        } else {
+            0
+        }
+    }
+
+    fn description(&self) -> String {
+        format!("Problème externe {}", self.command)
+    }
+
+    fn take_external_calls(&self) -> Option<ExternalCalls> {
+        let (calls, elapsed) = CALLS.with(|calls| calls.replace((0, Duration::ZERO)));
+        Some(ExternalCalls {
+            calls,
+            time_ms: elapsed.as_micros() as f64 / 1000.0,
+        })
+    }
+}
+
+impl BackwardProblem for SubprocessProblem {
+    fn goal_states(&self) -> Vec<ExternalState> {
+        Vec::new()
+    }
+
+    fn predecessors(&self, _state: &ExternalState) -> SuccessorList<ExternalState> {
+        SuccessorList::new()
+    }
+}
+
+pub struct SubprocessFactory;
+
+impl SubprocessFactory {
+    fn command(config: &BenchmarkConfig) -> Result<&str, String> {
+        config.problem_command.as_deref().ok_or_else(|| {
+            "--problem-command est requis (programme parlant le protocole JSON)".to_string()
+        })
+    }
+
+    fn probe(config: &BenchmarkConfig) -> Result<SubprocessProblem, String> {
+        SubprocessProblem::new(Self::command(config)?, config.size, config.seed)
+    }
+}
+
+impl ProblemFactory for SubprocessFactory {
+    type Instance = SubprocessProblem;
+
+    fn label(config: &BenchmarkConfig) -> String {
+        let name = config
+            .problem_command
+            .as_deref()
+            .and_then(|command| command.split_whitespace().last())
+            .and_then(|program| Path::new(program).file_stem())
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        format!("Subprocess-{}-{}", name, config.size)
+    }
+
+    fn max_depth(config: &BenchmarkConfig) -> usize {
+        Self::probe(config)
+            .ok()
+            .and_then(|problem| problem.max_depth)
+            .unwrap_or(DEFAULT_MAX_DEPTH)
+    }
+
+    fn generate(config: &BenchmarkConfig, seed: u64) -> SubprocessProblem {
+        let command = Self::command(config).expect("commande vérifiée par validate");
+        SubprocessProblem::new(command, config.size, seed)
+            .unwrap_or_else(|e| panic!("Processus {}: {}", command, e))
+    }
+
+    fn validate(config: &BenchmarkConfig) -> Result<(), String> {
+        let command = Self::command(config)?;
+        if config.direction != Direction::Forward {
+            return Err(format!(
+                "Processus {}: recherche arrière non supportée par le protocole",
+                command
+            ));
+        }
+        Self::probe(config)
+            .and_then(|problem| problem.check())
+            .map_err(|e| format!("Processus {}: {}", command, e))
+    }
+
+    fn admissible_heuristic(config: &BenchmarkConfig) -> bool {
+        Self::probe(config).is_ok_and(|problem| problem.admissible)
+    }
+
+    fn describe(instance: &SubprocessProblem) -> String {
+        instance.initial.0.clone()
+    }
+
+    fn notes(config: &BenchmarkConfig) -> Vec<String> {
+        vec![format!(
+            "Problème externe ({}): processus réutilisés entre les recherches, temps des échanges JSON dans properties.ipc_ms",
+            Self::command(config).unwrap_or_default()
+        )]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::bfs::BFS;
+    use crate::algorithms::SearchAlgorithm;
+
+    #[test]
+    fn test_protocol_round_trip() {
+        let script = concat!(env!("CARGO_MANIFEST_DIR"), "/data/subprocess/cruches.py");
+        let command = format!("python3 {}", script);
+        let problem = match SubprocessProblem::new(&command, 4, 0) {
+            Ok(problem) => problem,
+            Err(e) if e.contains("lancement") => return,
+            Err(e) => panic!("{}", e),
+        };
+        problem.check().unwrap();
+        problem.take_external_calls();
+
+        let result = BFS::new().search(&problem);
+        assert!(result.status.is_solved());
+        assert_eq!(result.solution.map(|actions| actions.len()), Some(4));
+        let calls = problem.take_external_calls().unwrap();
+        assert!(calls.calls > 0 && calls.time_ms > 0.0);
+        assert_eq!(problem.take_external_calls().unwrap().calls, 0);
+    }
+}
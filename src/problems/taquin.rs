@@ -1,23 +1,169 @@
-use crate::algorithms::Problem;
+use super::pattern_db::{self, PatternDatabase};
+use super::registry::ProblemFactory;
+use crate::algorithms::{
+    ActionList, BackwardProblem, HeuristicSuccessors, Problem, SuccessorList, Successors,
+};
+use crate::benchmarking::{BenchmarkConfig, Heuristic, InstanceProperties, MoveCost, ShuffleMode};
+use crate::utils::instance_sets;
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
-use rand::thread_rng;
+use rand::{Rng, SeedableRng};
 use std::fmt;
+use std::sync::Arc;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Move {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Move {
+    pub const ALL: [Move; 4] = [Move::Up, Move::Down, Move::Left, Move::Right];
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Move::Up => "U",
+            Move::Down => "D",
+            Move::Left => "L",
+            Move::Right => "R",
+        };
+        write!(f, "{}", label)
+    }
+}
 
 #[derive(Clone)]
 pub struct Taquin {
     size: usize,
     initial_state: Vec<u8>,
     goal_state: Vec<u8>,
+    goal_positions: Vec<usize>,
     heuristic_type: HeuristicType,
+    move_cost: MoveCost,
+    pattern_db: Option<Arc<PatternDatabase>>,
+}
+
+pub(crate) fn tile_weight(cost: MoveCost, tile: u8) -> usize {
+    match cost {
+        MoveCost::Unit => 1,
+        MoveCost::Face => tile as usize,
+        MoveCost::Square => tile as usize * tile as usize,
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
 pub enum HeuristicType {
     Manhattan,
     Hamming,
+    LinearConflict,
+    PatternDB,
     None,
 }
 
+impl From<Heuristic> for HeuristicType {
+    fn from(heuristic: Heuristic) -> Self {
+        match heuristic {
+            Heuristic::None => HeuristicType::None,
+            Heuristic::Hamming => HeuristicType::Hamming,
+            Heuristic::Manhattan => HeuristicType::Manhattan,
+            Heuristic::LinearConflict => HeuristicType::LinearConflict,
+            Heuristic::PatternDb => HeuristicType::PatternDB,
+        }
+    }
+}
+
+fn line_penalty(line: &mut Vec<(usize, usize)>) -> usize {
+    let conflicts = |line: &[(usize, usize)], i: usize| {
+        (0..line.len())
+            .filter(|&j| j != i && (j > i) == (line[j].0 < line[i].0))
+            .count()
+    };
+    let lightest = (0..line.len())
+        .filter(|&i| conflicts(line, i) > 0)
+        .map(|i| line[i].1)
+        .min()
+        .unwrap_or(0);
+    let mut penalty = 0;
+    loop {
+        let worst = (0..line.len())
+            .map(|i| (conflicts(line, i), std::cmp::Reverse(line[i].1), i))
+            .max();
+        match worst {
+            Some((count, _, i)) if count > 0 => {
+                line.remove(i);
+                penalty += 2 * lightest;
+            }
+            _ => return penalty,
+        }
+    }
+}
+
+pub(crate) fn pairwise_tile_cost(
+    size: usize,
+    heuristic: HeuristicType,
+    from: &[u8],
+    to: &[u8],
+    weight_of: impl Fn(u8) -> usize,
+) -> usize {
+    let mut target = vec![0; to.len()];
+    for (pos, &tile) in to.iter().enumerate() {
+        target[tile as usize] = pos;
+    }
+    from.iter()
+        .enumerate()
+        .filter(|&(_, &tile)| tile != 0)
+        .map(|(pos, &tile)| {
+            let goal = target[tile as usize];
+            let moves = match heuristic {
+                HeuristicType::Hamming => usize::from(pos != goal),
+                HeuristicType::None => 0,
+                _ => (pos / size).abs_diff(goal / size) + (pos % size).abs_diff(goal % size),
+            };
+            moves * weight_of(tile)
+        })
+        .sum()
+}
+
+pub(crate) fn linear_conflicts(
+    size: usize,
+    tiles: &[u8],
+    goal_of: impl Fn(u8) -> usize,
+    weight_of: impl Fn(u8) -> usize,
+) -> usize {
+    let mut penalty = 0;
+    let mut line = Vec::with_capacity(size);
+    for index in 0..size {
+        for by_row in [true, false] {
+            line.clear();
+            for offset in 0..size {
+                let pos = if by_row {
+                    index * size + offset
+                } else {
+                    offset * size + index
+                };
+                let tile = tiles[pos];
+                if tile == 0 {
+                    continue;
+                }
+                let goal = goal_of(tile);
+                let (goal_line, goal_offset) = if by_row {
+                    (goal / size, goal % size)
+                } else {
+                    (goal % size, goal / size)
+                };
+                if goal_line == index {
+                    line.push((goal_offset, weight_of(tile)));
+                }
+            }
+            penalty += line_penalty(&mut line);
+        }
+    }
+    penalty
+}
+
 impl Taquin {
     pub fn initial_state_string(&self) -> String {
         let mut result = String::new();
@@ -30,39 +176,212 @@ impl Taquin {
         result
     }
 
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn goal_state(&self) -> &[u8] {
+        &self.goal_state
+    }
+
     pub fn new(size: usize, heuristic: HeuristicType) -> Self {
         let goal_state: Vec<u8> = (0..(size * size) as u8).collect();
 
         Taquin {
             size,
             initial_state: goal_state.clone(),
+            goal_positions: (0..size * size).collect(),
             goal_state,
             heuristic_type: heuristic,
+            move_cost: MoveCost::Unit,
+            pattern_db: None,
         }
     }
 
-    pub fn generate_random(&mut self, moves: usize) {
+    pub fn with_move_cost(mut self, cost: MoveCost) -> Self {
+        self.move_cost = cost;
+        self
+    }
+
+    pub fn move_cost(&self) -> MoveCost {
+        self.move_cost
+    }
+
+    pub fn with_heuristic(mut self, heuristic: HeuristicType) -> Self {
+        self.heuristic_type = heuristic;
+        self
+    }
+
+    pub fn with_pattern_db(mut self, pattern_db: Arc<PatternDatabase>) -> Self {
+        self.pattern_db = Some(pattern_db);
+        self
+    }
+
+    pub fn pattern_db(&self) -> Option<&Arc<PatternDatabase>> {
+        self.pattern_db.as_ref()
+    }
+
+    pub fn with_goal(size: usize, goal: Vec<u8>, heuristic: HeuristicType) -> Result<Self, String> {
+        Self::check_permutation(size, &goal)?;
+        let mut goal_positions = vec![0; goal.len()];
+        for (pos, &tile) in goal.iter().enumerate() {
+            goal_positions[tile as usize] = pos;
+        }
+
+        Ok(Taquin {
+            size,
+            initial_state: goal.clone(),
+            goal_state: goal,
+            goal_positions,
+            heuristic_type: heuristic,
+            move_cost: MoveCost::Unit,
+            pattern_db: None,
+        })
+    }
+
+    pub fn parse_goal(size: usize, spec: &str) -> Result<Vec<u8>, String> {
+        let cells = size * size;
+        match spec {
+            "standard" => Ok((0..cells as u8).collect()),
+            "blank-last" => Ok((1..cells as u8).chain([0]).collect()),
+            "spiral" => {
+                let mut goal = vec![0u8; cells];
+                let side = size as isize;
+                let (mut row, mut col, mut d_row, mut d_col) = (0isize, 0isize, 0isize, 1isize);
+                for tile in 1..cells {
+                    goal[(row * side + col) as usize] = tile as u8;
+                    let (next_row, next_col) = (row + d_row, col + d_col);
+                    if !(0..side).contains(&next_row)
+                        || !(0..side).contains(&next_col)
+                        || goal[(next_row * side + next_col) as usize] != 0
+                    {
+                        (d_row, d_col) = (d_col, -d_row);
+                    }
+                    row += d_row;
+                    col += d_col;
+                }
+                Ok(goal)
+            }
+            tiles => {
+                let goal = tiles
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .filter(|value| !value.is_empty())
+                    .map(|value| value.parse::<u8>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|_| {
+                        format!(
+                            "But invalide: {} (standard, blank-last, spiral ou liste de tuiles)",
+                            tiles
+                        )
+                    })?;
+                Self::check_permutation(size, &goal)?;
+                Ok(goal)
+            }
+        }
+    }
+
+    pub fn goal_name(size: usize, spec: &str) -> String {
+        match spec {
+            "standard" | "blank-last" | "spiral" => spec.to_string(),
+            _ if Self::parse_goal(size, spec).ok() == Self::parse_goal(size, "standard").ok() => {
+                "standard".to_string()
+            }
+            _ => "custom".to_string(),
+        }
+    }
+
+    pub fn generate_random<R: Rng + ?Sized>(&mut self, moves: usize, rng: &mut R) {
         let mut current = self.goal_state.clone();
-        let mut rng = thread_rng();
 
         for _ in 0..moves {
-            let successors = self.get_successors(&current);
-            if let Some((next_state, _)) = successors.choose(&mut rng) {
-                current = next_state.clone();
+            let moves = self.legal_moves(&current);
+            if let Some(&direction) = moves.choose(rng) {
+                current = self.move_blank(&current, direction);
             }
         }
 
         self.initial_state = current;
     }
 
-    pub fn from_state(size: usize, state: Vec<u8>, heuristic: HeuristicType) -> Self {
-        let goal_state: Vec<u8> = (0..(size * size) as u8).collect();
+    pub fn generate_uniform<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let mut tiles = self.goal_state.clone();
+        tiles.shuffle(rng);
+
+        if !self.reaches_goal(&tiles) {
+            let mut non_blank = tiles
+                .iter()
+                .enumerate()
+                .filter(|(_, &tile)| tile != 0)
+                .map(|(i, _)| i);
+            let first = non_blank.next().unwrap();
+            let second = non_blank.next().unwrap();
+            tiles.swap(first, second);
+        }
+
+        self.initial_state = tiles;
+    }
 
+    pub fn is_solvable(size: usize, tiles: &[u8]) -> bool {
+        let non_blank: Vec<u8> = tiles.iter().copied().filter(|&tile| tile != 0).collect();
+        let inversions: usize = non_blank
+            .iter()
+            .enumerate()
+            .map(|(i, &tile)| non_blank[i + 1..].iter().filter(|&&t| t < tile).count())
+            .sum();
+
+        if size % 2 == 1 {
+            inversions.is_multiple_of(2)
+        } else {
+            let blank_row = tiles.iter().position(|&tile| tile == 0).unwrap() / size;
+            (inversions + blank_row).is_multiple_of(2)
+        }
+    }
+
+    fn reaches_goal(&self, tiles: &[u8]) -> bool {
+        Self::is_solvable(self.size, tiles) == Self::is_solvable(self.size, &self.goal_state)
+    }
+
+    pub(crate) fn check_permutation(size: usize, state: &[u8]) -> Result<(), String> {
+        let mut sorted = state.to_vec();
+        sorted.sort_unstable();
+        if !sorted
+            .iter()
+            .enumerate()
+            .all(|(i, &tile)| tile as usize == i)
+            || sorted.len() != size * size
+        {
+            return Err(format!(
+                "État invalide: attendu une permutation de 0 à {}",
+                size * size - 1
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn try_with_initial(mut self, state: Vec<u8>) -> Result<Self, String> {
+        Self::check_permutation(self.size, &state)?;
+        if !self.reaches_goal(&state) {
+            return Err(
+                "État insoluble: la parité de la permutation ne permet pas d'atteindre le but"
+                    .to_string(),
+            );
+        }
+        self.initial_state = state;
+        Ok(self)
+    }
+
+    pub fn try_from_state(
+        size: usize,
+        state: Vec<u8>,
+        heuristic: HeuristicType,
+    ) -> Result<Self, String> {
+        Self::new(size, heuristic).try_with_initial(state)
+    }
+
+    pub fn from_state(size: usize, state: Vec<u8>, heuristic: HeuristicType) -> Self {
         Taquin {
-            size,
             initial_state: state,
-            goal_state,
-            heuristic_type: heuristic,
+            ..Self::new(size, heuristic)
         }
     }
 
@@ -70,41 +389,35 @@ impl Taquin {
         state.iter().position(|&x| x == 0).unwrap()
     }
 
-    fn get_successors(&self, state: &[u8]) -> Vec<(Vec<u8>, usize)> {
-        let blank = self.find_blank(state);
+    fn swap_position(&self, blank: usize, direction: Move) -> Option<usize> {
         let row = blank / self.size;
         let col = blank % self.size;
-        let mut successors = Vec::new();
-
-        if row > 0 {
-            let mut new_state = state.to_vec();
-            let swap_pos = (row - 1) * self.size + col;
-            new_state.swap(blank, swap_pos);
-            successors.push((new_state, 1));
-        }
-
-        if row < self.size - 1 {
-            let mut new_state = state.to_vec();
-            let swap_pos = (row + 1) * self.size + col;
-            new_state.swap(blank, swap_pos);
-            successors.push((new_state, 1));
+        match direction {
+            Move::Up if row > 0 => Some(blank - self.size),
+            Move::Down if row < self.size - 1 => Some(blank + self.size),
+            Move::Left if col > 0 => Some(blank - 1),
+            Move::Right if col < self.size - 1 => Some(blank + 1),
+            _ => None,
         }
+    }
 
-        if col > 0 {
-            let mut new_state = state.to_vec();
-            let swap_pos = row * self.size + (col - 1);
-            new_state.swap(blank, swap_pos);
-            successors.push((new_state, 1));
-        }
+    fn legal_moves(&self, state: &[u8]) -> ActionList<Move> {
+        let blank = self.find_blank(state);
+        Move::ALL
+            .into_iter()
+            .filter(|&direction| self.swap_position(blank, direction).is_some())
+            .collect()
+    }
 
-        if col < self.size - 1 {
-            let mut new_state = state.to_vec();
-            let swap_pos = row * self.size + (col + 1);
-            new_state.swap(blank, swap_pos);
-            successors.push((new_state, 1));
-        }
+    fn move_blank(&self, state: &[u8], direction: Move) -> Vec<u8> {
+        let blank = self.find_blank(state);
+        let swap_pos = self
+            .swap_position(blank, direction)
+            .expect("Mouvement impossible depuis cette position");
 
-        successors
+        let mut new_state = state.to_vec();
+        new_state.swap(blank, swap_pos);
+        new_state
     }
 
     fn manhattan_distance(&self, state: &[u8]) -> usize {
@@ -115,29 +428,60 @@ impl Taquin {
                 continue;
             }
 
-            let current_row = i / self.size;
-            let current_col = i % self.size;
-            let goal_pos = tile as usize;
-            let goal_row = goal_pos / self.size;
-            let goal_col = goal_pos % self.size;
-
-            distance += current_row.abs_diff(goal_row) + current_col.abs_diff(goal_col);
+            distance += self.tile_manhattan(tile, i) * tile_weight(self.move_cost, tile);
         }
 
         distance
     }
 
+    fn tile_manhattan(&self, tile: u8, pos: usize) -> usize {
+        let goal_pos = self.goal_positions[tile as usize];
+        (pos / self.size).abs_diff(goal_pos / self.size)
+            + (pos % self.size).abs_diff(goal_pos % self.size)
+    }
+
+    fn tile_cost(&self, tile: u8, pos: usize) -> usize {
+        let weight = tile_weight(self.move_cost, tile);
+        match self.heuristic_type {
+            HeuristicType::Manhattan | HeuristicType::LinearConflict => {
+                self.tile_manhattan(tile, pos) * weight
+            }
+            HeuristicType::Hamming => {
+                usize::from(self.goal_positions[tile as usize] != pos) * weight
+            }
+            HeuristicType::PatternDB | HeuristicType::None => 0,
+        }
+    }
+
     fn hamming_distance(&self, state: &[u8]) -> usize {
         state
             .iter()
             .enumerate()
-            .filter(|(i, &tile)| tile != 0 && tile as usize != *i)
-            .count()
+            .filter(|(i, &tile)| tile != 0 && self.goal_positions[tile as usize] != *i)
+            .map(|(_, &tile)| tile_weight(self.move_cost, tile))
+            .sum()
+    }
+
+    fn linear_conflict(&self, state: &[u8]) -> usize {
+        self.manhattan_distance(state)
+            + linear_conflicts(
+                self.size,
+                state,
+                |tile| self.goal_positions[tile as usize],
+                |tile| tile_weight(self.move_cost, tile),
+            )
+    }
+
+    fn pattern_db_distance(&self, state: &[u8]) -> usize {
+        self.pattern_db
+            .as_ref()
+            .map_or(0, |pattern_db| pattern_db.evaluate(state.iter().copied()))
     }
 }
 
 impl Problem for Taquin {
     type State = Vec<u8>;
+    type Action = Move;
 
     fn initial_state(&self) -> Self::State {
         self.initial_state.clone()
@@ -147,26 +491,266 @@ impl Problem for Taquin {
         state == &self.goal_state
     }
 
-    fn successors(&self, state: &Self::State) -> Vec<(Self::State, usize)> {
-        self.get_successors(state)
+    fn actions(&self, state: &Self::State) -> ActionList<Move> {
+        self.legal_moves(state)
+    }
+
+    fn apply(&self, state: &Self::State, action: &Move) -> (Self::State, usize) {
+        let next = self.move_blank(state, *action);
+        let tile = state[self.find_blank(&next)];
+        (next, tile_weight(self.move_cost, tile))
+    }
+
+    fn successors_into(&self, state: &Self::State, buffer: &mut Successors<Self>) {
+        buffer.clear();
+        let blank = self.find_blank(state);
+        for direction in Move::ALL {
+            if let Some(swap_pos) = self.swap_position(blank, direction) {
+                let mut new_state = state.clone();
+                new_state.swap(blank, swap_pos);
+                let cost = tile_weight(self.move_cost, state[swap_pos]);
+                buffer.push((direction, new_state, cost));
+            }
+        }
+    }
+
+    fn successors_with_heuristic_into(
+        &self,
+        state: &Self::State,
+        heuristic: usize,
+        buffer: &mut HeuristicSuccessors<Self>,
+    ) {
+        buffer.clear();
+        let blank = self.find_blank(state);
+        for direction in Move::ALL {
+            if let Some(swap_pos) = self.swap_position(blank, direction) {
+                let tile = state[swap_pos];
+                let mut new_state = state.clone();
+                new_state.swap(blank, swap_pos);
+                let h = match self.heuristic_type {
+                    HeuristicType::LinearConflict => self.linear_conflict(&new_state),
+                    HeuristicType::PatternDB => self.pattern_db_distance(&new_state),
+                    _ => heuristic + self.tile_cost(tile, blank) - self.tile_cost(tile, swap_pos),
+                };
+                let cost = tile_weight(self.move_cost, tile);
+                buffer.push((direction, new_state, cost, h));
+            }
+        }
     }
 
     fn heuristic(&self, state: &Self::State) -> usize {
         match self.heuristic_type {
             HeuristicType::Manhattan => self.manhattan_distance(state),
             HeuristicType::Hamming => self.hamming_distance(state),
+            HeuristicType::LinearConflict => self.linear_conflict(state),
+            HeuristicType::PatternDB => self.pattern_db_distance(state),
             HeuristicType::None => 0,
         }
     }
 
+    fn state_heap_bytes(&self, state: &Self::State) -> usize {
+        state.capacity()
+    }
+
+    fn encode_state(&self, state: &Self::State) -> String {
+        let digits: Option<String> = state
+            .iter()
+            .map(|&tile| char::from_digit(tile as u32, 36))
+            .collect();
+        digits.unwrap_or_else(|| {
+            state
+                .iter()
+                .map(|tile| tile.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+    }
+
     fn description(&self) -> String {
         format!(
-            "Taquin {}x{} - Heuristique: {:?}",
-            self.size, self.size, self.heuristic_type
+            "Taquin {}x{} - Heuristique: {:?} - Coût des mouvements: {}",
+            self.size, self.size, self.heuristic_type, self.move_cost
         )
     }
 }
 
+impl BackwardProblem for Taquin {
+    fn goal_states(&self) -> Vec<Vec<u8>> {
+        vec![self.goal_state.clone()]
+    }
+
+    fn predecessors(&self, state: &Vec<u8>) -> SuccessorList<Vec<u8>> {
+        self.successors(state)
+    }
+
+    fn heuristic_between(&self, from: &Vec<u8>, to: &Vec<u8>) -> usize {
+        if self.is_goal(to) {
+            return self.heuristic(from);
+        }
+        pairwise_tile_cost(self.size, self.heuristic_type, from, to, |tile| {
+            tile_weight(self.move_cost, tile)
+        })
+    }
+}
+
+const MAX_SIZE: usize = 15;
+
+pub(crate) fn goal_properties(goal: &[u8]) -> InstanceProperties {
+    let mut properties = InstanceProperties::new();
+    if goal.iter().enumerate().any(|(i, &tile)| tile as usize != i) {
+        let tiles: Vec<String> = goal.iter().map(|tile| tile.to_string()).collect();
+        properties.insert("goal".to_string(), tiles.join(",").into());
+    }
+    properties
+}
+
+pub struct TaquinFactory;
+
+impl TaquinFactory {
+    fn goal(config: &BenchmarkConfig) -> Result<Vec<u8>, String> {
+        Taquin::parse_goal(config.size, config.goal.as_deref().unwrap_or("standard"))
+    }
+
+    fn heuristics(config: &BenchmarkConfig) -> Vec<Heuristic> {
+        match config.heuristics.as_slice() {
+            [] => vec![Heuristic::Manhattan],
+            heuristics => heuristics.to_vec(),
+        }
+    }
+
+    fn pattern_db(
+        config: &BenchmarkConfig,
+        goal: &[u8],
+    ) -> Result<Option<Arc<PatternDatabase>>, String> {
+        if !Self::heuristics(config).contains(&Heuristic::PatternDb) {
+            return Ok(None);
+        }
+        let partition = match config.pdb_partition.as_deref() {
+            Some(spec) => pattern_db::parse_partition(config.size, spec)?,
+            None => pattern_db::default_partition(config.size),
+        };
+        PatternDatabase::load(config.size, goal, &partition, &config.pdb_dir)
+            .map(|pattern_db| Some(Arc::new(pattern_db)))
+    }
+
+    fn instance(config: &BenchmarkConfig, goal: &[u8]) -> Result<Taquin, String> {
+        let taquin = Taquin::with_goal(config.size, goal.to_vec(), HeuristicType::Manhattan)?
+            .with_move_cost(config.move_cost);
+        Ok(match Self::pattern_db(config, goal)? {
+            Some(pattern_db) => taquin.with_pattern_db(pattern_db),
+            None => taquin,
+        })
+    }
+}
+
+impl ProblemFactory for TaquinFactory {
+    type Instance = Taquin;
+
+    fn label(config: &BenchmarkConfig) -> String {
+        let mut label = format!("Taquin-{}x{}", config.size, config.size);
+        if let Some(spec) = config.goal.as_deref() {
+            let name = Taquin::goal_name(config.size, spec);
+            if name != "standard" {
+                label = format!("{}-{}", label, name);
+            }
+        }
+        if config.move_cost != MoveCost::Unit {
+            label = format!("{}-{}", label, config.move_cost);
+        }
+        label
+    }
+
+    fn max_depth(config: &BenchmarkConfig) -> usize {
+        let cells = config.size * config.size;
+        cells * 10 * tile_weight(config.move_cost, cells.saturating_sub(1) as u8)
+    }
+
+    fn validate(config: &BenchmarkConfig) -> Result<(), String> {
+        if !(2..=MAX_SIZE).contains(&config.size) {
+            return Err(format!(
+                "Taille de taquin invalide: {} (entre 2 et {})",
+                config.size, MAX_SIZE
+            ));
+        }
+        let goal = Self::goal(config)?;
+        if Self::heuristics(config).contains(&Heuristic::PatternDb)
+            && config.move_cost != MoveCost::Unit
+        {
+            return Err(format!(
+                "--heuristics pattern-db suppose des mouvements de coût unitaire (--move-cost {})",
+                config.move_cost
+            ));
+        }
+        Self::pattern_db(config, &goal).map(|_| ())
+    }
+
+    fn generate(config: &BenchmarkConfig, seed: u64) -> Taquin {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let goal = Self::goal(config).expect("but vérifié par validate");
+        let mut problem = Self::instance(config, &goal).expect("PDB préparée par validate");
+        if config.shuffle == ShuffleMode::Uniform {
+            problem.generate_uniform(&mut rng);
+        } else {
+            problem.generate_random(config.size * config.size * 10, &mut rng);
+        }
+        problem
+    }
+
+    fn describe(instance: &Taquin) -> String {
+        instance.initial_state_string()
+    }
+
+    fn properties(instance: &Taquin) -> InstanceProperties {
+        let mut properties = goal_properties(&instance.goal_state);
+        if let Some(pattern_db) = &instance.pattern_db {
+            properties.insert("pdb".to_string(), pattern_db.label().into());
+        }
+        properties
+    }
+
+    fn load_instances(
+        config: &BenchmarkConfig,
+        source: &str,
+    ) -> Result<Vec<instance_sets::KnownInstance<Taquin>>, String> {
+        let contents = instance_sets::read(source)?;
+        let cells = config.size * config.size;
+        let goal = match instance_sets::parse_goal(&contents, cells)? {
+            Some(goal) => {
+                Taquin::check_permutation(config.size, &goal)
+                    .map_err(|e| format!("{}, but: {}", source, e))?;
+                goal
+            }
+            None => Self::goal(config)?,
+        };
+        instance_sets::parse_tiles(&contents, cells)?
+            .into_iter()
+            .enumerate()
+            .map(|(i, (tiles, known_cost))| {
+                Self::instance(config, &goal)
+                    .and_then(|taquin| taquin.try_with_initial(tiles))
+                    .map(|taquin| (taquin, known_cost))
+                    .map_err(|e| format!("{}, instance {}: {}", source, i + 1, e))
+            })
+            .collect()
+    }
+
+    fn unit_costs(config: &BenchmarkConfig) -> bool {
+        config.move_cost == MoveCost::Unit
+    }
+
+    fn heuristic_labels(config: &BenchmarkConfig) -> Vec<String> {
+        Self::heuristics(config)
+            .into_iter()
+            .map(|heuristic| heuristic.label().to_string())
+            .collect()
+    }
+
+    fn with_heuristic(instance: &Taquin, heuristic: Heuristic) -> Option<Taquin> {
+        (heuristic != Heuristic::PatternDb || instance.pattern_db.is_some())
+            .then(|| instance.clone().with_heuristic(heuristic.into()))
+    }
+}
+
 impl fmt::Display for Taquin {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "Taquin {}x{}", self.size, self.size)?;
@@ -188,6 +772,11 @@ impl fmt::Display for Taquin {
     }
 }
 
+#[cfg(test)]
+pub(crate) fn hardest_eight_puzzle() -> Taquin {
+    Taquin::from_state(3, vec![8, 6, 7, 2, 5, 4, 3, 0, 1], HeuristicType::Manhattan)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -209,11 +798,209 @@ mod tests {
         assert_eq!(taquin.manhattan_distance(&state), 1);
     }
 
+    #[test]
+    fn test_pairwise_manhattan_between_arbitrary_states() {
+        use crate::problems::PackedTaquin;
+
+        let taquin = Taquin::new(3, HeuristicType::Manhattan);
+        let one_move = vec![1, 0, 2, 3, 4, 5, 6, 7, 8];
+        let two_moves = vec![1, 2, 0, 3, 4, 5, 6, 7, 8];
+        let swapped = vec![2, 1, 0, 3, 4, 5, 6, 7, 8];
+        assert_eq!(taquin.heuristic_between(&one_move, &two_moves), 1);
+        assert_eq!(taquin.heuristic_between(&two_moves, &one_move), 1);
+        assert_eq!(taquin.heuristic_between(&one_move, &swapped), 3);
+        assert_eq!(
+            taquin.heuristic_between(&swapped, &taquin.goal_state),
+            taquin.heuristic(&swapped)
+        );
+
+        let packed = PackedTaquin::from_taquin(&taquin, HeuristicType::Manhattan);
+        let (from, to) = (PackedTaquin::pack(&one_move), PackedTaquin::pack(&swapped));
+        assert_eq!(packed.heuristic_between(&from, &to), 3);
+        let hamming = taquin.with_heuristic(HeuristicType::Hamming);
+        assert_eq!(hamming.heuristic_between(&one_move, &swapped), 2);
+    }
+
+    #[test]
+    fn test_incremental_heuristic_matches_full_scan() {
+        let state = vec![8, 6, 7, 2, 5, 4, 3, 0, 1];
+        for heuristic in [
+            HeuristicType::Manhattan,
+            HeuristicType::Hamming,
+            HeuristicType::LinearConflict,
+        ] {
+            let taquin = Taquin::from_state(3, state.clone(), heuristic);
+            let mut buffer = Vec::new();
+            taquin.successors_with_heuristic_into(&state, taquin.heuristic(&state), &mut buffer);
+
+            assert_eq!(buffer.len(), 3);
+            for (_, next, _, h) in &buffer {
+                assert_eq!(*h, taquin.heuristic(next));
+            }
+        }
+    }
+
+    #[test]
+    fn test_linear_conflict_is_admissible() {
+        use crate::algorithms::{astar::AStar, reference, SearchAlgorithm};
+
+        let tiles = [0, 2, 1, 3, 4, 5, 6, 7, 8];
+        assert_eq!(linear_conflicts(3, &tiles, |t| t as usize, |_| 1), 2);
+        assert_eq!(
+            linear_conflicts(3, &tiles, |t| t as usize, |t| t as usize),
+            2
+        );
+        let column = [0, 3, 2, 1];
+        assert_eq!(linear_conflicts(2, &column, |t| t as usize, |_| 1), 2);
+
+        let mut rng = StdRng::seed_from_u64(11);
+        for _ in 0..20 {
+            let mut taquin = Taquin::new(3, HeuristicType::LinearConflict);
+            taquin.generate_random(30, &mut rng);
+            let state = taquin.initial_state();
+            let manhattan = taquin.clone().with_heuristic(HeuristicType::Manhattan);
+            let optimum = reference::optimal_cost(&taquin, true).unwrap();
+            assert!(manhattan.heuristic(&state) <= taquin.heuristic(&state));
+            assert!(taquin.heuristic(&state) <= optimum);
+            assert_eq!(AStar::new().search(&taquin).metrics.solution_cost, optimum);
+        }
+    }
+
+    #[test]
+    fn test_custom_goal_positions() {
+        assert_eq!(
+            Taquin::parse_goal(3, "spiral").unwrap(),
+            vec![1, 2, 3, 8, 0, 4, 7, 6, 5]
+        );
+        assert_eq!(
+            Taquin::parse_goal(4, "spiral").unwrap(),
+            vec![1, 2, 3, 4, 12, 13, 14, 5, 11, 0, 15, 6, 10, 9, 8, 7]
+        );
+        assert_eq!(
+            Taquin::parse_goal(2, "blank-last").unwrap(),
+            vec![1, 2, 3, 0]
+        );
+        assert_eq!(Taquin::parse_goal(2, "3,1 2 0").unwrap(), vec![3, 1, 2, 0]);
+        assert!(Taquin::parse_goal(2, "1,1,2,0").is_err());
+
+        let spiral = Taquin::parse_goal(3, "spiral").unwrap();
+        let taquin = Taquin::with_goal(3, spiral.clone(), HeuristicType::Manhattan).unwrap();
+        assert!(taquin.is_goal(&spiral));
+        assert_eq!(taquin.heuristic(&spiral), 0);
+        let state = vec![1, 2, 3, 8, 4, 0, 7, 6, 5];
+        assert_eq!(taquin.heuristic(&state), 1);
+        let hamming = Taquin::with_goal(3, spiral, HeuristicType::Hamming).unwrap();
+        assert_eq!(hamming.heuristic(&state), 1);
+
+        assert!(taquin.clone().try_with_initial(state).is_ok());
+        assert!(taquin
+            .try_with_initial(vec![0, 1, 2, 3, 4, 5, 6, 7, 8])
+            .is_err());
+    }
+
+    #[test]
+    fn test_weighted_moves_cost_face_value() {
+        use crate::algorithms::{astar::AStar, bfs::BFS, reference, SearchAlgorithm};
+
+        let state = vec![8, 6, 7, 2, 5, 4, 3, 0, 1];
+        let taquin =
+            Taquin::from_state(3, vec![1, 2, 5, 3, 4, 0, 6, 7, 8], HeuristicType::Manhattan)
+                .with_move_cost(MoveCost::Face);
+        let (_, cost) = taquin.apply(&taquin.initial_state(), &Move::Up);
+        assert_eq!(cost, 5);
+        assert_eq!(taquin.heuristic(&taquin.initial_state()), 1 + 2 + 5);
+
+        for heuristic in [
+            HeuristicType::Manhattan,
+            HeuristicType::Hamming,
+            HeuristicType::LinearConflict,
+        ] {
+            let weighted =
+                Taquin::from_state(3, state.clone(), heuristic).with_move_cost(MoveCost::Square);
+            let mut buffer = Vec::new();
+            weighted.successors_with_heuristic_into(
+                &state,
+                weighted.heuristic(&state),
+                &mut buffer,
+            );
+            for (action, next, cost, h) in &buffer {
+                assert_eq!(*h, weighted.heuristic(next));
+                assert_eq!((next.clone(), *cost), weighted.apply(&state, action));
+            }
+        }
+
+        let taquin =
+            Taquin::from_state(3, vec![3, 0, 7, 4, 8, 2, 6, 1, 5], HeuristicType::Manhattan)
+                .with_move_cost(MoveCost::Face);
+        let optimum = reference::optimal_cost(&taquin, false).unwrap();
+        let astar = AStar::new().search(&taquin);
+        assert_eq!(astar.metrics.solution_cost, optimum);
+        assert!(taquin.heuristic(&taquin.initial_state()) <= optimum);
+        let bfs = BFS::new().search(&taquin);
+        assert_eq!(bfs.metrics.solution_length, astar.metrics.solution_length);
+        assert!(bfs.metrics.solution_cost > optimum);
+    }
+
+    #[test]
+    fn test_solvability_parity() {
+        assert!(Taquin::is_solvable(3, &[0, 1, 2, 3, 4, 5, 6, 7, 8]));
+        assert!(!Taquin::is_solvable(3, &[0, 2, 1, 3, 4, 5, 6, 7, 8]));
+
+        let goal: Vec<u8> = (0..16).collect();
+        assert!(Taquin::is_solvable(4, &goal));
+        let mut moved_down = goal.clone();
+        moved_down.swap(0, 4);
+        assert!(Taquin::is_solvable(4, &moved_down));
+        let mut swapped = goal.clone();
+        swapped.swap(1, 2);
+        assert!(!Taquin::is_solvable(4, &swapped));
+
+        assert!(Taquin::try_from_state(
+            3,
+            vec![0, 2, 1, 3, 4, 5, 6, 7, 8],
+            HeuristicType::Manhattan
+        )
+        .is_err());
+        assert!(Taquin::try_from_state(
+            3,
+            vec![0, 1, 1, 3, 4, 5, 6, 7, 8],
+            HeuristicType::Manhattan
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_uniform_generation_is_solvable() {
+        for size in [3, 4] {
+            let mut taquin = Taquin::new(size, HeuristicType::Manhattan);
+            let mut rng = StdRng::seed_from_u64(size as u64);
+            for _ in 0..20 {
+                taquin.generate_uniform(&mut rng);
+                assert!(Taquin::is_solvable(size, &taquin.initial_state));
+            }
+        }
+    }
+
     #[test]
     fn test_successors() {
         let state = vec![1, 2, 3, 4, 0, 5, 6, 7, 8];
         let taquin = Taquin::from_state(3, state.clone(), HeuristicType::Manhattan);
-        let successors = taquin.get_successors(&state);
+        let successors = taquin.successors(&state);
         assert_eq!(successors.len(), 4);
     }
+
+    #[test]
+    fn test_apply_moves_blank() {
+        let state = vec![1, 2, 3, 4, 0, 5, 6, 7, 8];
+        let taquin = Taquin::from_state(3, state.clone(), HeuristicType::Manhattan);
+        let (next, cost) = taquin.apply(&state, &Move::Up);
+        assert_eq!(next, vec![1, 0, 3, 4, 2, 5, 6, 7, 8]);
+        assert_eq!(cost, 1);
+
+        let corner = vec![0, 1, 2, 3, 4, 5, 6, 7, 8];
+        assert_eq!(
+            taquin.actions(&corner).as_slice(),
+            &[Move::Down, Move::Right]
+        );
+    }
 }
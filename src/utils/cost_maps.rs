@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock};
+
+const WALLS: &[char] = &['#', '@', 'X', 'T'];
+
+#[derive(Debug, Default)]
+pub struct CostMap {
+    pub width: usize,
+    pub height: usize,
+    pub costs: Vec<Option<usize>>,
+    pub start: Option<usize>,
+    pub goal: Option<usize>,
+}
+
+impl CostMap {
+    pub fn cost(&self, cell: usize) -> Option<usize> {
+        self.costs[cell]
+    }
+
+    pub fn open_cells(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.costs.len()).filter(|&cell| self.costs[cell].is_some())
+    }
+
+    pub fn min_cost(&self) -> usize {
+        self.costs.iter().flatten().copied().min().unwrap_or(1)
+    }
+
+    pub fn max_cost(&self) -> usize {
+        self.costs.iter().flatten().copied().max().unwrap_or(1)
+    }
+
+    fn check(self) -> Result<Self, String> {
+        if self.open_cells().next().is_none() {
+            return Err("aucune case praticable".to_string());
+        }
+        Ok(self)
+    }
+}
+
+fn ascii_cost(symbol: char) -> Result<Option<usize>, String> {
+    match symbol {
+        '.' | ' ' | 'S' | 'G' => Ok(Some(1)),
+        '0' => Ok(None),
+        symbol if WALLS.contains(&symbol) => Ok(None),
+        symbol => match symbol.to_digit(10) {
+            Some(cost) => Ok(Some(cost as usize)),
+            None => Err(format!("caractère inconnu '{}'", symbol)),
+        },
+    }
+}
+
+pub fn parse_ascii(contents: &str) -> Result<CostMap, String> {
+    let rows: Vec<&str> = contents
+        .lines()
+        .map(|line| line.trim_end_matches('\r'))
+        .filter(|line| !line.trim().is_empty() && !line.starts_with("//"))
+        .collect();
+    let width = rows
+        .iter()
+        .map(|row| row.chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut map = CostMap {
+        width,
+        height: rows.len(),
+        ..CostMap::default()
+    };
+
+    for (row, line) in rows.iter().enumerate() {
+        let mut symbols = line.chars();
+        for col in 0..width {
+            let cell = row * width + col;
+            let symbol = symbols.next().unwrap_or('#');
+            match symbol {
+                'S' if map.start.is_some() => {
+                    return Err(format!("ligne {}: plusieurs départs S", row + 1))
+                }
+                'G' if map.goal.is_some() => {
+                    return Err(format!("ligne {}: plusieurs buts G", row + 1))
+                }
+                'S' => map.start = Some(cell),
+                'G' => map.goal = Some(cell),
+                _ => {}
+            }
+            let cost = ascii_cost(symbol)
+                .map_err(|e| format!("ligne {}, colonne {}: {}", row + 1, col + 1, e))?;
+            map.costs.push(cost);
+        }
+    }
+
+    map.check()
+}
+
+fn gray_cost(value: u8) -> Option<usize> {
+    match value {
+        0 => None,
+        value => Some(1 + (255 - value as usize) / 32),
+    }
+}
+
+pub fn decode_png(bytes: &[u8]) -> Result<CostMap, String> {
+    let mut decoder = png::Decoder::new(bytes);
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+    let mut reader = decoder.read_info().map_err(|e| e.to_string())?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let frame = reader.next_frame(&mut buffer).map_err(|e| e.to_string())?;
+    let (width, height) = (frame.width as usize, frame.height as usize);
+    let channels = frame.color_type.samples();
+
+    let mut map = CostMap {
+        width,
+        height,
+        ..CostMap::default()
+    };
+    for row in 0..height {
+        let line = &buffer[row * frame.line_size..][..width * channels];
+        for pixel in line.chunks_exact(channels) {
+            let (value, alpha) = match pixel {
+                [gray] => (*gray, 255),
+                [gray, alpha] => (*gray, *alpha),
+                [r, g, b] => (luma(*r, *g, *b), 255),
+                [r, g, b, alpha] => (luma(*r, *g, *b), *alpha),
+                _ => return Err(format!("{} canaux non supportés", channels)),
+            };
+            map.costs
+                .push(if alpha == 0 { None } else { gray_cost(value) });
+        }
+    }
+
+    map.check()
+}
+
+fn luma(r: u8, g: u8, b: u8) -> u8 {
+    ((r as usize * 299 + g as usize * 587 + b as usize * 114) / 1000) as u8
+}
+
+pub fn parse(path: &str, bytes: &[u8]) -> Result<CostMap, String> {
+    let extension = Path::new(path)
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "png" => decode_png(bytes),
+        _ => parse_ascii(std::str::from_utf8(bytes).map_err(|e| e.to_string())?),
+    }
+}
+
+type MapCache = Mutex<HashMap<String, Arc<CostMap>>>;
+
+pub fn load(path: &str) -> Result<Arc<CostMap>, String> {
+    static CACHE: OnceLock<MapCache> = OnceLock::new();
+    if let Some(map) = CACHE
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap()
+        .get(path)
+    {
+        return Ok(Arc::clone(map));
+    }
+
+    let bytes = fs::read(path).map_err(|e| format!("Carte {}: {}", path, e))?;
+    let map = Arc::new(parse(path, &bytes).map_err(|e| format!("Carte {}: {}", path, e))?);
+    CACHE
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap()
+        .insert(path.to_string(), Arc::clone(&map));
+    Ok(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ascii_and_png_maps() {
+        let map = parse_ascii("S.3\n#9\n..G\n").unwrap();
+        assert_eq!((map.width, map.height), (3, 3));
+        assert_eq!(map.start, Some(0));
+        assert_eq!(map.goal, Some(8));
+        assert_eq!(map.costs[2], Some(3));
+        assert_eq!(map.costs[3], None);
+        assert_eq!(map.costs[4], Some(9));
+        assert_eq!(map.costs[5], None);
+        assert_eq!((map.min_cost(), map.max_cost()), (1, 9));
+        assert!(parse_ascii("S?G").is_err());
+        assert!(parse_ascii("SG\nG.").is_err());
+        assert!(parse_ascii("##\n##").is_err());
+
+        let mut bytes = Vec::new();
+        {
+            let mut encoder = png::Encoder::new(&mut bytes, 3, 1);
+            encoder.set_color(png::ColorType::Grayscale);
+            encoder.set_depth(png::BitDepth::Eight);
+            let mut writer = encoder.write_header().unwrap();
+            writer.write_image_data(&[255, 0, 1]).unwrap();
+        }
+        let map = parse("carte.png", &bytes).unwrap();
+        assert_eq!((map.width, map.height), (3, 1));
+        assert_eq!(map.costs, vec![Some(1), None, Some(8)]);
+        assert_eq!(map.start, None);
+    }
+}
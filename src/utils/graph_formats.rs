@@ -0,0 +1,283 @@
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock};
+
+const WEIGHT_ATTRIBUTES: &[&str] = &["weight", "cost", "length", "distance"];
+const HEADER_NAMES: &[&str] = &["source", "from", "src", "u"];
+
+#[derive(Default)]
+pub struct GraphData {
+    pub labels: Vec<String>,
+    pub edges: Vec<(usize, usize, usize)>,
+    index: HashMap<String, usize>,
+}
+
+impl GraphData {
+    pub fn node(&mut self, label: &str) -> usize {
+        if let Some(&id) = self.index.get(label) {
+            return id;
+        }
+        let id = self.labels.len();
+        self.labels.push(label.to_string());
+        self.index.insert(label.to_string(), id);
+        id
+    }
+
+    pub fn find(&self, label: &str) -> Option<usize> {
+        self.index.get(label).copied()
+    }
+
+    fn add_edge(&mut self, from: &str, to: &str, cost: usize, undirected: bool) {
+        let (from, to) = (self.node(from), self.node(to));
+        self.edges.push((from, to, cost));
+        if undirected && from != to {
+            self.edges.push((to, from, cost));
+        }
+    }
+}
+
+fn parse_cost(value: &str) -> Result<usize, String> {
+    let value = value.trim();
+    match value.parse::<f64>() {
+        Ok(cost) if cost >= 0.0 && cost.fract() == 0.0 => Ok(cost as usize),
+        _ => Err(format!(
+            "poids invalide: {} (entier positif attendu)",
+            value
+        )),
+    }
+}
+
+pub fn parse_edge_list(contents: &str, undirected: bool) -> Result<GraphData, String> {
+    let mut graph = GraphData::default();
+    let mut first = true;
+
+    for (line_number, line) in contents.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+        let fields: Vec<&str> = line
+            .split(|c: char| c == ',' || c == ';' || c.is_whitespace())
+            .filter(|field| !field.is_empty())
+            .collect();
+        let header = first
+            && (HEADER_NAMES.contains(&fields[0].to_lowercase().as_str())
+                || fields.get(2).is_some_and(|cost| parse_cost(cost).is_err()));
+        first = false;
+        if header {
+            continue;
+        }
+
+        let cost = match fields.as_slice() {
+            [_, _] => 1,
+            [_, _, cost] => {
+                parse_cost(cost).map_err(|e| format!("ligne {}: {}", line_number + 1, e))?
+            }
+            _ => {
+                return Err(format!(
+                    "ligne {}: 2 ou 3 colonnes attendues (source, cible, poids), {} trouvées",
+                    line_number + 1,
+                    fields.len()
+                ))
+            }
+        };
+        graph.add_edge(fields[0], fields[1], cost, undirected);
+    }
+
+    Ok(graph)
+}
+
+fn label(value: &Value) -> Result<String, String> {
+    match value {
+        Value::String(label) => Ok(label.clone()),
+        Value::Number(number) => Ok(number.to_string()),
+        other => Err(format!("nœud invalide: {}", other)),
+    }
+}
+
+fn json_cost(value: &Value) -> Result<usize, String> {
+    match value {
+        Value::String(cost) => parse_cost(cost),
+        other => parse_cost(&other.to_string()),
+    }
+}
+
+pub fn parse_adjacency_json(contents: &str, undirected: bool) -> Result<GraphData, String> {
+    let adjacency: serde_json::Map<String, Value> =
+        serde_json::from_str(contents).map_err(|e| e.to_string())?;
+    let mut graph = GraphData::default();
+
+    for (from, neighbors) in &adjacency {
+        graph.node(from);
+        match neighbors {
+            Value::Object(weights) => {
+                for (to, cost) in weights {
+                    graph.add_edge(from, to, json_cost(cost)?, undirected);
+                }
+            }
+            Value::Array(neighbors) => {
+                for neighbor in neighbors {
+                    let (to, cost) = match neighbor {
+                        Value::Array(pair) if pair.len() == 2 => {
+                            (label(&pair[0])?, json_cost(&pair[1])?)
+                        }
+                        neighbor => (label(neighbor)?, 1),
+                    };
+                    graph.add_edge(from, &to, cost, undirected);
+                }
+            }
+            other => return Err(format!("{}: voisins invalides: {}", from, other)),
+        }
+    }
+
+    Ok(graph)
+}
+
+pub fn parse_graphml(contents: &str, undirected: bool) -> Result<GraphData, String> {
+    let document = roxmltree::Document::parse(contents).map_err(|e| e.to_string())?;
+    let root = document.root_element();
+    let weight_key = root
+        .children()
+        .filter(|node| node.has_tag_name("key"))
+        .find(|key| {
+            matches!(key.attribute("for"), Some("edge") | Some("all"))
+                && key
+                    .attribute("attr.name")
+                    .is_some_and(|name| WEIGHT_ATTRIBUTES.contains(&name.to_lowercase().as_str()))
+        });
+    let default_cost = match weight_key
+        .and_then(|key| key.children().find(|node| node.has_tag_name("default")))
+        .and_then(|default| default.text())
+    {
+        Some(cost) => parse_cost(cost)?,
+        None => 1,
+    };
+    let weight_key = weight_key.and_then(|key| key.attribute("id"));
+
+    let graph_element = root
+        .children()
+        .find(|node| node.has_tag_name("graph"))
+        .ok_or("élément <graph> absent")?;
+    let directed_by_default = graph_element.attribute("edgedefault") != Some("undirected");
+
+    let mut graph = GraphData::default();
+    for element in graph_element.children().filter(|node| node.is_element()) {
+        if element.has_tag_name("node") {
+            graph.node(element.attribute("id").ok_or("<node> sans id")?);
+        } else if element.has_tag_name("edge") {
+            let from = element.attribute("source").ok_or("<edge> sans source")?;
+            let to = element.attribute("target").ok_or("<edge> sans target")?;
+            let directed = match element.attribute("directed") {
+                Some(directed) => directed == "true",
+                None => directed_by_default,
+            };
+            let cost = match element
+                .children()
+                .filter(|node| node.has_tag_name("data"))
+                .find(|data| data.attribute("key").is_some() && data.attribute("key") == weight_key)
+                .and_then(|data| data.text())
+            {
+                Some(cost) => parse_cost(cost).map_err(|e| format!("{} -> {}: {}", from, to, e))?,
+                None => default_cost,
+            };
+            graph.add_edge(from, to, cost, undirected || !directed);
+        }
+    }
+
+    Ok(graph)
+}
+
+pub fn parse(path: &str, contents: &str, undirected: bool) -> Result<GraphData, String> {
+    let extension = Path::new(path)
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "graphml" | "xml" => parse_graphml(contents, undirected),
+        "json" => parse_adjacency_json(contents, undirected),
+        _ => parse_edge_list(contents, undirected),
+    }
+}
+
+type GraphCache = Mutex<HashMap<(String, bool), Arc<GraphData>>>;
+
+pub fn load(path: &str, undirected: bool) -> Result<Arc<GraphData>, String> {
+    static CACHE: OnceLock<GraphCache> = OnceLock::new();
+    let key = (path.to_string(), undirected);
+    if let Some(graph) = CACHE
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap()
+        .get(&key)
+    {
+        return Ok(Arc::clone(graph));
+    }
+
+    let contents = fs::read_to_string(path).map_err(|e| format!("Graphe {}: {}", path, e))?;
+    let graph =
+        parse(path, &contents, undirected).map_err(|e| format!("Graphe {}: {}", path, e))?;
+    if graph.labels.is_empty() {
+        return Err(format!("Graphe {}: aucun nœud", path));
+    }
+    let graph = Arc::new(graph);
+    CACHE
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap()
+        .insert(key, Arc::clone(&graph));
+    Ok(graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edges(graph: &GraphData) -> Vec<(&str, &str, usize)> {
+        graph
+            .edges
+            .iter()
+            .map(|&(from, to, cost)| (graph.labels[from].as_str(), graph.labels[to].as_str(), cost))
+            .collect()
+    }
+
+    #[test]
+    fn test_formats_describe_the_same_graph() {
+        let expected = vec![("a", "b", 2), ("b", "c", 1), ("a", "c", 5)];
+
+        let csv = "source,target,weight\na,b,2\n# commentaire\nb;c\na\tc\t5.0\n";
+        assert_eq!(edges(&parse("g.csv", csv, false).unwrap()), expected);
+
+        let json = r#"{"a": [["b", 2], ["c", 5]], "b": {"c": 1}, "c": []}"#;
+        let graph = parse("g.json", json, false).unwrap();
+        let mut found = edges(&graph);
+        found.sort();
+        let mut sorted = expected.clone();
+        sorted.sort();
+        assert_eq!(found, sorted);
+
+        let graphml = r#"<?xml version="1.0"?>
+            <graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+              <key id="d0" for="edge" attr.name="weight" attr.type="int"><default>1</default></key>
+              <graph edgedefault="directed">
+                <node id="a"/><node id="b"/><node id="c"/><node id="isolé"/>
+                <edge source="a" target="b"><data key="d0">2</data></edge>
+                <edge source="b" target="c"/>
+                <edge source="a" target="c"><data key="d0">5</data></edge>
+              </graph>
+            </graphml>"#;
+        let graph = parse("g.graphml", graphml, false).unwrap();
+        assert_eq!(edges(&graph), expected);
+        assert_eq!(graph.find("isolé"), Some(3));
+
+        let undirected = parse(
+            "g.graphml",
+            &graphml.replace("\"directed\"", "\"undirected\""),
+            false,
+        )
+        .unwrap();
+        assert_eq!(undirected.edges.len(), 6);
+        assert!(parse("g.csv", "a,b,1\nb,c,-1\n", false).is_err());
+    }
+}
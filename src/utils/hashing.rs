@@ -0,0 +1,17 @@
+#[cfg(feature = "fxhash")]
+pub type StateSet<K> = rustc_hash::FxHashSet<K>;
+#[cfg(feature = "fxhash")]
+pub type StateMap<K, V> = rustc_hash::FxHashMap<K, V>;
+#[cfg(feature = "fxhash")]
+pub type StateHasher = rustc_hash::FxBuildHasher;
+#[cfg(feature = "fxhash")]
+pub const HASHER_NAME: &str = "fxhash";
+
+#[cfg(not(feature = "fxhash"))]
+pub type StateSet<K> = std::collections::HashSet<K>;
+#[cfg(not(feature = "fxhash"))]
+pub type StateMap<K, V> = std::collections::HashMap<K, V>;
+#[cfg(not(feature = "fxhash"))]
+pub type StateHasher = std::collections::hash_map::RandomState;
+#[cfg(not(feature = "fxhash"))]
+pub const HASHER_NAME: &str = "siphash";
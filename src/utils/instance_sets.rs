@@ -0,0 +1,115 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const INSTANCES_DIR: &str = "data/instances";
+
+pub type KnownInstance<T> = (T, Option<usize>);
+
+pub fn resolve(source: &str) -> PathBuf {
+    let path = Path::new(source);
+    if path.is_file() {
+        path.to_path_buf()
+    } else {
+        Path::new(INSTANCES_DIR).join(format!("{}.txt", source))
+    }
+}
+
+pub fn read(source: &str) -> Result<String, String> {
+    let path = resolve(source);
+    fs::read_to_string(&path).map_err(|e| {
+        format!(
+            "Jeu d'instances {} introuvable ({}): {}",
+            source,
+            path.display(),
+            e
+        )
+    })
+}
+
+const GOAL_DIRECTIVE: &str = "goal:";
+
+pub fn parse_goal(contents: &str, cells: usize) -> Result<Option<Vec<u8>>, String> {
+    for (line_number, line) in contents.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        let Some(tiles) = line.strip_prefix(GOAL_DIRECTIVE) else {
+            continue;
+        };
+        let goal = tiles
+            .split_whitespace()
+            .map(|value| value.parse::<u8>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("ligne {}: {}", line_number + 1, e))?;
+        if goal.len() != cells {
+            return Err(format!(
+                "ligne {}: but de {} tuiles, {} attendues",
+                line_number + 1,
+                goal.len(),
+                cells
+            ));
+        }
+        return Ok(Some(goal));
+    }
+    Ok(None)
+}
+
+pub fn parse_tiles(contents: &str, cells: usize) -> Result<Vec<KnownInstance<Vec<u8>>>, String> {
+    let mut instances = Vec::new();
+
+    for (line_number, line) in contents.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() || line.starts_with(GOAL_DIRECTIVE) {
+            continue;
+        }
+
+        let values = line
+            .split_whitespace()
+            .map(|value| value.parse::<usize>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("ligne {}: {}", line_number + 1, e))?;
+
+        let (tiles, known_cost) = match values.len() {
+            n if n == cells => (&values[..], None),
+            n if n == cells + 1 => (&values[..cells], Some(values[cells])),
+            n => {
+                return Err(format!(
+                    "ligne {}: {} valeurs, {} tuiles attendues (suivies éventuellement de la longueur optimale)",
+                    line_number + 1,
+                    n,
+                    cells
+                ))
+            }
+        };
+        if let Some(&tile) = tiles.iter().find(|&&tile| tile >= cells) {
+            return Err(format!(
+                "ligne {}: tuile {} hors limites",
+                line_number + 1,
+                tile
+            ));
+        }
+
+        instances.push((tiles.iter().map(|&tile| tile as u8).collect(), known_cost));
+    }
+
+    Ok(instances)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tiles_with_optional_cost() {
+        let contents = "# taquin 2x2\n1 0 2 3 1\n\n3 1 2 0  # sans coût connu\n";
+        let parsed = parse_tiles(contents, 4).unwrap();
+
+        assert_eq!(parsed[0], (vec![1, 0, 2, 3], Some(1)));
+        assert_eq!(parsed[1], (vec![3, 1, 2, 0], None));
+        assert!(parse_tiles("1 0 2", 4).is_err());
+        assert!(parse_tiles("1 0 2 4", 4).is_err());
+
+        let with_goal = "goal: 1 2 3 0\n1 2 0 3 1\n";
+        assert_eq!(parse_goal(with_goal, 4).unwrap(), Some(vec![1, 2, 3, 0]));
+        assert_eq!(parse_tiles(with_goal, 4).unwrap().len(), 1);
+        assert_eq!(parse_goal(contents, 4).unwrap(), None);
+    }
+}
@@ -0,0 +1,109 @@
+use super::cost_maps::CostMap;
+use crate::benchmarking::MazeGenerator;
+use rand::Rng;
+
+fn neighbours(side: usize, cell: usize) -> impl Iterator<Item = usize> {
+    let (row, col) = (cell / side, cell % side);
+    [
+        (row > 0).then(|| cell - side),
+        (row + 1 < side).then(|| cell + side),
+        (col > 0).then(|| cell - 1),
+        (col + 1 < side).then(|| cell + 1),
+    ]
+    .into_iter()
+    .flatten()
+}
+
+fn recursive_backtracker<R: Rng>(side: usize, rng: &mut R) -> Vec<(usize, usize)> {
+    let mut visited = vec![false; side * side];
+    let mut passages = Vec::with_capacity(side * side - 1);
+    let first = rng.gen_range(0..side * side);
+    visited[first] = true;
+    let mut stack = vec![first];
+    while let Some(&cell) = stack.last() {
+        let unvisited: Vec<usize> = neighbours(side, cell).filter(|&n| !visited[n]).collect();
+        if unvisited.is_empty() {
+            stack.pop();
+            continue;
+        }
+        let next = unvisited[rng.gen_range(0..unvisited.len())];
+        visited[next] = true;
+        passages.push((cell, next));
+        stack.push(next);
+    }
+    passages
+}
+
+fn prim<R: Rng>(side: usize, rng: &mut R) -> Vec<(usize, usize)> {
+    let mut visited = vec![false; side * side];
+    let mut passages = Vec::with_capacity(side * side - 1);
+    let first = rng.gen_range(0..side * side);
+    visited[first] = true;
+    let mut frontier: Vec<(usize, usize)> = neighbours(side, first).map(|n| (first, n)).collect();
+    while !frontier.is_empty() {
+        let (from, cell) = frontier.swap_remove(rng.gen_range(0..frontier.len()));
+        if visited[cell] {
+            continue;
+        }
+        visited[cell] = true;
+        passages.push((from, cell));
+        frontier.extend(
+            neighbours(side, cell)
+                .filter(|&n| !visited[n])
+                .map(|n| (cell, n)),
+        );
+    }
+    passages
+}
+
+pub fn generate<R: Rng>(side: usize, generator: MazeGenerator, rng: &mut R) -> CostMap {
+    let passages = match generator {
+        MazeGenerator::Backtracker => recursive_backtracker(side, rng),
+        MazeGenerator::Prim => prim(side, rng),
+    };
+    let width = 2 * side + 1;
+    let open = |cell: usize| (2 * (cell / side) + 1) * width + 2 * (cell % side) + 1;
+    let mut costs = vec![None; width * width];
+    for cell in 0..side * side {
+        costs[open(cell)] = Some(1);
+    }
+    for (from, to) in passages {
+        costs[(open(from) + open(to)) / 2] = Some(1);
+    }
+    CostMap {
+        width,
+        height: width,
+        costs,
+        start: Some(open(0)),
+        goal: Some(open(side * side - 1)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::bfs::BFS;
+    use crate::algorithms::dfs::DFS;
+    use crate::algorithms::SearchAlgorithm;
+    use crate::problems::ShortestPath;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_generated_mazes_are_perfect() {
+        let mut rng = StdRng::seed_from_u64(5);
+        for generator in [MazeGenerator::Backtracker, MazeGenerator::Prim] {
+            let side = 12;
+            let map = generate(side, generator, &mut rng);
+            let open = map.open_cells().count();
+            assert_eq!(open, side * side + side * side - 1);
+
+            let maze = ShortestPath::from_cost_map(&map, map.start.unwrap(), map.goal.unwrap());
+            assert_eq!(maze.reachable_from_start().len(), open);
+            let bfs = BFS::new().search(&maze);
+            let dfs = DFS::new().search(&maze);
+            assert!(bfs.status.is_solved());
+            assert_eq!(dfs.metrics.solution_cost, bfs.metrics.solution_cost);
+        }
+    }
+}
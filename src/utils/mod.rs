@@ -1 +1,7 @@
+pub mod cost_maps;
+pub mod graph_formats;
+pub mod hashing;
 pub mod heuristics;
+pub mod instance_sets;
+pub mod mazes;
+pub mod seeding;
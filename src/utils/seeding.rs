@@ -0,0 +1,35 @@
+fn splitmix64(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+pub fn instance_seed(master: u64, problem: &str, size: usize, instance_id: usize) -> u64 {
+    let mut seed = splitmix64(master);
+    for byte in problem.bytes() {
+        seed = splitmix64(seed ^ u64::from(byte));
+    }
+    seed = splitmix64(seed ^ size as u64);
+    splitmix64(seed ^ instance_id as u64)
+}
+
+pub fn reseed(seed: u64) -> u64 {
+    splitmix64(seed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_instance_seed_is_stable_and_distinct() {
+        let seed = instance_seed(42, "Taquin-3x3", 3, 0);
+        assert_eq!(seed, instance_seed(42, "Taquin-3x3", 3, 0));
+
+        assert_ne!(seed, instance_seed(43, "Taquin-3x3", 3, 0));
+        assert_ne!(seed, instance_seed(42, "Taquin-4x4", 3, 0));
+        assert_ne!(seed, instance_seed(42, "Taquin-3x3", 4, 0));
+        assert_ne!(seed, instance_seed(42, "Taquin-3x3", 3, 1));
+    }
+}